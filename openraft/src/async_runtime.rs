@@ -6,6 +6,8 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::future::Future;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use crate::Instant;
 use crate::OptionalSend;
@@ -66,6 +68,17 @@ pub trait AsyncRuntime: Debug + Default + PartialEq + Eq + OptionalSend + Option
         T: Future + OptionalSend + 'static,
         T::Output: OptionalSend + 'static;
 
+    /// Return the wall clock time, as the duration since the UNIX epoch.
+    ///
+    /// It is used to stamp the log entries a leader writes, see
+    /// [`RaftEntry::set_timestamp()`](crate::entry::RaftEntry::set_timestamp).
+    ///
+    /// The default implementation reads [`SystemTime::now()`]. A runtime without a system clock,
+    /// e.g., on `wasm32-unknown-unknown`, or with a simulated clock for tests, should override it.
+    fn wall_clock() -> Duration {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+    }
+
     /// Wait until `duration` has elapsed.
     fn sleep(duration: Duration) -> Self::Sleep;
 
@@ -182,6 +195,13 @@ pub trait AsyncOneshotSendExt<T> {
     /// method can be used in both synchronous and asynchronous code without
     /// problems.
     fn send(self, t: T) -> Result<(), T>;
+
+    /// Returns `true` if the associated receiver has been dropped.
+    ///
+    /// If the implementation can not tell it, it should return `false`.
+    fn is_closed(&self) -> bool {
+        false
+    }
 }
 
 impl<T: OptionalSend> AsyncOneshotSendExt<T> for TokioOneShotSender<T> {
@@ -189,6 +209,11 @@ impl<T: OptionalSend> AsyncOneshotSendExt<T> for TokioOneShotSender<T> {
     fn send(self, t: T) -> Result<(), T> {
         self.0.send(t)
     }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
 }
 
 impl<T: OptionalSend> Debug for TokioOneShotSender<T> {
@@ -57,6 +57,8 @@ pub enum ChangeMembers<NID: NodeId, N: Node> {
 }
 
 /// Convert a series of ids to a `Replace` operation.
+///
+/// Duplicate ids are merged into one, since voter ids are stored in a set.
 impl<NID, N, I> From<I> for ChangeMembers<NID, N>
 where
     NID: NodeId,
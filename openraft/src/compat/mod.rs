@@ -2,6 +2,9 @@
 //! read data written by an older application.
 
 mod upgrade;
+#[cfg(feature = "serde")] mod versioned_vote;
 
 pub use upgrade::Compat;
 pub use upgrade::Upgrade;
+#[cfg(feature = "serde")] pub use versioned_vote::HardState;
+#[cfg(feature = "serde")] pub use versioned_vote::VersionedVote;
@@ -0,0 +1,153 @@
+use crate::compat::Upgrade;
+use crate::LeaderId;
+use crate::NodeId;
+use crate::Vote;
+
+/// The hard state persisted by openraft 0.7: the current term and the node voted for in it.
+///
+/// It is replaced by [`Vote`] since openraft 0.8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct HardState<NID: NodeId> {
+    pub current_term: u64,
+    pub voted_for: Option<NID>,
+}
+
+impl<NID> Upgrade<Vote<NID>> for HardState<NID>
+where NID: NodeId
+{
+    fn upgrade(self) -> Vote<NID> {
+        match self.voted_for {
+            Some(node_id) => Vote::new(self.current_term, node_id),
+            // Not voted in this term: an uncommitted vote with no leader, the same as the initial
+            // vote, so that this node can still vote for any candidate in this term.
+            None => Vote {
+                leader_id: LeaderId {
+                    term: self.current_term,
+                    ..Default::default()
+                },
+                committed: false,
+            },
+        }
+    }
+}
+
+/// The persisted vote, a.k.a. the hard state, tagged with the version of its layout.
+///
+/// An application stores the vote in [`RaftLogStorage::save_vote`] as `VersionedVote::new(vote)`,
+/// which is always in the current layout, and reads it back in [`RaftLogReader::read_vote`] with
+/// [`upgrade()`](`Upgrade::upgrade`), which migrates a vote stored in an older layout to the
+/// current one. Thus a node upgraded to a newer openraft still reads the vote written by the older
+/// one.
+///
+/// It is serialized as `{"version": "<version>", "data": <vote>}`.
+///
+/// [`RaftLogStorage::save_vote`]: `crate::storage::RaftLogStorage::save_vote`
+/// [`RaftLogReader::read_vote`]: `crate::storage::RaftLogReader::read_vote`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(tag = "version", content = "data")]
+#[serde(bound = "")]
+pub enum VersionedVote<NID: NodeId> {
+    /// The [`HardState`] of openraft 0.7.
+    #[serde(rename = "1")]
+    V1(HardState<NID>),
+
+    /// The [`Vote`] since openraft 0.8.
+    #[serde(rename = "2")]
+    V2(Vote<NID>),
+}
+
+impl<NID> VersionedVote<NID>
+where NID: NodeId
+{
+    /// The version of the layout written by this openraft.
+    pub const CURRENT_VERSION: &'static str = "2";
+
+    /// Tag a vote with the current version.
+    pub fn new(vote: Vote<NID>) -> Self {
+        Self::V2(vote)
+    }
+
+    /// Return the version of the layout this vote is stored in.
+    pub fn version(&self) -> &'static str {
+        match self {
+            Self::V1(_) => "1",
+            Self::V2(_) => "2",
+        }
+    }
+}
+
+impl<NID> Upgrade<Vote<NID>> for VersionedVote<NID>
+where NID: NodeId
+{
+    fn upgrade(self) -> Vote<NID> {
+        match self {
+            Self::V1(hs) => hs.upgrade(),
+            Self::V2(vote) => vote,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HardState;
+    use super::VersionedVote;
+    use crate::compat::Upgrade;
+    use crate::LeaderId;
+    use crate::Vote;
+
+    #[test]
+    fn test_versioned_vote_write_current_version() -> anyhow::Result<()> {
+        let v = VersionedVote::new(Vote::new_committed(3, 2u64));
+        assert_eq!(VersionedVote::<u64>::CURRENT_VERSION, v.version());
+
+        let s = serde_json::to_string(&v)?;
+        assert!(s.starts_with(r#"{"version":"2","data":"#), "got: {}", s);
+
+        let got: VersionedVote<u64> = serde_json::from_str(&s)?;
+        assert_eq!(v, got);
+        assert_eq!(Vote::new_committed(3, 2), got.upgrade());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_versioned_vote_migrate_v1() -> anyhow::Result<()> {
+        let s = r#"{"version":"1","data":{"current_term":3,"voted_for":2}}"#;
+        let got: VersionedVote<u64> = serde_json::from_str(s)?;
+        assert_eq!(
+            VersionedVote::V1(HardState {
+                current_term: 3,
+                voted_for: Some(2)
+            }),
+            got
+        );
+        assert_eq!(Vote::new(3, 2), got.upgrade());
+
+        // Not voted yet.
+        let s = r#"{"version":"1","data":{"current_term":3,"voted_for":null}}"#;
+        let got: VersionedVote<u64> = serde_json::from_str(s)?;
+        let vote = got.upgrade();
+        assert_eq!(
+            Vote {
+                leader_id: LeaderId {
+                    term: 3,
+                    ..Default::default()
+                },
+                committed: false,
+            },
+            vote
+        );
+        #[cfg(feature = "single-term-leader")]
+        assert_eq!(None, vote.leader_id().voted_for());
+
+        // The migrated vote is written back in the current layout.
+        let s = serde_json::to_string(&VersionedVote::new(got.upgrade()))?;
+        let got: VersionedVote<u64> = serde_json::from_str(&s)?;
+        assert_eq!(VersionedVote::<u64>::CURRENT_VERSION, got.version());
+
+        Ok(())
+    }
+}
@@ -1,14 +1,18 @@
 //! Raft runtime configuration.
 
+use std::collections::BTreeMap;
 use std::ops::Deref;
 use std::sync::atomic::AtomicBool;
 use std::time::Duration;
 
 use anyerror::AnyError;
 use clap::Parser;
+use rand::rngs::StdRng;
 use rand::Rng;
+use rand::SeedableRng;
 
 use crate::config::error::ConfigError;
+use crate::digest::Digest;
 use crate::raft_state::LogStateReader;
 use crate::AsyncRuntime;
 use crate::LogIdOptionExt;
@@ -47,6 +51,31 @@ impl SnapshotPolicy {
     }
 }
 
+/// What a Raft node does when storage operations keep failing.
+///
+/// A failed storage write, such as saving the vote, the committed log id, or purging or
+/// truncating logs, is retried until it fails
+/// [`Config::storage_failure_threshold`] times in a row within
+/// [`Config::storage_failure_window`] milliseconds. Then this policy decides what to do.
+///
+/// Appending log entries is never retried: a failure to append is always fatal.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum StorageFailurePolicy {
+    /// Shut down the Raft node with a [`Fatal::StorageError`](`crate::error::Fatal`).
+    Shutdown,
+
+    /// Keep the node running in a degraded mode.
+    ///
+    /// A degraded node refuses client writes with
+    /// [`ClientWriteError::StorageDegraded`](`crate::error::ClientWriteError::StorageDegraded`),
+    /// reports [`RaftMetrics::storage_degraded`](`crate::RaftMetrics::storage_degraded`),
+    /// and keeps retrying the failed operation. The state machine is still readable, but
+    /// it may be stale. The node leaves the degraded mode once the operation succeeds.
+    Degrade,
+}
+
 /// Parse number with unit such as 5.3 KB
 fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     let res = byte_unit::Byte::from_str(src).map_err(|e| ConfigError::InvalidNumber {
@@ -57,6 +86,27 @@ fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     Ok(res.get_bytes() as u64)
 }
 
+fn parse_label(src: &str) -> Result<(String, String), ConfigError> {
+    match src.split_once('=') {
+        Some((k, v)) if !k.is_empty() => Ok((k.to_string(), v.to_string())),
+        _ => Err(ConfigError::InvalidLabel {
+            invalid: src.to_string(),
+            syntax: "<key>=<value>".to_string(),
+        }),
+    }
+}
+
+fn parse_storage_failure_policy(src: &str) -> Result<StorageFailurePolicy, ConfigError> {
+    match src {
+        "shutdown" => Ok(StorageFailurePolicy::Shutdown),
+        "degrade" => Ok(StorageFailurePolicy::Degrade),
+        _ => Err(ConfigError::InvalidStorageFailurePolicy {
+            syntax: "shutdown|degrade".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
 fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
     if src == "never" {
         return Ok(SnapshotPolicy::Never);
@@ -115,6 +165,17 @@ pub struct Config {
     #[clap(long, default_value = "foo")]
     pub cluster_name: String,
 
+    /// Static labels of this node, such as the zone or the instance type, as `key=value` pairs.
+    ///
+    /// They are not used by Raft itself. They are included in [`RaftMetrics::labels`] and in the
+    /// `RaftCore` tracing span, which all of the events emitted by a node belong to, so that
+    /// nodes can be grouped by their attributes without an external mapping. If a key is given
+    /// more than once, the last one wins.
+    ///
+    /// [`RaftMetrics::labels`]: crate::metrics::RaftMetrics::labels
+    #[clap(long = "label", value_parser = parse_label)]
+    pub labels: Vec<(String, String)>,
+
     /// The minimum election timeout in milliseconds
     #[clap(long, default_value = "150")]
     pub election_timeout_min: u64,
@@ -151,6 +212,82 @@ pub struct Config {
     #[clap(long, default_value = "300")]
     pub max_payload_entries: u64,
 
+    /// The maximum size in bytes of an AppendEntries request or a snapshot chunk sent during
+    /// replication.
+    ///
+    /// The leader sends fewer entries in one AppendEntries request than `max_payload_entries` if
+    /// their total size, see [`RaftEntry::size_hint()`], exceeds this limit. At least one entry is
+    /// sent in every request, even if it alone exceeds the limit. Snapshot chunks are at most this
+    /// size as well. `0` disables the limit.
+    ///
+    /// It can only be set if the log entry type implements [`RaftEntry::size_hint()`], otherwise
+    /// [`Raft::new()`] panics.
+    ///
+    /// [`RaftEntry::size_hint()`]: crate::entry::RaftEntry::size_hint
+    /// [`Raft::new()`]: crate::Raft::new
+    #[clap(long, default_value = "0", value_parser=parse_bytes_with_unit)]
+    pub max_message_size: u64,
+
+    /// The max number of log entries applied to the state machine in every `apply_rate_interval`.
+    ///
+    /// A burst of commits may overwhelm a slow state machine. If it is set, committed entries are
+    /// applied no faster than this rate, and the ones not yet applied wait in the state machine
+    /// worker, without blocking RaftCore. The number of them is reported in
+    /// [`RaftMetrics::apply_backlog`]. It is a rate limit, unlike `max_payload_entries`, which
+    /// limits the size of a batch.
+    ///
+    /// It is disabled by default.
+    ///
+    /// [`RaftMetrics::apply_backlog`]: crate::RaftMetrics::apply_backlog
+    #[clap(long)]
+    pub apply_rate_limit: Option<u64>,
+
+    /// The interval in milliseconds in which at most `apply_rate_limit` entries are applied.
+    #[clap(long, default_value = "1000")]
+    pub apply_rate_interval: u64,
+
+    /// The number of committed but not applied entries beyond which client writes are rejected
+    /// while applying to the state machine keeps failing.
+    ///
+    /// If it is set, a failed [`RaftStateMachine::apply()`] does not shut down the node. Instead
+    /// RaftCore asks the state machine worker to retry it every `heartbeat_interval`, until it
+    /// succeeds; the entries after the last applied log id reported by the state machine are read
+    /// again from the log and applied. Meanwhile, entries keep being committed but not applied;
+    /// once more than this many are waiting, client writes are rejected with
+    /// [`ClientWriteError::ApplyStalled`], until applying recovers. A client whose entry is
+    /// applied by a failed `apply()` receives [`ClientWriteError::ApplyResultLost`]: the entry is
+    /// committed and applied, but the result of applying it is lost.
+    ///
+    /// It is disabled by default: an apply failure shuts down the node.
+    ///
+    /// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+    /// [`ClientWriteError::ApplyStalled`]: crate::error::ClientWriteError::ApplyStalled
+    /// [`ClientWriteError::ApplyResultLost`]: crate::error::ClientWriteError::ApplyResultLost
+    #[clap(long)]
+    pub apply_stall_backlog: Option<u64>,
+
+    /// The number of the most recent [`RaftMetrics`] samples a node keeps in memory.
+    ///
+    /// A sample is taken on every tick, i.e., every `heartbeat_interval * 3 / 2` milliseconds, and
+    /// the oldest one is evicted when the history is full. The history can be read with
+    /// [`Raft::get_metrics_history()`] for post-incident analysis without an external time series
+    /// database. `0` disables it.
+    ///
+    /// [`RaftMetrics`]: crate::RaftMetrics
+    /// [`Raft::get_metrics_history()`]: crate::Raft::get_metrics_history
+    #[clap(long, default_value = "0")]
+    pub metrics_history_size: u64,
+
+    /// The number of the most recent vote decisions a node keeps in memory.
+    ///
+    /// Every vote request this node handles is recorded with the candidate, the term and, if
+    /// denied, the reason. The records can be read with [`Raft::get_vote_log()`] to find out why
+    /// an election went the way it did. `0` disables it.
+    ///
+    /// [`Raft::get_vote_log()`]: crate::Raft::get_vote_log
+    #[clap(long, default_value = "64")]
+    pub vote_log_size: u64,
+
     /// The distance behind in log replication a follower must fall before it is considered lagging
     ///
     /// A follower falls behind this index are replicated with snapshot.
@@ -161,6 +298,64 @@ pub struct Config {
     #[clap(long, default_value = "5000")]
     pub replication_lag_threshold: u64,
 
+    /// The percentage of `max_payload_entries` a learner is allowed to receive in one replication
+    /// request, when replication to voters is backed up.
+    ///
+    /// Replication to voters is backed up if a quorum of voters has not yet received all of the
+    /// leader's logs. In this case the leader sends smaller payloads to learners, so that catching
+    /// up learners does not steal bandwidth from the replication that affects commit.
+    ///
+    /// It must be in range `[1, 100]`. The default value 100 disables throttling.
+    #[clap(long, default_value = "100")]
+    pub learner_replication_budget: u64,
+
+    /// The time in milliseconds after a node is added to the membership, during which the leader
+    /// rejects a membership change that removes it.
+    ///
+    /// Removing and re-adding a flapping node in quick succession churns the membership config.
+    /// Within this grace period, a [`Raft::change_membership()`] that removes a recently added
+    /// node, or demotes it from a voter, is rejected with
+    /// [`ChangeMembershipError::RecentlyAdded`]. The check is advisory:
+    /// [`Raft::force_change_membership()`] proceeds anyway, with a warning. The leader only
+    /// tracks the nodes added during its own leadership.
+    ///
+    /// The default `0` disables it.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`Raft::force_change_membership()`]: crate::Raft::force_change_membership
+    /// [`ChangeMembershipError::RecentlyAdded`]: crate::error::ChangeMembershipError::RecentlyAdded
+    #[clap(long, default_value = "0")]
+    pub member_removal_grace: u64,
+
+    /// The distance behind the leader's committed log a follower may fall before it is
+    /// replicated with a snapshot, even if the logs it needs are not yet purged.
+    ///
+    /// Finding the matching log on a follower that is far behind may take many AppendEntries
+    /// probes, followed by replicating all of the logs it lacks. With this option the leader sends
+    /// the snapshot right away if `leader_commit - match_index` exceeds it, as long as a snapshot
+    /// that includes logs the follower lacks is available.
+    ///
+    /// It is disabled by default: a follower is replicated with a snapshot only when the logs it
+    /// needs are purged.
+    #[clap(long)]
+    pub snapshot_lag_threshold: Option<u64>,
+
+    /// The number of AppendEntries a follower may reject in a row because of a log conflict,
+    /// before the leader considers replication to it stalled.
+    ///
+    /// Finding the matching log normally takes a few rejected probes. A follower that keeps
+    /// rejecting, e.g., because of a persistent log conflict or a bug, would make the leader spin
+    /// on it silently. Once the threshold is reached, the leader logs a warning, counts it in
+    /// [`RaftMetrics::replication_stalls`], and replicates the follower with a snapshot, if a
+    /// snapshot newer than the follower's matching log is available. The follower is no longer
+    /// considered stalled once it accepts logs.
+    ///
+    /// It is disabled by default.
+    ///
+    /// [`RaftMetrics::replication_stalls`]: crate::RaftMetrics::replication_stalls
+    #[clap(long)]
+    pub append_rejection_threshold: Option<u64>,
+
     /// The snapshot policy to use for a Raft node.
     #[clap(
         long,
@@ -173,6 +368,28 @@ pub struct Config {
     #[clap(long, default_value = "3MiB", value_parser=parse_bytes_with_unit)]
     pub snapshot_max_chunk_size: u64,
 
+    /// The max bandwidth in bytes per second of a single snapshot transfer.
+    ///
+    /// Streaming a snapshot as fast as possible may saturate the network and starve the
+    /// replication of logs. If it is set, the chunks of a snapshot are paced so that the transfer
+    /// does not exceed this rate. It only applies to the chunk based transfer, i.e., the default
+    /// [`RaftNetwork::full_snapshot()`].
+    ///
+    /// It is disabled by default.
+    ///
+    /// [`RaftNetwork::full_snapshot()`]: crate::network::RaftNetwork::full_snapshot
+    #[clap(long, value_parser=parse_bytes_with_unit)]
+    pub snapshot_max_bytes_per_sec: Option<u64>,
+
+    /// The max bandwidth in bytes per second of all snapshot transfers of this node.
+    ///
+    /// It is divided evenly among the snapshot transfers in progress, and applies along with
+    /// `snapshot_max_bytes_per_sec`.
+    ///
+    /// It is disabled by default.
+    #[clap(long, value_parser=parse_bytes_with_unit)]
+    pub snapshot_max_total_bytes_per_sec: Option<u64>,
+
     /// The maximum number of logs to keep that are already included in **snapshot**.
     ///
     /// Logs that are not in snapshot will never be purged.
@@ -183,6 +400,14 @@ pub struct Config {
     #[clap(long, default_value = "1")]
     pub purge_batch_size: u64,
 
+    /// The maximum number of leaders whose first log id is kept after their logs are purged.
+    ///
+    /// It keeps a history of which leader, i.e., which term, proposed the logs at which index, even
+    /// after the logs are compacted. It is for debugging and is returned by
+    /// [`Raft::term_history()`](crate::Raft::term_history). `0` disables it.
+    #[clap(long, default_value = "0")]
+    pub term_history_size: u64,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout based events are all disabled:
@@ -227,6 +452,382 @@ pub struct Config {
            default_missing_value = "true"
     )]
     pub enable_elect: bool,
+
+    /// What to do when storage operations keep failing: `shutdown` or `degrade`.
+    ///
+    /// See [`StorageFailurePolicy`].
+    #[clap(long, default_value = "shutdown", value_parser=parse_storage_failure_policy)]
+    pub storage_failure_policy: StorageFailurePolicy,
+
+    /// The number of consecutive storage failures within `storage_failure_window` that triggers
+    /// the `storage_failure_policy`.
+    ///
+    /// Before reaching it, a failed storage operation is retried every `heartbeat_interval`.
+    /// The default `1` applies the policy upon the first failure.
+    #[clap(long, default_value = "1")]
+    pub storage_failure_threshold: u64,
+
+    /// The time window in milliseconds in which consecutive storage failures are counted.
+    ///
+    /// A failure that happens more than this long after the first failure of the current streak
+    /// starts a new streak.
+    #[clap(long, default_value = "10000")]
+    pub storage_failure_window: u64,
+
+    /// The maximum term increase accepted from a single message.
+    ///
+    /// A message carrying a term greater than the local term by more than this value is
+    /// considered suspicious, e.g., it is sent by a misconfigured peer, and a warning is logged.
+    /// Whether such a message is rejected is controlled by `reject_term_jump`.
+    ///
+    /// It is disabled by default: any term increase is accepted.
+    #[clap(long)]
+    pub max_term_jump: Option<u64>,
+
+    /// Whether to reject a message whose term exceeds the local term by more than
+    /// `max_term_jump`, instead of just logging it.
+    ///
+    /// Note that a node that has been down for a long time may fall behind the cluster term by
+    /// more than `max_term_jump` and can not rejoin the cluster while this is enabled.
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub reject_term_jump: bool,
+
+    /// The maximum gap between the persisted term and the term of the last log, checked when a
+    /// node starts.
+    ///
+    /// A term far ahead of the last log term suggests the persisted vote is corrupted. If the gap
+    /// exceeds this value, the node starts in a degraded state: it logs an error, reports it in
+    /// [`RaftMetrics::term_gap_degraded`], and refuses to elect itself, until an operator clears
+    /// it with [`Trigger::clear_term_gap_degraded()`]. It still follows a leader.
+    ///
+    /// It is disabled by default.
+    ///
+    /// [`RaftMetrics::term_gap_degraded`]: crate::metrics::RaftMetrics::term_gap_degraded
+    /// [`Trigger::clear_term_gap_degraded()`]: crate::raft::trigger::Trigger::clear_term_gap_degraded
+    #[clap(long)]
+    pub max_term_log_gap: Option<u64>,
+
+    /// Whether a candidate that can not reach any peer keeps its term in the next election.
+    ///
+    /// By default a candidate increments its term on every election timeout, even if no peer
+    /// responded to its last vote request, e.g., when it is isolated from the cluster. The term
+    /// of an isolated node then keeps growing, and it disrupts the cluster when it rejoins.
+    ///
+    /// If it is enabled, a candidate that received no vote response from any peer re-sends the
+    /// vote request with the same term on election timeout, until some peer responds. An election
+    /// requested with [`Trigger::elect()`] always increases the term.
+    ///
+    /// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub keep_term_when_isolated: bool,
+
+    /// The number of additional heartbeat intervals a follower waits for an active leader before
+    /// campaigning against it.
+    ///
+    /// A follower that has been hearing from a leader only starts an election after the leader
+    /// has been silent for the leader lease plus the election timeout. With a non-zero
+    /// `leader_stickiness`, it also has to miss `leader_stickiness` more heartbeats, so that a
+    /// brief network hiccup does not replace a healthy leader. A follower also keeps rejecting vote
+    /// requests from other candidates during this extended period.
+    ///
+    /// The default `0` disables it. A genuine leader loss is detected
+    /// `leader_stickiness * heartbeat_interval` milliseconds later with it enabled.
+    #[clap(long, default_value = "0")]
+    pub leader_stickiness: u64,
+
+    /// The time in milliseconds a follower defers an election after its election timeout, to
+    /// absorb a heartbeat that is only slightly delayed.
+    ///
+    /// A follower whose leader has been silent for the election timeout does not campaign yet if
+    /// the leader was heard from within this grace period past the timeout: a heartbeat arriving
+    /// in it cancels the election. Unlike `leader_stickiness`, it does not affect how vote
+    /// requests from other candidates are granted.
+    ///
+    /// The default `0` disables it.
+    #[clap(long, default_value = "0")]
+    pub election_grace_period: u64,
+
+    /// The bound in milliseconds of the clock drift between the leader and the followers, for
+    /// serving linearizable reads with a leader lease.
+    ///
+    /// A follower does not vote for another candidate within `election_timeout_max` since it last
+    /// heard from the leader. Thus a leader acknowledged by a quorum at time `T` remains the only
+    /// leader until `T + election_timeout_max`, if the clocks do not drift. If it is set, the
+    /// leader serves a read request without confirming its leadership with a quorum, as long as
+    /// `election_timeout_max` minus this value has not elapsed since the last quorum
+    /// acknowledgement; otherwise it falls back to confirming with a round of heartbeats.
+    ///
+    /// A value not less than `election_timeout_max` makes the lease never valid, and a warning is
+    /// logged by [`Config::validate()`].
+    ///
+    /// It is disabled by default: every read confirms the leadership with a quorum.
+    #[clap(long)]
+    pub read_lease_clock_uncertainty: Option<u64>,
+
+    /// The number of heartbeats a newly elected leader has to get acknowledged by a quorum before
+    /// it serves linearizable reads.
+    ///
+    /// A leader that has just been elected may not be the leader anymore by the time it serves a
+    /// read. If it is set, the leader rejects [`Raft::ensure_linearizable()`] and
+    /// [`Raft::get_read_log_id()`] with [`LeaderNotReady`] until its blank log is committed and
+    /// this many rounds of heartbeats after it are acknowledged by a quorum. A leader that alone
+    /// forms a quorum only waits for its blank log to be committed.
+    ///
+    /// The default `0` disables it: a read is served as soon as the leadership is confirmed.
+    ///
+    /// [`Raft::ensure_linearizable()`]: crate::Raft::ensure_linearizable
+    /// [`Raft::get_read_log_id()`]: crate::Raft::get_read_log_id
+    /// [`LeaderNotReady`]: crate::error::LeaderNotReady
+    #[clap(long, default_value = "0")]
+    pub read_min_heartbeats: u64,
+
+    /// Whether the leader saves the matching log index of every replication target to the log
+    /// store, to speed up replication after it restarts and is re-elected.
+    ///
+    /// If it is enabled, the leader calls [`RaftLogStorage::save_replication_hints()`] every
+    /// `heartbeat_interval` when the matching log indexes change. A leader re-elected after a
+    /// restart starts replicating to a target at the saved index, instead of searching for the
+    /// matching log from the tip of the log. The hints are advisory: a stale one is corrected by
+    /// the response of the follower.
+    ///
+    /// [`RaftLogStorage::save_replication_hints()`]: crate::storage::RaftLogStorage::save_replication_hints
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub persist_replication_hints: bool,
+
+    /// Whether to coalesce the vote saves caused by a burst of vote requests into one.
+    ///
+    /// By default every vote or term change is saved with [`RaftLogStorage::save_vote()`] before
+    /// the next message is handled, and a store that syncs to disk on every save may become the
+    /// bottleneck when vote requests with increasing terms arrive rapidly, e.g., in an election
+    /// storm.
+    ///
+    /// If it is enabled, the vote requests already queued are handled in one batch, and only the
+    /// last vote of the batch is saved, before any response of the batch is sent. Because a vote
+    /// never decreases, a vote is still never granted and a term is never acknowledged before a
+    /// vote at least as great is durable.
+    ///
+    /// [`RaftLogStorage::save_vote()`]: crate::storage::RaftLogStorage::save_vote
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub batch_vote_saves: bool,
+
+    /// Whether to deny the granted votes waiting for a vote to be saved, if saving it fails.
+    ///
+    /// A granted vote is not responded until the vote is saved with
+    /// [`RaftLogStorage::save_vote()`]. If saving fails, by default the pending vote responses
+    /// are answered with `vote_granted: false` at once, instead of being held until a retry
+    /// succeeds, or being dropped if the node shuts down because of the failure. The candidate
+    /// then goes on without this vote, and the granted vote, which is not durable, is never
+    /// counted.
+    ///
+    /// If it is disabled, the responses are sent only after the vote is saved.
+    ///
+    /// [`RaftLogStorage::save_vote()`]: crate::storage::RaftLogStorage::save_vote
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub deny_vote_on_save_failure: bool,
+
+    /// Whether a node that is not the leader forwards client writes to the current leader.
+    ///
+    /// By default [`Raft::client_write()`] on a follower or learner returns a `ForwardToLeader`
+    /// error and the client has to resend it to the leader.
+    ///
+    /// If it is enabled, the write is sent to the leader with [`RaftNetwork::client_write()`] and
+    /// the leader's response is returned. A `ForwardToLeader` error is still returned if the leader
+    /// is unknown or forwarding fails. Note that in the latter case the write may have been
+    /// committed by the leader.
+    ///
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`RaftNetwork::client_write()`]: crate::network::RaftNetwork::client_write
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub forward_client_write: bool,
+
+    /// Whether to reject a membership change that promotes a learner that has not caught up with
+    /// the leader.
+    ///
+    /// A learner is caught up if it lags behind the leader's last log by no more than
+    /// [`replication_lag_threshold`](Self::replication_lag_threshold). If it is enabled and any
+    /// learner to promote is lagging, [`Raft::change_membership()`] returns a `LearnerIsLagging`
+    /// error and the whole change is rejected.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub reject_lagging_promotion: bool,
+
+    /// The time in milliseconds a Raft group must stay idle before it is quiesced.
+    ///
+    /// A group is idle if no log is appended, every log is committed, and the leader has
+    /// replicated every log to every target. The leader of a quiesced group stops sending
+    /// heartbeats, and a follower of it does not time out into an election. The next log appended
+    /// by the leader, e.g., a client write, wakes up the group.
+    ///
+    /// It is meant for systems running many Raft groups, most of which are idle. Note that a
+    /// leader failure is not detected while its group is quiesced: the application has to detect
+    /// it by other means and call [`Trigger::elect()`] on a follower.
+    ///
+    /// It is disabled by default.
+    ///
+    /// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
+    #[clap(long)]
+    pub quiesce_timeout: Option<u64>,
+
+    /// The time in milliseconds a leader may go without serving a client request before it checks
+    /// that it can still reach a quorum.
+    ///
+    /// When a leader has received no client write or read for this long, it sends a round of
+    /// heartbeats to reaffirm its authority and to refresh its lease, even if heartbeats are
+    /// disabled or the group is quiesced. It repeats this every `leader_idle_timeout` while it
+    /// stays idle. If a quorum has not acknowledged the previous round when the next one is due,
+    /// the leader can not reach a quorum and it steps down.
+    ///
+    /// It is disabled by default.
+    #[clap(long)]
+    pub leader_idle_timeout: Option<u64>,
+
+    /// Whether to scan the log for missing entries when a Raft node starts.
+    ///
+    /// Every gap found between the last purged log and the last log is reported as an error log.
+    /// The scan reads the whole log, thus it slows down startup if the log is long. The scan can
+    /// also be run on demand with [`Raft::check_log_gaps()`](crate::Raft::check_log_gaps).
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub check_log_gaps_at_startup: bool,
+
+    /// Whether to include the [`timing_hash()`](Self::timing_hash) of this node in the
+    /// AppendEntries requests it sends as a leader.
+    ///
+    /// Nodes that disagree on `heartbeat_interval` or election timeouts may cause subtle issues,
+    /// such as unnecessary elections. A node receiving a hash that differs from its own logs a
+    /// warning and reports the sender in [`RaftMetrics::config_mismatches`]. It is only for
+    /// observability: a request with a mismatching hash is still handled as usual.
+    ///
+    /// [`RaftMetrics::config_mismatches`]: crate::metrics::RaftMetrics::config_mismatches
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub exchange_config_hash: bool,
+
+    /// Whether to report the committed and last log id of this node in the responses to the
+    /// AppendEntries requests, including heartbeats, it receives as a follower or learner.
+    ///
+    /// The leader records them in [`RaftMetrics::follower_log_state`]. Enable it only when every
+    /// node in the cluster is upgraded to a version that understands
+    /// [`AppendEntriesResponse::SuccessWithLogState`]: an older leader can not decode it.
+    ///
+    /// [`RaftMetrics::follower_log_state`]: crate::metrics::RaftMetrics::follower_log_state
+    /// [`AppendEntriesResponse::SuccessWithLogState`]: crate::raft::AppendEntriesResponse::SuccessWithLogState
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub report_log_state: bool,
+
+    /// The seed of the random number generator for randomized timing, i.e., the election timeout.
+    ///
+    /// With the same seed, a node picks the same sequence of election timeouts in every run, so
+    /// that a cluster can be replayed in a deterministic simulation. By default the generator is
+    /// seeded by [`AsyncRuntime::thread_rng()`].
+    #[clap(long)]
+    pub rand_seed: Option<u64>,
+
+    /// **DANGEROUS**: whether [`Raft::set_unsafe_commit_quorum()`] is allowed to lower the commit
+    /// quorum to a given set of surviving nodes.
+    ///
+    /// It is only meant for disaster recovery, when a majority of the voters is permanently lost
+    /// and an operator consciously trades safety for availability. Logs committed by the lowered
+    /// quorum may be lost, and two leaders may commit conflicting logs, i.e., split-brain.
+    ///
+    /// Enabling this option does not change the quorum by itself. The override has to be set
+    /// explicitly at runtime.
+    ///
+    /// [`Raft::set_unsafe_commit_quorum()`]: crate::Raft::set_unsafe_commit_quorum
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub allow_unsafe_commit_quorum: bool,
+
+    /// **DANGEROUS**: whether [`Raft::set_applied_index()`] is allowed to tell Raft that the state
+    /// machine has been restored out-of-band up to a given log index.
+    ///
+    /// It is only meant for manual recovery. Entries up to the given index are not applied to the
+    /// state machine: if the state machine does not actually contain them, they are lost on this
+    /// node.
+    ///
+    /// [`Raft::set_applied_index()`]: crate::Raft::set_applied_index
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub allow_unsafe_set_applied: bool,
 }
 
 /// Updatable config for a raft runtime.
@@ -251,11 +852,63 @@ impl Default for Config {
 }
 
 impl Config {
+    /// The labels of this node as a map, in which the last value of a key wins.
+    pub(crate) fn label_map(&self) -> BTreeMap<String, String> {
+        self.labels.iter().cloned().collect()
+    }
+
+    /// Get the size in bytes of a snapshot chunk to send, which is at most `max_message_size` if
+    /// it is set.
+    pub fn snapshot_chunk_size(&self) -> u64 {
+        if self.max_message_size == 0 {
+            self.snapshot_max_chunk_size
+        } else {
+            std::cmp::min(self.snapshot_max_chunk_size, self.max_message_size)
+        }
+    }
+
+    /// The duration since the last quorum acknowledgement in which a leader serves reads without
+    /// confirming its leadership, i.e., `election_timeout_max` minus
+    /// `read_lease_clock_uncertainty`.
+    ///
+    /// It returns `None` if lease reads are disabled or the lease is never valid.
+    pub(crate) fn read_lease(&self) -> Option<Duration> {
+        let uncertainty = self.read_lease_clock_uncertainty?;
+        let lease = self.election_timeout_max.saturating_sub(uncertainty);
+        if lease == 0 {
+            return None;
+        }
+        Some(Duration::from_millis(lease))
+    }
+
+    /// Compute a hash of the timing parameters that all nodes in a cluster should agree on:
+    /// `heartbeat_interval`, `election_timeout_min` and `election_timeout_max`.
+    ///
+    /// The hash does not change across processes, platforms or Rust versions.
+    pub fn timing_hash(&self) -> u64 {
+        let mut d = Digest::default();
+        for v in [
+            self.heartbeat_interval,
+            self.election_timeout_min,
+            self.election_timeout_max,
+        ] {
+            d.update(&v.to_le_bytes());
+        }
+        d.finish()
+    }
+
     /// Generate a new random election timeout within the configured min & max.
     pub fn new_rand_election_timeout<RT: AsyncRuntime>(&self) -> u64 {
         RT::thread_rng().gen_range(self.election_timeout_min..self.election_timeout_max)
     }
 
+    /// Create the random number generator for randomized timing, seeded with `rand_seed` if it is
+    /// set.
+    pub(crate) fn new_timing_rng<RT: AsyncRuntime>(&self) -> StdRng {
+        let seed = self.rand_seed.unwrap_or_else(|| RT::thread_rng().gen());
+        StdRng::seed_from_u64(seed)
+    }
+
     /// Get the timeout for sending and installing the last snapshot segment.
     pub fn install_snapshot_timeout(&self) -> Duration {
         Duration::from_millis(self.install_snapshot_timeout)
@@ -288,24 +941,69 @@ impl Config {
 
     /// Validate the state of this config.
     pub fn validate(self) -> Result<Config, ConfigError> {
-        if self.election_timeout_min >= self.election_timeout_max {
-            return Err(ConfigError::ElectionTimeout {
-                min: self.election_timeout_min,
-                max: self.election_timeout_max,
-            });
+        self.validate_election_timeout(self.election_timeout_min, self.election_timeout_max)?;
+
+        if self.max_payload_entries == 0 {
+            return Err(ConfigError::MaxPayloadIs0);
         }
 
-        if self.election_timeout_min <= self.heartbeat_interval {
-            return Err(ConfigError::ElectionTimeoutLTHeartBeat {
-                election_timeout_min: self.election_timeout_min,
-                heartbeat_interval: self.heartbeat_interval,
+        if self.learner_replication_budget == 0 || self.learner_replication_budget > 100 {
+            return Err(ConfigError::InvalidLearnerReplicationBudget {
+                budget: self.learner_replication_budget,
             });
         }
 
-        if self.max_payload_entries == 0 {
-            return Err(ConfigError::MaxPayloadIs0);
+        if self.max_term_jump == Some(0) {
+            return Err(ConfigError::MaxTermJumpIs0);
+        }
+
+        if self.max_term_log_gap == Some(0) {
+            return Err(ConfigError::MaxTermLogGapIs0);
+        }
+
+        if let Some(uncertainty) = self.read_lease_clock_uncertainty {
+            if uncertainty >= self.election_timeout_max {
+                tracing::warn!(
+                    read_lease_clock_uncertainty = uncertainty,
+                    election_timeout_max = self.election_timeout_max,
+                    "read lease is never valid: read_lease_clock_uncertainty >= election_timeout_max, every read confirms the leadership"
+                );
+            }
+        }
+
+        if self.snapshot_max_bytes_per_sec == Some(0) || self.snapshot_max_total_bytes_per_sec == Some(0) {
+            return Err(ConfigError::SnapshotBandwidthIs0);
+        }
+
+        if self.storage_failure_threshold == 0 {
+            return Err(ConfigError::StorageFailureThresholdIs0);
+        }
+
+        if self.apply_rate_limit == Some(0) || self.apply_rate_interval == 0 {
+            return Err(ConfigError::ApplyRateIs0);
+        }
+
+        if self.append_rejection_threshold == Some(0) {
+            return Err(ConfigError::AppendRejectionThresholdIs0);
         }
 
         Ok(self)
     }
+
+    /// Validate an election timeout range `[min, max)` in milliseconds against the heartbeat
+    /// interval.
+    pub(crate) fn validate_election_timeout(&self, min: u64, max: u64) -> Result<(), ConfigError> {
+        if min >= max {
+            return Err(ConfigError::ElectionTimeout { min, max });
+        }
+
+        if min <= self.heartbeat_interval {
+            return Err(ConfigError::ElectionTimeoutLTHeartBeat {
+                election_timeout_min: min,
+                heartbeat_interval: self.heartbeat_interval,
+            });
+        }
+
+        Ok(())
+    }
 }
@@ -1,8 +1,11 @@
 use core::time::Duration;
 
+use maplit::btreemap;
+
 use crate::config::error::ConfigError;
 use crate::Config;
 use crate::SnapshotPolicy;
+use crate::StorageFailurePolicy;
 
 #[test]
 fn test_config_defaults() {
@@ -13,6 +16,7 @@ fn test_config_defaults() {
 
     assert_eq!(50, cfg.heartbeat_interval);
     assert_eq!(300, cfg.max_payload_entries);
+    assert_eq!(0, cfg.max_message_size);
     assert_eq!(5000, cfg.replication_lag_threshold);
 
     assert_eq!(3 * 1024 * 1024, cfg.snapshot_max_chunk_size);
@@ -62,6 +66,7 @@ fn test_build() -> anyhow::Result<()> {
         "--snapshot-max-chunk-size=204",
         "--max-in-snapshot-log-to-keep=205",
         "--purge-batch-size=207",
+        "--max-message-size=1KiB",
     ])?;
 
     assert_eq!("bar", config.cluster_name);
@@ -80,6 +85,8 @@ fn test_build() -> anyhow::Result<()> {
     assert_eq!(204, config.snapshot_max_chunk_size);
     assert_eq!(205, config.max_in_snapshot_log_to_keep);
     assert_eq!(207, config.purge_batch_size);
+    assert_eq!(1024, config.max_message_size);
+    assert_eq!(204, config.snapshot_chunk_size());
 
     // Test config methods
     #[allow(deprecated)]
@@ -162,3 +169,466 @@ fn test_config_enable_elect() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_config_snapshot_lag_threshold() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.snapshot_lag_threshold);
+
+    let config = Config::build(&["foo", "--snapshot-lag-threshold=1000"])?;
+    assert_eq!(Some(1000), config.snapshot_lag_threshold);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_member_removal_grace() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.member_removal_grace);
+
+    let config = Config::build(&["foo", "--member-removal-grace=3000"])?;
+    assert_eq!(3000, config.member_removal_grace);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_append_rejection_threshold() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.append_rejection_threshold);
+
+    let config = Config::build(&["foo", "--append-rejection-threshold=16"])?;
+    assert_eq!(Some(16), config.append_rejection_threshold);
+
+    let res = Config::build(&["foo", "--append-rejection-threshold=0"]);
+    assert_eq!(Err(ConfigError::AppendRejectionThresholdIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_snapshot_bandwidth() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.snapshot_max_bytes_per_sec);
+    assert_eq!(None, config.snapshot_max_total_bytes_per_sec);
+
+    let config = Config::build(&[
+        "foo",
+        "--snapshot-max-bytes-per-sec=1MiB",
+        "--snapshot-max-total-bytes-per-sec=4000",
+    ])?;
+    assert_eq!(Some(1024 * 1024), config.snapshot_max_bytes_per_sec);
+    assert_eq!(Some(4000), config.snapshot_max_total_bytes_per_sec);
+
+    let res = Config::build(&["foo", "--snapshot-max-bytes-per-sec=0"]);
+    assert_eq!(Err(ConfigError::SnapshotBandwidthIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_max_term_log_gap() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.max_term_log_gap);
+
+    let config = Config::build(&["foo", "--max-term-log-gap=1000"])?;
+    assert_eq!(Some(1000), config.max_term_log_gap);
+
+    let res = Config::build(&["foo", "--max-term-log-gap=0"]);
+    assert_eq!(Err(ConfigError::MaxTermLogGapIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_read_lease_clock_uncertainty() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.read_lease_clock_uncertainty);
+    assert_eq!(None, config.read_lease());
+
+    let config = Config::build(&[
+        "foo",
+        "--election-timeout-min=200",
+        "--election-timeout-max=300",
+        "--read-lease-clock-uncertainty=100",
+    ])?;
+    assert_eq!(Some(100), config.read_lease_clock_uncertainty);
+    assert_eq!(Some(Duration::from_millis(200)), config.read_lease());
+
+    // Too large, the lease is never valid
+    let config = Config::build(&[
+        "foo",
+        "--election-timeout-min=200",
+        "--election-timeout-max=300",
+        "--read-lease-clock-uncertainty=300",
+    ])?;
+    assert_eq!(None, config.read_lease());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_max_term_jump() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.max_term_jump);
+    assert_eq!(false, config.reject_term_jump);
+
+    let config = Config::build(&["foo", "--max-term-jump=100", "--reject-term-jump"])?;
+    assert_eq!(Some(100), config.max_term_jump);
+    assert_eq!(true, config.reject_term_jump);
+
+    let res = Config::build(&["foo", "--max-term-jump=0"]);
+    assert_eq!(Err(ConfigError::MaxTermJumpIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_labels() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert!(config.labels.is_empty());
+
+    let config = Config::build(&[
+        "foo",
+        "--label=zone=us-east-1a",
+        "--label=role=",
+        "--label=zone=us-east-1b",
+    ])?;
+    assert_eq!(
+        vec![
+            ("zone".to_string(), "us-east-1a".to_string()),
+            ("role".to_string(), "".to_string()),
+            ("zone".to_string(), "us-east-1b".to_string()),
+        ],
+        config.labels
+    );
+    assert_eq!(
+        btreemap! {"zone".to_string() => "us-east-1b".to_string(), "role".to_string() => "".to_string()},
+        config.label_map()
+    );
+
+    let res = Config::build(&["foo", "--label=zone"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--label==x"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_storage_failure_policy() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(StorageFailurePolicy::Shutdown, config.storage_failure_policy);
+    assert_eq!(1, config.storage_failure_threshold);
+    assert_eq!(10_000, config.storage_failure_window);
+
+    let config = Config::build(&[
+        "foo",
+        "--storage-failure-policy=degrade",
+        "--storage-failure-threshold=3",
+        "--storage-failure-window=500",
+    ])?;
+    assert_eq!(StorageFailurePolicy::Degrade, config.storage_failure_policy);
+    assert_eq!(3, config.storage_failure_threshold);
+    assert_eq!(500, config.storage_failure_window);
+
+    let res = Config::build(&["foo", "--storage-failure-policy=bar"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--storage-failure-threshold=0"]);
+    assert_eq!(Err(ConfigError::StorageFailureThresholdIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_keep_term_when_isolated() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.keep_term_when_isolated);
+
+    let config = Config::build(&["foo", "--keep-term-when-isolated"])?;
+    assert_eq!(true, config.keep_term_when_isolated);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_leader_stickiness() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.leader_stickiness);
+
+    let config = Config::build(&["foo", "--leader-stickiness=5"])?;
+    assert_eq!(5, config.leader_stickiness);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_quiesce_timeout() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.quiesce_timeout);
+
+    let config = Config::build(&["foo", "--quiesce-timeout=3000"])?;
+    assert_eq!(Some(3000), config.quiesce_timeout);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_leader_idle_timeout() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.leader_idle_timeout);
+
+    let config = Config::build(&["foo", "--leader-idle-timeout=1000"])?;
+    assert_eq!(Some(1000), config.leader_idle_timeout);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_read_min_heartbeats() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.read_min_heartbeats);
+
+    let config = Config::build(&["foo", "--read-min-heartbeats=2"])?;
+    assert_eq!(2, config.read_min_heartbeats);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_persist_replication_hints() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.persist_replication_hints);
+
+    let config = Config::build(&["foo", "--persist-replication-hints"])?;
+    assert_eq!(true, config.persist_replication_hints);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_batch_vote_saves() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.batch_vote_saves);
+
+    let config = Config::build(&["foo", "--batch-vote-saves"])?;
+    assert_eq!(true, config.batch_vote_saves);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_deny_vote_on_save_failure() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(true, config.deny_vote_on_save_failure);
+
+    let config = Config::build(&["foo", "--deny-vote-on-save-failure=false"])?;
+    assert_eq!(false, config.deny_vote_on_save_failure);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_forward_client_write() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.forward_client_write);
+
+    let config = Config::build(&["foo", "--forward-client-write"])?;
+    assert_eq!(true, config.forward_client_write);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_reject_lagging_promotion() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.reject_lagging_promotion);
+
+    let config = Config::build(&["foo", "--reject-lagging-promotion"])?;
+    assert_eq!(true, config.reject_lagging_promotion);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_allow_unsafe_commit_quorum() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.allow_unsafe_commit_quorum);
+
+    let config = Config::build(&["foo", "--allow-unsafe-commit-quorum"])?;
+    assert_eq!(true, config.allow_unsafe_commit_quorum);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_allow_unsafe_set_applied() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.allow_unsafe_set_applied);
+
+    let config = Config::build(&["foo", "--allow-unsafe-set-applied"])?;
+    assert_eq!(true, config.allow_unsafe_set_applied);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_rand_seed() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.rand_seed);
+
+    let config = Config::build(&["foo", "--rand-seed=42"])?;
+    assert_eq!(Some(42), config.rand_seed);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_learner_replication_budget() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(100, config.learner_replication_budget);
+
+    let config = Config::build(&["foo", "--learner-replication-budget=20"])?;
+    assert_eq!(20, config.learner_replication_budget);
+
+    let res = Config::build(&["foo", "--learner-replication-budget=0"]);
+    assert_eq!(
+        Err(ConfigError::InvalidLearnerReplicationBudget { budget: 0 }),
+        res.map(|_| ())
+    );
+
+    let res = Config::build(&["foo", "--learner-replication-budget=101"]);
+    assert_eq!(
+        Err(ConfigError::InvalidLearnerReplicationBudget { budget: 101 }),
+        res.map(|_| ())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_config_check_log_gaps_at_startup() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.check_log_gaps_at_startup);
+
+    let config = Config::build(&["foo", "--check-log-gaps-at-startup"])?;
+    assert_eq!(true, config.check_log_gaps_at_startup);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_exchange_config_hash() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.exchange_config_hash);
+
+    let config = Config::build(&["foo", "--exchange-config-hash"])?;
+    assert_eq!(true, config.exchange_config_hash);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_report_log_state() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.report_log_state);
+
+    let config = Config::build(&["foo", "--report-log-state"])?;
+    assert_eq!(true, config.report_log_state);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_timing_hash() -> anyhow::Result<()> {
+    let a = Config::build(&["foo", "--election-timeout-min=200", "--election-timeout-max=300"])?;
+    let b = Config::build(&[
+        "foo",
+        "--election-timeout-min=200",
+        "--election-timeout-max=300",
+        "--max-payload-entries=1",
+    ])?;
+    assert_eq!(
+        a.timing_hash(),
+        b.timing_hash(),
+        "non-timing params do not affect the hash"
+    );
+
+    let c = Config::build(&["foo", "--election-timeout-min=200", "--election-timeout-max=400"])?;
+    assert_ne!(a.timing_hash(), c.timing_hash());
+
+    let d = Config::build(&[
+        "foo",
+        "--election-timeout-min=200",
+        "--election-timeout-max=300",
+        "--heartbeat-interval=20",
+    ])?;
+    assert_ne!(a.timing_hash(), d.timing_hash());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_election_grace_period() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.election_grace_period);
+
+    let config = Config::build(&["foo", "--election-grace-period=200"])?;
+    assert_eq!(200, config.election_grace_period);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_metrics_history_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.metrics_history_size);
+
+    let config = Config::build(&["foo", "--metrics-history-size=60"])?;
+    assert_eq!(60, config.metrics_history_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_vote_log_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(64, config.vote_log_size);
+
+    let config = Config::build(&["foo", "--vote-log-size=0"])?;
+    assert_eq!(0, config.vote_log_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_apply_rate_limit() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.apply_rate_limit);
+    assert_eq!(1000, config.apply_rate_interval);
+
+    let config = Config::build(&["foo", "--apply-rate-limit=10", "--apply-rate-interval=100"])?;
+    assert_eq!(Some(10), config.apply_rate_limit);
+    assert_eq!(100, config.apply_rate_interval);
+
+    let res = Config::build(&["foo", "--apply-rate-limit=0"]);
+    assert_eq!(Err(ConfigError::ApplyRateIs0), res.map(|_| ()));
+
+    let res = Config::build(&["foo", "--apply-rate-interval=0"]);
+    assert_eq!(Err(ConfigError::ApplyRateIs0), res.map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_apply_stall_backlog() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.apply_stall_backlog);
+
+    let config = Config::build(&["foo", "--apply-stall-backlog=100"])?;
+    assert_eq!(Some(100), config.apply_stall_backlog);
+
+    Ok(())
+}
@@ -14,6 +14,15 @@ pub enum ConfigError {
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    #[error("learner_replication_budget({budget}) must be in range [1, 100]")]
+    InvalidLearnerReplicationBudget { budget: u64 },
+
+    #[error("max_term_jump must be > 0")]
+    MaxTermJumpIs0,
+
+    #[error("max_term_log_gap must be > 0")]
+    MaxTermLogGapIs0,
+
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
         election_timeout_min: u64,
@@ -23,6 +32,24 @@ pub enum ConfigError {
     #[error("snapshot policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
     InvalidSnapshotPolicy { invalid: String, syntax: String },
 
+    #[error("label string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidLabel { invalid: String, syntax: String },
+
+    #[error("storage failure policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidStorageFailurePolicy { invalid: String, syntax: String },
+
+    #[error("snapshot_max_bytes_per_sec and snapshot_max_total_bytes_per_sec must be > 0")]
+    SnapshotBandwidthIs0,
+
+    #[error("storage_failure_threshold must be > 0")]
+    StorageFailureThresholdIs0,
+
+    #[error("apply_rate_limit and apply_rate_interval must be > 0")]
+    ApplyRateIs0,
+
+    #[error("append_rejection_threshold must be > 0")]
+    AppendRejectionThresholdIs0,
+
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber { invalid: String, reason: String },
 }
@@ -6,4 +6,5 @@ mod error;
 pub use config::Config;
 pub(crate) use config::RuntimeConfig;
 pub use config::SnapshotPolicy;
+pub use config::StorageFailurePolicy;
 pub use error::ConfigError;
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::raft::ClientWriteResponse;
+use crate::raft::CommitWait;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::ResponderOf;
+use crate::RaftTypeConfig;
+
+/// A client write waiting for a follower in a group to acknowledge its entry.
+pub(crate) struct PendingCommitWait<C>
+where C: RaftTypeConfig
+{
+    pub(crate) wait: CommitWait,
+
+    /// When the write fails if no follower in the group acknowledges the entry.
+    deadline: InstantOf<C>,
+
+    /// The responder and the response of the entry, held since the entry is applied until a
+    /// follower in the group acknowledges it.
+    pub(crate) response: Option<(ResponderOf<C>, ClientWriteResponse<C>)>,
+}
+
+/// The client writes with a [`CommitWait`], by log index.
+pub(crate) struct CommitWaits<C>
+where C: RaftTypeConfig
+{
+    waits: BTreeMap<u64, PendingCommitWait<C>>,
+}
+
+impl<C> Default for CommitWaits<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self { waits: BTreeMap::new() }
+    }
+}
+
+impl<C> CommitWaits<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn insert(&mut self, index: u64, wait: CommitWait, now: InstantOf<C>) {
+        let deadline = now + wait.timeout;
+        self.waits.insert(index, PendingCommitWait {
+            wait,
+            deadline,
+            response: None,
+        });
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.waits.is_empty()
+    }
+
+    pub(crate) fn contains(&self, index: u64) -> bool {
+        self.waits.contains_key(&index)
+    }
+
+    /// Hold the response of the applied entry at `index` until it is resolved.
+    pub(crate) fn hold(&mut self, index: u64, tx: ResponderOf<C>, resp: ClientWriteResponse<C>) {
+        if let Some(w) = self.waits.get_mut(&index) {
+            w.response = Some((tx, resp));
+        }
+    }
+
+    /// Remove and return the resolved writes, with whether they are acknowledged.
+    ///
+    /// A write is resolved as acknowledged once it is applied and `acked` returns `true`, or as
+    /// not acknowledged when its deadline is reached before that.
+    pub(crate) fn take_resolved(
+        &mut self,
+        now: InstantOf<C>,
+        acked: impl Fn(u64, &CommitWait) -> bool,
+    ) -> Vec<(u64, PendingCommitWait<C>, bool)> {
+        let mut resolved_indexes = vec![];
+
+        for (index, w) in self.waits.iter() {
+            if w.response.is_some() && acked(*index, &w.wait) {
+                resolved_indexes.push((*index, true));
+            } else if now >= w.deadline {
+                resolved_indexes.push((*index, false));
+            }
+        }
+
+        let mut resolved = Vec::with_capacity(resolved_indexes.len());
+        for (index, is_acked) in resolved_indexes {
+            // Safe unwrap: the index is just found.
+            let w = self.waits.remove(&index).unwrap();
+            resolved.push((index, w, is_acked));
+        }
+        resolved
+    }
+}
@@ -6,13 +6,19 @@
 
 pub(crate) mod balancer;
 pub(crate) mod command_state;
+mod commit_waits;
 pub(crate) mod notify;
+mod quiescence;
 mod raft_core;
 pub(crate) mod raft_msg;
+mod read_batch;
 mod replication_state;
 mod server_state;
 pub(crate) mod sm;
+mod storage_failures;
 mod tick;
+mod vote_requests;
+mod write_dedup;
 
 pub(crate) use raft_core::ApplyResult;
 pub(crate) use raft_core::ApplyingEntry;
@@ -1,9 +1,12 @@
 use std::fmt;
 
 use crate::core::sm;
+use crate::metrics::FollowerLogState;
 use crate::raft::VoteResponse;
 use crate::replication;
+use crate::type_config::alias::InstantOf;
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::Vote;
 
 /// A message coming from the internal components.
@@ -18,6 +21,15 @@ where C: RaftTypeConfig
         ///
         /// A vote identifies a unique server state.
         sender_vote: Vote<C::NodeId>,
+
+        /// When the vote request was sent, for measuring the RPC latency.
+        sending_time: InstantOf<C>,
+    },
+
+    /// The log state of a follower or learner, reported in a successful `AppendEntries` response.
+    FollowerLogState {
+        target: C::NodeId,
+        state: FollowerLogState<C>,
     },
 
     /// Seen a higher `vote`.
@@ -43,6 +55,23 @@ where C: RaftTypeConfig
     /// Result of executing a command sent from state machine worker.
     StateMachine { command_result: sm::CommandResult<C> },
 
+    /// Applying entries to the state machine failed and is being retried, or recovered.
+    ///
+    /// It is sent only if `Config::apply_stall_backlog` is set.
+    ApplyFailing {
+        /// The error of the last failed apply, or `None` if a retry succeeded.
+        error: Option<StorageError<C::NodeId>>,
+    },
+
+    /// The permit returned by the election admission hook is resolved.
+    ElectionAdmitted,
+
+    /// A round of leadership confirmation for read requests is finished.
+    ReadConfirmed {
+        /// The id of the finished round.
+        round: u64,
+    },
+
     /// A tick event to wake up RaftCore to check timeout etc.
     Tick {
         /// ith tick
@@ -70,6 +99,9 @@ where C: RaftTypeConfig
             } => {
                 write!(f, "VoteResponse: from: {}: {}, res-vote: {}", target, resp, vote)
             }
+            Self::FollowerLogState { target, state } => {
+                write!(f, "FollowerLogState: target: {}, state: {}", target, state)
+            }
             Self::HigherVote {
                 ref target,
                 higher: ref new_vote,
@@ -87,6 +119,16 @@ where C: RaftTypeConfig
             Self::StateMachine { command_result } => {
                 write!(f, "StateMachine command done: {:?}", command_result)
             }
+            Self::ApplyFailing { error } => match error {
+                Some(e) => write!(f, "ApplyFailing: {}", e),
+                None => write!(f, "ApplyFailing: recovered"),
+            },
+            Self::ElectionAdmitted => {
+                write!(f, "ElectionAdmitted")
+            }
+            Self::ReadConfirmed { round } => {
+                write!(f, "ReadConfirmed: round: {}", round)
+            }
             Self::Tick { i } => {
                 write!(f, "Tick {}", i)
             }
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use crate::type_config::alias::InstantOf;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// Tracks the activity of a Raft group, to tell when it has been idle long enough to quiesce.
+///
+/// An activity is a change of the last log id. It is observed on every tick, thus the time of an
+/// activity is accurate to a tick interval.
+pub(crate) struct Quiescence<C>
+where C: RaftTypeConfig
+{
+    /// The last log id when the last activity was observed.
+    last_log_id: Option<LogId<C::NodeId>>,
+
+    /// When the last activity was observed.
+    since: Option<InstantOf<C>>,
+
+    /// Whether the group was quiesced at the last check.
+    quiesced: bool,
+}
+
+impl<C> Default for Quiescence<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            last_log_id: None,
+            since: None,
+            quiesced: false,
+        }
+    }
+}
+
+impl<C> Quiescence<C>
+where C: RaftTypeConfig
+{
+    /// Observe the last log id at `now`, and return if no activity happened in the last `timeout`.
+    pub(crate) fn is_idle(
+        &mut self,
+        last_log_id: Option<&LogId<C::NodeId>>,
+        now: InstantOf<C>,
+        timeout: Duration,
+    ) -> bool {
+        if self.since.is_none() || self.last_log_id.as_ref() != last_log_id {
+            self.last_log_id = last_log_id.copied();
+            self.since = Some(now);
+        }
+
+        // Safe unwrap: `since` is set above.
+        now - self.since.unwrap() >= timeout
+    }
+
+    /// Record whether the group is quiesced, and return `true` if it changed.
+    pub(crate) fn set_quiesced(&mut self, quiesced: bool) -> bool {
+        let changed = self.quiesced != quiesced;
+        self.quiesced = quiesced;
+        changed
+    }
+}
@@ -1,5 +1,6 @@
 use std::borrow::Borrow;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -7,6 +8,7 @@ use std::marker::PhantomData;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::SystemTime;
 
 use anyerror::AnyError;
 use futures::stream::FuturesUnordered;
@@ -25,52 +27,95 @@ use crate::config::Config;
 use crate::config::RuntimeConfig;
 use crate::core::balancer::Balancer;
 use crate::core::command_state::CommandState;
+use crate::core::commit_waits::CommitWaits;
 use crate::core::notify::Notify;
+use crate::core::quiescence::Quiescence;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::AppendEntriesTx;
+use crate::core::raft_msg::ClientPayload;
 use crate::core::raft_msg::ClientReadTx;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::ResultSender;
 use crate::core::raft_msg::VoteTx;
+use crate::core::read_batch::ReadBatch;
+use crate::core::replication_lag;
 use crate::core::sm;
 use crate::core::sm::handle;
 use crate::core::sm::CommandSeq;
+use crate::core::storage_failures;
+use crate::core::storage_failures::StorageFailures;
+use crate::core::vote_requests::VoteRequests;
+use crate::core::write_dedup::WriteDedup;
 use crate::core::ServerState;
 use crate::display_ext::DisplayOption;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::DecisionTrace;
 use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::Engine;
 use crate::engine::Respond;
 use crate::entry::FromAppData;
 use crate::entry::RaftEntry;
+use crate::error::ApplyResultLost;
+use crate::error::ApplySkipped;
+use crate::error::ApplyStalled;
+use crate::error::CatchUpError;
+use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
+use crate::error::CommitWaitTimeout;
 use crate::error::Fatal;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::LearnerIsLagging;
+use crate::error::NoConfiguration;
+use crate::error::NodeIsWitness;
 use crate::error::QuorumNotEnough;
 use crate::error::RPCError;
+use crate::error::RaftError;
+use crate::error::RecentlyAdded;
+use crate::error::RemoteError;
+use crate::error::Sealed;
+use crate::error::SetAppliedIndexError;
+use crate::error::ShuttingDown;
+use crate::error::StorageDegraded;
 use crate::error::Timeout;
+use crate::error::WitnessNotVoter;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id::RaftLogId;
+use crate::metrics::CommitLatencyWindow;
+use crate::metrics::FollowerLogState;
+use crate::metrics::LatencyWindow;
+use crate::metrics::MetricsHistory;
+use crate::metrics::MetricsSample;
+use crate::metrics::PendingWrites;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ShutdownReport;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::network::RaftNetwork;
 use crate::network::RaftNetworkFactory;
+use crate::network::SnapshotBandwidth;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
 use crate::progress::Progress;
 use crate::quorum::QuorumSet;
+use crate::raft::feed_committed;
+use crate::raft::responder;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::AppendEntriesValidator;
 use crate::raft::ClientWriteResponse;
+use crate::raft::CommitWait;
+use crate::raft::Committed;
+use crate::raft::CoreAccess;
+use crate::raft::ElectionAdmission;
 use crate::raft::VoteRequest;
 use crate::raft_state::LogStateReader;
 use crate::replication;
@@ -82,6 +127,7 @@ use crate::replication::ReplicationHandle;
 use crate::replication::ReplicationSessionId;
 use crate::runtime::RaftRuntime;
 use crate::storage::LogFlushed;
+use crate::storage::RaftLogReader;
 use crate::storage::RaftLogReaderExt;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
@@ -97,9 +143,13 @@ use crate::Membership;
 use crate::OptionalSend;
 use crate::RaftTypeConfig;
 use crate::StorageError;
+use crate::StorageFailurePolicy;
 use crate::StorageIOError;
 use crate::Vote;
 
+/// The max number of pending client responders checked for a closed client per heartbeat.
+const SWEEP_CLOSED_RESPONDERS: usize = 256;
+
 /// A temp struct to hold the data for a node that is being applied.
 #[derive(Debug)]
 pub(crate) struct ApplyingEntry<C: RaftTypeConfig> {
@@ -115,16 +165,22 @@ impl<C: RaftTypeConfig> ApplyingEntry<C> {
 
 /// The result of applying log entries to state machine.
 pub(crate) struct ApplyResult<C: RaftTypeConfig> {
+    /// Entries in `[skip_since, since)` are not applied, because the state machine is restored
+    /// out-of-band, see `Raft::set_applied_index()`.
+    pub(crate) skip_since: u64,
     pub(crate) since: u64,
     pub(crate) end: u64,
     pub(crate) last_applied: LogId<C::NodeId>,
     pub(crate) applying_entries: Vec<ApplyingEntry<C>>,
     pub(crate) apply_results: Vec<C::R>,
+    /// The greatest timestamp of the applied entries, if any of them is stamped.
+    pub(crate) last_timestamp: Option<u64>,
 }
 
 impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ApplyResult")
+            .field("skip_since", &self.skip_since)
             .field("since", &self.since)
             .field("end", &self.end)
             .field("last_applied", &self.last_applied)
@@ -143,6 +199,27 @@ pub(crate) struct LeaderData<C: RaftTypeConfig> {
 
     /// The time to send next heartbeat.
     pub(crate) next_heartbeat: InstantOf<C>,
+
+    /// The replication hints last saved to the log store.
+    pub(crate) saved_replication_hints: BTreeMap<C::NodeId, u64>,
+
+    /// The time every client write not yet committed is appended, by log index.
+    pub(crate) append_times: BTreeMap<u64, InstantOf<C>>,
+
+    /// The append-to-commit latency of the recent client writes.
+    pub(crate) commit_latency: CommitLatencyWindow,
+
+    /// The time every node is added to the membership by this leader, to apply
+    /// [`Config::member_removal_grace`].
+    pub(crate) member_added_at: BTreeMap<C::NodeId, InstantOf<C>>,
+
+    /// The time the last client write or read is received, to apply
+    /// [`Config::leader_idle_timeout`].
+    pub(crate) last_client_activity: InstantOf<C>,
+
+    /// The time the last idle check heartbeat round is sent, `None` if none is sent since the last
+    /// client activity.
+    pub(crate) idle_check_sent_at: Option<InstantOf<C>>,
 }
 
 impl<C: RaftTypeConfig> LeaderData<C> {
@@ -150,6 +227,12 @@ impl<C: RaftTypeConfig> LeaderData<C> {
         Self {
             replications: BTreeMap::new(),
             next_heartbeat: InstantOf::<C>::now(),
+            saved_replication_hints: BTreeMap::new(),
+            append_times: BTreeMap::new(),
+            commit_latency: CommitLatencyWindow::new(),
+            member_added_at: BTreeMap::new(),
+            last_client_activity: InstantOf::<C>::now(),
+            idle_check_sent_at: None,
         }
     }
 }
@@ -185,8 +268,68 @@ where
     /// Channels to send result back to client when logs are applied.
     pub(crate) client_resp_channels: BTreeMap<u64, ResponderOf<C>>,
 
+    /// The log index to resume the sweep of closed responders in `client_resp_channels` from.
+    pub(crate) responder_sweep_cursor: u64,
+
+    /// The time every client write in `client_resp_channels` is submitted, by log index.
+    ///
+    /// It may contain indexes already removed from `client_resp_channels`, which are ignored.
+    pub(crate) client_submit_times: BTreeMap<u64, InstantOf<C>>,
+
     pub(crate) leader_data: Option<LeaderData<C>>,
 
+    /// Whether this node is prepared for shutdown: new client writes are rejected.
+    pub(crate) shutting_down: bool,
+
+    /// The deadline and the sender of a `prepare_shutdown()` request waiting for in-flight
+    /// operations to settle.
+    pub(crate) shutdown_waiter: Option<(InstantOf<C>, ResultSender<C, ShutdownReport<C>>)>,
+
+    /// The error of the last failed apply, while it is being retried.
+    pub(crate) apply_error: Option<StorageError<C::NodeId>>,
+
+    /// When to ask the state machine worker to retry the failed apply.
+    pub(crate) apply_retry_at: Option<InstantOf<C>>,
+
+    /// The greatest entry timestamp, in milliseconds, this node has stamped, appended or applied.
+    ///
+    /// A leader never stamps an entry with a timestamp below it.
+    pub(crate) last_timestamp: u64,
+
+    /// Linearizable read requests waiting for the leadership to be confirmed.
+    pub(crate) read_batch: ReadBatch<C>,
+
+    /// Consecutive failures of storage commands, for applying the `storage_failure_policy`.
+    pub(crate) storage_failures: StorageFailures<C>,
+
+    /// Vote requests of the current election round that may still be in flight.
+    pub(crate) vote_requests: VoteRequests<C>,
+
+    /// Tracks the activity of this group, to quiesce it after `Config::quiesce_timeout`.
+    pub(crate) quiescence: Quiescence<C>,
+
+    /// The most recent metrics samples, taken on every tick.
+    pub(crate) metrics_history: MetricsHistory<C>,
+
+    /// The application defined check on received AppendEntries requests.
+    pub(crate) append_entries_validator: Option<Box<dyn AppendEntriesValidator<C>>>,
+
+    /// The application defined hook to consult before starting an election.
+    pub(crate) election_admission: Option<Box<dyn ElectionAdmission<C>>>,
+
+    /// The vote of this node when an election permit was requested, while the permit is pending.
+    pub(crate) admission_waiting: Option<Vote<C::NodeId>>,
+
+    /// Whether this node is campaigning in an election admitted by the election admission hook.
+    pub(crate) admitted_campaign: bool,
+
+    /// Deduplicates consecutive identical client writes, if a deduplicator is set.
+    pub(crate) write_dedup: Option<WriteDedup<C>>,
+
+    /// The membership log id and the nodes that are registered with
+    /// [`RaftNetworkFactory::register_node()`].
+    pub(crate) registered_nodes: (Option<LogId<C::NodeId>>, BTreeMap<C::NodeId, C::Node>),
+
     #[allow(dead_code)]
     pub(crate) tx_api: mpsc::UnboundedSender<RaftMsg<C>>,
     pub(crate) rx_api: mpsc::UnboundedReceiver<RaftMsg<C>>,
@@ -202,6 +345,33 @@ where
     pub(crate) tx_data_metrics: watch::Sender<RaftDataMetrics<C>>,
     pub(crate) tx_server_metrics: watch::Sender<RaftServerMetrics<C>>,
 
+    /// The latency of the recent RPCs sent to each peer.
+    pub(crate) rpc_latency: BTreeMap<C::NodeId, LatencyWindow>,
+
+    /// The log state of each follower and learner reported to this leader.
+    pub(crate) follower_log_state: BTreeMap<C::NodeId, FollowerLogState<C>>,
+
+    /// The peers whose [`Config::timing_hash()`] differs from this node's, and their hash.
+    pub(crate) config_mismatches: BTreeMap<C::NodeId, u64>,
+
+    /// Limits and measures the bandwidth of snapshot transfers to all targets.
+    pub(crate) snapshot_bandwidth: SnapshotBandwidth,
+
+    /// The bytes of snapshot data sent and when it was sampled, to compute the send rate.
+    pub(crate) snapshot_sent_sample: Option<(u64, InstantOf<C>)>,
+
+    /// The snapshot send rate in bytes per second, sampled on every tick.
+    pub(crate) snapshot_send_rate: u64,
+
+    /// The static labels of this node, from `Config::labels`.
+    pub(crate) labels: Arc<BTreeMap<String, String>>,
+
+    /// The labels of the other nodes, set with `Raft::set_peer_labels()`.
+    pub(crate) peer_labels: BTreeMap<C::NodeId, BTreeMap<String, String>>,
+
+    /// Client writes waiting for a follower in a group to acknowledge their entries.
+    pub(crate) commit_waits: CommitWaits<C>,
+
     pub(crate) command_state: CommandState,
 
     pub(crate) span: Span,
@@ -251,6 +421,10 @@ where
     async fn do_main(&mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         tracing::debug!("raft node is initializing");
 
+        if self.config.check_log_gaps_at_startup {
+            self.check_log_gaps_at_startup().await?;
+        }
+
         self.engine.startup();
         // It may not finish running all of the commands, if there is a command waiting for a callback.
         self.run_engine_commands().await?;
@@ -263,8 +437,8 @@ where
 
     /// Handle `is_leader` requests.
     ///
-    /// Send heartbeat to all voters. We respond once we have
-    /// a quorum of agreement.
+    /// The request is queued and is confirmed along with other queued requests by a single round
+    /// of heartbeats to all voters. We respond once we have a quorum of agreement.
     ///
     /// Why:
     /// To ensure linearizability, a read request proposed at time `T1` confirms this node's
@@ -274,27 +448,56 @@ where
     //       at `T1` is committed.
     #[tracing::instrument(level = "trace", skip(self, tx))]
     pub(super) async fn handle_check_is_leader_request(&mut self, tx: ClientReadTx<C>) {
-        // Setup sentinel values to track when we've received majority confirmation of leadership.
-
-        let resp = {
-            let l = self.engine.leader_handler();
-            let lh = match l {
-                Ok(leading_handler) => leading_handler,
-                Err(forward) => {
-                    let _ = tx.send(Err(forward.into()));
-                    return;
-                }
-            };
+        self.read_batch.push(tx);
+        self.confirm_pending_reads().await;
+    }
 
-            let read_log_id = lh.get_read_log_id();
+    /// Start a round of heartbeats to confirm the leadership for all queued read requests.
+    ///
+    /// It does nothing if there is already a round in flight:
+    /// requests queued meanwhile will be confirmed by the next round, which is started when the
+    /// current round finishes.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn confirm_pending_reads(&mut self) {
+        let Some((round, txs)) = self.read_batch.start_round() else {
+            return;
+        };
 
-            // TODO: this applied is a little stale when being returned to client.
-            //       Fix this when the following heartbeats are replaced with calling RaftNetwork.
-            let applied = self.engine.state.io_applied().copied();
+        tracing::debug!(
+            round = display(round),
+            n = display(txs.len()),
+            "confirm leadership for reads"
+        );
 
-            (read_log_id, applied)
+        let ready = match self.engine.leader_handler() {
+            Ok(lh) => lh.check_ready_to_read().map(|_| lh.get_read_log_id()),
+            Err(_forward) => {
+                for tx in txs {
+                    self.reject_with_forward_to_leader(tx);
+                }
+                self.read_batch.finish_round(round);
+                return;
+            }
+        };
+
+        let read_log_id = match ready {
+            Ok(read_log_id) => read_log_id,
+            Err(not_ready) => {
+                tracing::debug!(error = display(&not_ready), "leader is not ready to serve reads");
+                for tx in txs {
+                    let _ = tx.send(Err(not_ready.clone().into()));
+                }
+                self.read_batch.finish_round(round);
+                return;
+            }
         };
 
+        // TODO: this applied is a little stale when being returned to client.
+        //       Fix this when the following heartbeats are replaced with calling RaftNetwork.
+        let applied = self.engine.state.io_applied().copied();
+
+        let resp = (read_log_id, applied);
+
         let my_id = self.id;
         let my_vote = *self.engine.state.vote_ref();
         let ttl = Duration::from_millis(self.config.heartbeat_interval);
@@ -304,10 +507,31 @@ where
         let mut granted = btreeset! {my_id};
 
         if eff_mem.is_quorum(granted.iter()) {
-            let _ = tx.send(Ok(resp));
+            for tx in txs {
+                let _ = tx.send(Ok(resp));
+            }
+            self.read_batch.finish_round(round);
             return;
         }
 
+        // Within the read lease, no other leader can be elected: serve the reads without a round
+        // of heartbeats.
+        if let Some(lease) = self.config.read_lease() {
+            let acked = self.last_quorum_acked_time();
+            if acked.map_or(false, |t| InstantOf::<C>::now() < t + lease) {
+                tracing::debug!(
+                    lease = debug(lease),
+                    acked = debug(acked),
+                    "serve reads within the read lease"
+                );
+                for tx in txs {
+                    let _ = tx.send(Ok(resp));
+                }
+                self.read_batch.finish_round(round);
+                return;
+            }
+        }
+
         // Spawn parallel requests, all with the standard timeout for heartbeats.
         let mut pending = FuturesUnordered::new();
 
@@ -323,12 +547,15 @@ where
                 continue;
             }
 
-            let rpc = AppendEntriesRequest {
-                vote: my_vote,
-                prev_log_id: progress.matching,
-                entries: vec![],
-                leader_commit: self.engine.state.committed().copied(),
-            };
+            let mut rpc = AppendEntriesRequest::new(
+                my_vote,
+                progress.matching,
+                vec![],
+                self.engine.state.committed().copied(),
+            );
+            if self.config.exchange_config_hash {
+                rpc = rpc.with_config_hash(self.config.timing_hash());
+            }
 
             // Safe unwrap(): target is in membership
             let target_node = eff_mem.get_node(&target).unwrap().clone();
@@ -362,7 +589,7 @@ where
             pending.push(task);
         }
 
-        let waiting_fu = async move {
+        let confirm_fu = async move {
             // Handle responses as they return.
             while let Some(res) = pending.next().await {
                 let (target, append_res) = match res {
@@ -380,12 +607,15 @@ where
                 // If we receive a response with a greater vote, then revert to follower and abort this
                 // request.
                 if let AppendEntriesResponse::HigherVote(vote) = append_res {
-                    debug_assert!(
-                        vote > my_vote,
-                        "committed vote({}) has total order relation with other votes({})",
-                        my_vote,
-                        vote
-                    );
+                    // A vote that is not greater is responded by an `AppendEntriesValidator`
+                    // rejecting this leader: the target does not grant the leadership.
+                    if vote <= my_vote {
+                        tracing::warn!(
+                            target = display(target),
+                            "heartbeat is rejected by target validator while confirming leadership"
+                        );
+                        continue;
+                    }
 
                     let send_res = core_tx.send(Notify::HigherVote {
                         target,
@@ -398,30 +628,38 @@ where
                     }
 
                     // we are no longer leader so error out early
-                    let err = ForwardToLeader::empty();
-                    let _ = tx.send(Err(err.into()));
-                    return;
+                    return Err(ForwardToLeader::empty().into());
                 }
 
                 granted.insert(target);
 
                 if eff_mem.is_quorum(granted.iter()) {
-                    let _ = tx.send(Ok(resp));
-                    return;
+                    return Ok(resp);
                 }
             }
 
             // If we've hit this location, then we've failed to gather needed confirmations due to
             // request failures.
 
-            let _ = tx.send(Err(QuorumNotEnough {
+            Err(QuorumNotEnough {
                 cluster: eff_mem.membership().to_string(),
                 got: granted,
             }
-            .into()));
+            .into())
         };
 
-        // TODO: do not spawn, manage read requests with a queue by RaftCore
+        let core_tx = self.tx_notify.clone();
+
+        let waiting_fu = async move {
+            let res: Result<_, CheckIsLeaderError<C>> = confirm_fu.await;
+
+            for tx in txs {
+                let _ = tx.send(res.clone());
+            }
+
+            // Let RaftCore start the next round for the reads queued meanwhile.
+            let _ = core_tx.send(Notify::ReadConfirmed { round });
+        };
 
         // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
         #[allow(clippy::let_underscore_future)]
@@ -450,6 +688,7 @@ where
         &mut self,
         changes: ChangeMembers<C::NodeId, C::Node>,
         retain: bool,
+        force: bool,
         tx: ResponderOf<C>,
     ) {
         let res = self.engine.state.membership_state.change_handler().apply(changes, retain);
@@ -461,10 +700,200 @@ where
             }
         };
 
+        if let Err(e) = self.check_promoted_learners(&new_membership) {
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
+        if let Err(e) = self.check_removed_members(&new_membership, force) {
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
+        let added = {
+            let effective = self.engine.state.membership_state.effective();
+            new_membership
+                .nodes()
+                .map(|(id, _)| *id)
+                .filter(|id| effective.get_node(id).is_none())
+                .collect::<Vec<_>>()
+        };
+
+        let ent = C::Entry::new_membership(LogId::default(), new_membership);
+        if self.write_entry(ent, Some(tx)) {
+            if let Some(l) = &mut self.leader_data {
+                let now = InstantOf::<C>::now();
+                l.member_added_at.extend(added.into_iter().map(|id| (id, now)));
+            }
+        }
+    }
+
+    /// Check that no node removed by `new_membership` is added within
+    /// [`Config::member_removal_grace`].
+    ///
+    /// A node is removed if it is no longer in `new_membership`, or it is a voter and is not a
+    /// voter in the last config of `new_membership`. Thus a node removed with a joint config is
+    /// checked when the joint config is proposed.
+    ///
+    /// If `force` is `true`, it only warns about such a node.
+    fn check_removed_members(&mut self, new_membership: &Membership<C>, force: bool) -> Result<(), RecentlyAdded<C>> {
+        let Some(l) = &mut self.leader_data else {
+            return Ok(());
+        };
+
+        let grace = Duration::from_millis(self.config.member_removal_grace);
+
+        let effective = self.engine.state.membership_state.effective();
+        let last_voters = new_membership.get_joint_config().last().cloned().unwrap_or_default();
+
+        let removed = effective
+            .nodes()
+            .map(|(id, _)| *id)
+            .filter(|id| new_membership.get_node(id).is_none() || (effective.is_voter(id) && !last_voters.contains(id)))
+            .collect::<Vec<_>>();
+
+        for node_id in removed {
+            let Some(added_at) = l.member_added_at.get(&node_id) else {
+                continue;
+            };
+
+            let since_added = added_at.elapsed();
+            if since_added >= grace {
+                l.member_added_at.remove(&node_id);
+                continue;
+            }
+
+            let err = RecentlyAdded {
+                node_id,
+                since_added,
+                grace,
+            };
+
+            if !force {
+                tracing::warn!(error = display(&err), "reject removing a recently added node");
+                return Err(err);
+            }
+
+            tracing::warn!(error = display(&err), "force removing a recently added node");
+        }
+
+        Ok(())
+    }
+
+    /// Seal or unseal the cluster by proposing a membership entry that has the same config as the
+    /// effective one, but with the `sealed` flag set to `sealed`.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(super) fn set_sealed(&mut self, sealed: bool, tx: ResponderOf<C>) {
+        let change_handler = self.engine.state.membership_state.change_handler();
+
+        if let Err(e) = change_handler.ensure_committed() {
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
+        let new_membership = self.engine.state.membership_state.effective().membership().clone().with_sealed(sealed);
+
+        let ent = C::Entry::new_membership(LogId::default(), new_membership);
+        self.write_entry(ent, Some(tx));
+    }
+
+    /// Make voters witnesses by proposing a membership entry that has the same config as the
+    /// effective one, but with `witnesses` added as witnesses.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(super) fn add_witnesses(&mut self, witnesses: BTreeSet<C::NodeId>, tx: ResponderOf<C>) {
+        let change_handler = self.engine.state.membership_state.change_handler();
+
+        if let Err(e) = change_handler.ensure_committed() {
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
+        let membership = self.engine.state.membership_state.effective().membership();
+
+        for node_id in witnesses.iter() {
+            if !membership.is_voter(node_id) {
+                let err = WitnessNotVoter { node_id: *node_id };
+                tx.send(Err(ClientWriteError::ChangeMembershipError(err.into())));
+                return;
+            }
+        }
+
+        let new_membership = membership.clone().with_witnesses(witnesses);
+
         let ent = C::Entry::new_membership(LogId::default(), new_membership);
         self.write_entry(ent, Some(tx));
     }
 
+    /// Return an error if the cluster is sealed and client writes should be rejected.
+    ///
+    /// The cluster is considered sealed if either the effective or the committed membership is
+    /// sealed: a seal takes effect as soon as it is proposed, while an unseal takes effect only
+    /// after it is committed.
+    ///
+    /// It does nothing if this node is not a leader: the write will be rejected with
+    /// `ForwardToLeader`.
+    fn ensure_not_sealed(&self) -> Result<(), Sealed<C>> {
+        if !self.engine.state.is_leader(&self.id) {
+            return Ok(());
+        }
+
+        let membership_state = &self.engine.state.membership_state;
+
+        for em in [membership_state.effective(), membership_state.committed()] {
+            if em.membership().is_sealed() {
+                return Err(Sealed {
+                    membership_log_id: *em.log_id(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every learner to promote to voter in `new_membership` has caught up with the
+    /// leader.
+    ///
+    /// A learner is considered caught up if it lags behind the leader's last log by no more than
+    /// [`Config::replication_lag_threshold`]. If any of them is lagging, the whole change is
+    /// rejected, so that multiple learners are promoted atomically.
+    ///
+    /// It does nothing if [`Config::reject_lagging_promotion`] is disabled, or if this node is not
+    /// a leader: the change will be rejected when writing the membership entry.
+    fn check_promoted_learners(&self, new_membership: &Membership<C>) -> Result<(), LearnerIsLagging<C>> {
+        if !self.config.reject_lagging_promotion {
+            return Ok(());
+        }
+
+        let Some(leading) = self.engine.internal_server_state.leading() else {
+            return Ok(());
+        };
+
+        let effective = self.engine.state.membership_state.effective();
+        let last_log_index = self.engine.state.last_log_id().index();
+
+        for node_id in new_membership.voter_ids() {
+            if effective.is_voter(&node_id) {
+                continue;
+            }
+
+            let Some(prog_entry) = leading.progress.try_get(&node_id) else {
+                continue;
+            };
+
+            let distance = replication_lag(&prog_entry.matching.index(), &last_log_index);
+
+            if distance > self.config.replication_lag_threshold {
+                return Err(LearnerIsLagging {
+                    node_id,
+                    matching: prog_entry.matching,
+                    distance,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write a log entry to the cluster through raft protocol.
     ///
     /// I.e.: append the log entry to local store, forward it to a quorum(including the leader),
@@ -473,9 +902,17 @@ where
     /// The result of applying it to state machine is sent to `resp_tx`, if it is not `None`.
     /// The calling side may not receive a result from `resp_tx`, if raft is shut down.
     #[tracing::instrument(level = "debug", skip_all, fields(id = display(self.id)))]
-    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<ResponderOf<C>>) -> bool {
+    pub fn write_entry(&mut self, mut entry: C::Entry, resp_tx: Option<ResponderOf<C>>) -> bool {
         tracing::debug!(payload = display(&entry), "write_entry");
 
+        // Stamp the entry with the leader's wall clock, so that every node applies it with the same
+        // time. The wall clock may go backward, e.g., after a leader change to a node with a slower
+        // clock, thus the timestamp never goes below the last one seen.
+        let now = AsyncRuntimeOf::<C>::wall_clock().as_millis() as u64;
+        let timestamp = std::cmp::max(now, self.last_timestamp);
+        self.last_timestamp = timestamp;
+        entry.set_timestamp(timestamp);
+
         let (mut lh, tx) = if let Some((lh, tx)) = self.engine.get_leader_handler_or_reject(resp_tx) {
             (lh, tx)
         } else {
@@ -491,11 +928,64 @@ where
         // Install callback channels.
         if let Some(tx) = tx {
             self.client_resp_channels.insert(index, tx);
+
+            let now = InstantOf::<C>::now();
+            self.client_submit_times.insert(index, now);
+
+            if let Some(l) = &mut self.leader_data {
+                l.append_times.insert(index, now);
+            }
         }
 
         true
     }
 
+    /// Write the app data of a client write, unless it is deduplicated into the last entry.
+    ///
+    /// See [`WriteDeduplicator`](crate::raft::WriteDeduplicator). The `metadata` is not compared
+    /// for deduplication.
+    fn write_app_data(&mut self, app_data: C::D, metadata: Option<Vec<u8>>, tx: ResponderOf<C>) {
+        let new_entry = |app_data| {
+            let mut entry = C::Entry::from_app_data(app_data);
+            if let Some(metadata) = metadata {
+                entry.set_metadata(metadata);
+            }
+            entry
+        };
+
+        let Some(dedup) = &mut self.write_dedup else {
+            self.write_entry(new_entry(app_data), Some(tx));
+            return;
+        };
+
+        let hash = dedup.content_hash(&app_data);
+
+        if self.engine.leader_handler().is_ok() {
+            if let Some(log_id) = dedup.duplicate_of(hash, self.engine.state.last_log_id()) {
+                tracing::debug!(log_id = display(log_id), "client write is deduplicated");
+
+                if let Some(data) = dedup.applied_response(&log_id) {
+                    tx.send(Ok(ClientWriteResponse {
+                        log_id,
+                        data,
+                        membership: None,
+                    }));
+                } else {
+                    dedup.wait(log_id.index, tx);
+                }
+                return;
+            }
+        }
+
+        if self.write_entry(new_entry(app_data), Some(tx)) {
+            // Safe unwrap: an entry is just appended.
+            let log_id = *self.engine.state.last_log_id().unwrap();
+            if let Some(dedup) = &mut self.write_dedup {
+                dedup.written(log_id, hash);
+            }
+        }
+    }
+
     /// Send a heartbeat message to every followers/learners.
     ///
     /// Currently heartbeat is a blank log
@@ -531,6 +1021,251 @@ where
         self.report_metrics(leader_metrics);
     }
 
+    /// Skip applying entries up to `index`, because the state machine is restored out-of-band.
+    ///
+    /// `index` must not be greater than the committed index.
+    fn set_applied_index(&mut self, index: u64) -> Result<(), SetAppliedIndexError<C>> {
+        let committed = self.engine.state.committed().copied();
+
+        if Some(index) > committed.index() {
+            tracing::error!(
+                index,
+                committed = display(committed.display()),
+                "refuse to set applied index beyond committed"
+            );
+            return Err(SetAppliedIndexError::BeyondCommitted { index, committed });
+        }
+
+        let applied = self.engine.state.io_applied().copied();
+        if Some(index) <= applied.index() {
+            tracing::info!(
+                index,
+                applied = display(applied.display()),
+                "applied index is already greater than or equal to the given one"
+            );
+            return Ok(());
+        }
+
+        tracing::warn!(
+            index,
+            applied = display(applied.display()),
+            committed = display(committed.display()),
+            "DANGEROUS: set applied index by operator, entries up to it will not be applied"
+        );
+
+        self.sm_handle.skip_apply_upto(index);
+        Ok(())
+    }
+
+    /// Save the matching log index of every replication target if they changed since the last
+    /// save, so that this node can resume replication near them when re-elected after a restart.
+    fn save_replication_hints(&mut self) {
+        let Some(leading) = self.engine.internal_server_state.leading() else {
+            return;
+        };
+        let Some(leader_data) = &mut self.leader_data else {
+            return;
+        };
+
+        let hints = leading
+            .progress
+            .iter()
+            .filter(|(id, _)| *id != self.id)
+            .filter_map(|(id, p)| p.matching.map(|m| (*id, m.index)))
+            .collect::<BTreeMap<_, _>>();
+
+        if hints == leader_data.saved_replication_hints {
+            return;
+        }
+
+        leader_data.saved_replication_hints = hints.clone();
+        self.engine.output.push_command(Command::SaveReplicationHints { hints });
+    }
+
+    /// Update the snapshot send rate with the bytes sent since the last sample.
+    fn sample_snapshot_send_rate(&mut self, now: InstantOf<C>) {
+        let sent = self.snapshot_bandwidth.sent_bytes();
+
+        if let Some((prev, at)) = self.snapshot_sent_sample {
+            let elapsed = now - at;
+            if elapsed.is_zero() {
+                return;
+            }
+            self.snapshot_send_rate = ((sent - prev) as f64 / elapsed.as_secs_f64()) as u64;
+        }
+
+        self.snapshot_sent_sample = Some((sent, now));
+    }
+
+    /// Add the current metrics to the metrics history.
+    fn sample_metrics(&mut self) {
+        self.metrics_history.push(MetricsSample {
+            time: SystemTime::now(),
+            metrics: self.tx_metrics.borrow().clone(),
+        });
+    }
+
+    /// Record that a client write or read is received, which resets the leader idle check.
+    fn note_client_activity(&mut self) {
+        if let Some(l) = &mut self.leader_data {
+            l.last_client_activity = InstantOf::<C>::now();
+            l.idle_check_sent_at = None;
+        }
+    }
+
+    /// Check if this leader has served no client request for `Config::leader_idle_timeout`, in
+    /// which case it sends a round of heartbeats to reaffirm its authority.
+    ///
+    /// If a quorum has not acknowledged the previous round when the next one is due, this leader
+    /// can not reach a quorum and it steps down.
+    fn check_leader_idle(&mut self, now: InstantOf<C>) {
+        let Some(timeout) = self.config.leader_idle_timeout else {
+            return;
+        };
+        let Some(l) = &self.leader_data else {
+            return;
+        };
+
+        let last_check = l.idle_check_sent_at.unwrap_or(l.last_client_activity);
+        if now < last_check + Duration::from_millis(timeout) {
+            return;
+        }
+
+        if let Some(sent_at) = l.idle_check_sent_at {
+            let acked = self.last_quorum_acked_time();
+            if acked.map_or(true, |t| t < sent_at) {
+                tracing::warn!(
+                    sent_at = debug(sent_at),
+                    last_quorum_acked = debug(acked),
+                    "idle leader can not reach a quorum, step down"
+                );
+                self.engine.step_down();
+                return;
+            }
+        }
+
+        tracing::debug!(now = debug(now), "leader is idle, reaffirm its authority");
+        self.send_heartbeat("idle-check");
+
+        if let Some(l) = &mut self.leader_data {
+            l.idle_check_sent_at = Some(now);
+        }
+    }
+
+    /// Check if this group has been idle for `Config::quiesce_timeout`, in which case the leader
+    /// stops sending heartbeats and a follower stops timing out into elections.
+    ///
+    /// Only a group with nothing left to replicate is quiesced: every log is committed, and the
+    /// leader has replicated every log to every target. A follower is quiesced only if it follows
+    /// a leader, i.e., its vote is committed.
+    fn check_quiesced(&mut self, now: InstantOf<C>) -> bool {
+        let Some(timeout) = self.config.quiesce_timeout else {
+            return false;
+        };
+
+        let st = &self.engine.state;
+        let idle = self.quiescence.is_idle(st.last_log_id(), now, Duration::from_millis(timeout));
+
+        let replicated = if let Some(leading) = self.engine.internal_server_state.leading() {
+            leading
+                .progress
+                .iter()
+                .filter(|(id, _)| *id != self.id)
+                .all(|(_, p)| p.matching.as_ref() == st.last_log_id())
+        } else {
+            st.vote_ref().is_committed()
+        };
+
+        let quiesced = idle && replicated && st.committed() == st.last_log_id();
+
+        if self.quiescence.set_quiesced(quiesced) {
+            tracing::info!(
+                quiesced,
+                last_log_id = display(st.last_log_id().display()),
+                "quiescence changed"
+            );
+        }
+
+        quiesced
+    }
+
+    /// Reject writes if this node is degraded because its storage keeps failing.
+    fn ensure_storage_not_degraded(&self) -> Result<(), StorageDegraded<C>> {
+        if self.storage_failures.is_degraded() {
+            return Err(StorageDegraded {
+                node_id: self.id,
+                failures: self.storage_failures.count(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reject writes if applying keeps failing and more than `Config::apply_stall_backlog`
+    /// committed entries are waiting to be applied.
+    fn ensure_apply_not_stalled(&self) -> Result<(), ApplyStalled<C>> {
+        let (Some(threshold), Some(_)) = (self.config.apply_stall_backlog, &self.apply_error) else {
+            return Ok(());
+        };
+
+        let st = &self.engine.state;
+        let backlog = st.committed().next_index().saturating_sub(st.io_applied().next_index());
+
+        if backlog > threshold {
+            return Err(ApplyStalled {
+                node_id: self.id,
+                backlog,
+                threshold,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reject writes if this node is prepared for shutdown.
+    fn ensure_not_shutting_down(&self) -> Result<(), ShuttingDown<C>> {
+        if self.shutting_down {
+            return Err(ShuttingDown { node_id: self.id });
+        }
+
+        Ok(())
+    }
+
+    /// Reject writes if this node has no voter in its membership config.
+    ///
+    /// Such a node can not tell which node is the leader to forward to, until it is initialized or
+    /// receives a membership config from a leader.
+    fn ensure_configured(&self) -> Result<(), NoConfiguration<C>> {
+        if !self.engine.state.membership_state.is_configured() {
+            return Err(NoConfiguration { node_id: self.id });
+        }
+
+        Ok(())
+    }
+
+    /// Register the nodes that are added or updated in the effective membership to the network.
+    async fn register_nodes(&mut self) {
+        let effective = self.engine.state.membership_state.effective();
+        if self.registered_nodes.0.as_ref() == effective.log_id().as_ref() {
+            return;
+        }
+
+        let registered = &self.registered_nodes.1;
+        let changed = effective
+            .nodes()
+            .filter(|(id, node)| **id != self.id && registered.get(id) != Some(*node))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect::<Vec<_>>();
+
+        self.registered_nodes.0 = *effective.log_id();
+        self.registered_nodes.1 = effective.nodes().map(|(id, node)| (*id, node.clone())).collect();
+
+        for (id, node) in changed {
+            tracing::info!(target = display(id), node = debug(&node), "register node to network");
+            self.network.register_node(id, &node).await;
+        }
+    }
+
     /// Report a metrics payload on the current state of the Raft node.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn report_metrics(&mut self, replication: Option<ReplicationMetrics<C::NodeId>>) {
@@ -539,18 +1274,29 @@ where
 
         let st = &self.engine.state;
 
+        let millis_since_leader_contact = st.time_since_leader_contact().map(|d| d.as_millis() as u64);
+
         let membership_config = st.membership_state.effective().stored_membership().clone();
         let current_leader = self.current_leader();
 
+        let replication_stalls = match self.engine.internal_server_state.leading() {
+            Some(leading) => {
+                leading.progress.iter().filter(|(_, p)| p.stalls > 0).map(|(id, p)| (*id, p.stalls)).collect()
+            }
+            None => BTreeMap::new(),
+        };
+
         let m = RaftMetrics {
             running_state: Ok(()),
             id: self.id,
+            labels: self.labels.clone(),
 
             // --- data ---
             current_term: st.vote_ref().leader_id().get_term(),
             vote: *st.io_state().vote(),
             last_log_index: st.last_log_id().index(),
             last_applied: st.io_applied().copied(),
+            apply_backlog: st.committed().next_index().saturating_sub(st.io_applied().next_index()),
             snapshot: st.io_snapshot_last_log_id().copied(),
             purged: st.io_purged().copied(),
 
@@ -558,10 +1304,20 @@ where
             state: st.server_state,
             current_leader,
             millis_since_quorum_ack,
+            millis_since_leader_contact,
+            storage_degraded: self.storage_failures.is_degraded(),
+            term_gap_degraded: self.engine.config.term_gap_degraded,
+            no_configuration: !st.membership_state.is_configured(),
             membership_config: membership_config.clone(),
 
             // --- replication ---
             replication: replication.clone(),
+            rpc_latency: self.rpc_latency.iter().map(|(id, w)| (*id, w.latency())).collect(),
+            follower_log_state: self.follower_log_state.clone(),
+            replication_stalls,
+            config_mismatches: self.config_mismatches.clone(),
+            commit_latency: self.leader_data.as_ref().and_then(|l| l.commit_latency.latency()),
+            snapshot_send_rate: self.snapshot_send_rate,
         };
 
         let data_metrics = RaftDataMetrics {
@@ -570,11 +1326,13 @@ where
             snapshot: st.io_snapshot_last_log_id().copied(),
             purged: st.io_purged().copied(),
             millis_since_quorum_ack,
+            millis_since_leader_contact,
             replication,
         };
 
         let server_metrics = RaftServerMetrics {
             id: self.id,
+            labels: self.labels.clone(),
             vote: *st.io_state().vote(),
             state: st.server_state,
             current_leader,
@@ -658,6 +1416,184 @@ where
         let _ = tx.send(Err(err.into()));
     }
 
+    /// Forward a client write to the current leader and relay the response to the client.
+    ///
+    /// The client receives a `ForwardToLeader` error if the leader is unknown or forwarding fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn forward_client_write(&mut self, app_data: C::D, tx: ResponderOf<C>) {
+        let leader_id = self.current_leader().filter(|id| *id != self.id);
+        let leader_node = self.get_leader_node(leader_id);
+
+        let (Some(leader_id), Some(leader_node)) = (leader_id, leader_node) else {
+            tx.send(Err(ForwardToLeader::empty().into()));
+            return;
+        };
+
+        let mut client = self.network.new_client(leader_id, &leader_node).await;
+        let option = RPCOption::new(Duration::from_millis(self.config.election_timeout_max));
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                let res = match client.client_write(app_data, option).await {
+                    Ok(resp) => Ok(resp),
+                    Err(RPCError::RemoteError(RemoteError {
+                        source: RaftError::APIError(e),
+                        ..
+                    })) => Err(e),
+                    Err(e) => {
+                        tracing::warn!(error = display(&e), "failed to forward client write to leader");
+                        Err(ForwardToLeader::new(leader_id, leader_node).into())
+                    }
+                };
+                tx.send(res);
+            }
+            .instrument(tracing::debug_span!(
+                parent: &Span::current(),
+                "forward_client_write",
+                leader = display(leader_id)
+            )),
+        );
+    }
+
+    /// Get the read log id from the leader, for a read on this node after catching up with it.
+    pub(crate) async fn get_leader_read_log_id(
+        &mut self,
+        timeout: Duration,
+        tx: ResultSender<C, Option<LogId<C::NodeId>>, CatchUpError<C>>,
+    ) {
+        if self.engine.is_witness() {
+            let _ = tx.send(Err(NodeIsWitness { node_id: self.id }.into()));
+            return;
+        }
+
+        let leader_id = self.current_leader().filter(|id| *id != self.id);
+        let leader_node = self.get_leader_node(leader_id);
+
+        let (Some(leader_id), Some(leader_node)) = (leader_id, leader_node) else {
+            let _ = tx.send(Err(ForwardToLeader::empty().into()));
+            return;
+        };
+
+        let mut client = self.network.new_client(leader_id, &leader_node).await;
+        let option = RPCOption::new(timeout);
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                let res = match C::AsyncRuntime::timeout(timeout, client.get_read_log_id(option)).await {
+                    Ok(Ok(read_log_id)) => Ok(read_log_id),
+                    Ok(Err(RPCError::RemoteError(RemoteError {
+                        source: RaftError::APIError(e),
+                        ..
+                    }))) => Err(e.into()),
+                    Ok(Err(e)) => {
+                        tracing::warn!(error = display(&e), "failed to get read log id from leader");
+                        Err(ForwardToLeader::new(leader_id, leader_node).into())
+                    }
+                    Err(_elapsed) => Err(CatchUpError::Timeout(timeout)),
+                };
+                let _ = tx.send(res);
+            }
+            .instrument(tracing::debug_span!(
+                parent: &Span::current(),
+                "get_leader_read_log_id",
+                leader = display(leader_id)
+            )),
+        );
+    }
+
+    /// Summarize the client writes waiting for their entries to be committed and applied.
+    ///
+    /// Only the entries with a waiting client are counted, i.e., the keys of
+    /// `client_resp_channels`. Entries no client waits for, such as the blank entry of a leader, a
+    /// membership entry or a forwarded write, are not counted.
+    pub(crate) fn pending_writes(&self) -> PendingWrites {
+        if self.client_resp_channels.is_empty() {
+            return PendingWrites::default();
+        }
+
+        let st = &self.engine.state;
+        let committed = st.committed().next_index();
+        let applied = std::cmp::min(st.io_applied().next_index(), committed);
+
+        let awaiting_commit = self.client_resp_channels.range(committed..).count() as u64;
+        let awaiting_apply = self.client_resp_channels.range(applied..committed).count() as u64;
+
+        let first_index = self.client_resp_channels.first_key_value().map(|(k, _)| *k);
+        let last_index = self.client_resp_channels.last_key_value().map(|(k, _)| *k);
+
+        let now = InstantOf::<C>::now();
+        let oldest_age = self
+            .client_resp_channels
+            .keys()
+            .find_map(|index| self.client_submit_times.get(index))
+            .map(|t| now - *t);
+
+        PendingWrites {
+            awaiting_commit,
+            awaiting_apply,
+            oldest_age,
+            first_index,
+            last_index,
+        }
+    }
+
+    /// Collect the operations that have not finished, for preparing shutdown.
+    pub(crate) fn shutdown_report(&self) -> ShutdownReport<C> {
+        let mut replicating = BTreeSet::new();
+        let mut sending_snapshot = BTreeSet::new();
+
+        if let Some(leading) = self.engine.internal_server_state.leading() {
+            let last_log_id = self.engine.state.last_log_id();
+
+            for (id, p) in leading.progress.iter() {
+                if *id == self.id {
+                    continue;
+                }
+
+                if p.inflight.is_sending_snapshot() {
+                    sending_snapshot.insert(*id);
+                } else if p.matching.as_ref() < last_log_id {
+                    replicating.insert(*id);
+                }
+            }
+        }
+
+        ShutdownReport {
+            pending_writes: self.pending_writes(),
+            replicating,
+            sending_snapshot,
+            building_snapshot: self.engine.state.io_state().building_snapshot(),
+        }
+    }
+
+    /// Respond to the pending `prepare_shutdown()` request, once the in-flight operations settle
+    /// or its deadline is reached.
+    pub(crate) fn check_shutdown_settled(&mut self) {
+        let Some((deadline, _)) = &self.shutdown_waiter else {
+            return;
+        };
+
+        let report = self.shutdown_report();
+        let timed_out = InstantOf::<C>::now() >= *deadline;
+
+        if !report.is_settled() && !timed_out {
+            return;
+        }
+
+        tracing::info!(
+            report = display(&report),
+            timed_out,
+            "in-flight operations settled for shutdown"
+        );
+
+        let (_, tx) = self.shutdown_waiter.take().unwrap();
+        let _ = tx.send(Ok(report));
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn current_leader(&self) -> Option<C::NodeId> {
         tracing::debug!(
@@ -666,23 +1602,7 @@ where
             "get current_leader"
         );
 
-        let vote = self.engine.state.vote_ref();
-
-        if !vote.is_committed() {
-            return None;
-        }
-
-        // Safe unwrap(): vote that is committed has to already have voted for some node.
-        let id = vote.leader_id().voted_for().unwrap();
-
-        // TODO: `is_voter()` is slow, maybe cache `current_leader`,
-        //       e.g., only update it when membership or vote changes
-        if self.engine.state.membership_state.effective().is_voter(&id) {
-            Some(id)
-        } else {
-            tracing::debug!("id={} is not a voter", id);
-            None
-        }
+        self.engine.state.current_leader()
     }
 
     /// Retrieves the most recent timestamp that is acknowledged by a quorum.
@@ -768,15 +1688,174 @@ where
     pub(crate) fn handle_apply_result(&mut self, res: ApplyResult<C>) {
         tracing::debug!(last_applied = display(res.last_applied), "{}", func_name!());
 
+        if let Some(t) = res.last_timestamp {
+            self.last_timestamp = std::cmp::max(self.last_timestamp, t);
+        }
+
+        // Entries before `since` are skipped because the state machine is restored out-of-band, see
+        // `Raft::set_applied_index()`, or they are applied by a failed apply that is retried. There
+        // is no result to send to the clients waiting for them.
+        let mut skipped = vec![];
+        if self.client_resp_channels.first_key_value().map_or(false, |(k, _)| *k < res.since) {
+            let rest = self.client_resp_channels.split_off(&res.since);
+            skipped.extend(std::mem::replace(&mut self.client_resp_channels, rest));
+        }
+        if let Some(dedup) = &mut self.write_dedup {
+            skipped.extend(dedup.remove_waiting(0, res.since));
+        }
+
+        for (log_index, tx) in skipped {
+            if log_index >= res.skip_since {
+                tracing::warn!(log_index, "entry is skipped by set_applied_index");
+                tx.send(Err(ApplySkipped {
+                    node_id: self.id,
+                    index: log_index,
+                }
+                .into()));
+            } else {
+                tracing::warn!(log_index, "entry is applied by a failed apply, its result is lost");
+                tx.send(Err(ApplyResultLost {
+                    node_id: self.id,
+                    index: log_index,
+                }
+                .into()));
+            }
+        }
+
+        // Take the responders of all the applied entries at once, instead of looking up every
+        // entry, and resolve them in a single pass in index order.
+        let rest = self.client_resp_channels.split_off(&res.end);
+        let applied = std::mem::replace(&mut self.client_resp_channels, rest);
+        self.client_submit_times = self.client_submit_times.split_off(&res.end);
+        let mut responders = applied.into_iter().peekable();
+
         let mut results = res.apply_results.into_iter();
         let mut applying_entries = res.applying_entries.into_iter();
 
         for log_index in res.since..res.end {
             let ent = applying_entries.next().unwrap();
             let apply_res = results.next().unwrap();
-            let tx = self.client_resp_channels.remove(&log_index);
+            let tx = responders.next_if(|(index, _)| *index == log_index).map(|(_, tx)| tx);
+
+            if let Some(dedup) = &mut self.write_dedup {
+                for (dup_tx, data) in dedup.on_applied(&ent.log_id, &apply_res) {
+                    dup_tx.send(Ok(ClientWriteResponse {
+                        log_id: ent.log_id,
+                        data,
+                        membership: ent.membership.clone(),
+                    }));
+                }
+            }
+
+            match tx {
+                Some(tx) if self.commit_waits.contains(log_index) => {
+                    let resp = ClientWriteResponse {
+                        log_id: ent.log_id,
+                        data: apply_res,
+                        membership: ent.membership,
+                    };
+                    self.commit_waits.hold(log_index, tx, resp);
+                }
+                _ => Self::send_response(ent, apply_res, tx),
+            }
+        }
+    }
+
+    /// Wait for a follower in the group of `commit_wait` to acknowledge the entry just written.
+    fn add_commit_wait(&mut self, commit_wait: Option<CommitWait>) {
+        let Some(wait) = commit_wait else {
+            return;
+        };
+
+        // Safe unwrap: an entry is just appended.
+        let index = self.engine.state.last_log_id().unwrap().index;
+        tracing::debug!(index, wait = display(&wait), "client write waits for commit");
+
+        self.commit_waits.insert(index, wait, InstantOf::<C>::now());
+    }
+
+    /// Respond to the client writes with a commit wait that are acknowledged by a follower in the
+    /// group, or that timed out.
+    fn check_commit_waits(&mut self) {
+        if self.commit_waits.is_empty() {
+            return;
+        }
+
+        let id = self.id;
+        let peer_labels = &self.peer_labels;
+        let leading = self.engine.internal_server_state.leading();
+
+        let acked = |index: u64, wait: &CommitWait| {
+            let Some(leading) = leading else {
+                return false;
+            };
+
+            leading.progress.iter().any(|(target, p)| {
+                *target != id
+                    && peer_labels.get(target).and_then(|l| l.get(&wait.label)) == Some(&wait.value)
+                    && p.matching.index() >= Some(index)
+            })
+        };
+
+        let resolved = self.commit_waits.take_resolved(InstantOf::<C>::now(), acked);
+
+        for (index, pending, is_acked) in resolved {
+            let tx = match pending.response {
+                Some((tx, resp)) if is_acked => {
+                    tx.send(Ok(resp));
+                    continue;
+                }
+                Some((tx, _resp)) => Some(tx),
+                // Not applied yet: the responder is still waiting for the entry to be applied.
+                None => {
+                    self.client_submit_times.remove(&index);
+                    self.client_resp_channels.remove(&index)
+                }
+            };
+
+            tracing::warn!(
+                index,
+                wait = display(&pending.wait),
+                "client write commit wait timed out"
+            );
+
+            if let Some(tx) = tx {
+                tx.send(Err(ClientWriteError::CommitWaitTimeout(CommitWaitTimeout {
+                    node_id: self.id,
+                    log_index: index,
+                    label: pending.wait.label,
+                    value: pending.wait.value,
+                    timeout: pending.wait.timeout,
+                })));
+            }
+        }
+    }
+
+    /// Remove responders of pending client write requests whose client stopped waiting.
+    ///
+    /// The entries are still committed and applied, only the bookkeeping of the response is
+    /// dropped, so that it does not grow with clients that disconnect.
+    ///
+    /// Every call checks at most [`SWEEP_CLOSED_RESPONDERS`] responders, and the next call resumes
+    /// where this one stopped, so that the cost does not grow with the number of pending writes.
+    pub(crate) fn remove_closed_responders(&mut self) {
+        let (removed, next) = responder::remove_closed(
+            &mut self.client_resp_channels,
+            self.responder_sweep_cursor,
+            SWEEP_CLOSED_RESPONDERS,
+        );
+        self.responder_sweep_cursor = next;
 
-            Self::send_response(ent, apply_res, tx);
+        if !removed.is_empty() {
+            for index in removed.iter() {
+                self.client_submit_times.remove(index);
+            }
+
+            tracing::debug!(
+                removed = removed.len(),
+                remaining = self.client_resp_channels.len(),
+                "removed closed client responders"
+            );
         }
     }
 
@@ -817,15 +1896,18 @@ where
         let snapshot_network = self.network.new_client(target, target_node).await;
 
         let session_id = ReplicationSessionId::new(*self.engine.state.vote_ref(), *membership_log_id);
+        let witness = self.engine.state.membership_state.effective().membership().is_witness(&target);
 
         ReplicationCore::<C, N, LS>::spawn(
             target,
+            witness,
             session_id,
             self.config.clone(),
             self.engine.state.committed().copied(),
             progress_entry.matching,
             network,
             snapshot_network,
+            self.snapshot_bandwidth.clone(),
             self.log_store.get_log_reader().await,
             self.sm_handle.new_snapshot_reader(),
             self.tx_notify.clone(),
@@ -833,6 +1915,59 @@ where
         )
     }
 
+    /// Measure the latency of the client writes committed up to `upto`, since they are appended.
+    fn observe_commit_latency(&mut self, upto: u64) {
+        let Some(l) = &mut self.leader_data else {
+            return;
+        };
+
+        let rest = l.append_times.split_off(&(upto + 1));
+        let committed = std::mem::replace(&mut l.append_times, rest);
+
+        let now = InstantOf::<C>::now();
+        l.commit_latency.extend(committed.into_values().map(|t| now - t));
+    }
+
+    /// Spawn a task to feed the committed entries since `start` to `tx`, so that it does not block
+    /// RaftCore.
+    pub(crate) async fn subscribe_committed(
+        &mut self,
+        start: u64,
+        tx: mpsc::Sender<Result<Committed<C>, StorageError<C::NodeId>>>,
+    ) {
+        let log_reader = self.log_store.get_log_reader().await;
+        let rx_metrics = self.tx_metrics.subscribe();
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(feed_committed(start, log_reader, rx_metrics, tx));
+    }
+
+    /// The inclusive range of log indexes that must be present in the log store: after the last
+    /// purged log, up to the last log.
+    fn present_log_range(&self) -> Option<(u64, u64)> {
+        let last = self.engine.state.last_log_id()?;
+        let start = self.engine.state.last_purged_log_id().next_index();
+        Some((start, last.index))
+    }
+
+    /// Scan the log for missing entries at startup, and report every gap found as an error log.
+    async fn check_log_gaps_at_startup(&mut self) -> Result<(), StorageError<C::NodeId>> {
+        let Some((start, end)) = self.present_log_range() else {
+            return Ok(());
+        };
+
+        let gaps = self.log_store.find_log_gaps(start, end).await?;
+        for gap in gaps.iter() {
+            tracing::error!(
+                gap = display(gap),
+                "missing log entries found at startup, the log store may be corrupted"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Remove all replication.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn remove_all_replication(&mut self) {
@@ -867,6 +2002,8 @@ where
     /// next RaftMsg.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn run_engine_commands(&mut self) -> Result<(), StorageError<C::NodeId>> {
+        self.register_nodes().await;
+
         if tracing::enabled!(Level::DEBUG) {
             tracing::debug!("queued commands: start...");
             for c in self.engine.output.iter_commands() {
@@ -875,10 +2012,41 @@ where
             tracing::debug!("queued commands: end...");
         }
 
+        if self.storage_failures.is_backing_off(InstantOf::<C>::now()) {
+            tracing::debug!("storage is failing, retry commands later");
+            return Ok(());
+        }
+
+        if self.config.batch_vote_saves {
+            let removed = self.engine.output.coalesce_save_vote();
+            if removed > 0 {
+                tracing::debug!("coalesced {} SaveVote commands", removed);
+            }
+        }
+
         while let Some(cmd) = self.engine.output.pop_command() {
             tracing::debug!("run command: {:?}", cmd);
 
-            let res = self.run_command(cmd).await?;
+            let retry = storage_failures::retryable_copy(&cmd);
+
+            let res = match self.run_command(cmd).await {
+                Ok(x) => {
+                    if retry.is_some() && self.storage_failures.is_retrying() {
+                        self.on_storage_recovered();
+                    }
+                    x
+                }
+                Err(e) => {
+                    let Some(retry) = retry else {
+                        return Err(e);
+                    };
+                    if matches!(retry, Command::SaveVote { .. }) && self.config.deny_vote_on_save_failure {
+                        self.deny_unsaved_votes();
+                    }
+                    self.on_storage_failure(e)?;
+                    Some(retry)
+                }
+            };
 
             if let Some(cmd) = res {
                 tracing::debug!("early return: postpone command: {:?}", cmd);
@@ -897,6 +2065,66 @@ where
         Ok(())
     }
 
+    /// Count a failure of a retryable storage command and apply the `storage_failure_policy`.
+    ///
+    /// It returns the error if the node should shut down. Otherwise the failed command will be
+    /// retried after `heartbeat_interval`.
+    fn on_storage_failure(&mut self, err: StorageError<C::NodeId>) -> Result<(), StorageError<C::NodeId>> {
+        let now = InstantOf::<C>::now();
+        let window = Duration::from_millis(self.config.storage_failure_window);
+        let retry_at = now + Duration::from_millis(self.config.heartbeat_interval);
+
+        let failures = self.storage_failures.record_failure(now, window, retry_at);
+
+        if failures < self.config.storage_failure_threshold {
+            tracing::warn!(error = display(&err), failures, "storage command failed, retry later");
+            return Ok(());
+        }
+
+        match self.config.storage_failure_policy {
+            StorageFailurePolicy::Shutdown => Err(err),
+            StorageFailurePolicy::Degrade => {
+                if !self.storage_failures.is_degraded() {
+                    tracing::error!(
+                        error = display(&err),
+                        failures,
+                        "storage keeps failing, enter degraded mode and reject writes"
+                    );
+                    self.storage_failures.set_degraded();
+                } else {
+                    tracing::warn!(error = display(&err), failures, "storage is still failing, retry later");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Respond to the granted votes waiting for a vote that failed to save with `vote_granted:
+    /// false`: a vote that is not durable must not be counted by a candidate.
+    fn deny_unsaved_votes(&mut self) {
+        let denied = self.engine.output.take_denied_votes();
+        if !denied.is_empty() {
+            tracing::warn!(
+                count = denied.len(),
+                "saving vote failed, deny the pending granted votes"
+            );
+        }
+
+        for resp in denied {
+            resp.send();
+        }
+    }
+
+    /// A failed storage command succeeded on retry: leave degraded mode if it was in.
+    fn on_storage_recovered(&mut self) {
+        let was_degraded = self.storage_failures.record_success();
+        if was_degraded {
+            tracing::info!("storage recovered, leave degraded mode");
+        } else {
+            tracing::info!("storage recovered");
+        }
+    }
+
     /// Run an event handling loop
     ///
     /// It always returns a [`Fatal`] error upon returning.
@@ -953,6 +2181,13 @@ where
             let raft_msg_processed = self.process_raft_msg(balancer.raft_msg()).await?;
             let notify_processed = self.process_notify(balancer.notify()).await?;
 
+            // Reads queued while the previous confirmation round was in flight.
+            self.confirm_pending_reads().await;
+
+            self.check_shutdown_settled();
+            self.check_admitted_campaign_done();
+            self.check_commit_waits();
+
             // If one of the channel consumed all its budget, re-balance the budget ratio.
 
             #[allow(clippy::collapsible_else_if)]
@@ -973,6 +2208,9 @@ where
     /// It returns the number of processed message.
     /// If the input channel is closed, it returns `Fatal::Stopped`.
     async fn process_raft_msg(&mut self, at_most: u64) -> Result<u64, Fatal<C>> {
+        // Whether there are commands of vote requests not yet run, with `Config::batch_vote_saves`.
+        let mut deferred = false;
+
         for i in 0..at_most {
             let res = self.rx_api.try_recv();
             let msg = match res {
@@ -980,6 +2218,9 @@ where
                 Err(e) => match e {
                     mpsc::error::TryRecvError::Empty => {
                         tracing::debug!("all RaftMsg are processed, wait for more");
+                        if deferred {
+                            self.run_engine_commands().await?;
+                        }
                         return Ok(i + 1);
                     }
                     mpsc::error::TryRecvError::Disconnected => {
@@ -989,6 +2230,19 @@ where
                 },
             };
 
+            // The commands of a vote request are run along with those of the following vote
+            // requests, so that the votes to save are coalesced.
+            if self.config.batch_vote_saves && matches!(msg, RaftMsg::RequestVote { .. }) {
+                self.handle_api_msg(msg).await;
+                deferred = true;
+                continue;
+            }
+
+            if deferred {
+                self.run_engine_commands().await?;
+                deferred = false;
+            }
+
             self.handle_api_msg(msg).await;
 
             // TODO: does run_engine_commands() run too frequently?
@@ -998,6 +2252,10 @@ where
             self.run_engine_commands().await?;
         }
 
+        if deferred {
+            self.run_engine_commands().await?;
+        }
+
         tracing::debug!("at_most({}) reached, there are more queued RaftMsg to process", at_most);
 
         Ok(at_most)
@@ -1039,13 +2297,20 @@ where
     }
 
     /// Spawn parallel vote requests to all cluster members.
+    ///
+    /// Every request is sent in its own task, so that a slow peer does not delay the others.
+    /// Outstanding requests of the previous round are cancelled; and the requests of this round are
+    /// cancelled as soon as a quorum grants the vote and this node becomes the leader.
     #[tracing::instrument(level = "trace", skip_all)]
     async fn spawn_parallel_vote_requests(&mut self, vote_req: &VoteRequest<C>) {
         let members = self.engine.state.membership_state.effective().voter_ids();
 
         let vote = vote_req.vote;
 
+        self.vote_requests.new_round(vote);
+
         for target in members {
+            // The candidate has granted its own vote in `Engine::elect()`, never send it to itself.
             if target == self.id {
                 continue;
             }
@@ -1062,10 +2327,9 @@ where
             let id = self.id;
             let option = RPCOption::new(ttl);
 
-            // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
-            #[allow(clippy::let_underscore_future)]
-            let _ = C::AsyncRuntime::spawn(
+            self.vote_requests.spawn(
                 async move {
+                    let sending_time = InstantOf::<C>::now();
                     let tm_res = C::AsyncRuntime::timeout(ttl, client.vote(req, option)).await;
                     let res = match tm_res {
                         Ok(res) => res,
@@ -1088,6 +2352,7 @@ where
                                 target,
                                 resp,
                                 sender_vote: vote,
+                                sending_time,
                             });
                         }
                         Err(err) => tracing::error!({error=%err, target=display(target)}, "while requesting vote"),
@@ -1107,6 +2372,10 @@ where
         tracing::info!(req = display(&req), func = func_name!());
 
         let resp = self.engine.handle_vote_req(req);
+
+        // The response must be queued after the `SaveVote` command output by the engine:
+        // commands are run in order, thus a granted vote is not responded until it is persisted.
+        // Otherwise this node may grant another candidate of the same term after a restart.
         self.engine.output.push_command(Command::Respond {
             when: None,
             resp: Respond::new(Ok(resp), tx),
@@ -1117,10 +2386,49 @@ where
     pub(super) fn handle_append_entries_request(&mut self, req: AppendEntriesRequest<C>, tx: AppendEntriesTx<C>) {
         tracing::debug!(req = display(&req), func = func_name!());
 
-        let is_ok = self.engine.handle_append_entries(&req.vote, req.prev_log_id, req.entries, Some(tx));
+        if let Some(config_hash) = req.config_hash() {
+            self.check_config_hash(req.vote.leader_id().voted_for(), config_hash);
+        }
+
+        // The vote check takes precedence: a request rejected by vote is not validated.
+        if let Some(validator) = &mut self.append_entries_validator {
+            if &req.vote >= self.engine.state.vote_ref() && !validator.validate(&req) {
+                tracing::info!(req = display(&req), "AppendEntries is rejected by validator");
+                // Respond with the local vote, which is not greater than the leader's: the leader
+                // takes it as a rejection rather than a higher vote.
+                let _ = tx.send(Ok(AppendEntriesResponse::HigherVote(*self.engine.state.vote_ref())));
+                return;
+            }
+        }
+
+        self.engine
+            .handle_append_entries(&req.vote, req.prev_log_id, req.entries, req.leader_commit, Some(tx));
+    }
+
+    /// Compare the [`Config::timing_hash()`] received from a peer with this node's, and record the
+    /// peer if they differ.
+    ///
+    /// A warning is logged only when a mismatch is found for the first time, or the peer's hash
+    /// changes.
+    fn check_config_hash(&mut self, peer: Option<C::NodeId>, peer_hash: u64) {
+        let Some(peer) = peer else {
+            return;
+        };
+
+        let my_hash = self.config.timing_hash();
+        if peer_hash == my_hash {
+            self.config_mismatches.remove(&peer);
+            return;
+        }
 
-        if is_ok {
-            self.engine.handle_commit_entries(req.leader_commit);
+        let prev = self.config_mismatches.insert(peer, peer_hash);
+        if prev != Some(peer_hash) {
+            tracing::warn!(
+                peer = display(peer),
+                peer_hash = display(peer_hash),
+                my_hash = display(my_hash),
+                "timing config mismatch: heartbeat_interval or election timeouts differ from the peer's"
+            );
         }
     }
 
@@ -1147,14 +2455,82 @@ where
             RaftMsg::BeginReceivingSnapshot { tx } => {
                 self.engine.handle_begin_receiving_snapshot(tx);
             }
+            RaftMsg::MigrateSnapshot { snapshot, tx } => {
+                self.engine.handle_migrate_snapshot(snapshot, tx);
+            }
             RaftMsg::InstallFullSnapshot { vote, snapshot, tx } => {
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
             }
+            RaftMsg::ReadBarrier { tx } => {
+                let res = if self.engine.is_witness() {
+                    Err(NodeIsWitness { node_id: self.id })
+                } else {
+                    let st = &self.engine.state;
+                    Ok((st.committed().copied(), st.io_applied().copied()))
+                };
+                let _ = tx.send(res);
+            }
+            RaftMsg::GetLeaderReadLogId { timeout, tx } => {
+                self.note_client_activity();
+                self.get_leader_read_log_id(timeout, tx).await;
+            }
+            RaftMsg::SubscribeCommitted { start, tx } => {
+                self.subscribe_committed(start, tx).await;
+            }
             RaftMsg::CheckIsLeaderRequest { tx } => {
+                self.note_client_activity();
                 self.handle_check_is_leader_request(tx).await;
             }
-            RaftMsg::ClientWriteRequest { app_data, tx } => {
-                self.write_entry(C::Entry::from_app_data(app_data), Some(tx));
+            RaftMsg::ClientWriteRequest {
+                payload,
+                metadata,
+                commit_wait,
+                tx,
+            } => {
+                self.note_client_activity();
+                if let Err(e) = self.ensure_not_shutting_down() {
+                    tx.send(Err(e.into()));
+                } else if let Err(e) = self.ensure_not_sealed() {
+                    tx.send(Err(e.into()));
+                } else if let Err(e) = self.ensure_storage_not_degraded() {
+                    tx.send(Err(e.into()));
+                } else if let Err(e) = self.ensure_apply_not_stalled() {
+                    tx.send(Err(e.into()));
+                } else if let Err(e) = self.ensure_configured() {
+                    tx.send(Err(e.into()));
+                } else {
+                    match payload {
+                        ClientPayload::Checkpoint(app_data) => {
+                            // A checkpoint is not forwarded: the network API can not tell it from a
+                            // normal write.
+                            if self.write_entry(C::Entry::new_checkpoint(app_data), Some(tx)) {
+                                self.add_commit_wait(commit_wait);
+                            }
+                        }
+                        ClientPayload::AppData(app_data) => {
+                            if commit_wait.is_some() {
+                                // A write with a commit wait is neither forwarded nor deduplicated:
+                                // it waits for its own entry to be acknowledged.
+                                let mut entry = C::Entry::from_app_data(app_data);
+                                if let Some(metadata) = metadata {
+                                    entry.set_metadata(metadata);
+                                }
+                                if self.write_entry(entry, Some(tx)) {
+                                    self.add_commit_wait(commit_wait);
+                                }
+                            } else if self.config.forward_client_write
+                                && metadata.is_none()
+                                && self.engine.leader_handler().is_err()
+                            {
+                                // A write with metadata is not forwarded: the network API does not
+                                // carry it.
+                                self.forward_client_write(app_data, tx).await;
+                            } else {
+                                self.write_app_data(app_data, metadata, tx);
+                            }
+                        }
+                    }
+                }
             }
             RaftMsg::Initialize { members, tx } => {
                 tracing::info!(
@@ -1165,18 +2541,64 @@ where
 
                 self.handle_initialize(members, tx);
             }
-            RaftMsg::ChangeMembership { changes, retain, tx } => {
+            RaftMsg::InitializeWithSnapshot { snapshot, tx } => {
+                tracing::info!(
+                    snapshot = display(&snapshot),
+                    "received RaftMsg::InitializeWithSnapshot: {}",
+                    func_name!()
+                );
+
+                let res = self.engine.initialize_with_snapshot(snapshot);
+
+                // Respond after the snapshot is installed to the state machine.
+                let when = res.is_ok().then(|| Condition::StateMachineCommand {
+                    command_seq: self.engine.output.last_sm_seq(),
+                });
+                self.engine.output.push_command(Command::Respond {
+                    when,
+                    resp: Respond::new(res, tx),
+                });
+            }
+            RaftMsg::ChangeMembership {
+                changes,
+                retain,
+                force,
+                tx,
+            } => {
                 tracing::info!(
                     members = debug(&changes),
                     retain = debug(&retain),
+                    force = debug(&force),
                     "received RaftMsg::ChangeMembership: {}",
                     func_name!()
                 );
 
-                self.change_membership(changes, retain, tx);
+                if let Err(e) = self.ensure_not_shutting_down() {
+                    tx.send(Err(e.into()));
+                } else {
+                    self.change_membership(changes, retain, force, tx);
+                }
+            }
+            RaftMsg::SetSealed { sealed, tx } => {
+                tracing::info!(
+                    sealed = display(sealed),
+                    "received RaftMsg::SetSealed: {}",
+                    func_name!()
+                );
+
+                self.set_sealed(sealed, tx);
+            }
+            RaftMsg::AddWitnesses { witnesses, tx } => {
+                tracing::info!(
+                    witnesses = debug(&witnesses),
+                    "received RaftMsg::AddWitnesses: {}",
+                    func_name!()
+                );
+
+                self.add_witnesses(witnesses, tx);
             }
             RaftMsg::ExternalCoreRequest { req } => {
-                req(&self.engine.state);
+                req(self);
             }
             RaftMsg::ExternalCommand { cmd } => {
                 tracing::info!(cmd = debug(&cmd), "received RaftMsg::ExternalCommand: {}", func_name!());
@@ -1194,6 +2616,9 @@ where
                     ExternalCommand::Heartbeat => {
                         self.send_heartbeat("ExternalCommand");
                     }
+                    ExternalCommand::StepDown => {
+                        self.engine.step_down();
+                    }
                     ExternalCommand::Snapshot => self.trigger_snapshot(),
                     ExternalCommand::GetSnapshot { tx } => {
                         let cmd = sm::Command::get_snapshot(tx);
@@ -1205,6 +2630,69 @@ where
                     ExternalCommand::PurgeLog { upto } => {
                         self.engine.trigger_purge_log(upto);
                     }
+                    ExternalCommand::SetApplyObserver { observer } => {
+                        let cmd = sm::Command::set_apply_observer(observer);
+                        let res = self.sm_handle.send(cmd);
+                        if let Err(e) = res {
+                            tracing::error!(error = display(e), "error sending SetApplyObserver to sm worker");
+                        }
+                    }
+                    ExternalCommand::SetApplyCoordinator { coordinator } => {
+                        let cmd = sm::Command::set_apply_coordinator(coordinator);
+                        let res = self.sm_handle.send(cmd);
+                        if let Err(e) = res {
+                            tracing::error!(error = display(e), "error sending SetApplyCoordinator to sm worker");
+                        }
+                    }
+                    ExternalCommand::SetAppendEntriesValidator { validator } => {
+                        self.append_entries_validator = Some(validator);
+                    }
+                    ExternalCommand::SetElectionTiebreaker { tiebreaker } => {
+                        self.engine.election_tiebreaker = Some(tiebreaker);
+                    }
+                    ExternalCommand::SetPeerLabels { labels } => {
+                        self.peer_labels = labels;
+                    }
+                    ExternalCommand::SetElectionAdmission { admission } => {
+                        self.election_admission = Some(admission);
+                    }
+                    #[cfg(feature = "decision-trace")]
+                    ExternalCommand::SetDecisionTrace { writer } => {
+                        self.engine.decision_trace = writer.map(DecisionTrace::new);
+                    }
+                    ExternalCommand::SetWriteDeduplicator { deduplicator } => {
+                        if let Some(d) = &mut self.write_dedup {
+                            d.set_deduplicator(deduplicator);
+                        } else {
+                            self.write_dedup = Some(WriteDedup::new(deduplicator));
+                        }
+                    }
+                    ExternalCommand::SetElectionTimeout { min, max } => {
+                        self.engine.config.election_timeout_range = min..max;
+                    }
+                    ExternalCommand::ResetElectionTimeout => {
+                        self.engine.config.election_timeout_range =
+                            self.config.election_timeout_min..self.config.election_timeout_max;
+                    }
+                    ExternalCommand::SetUnsafeCommitQuorum { surviving } => {
+                        self.engine.set_unsafe_commit_quorum(surviving);
+                    }
+                    ExternalCommand::PauseReplication { target } => {
+                        self.engine.pause_replication(target);
+                    }
+                    ExternalCommand::ResumeReplication { target } => {
+                        self.engine.resume_replication(target);
+                    }
+                    ExternalCommand::ForceSnapshotTo { target } => {
+                        self.engine.force_snapshot_to(target);
+                    }
+                    ExternalCommand::ClearTermGapDegraded => {
+                        self.engine.clear_term_gap_degraded();
+                    }
+                    ExternalCommand::SetAppliedIndex { index, tx } => {
+                        let res = self.set_applied_index(index);
+                        let _ = tx.send(res);
+                    }
                 }
             }
         };
@@ -1220,8 +2708,10 @@ where
                 target,
                 resp,
                 sender_vote: vote,
+                sending_time,
             } => {
                 let now = InstantOf::<C>::now();
+                self.record_rpc_latency(target, now - sending_time);
 
                 tracing::info!(
                     now = debug(now),
@@ -1235,6 +2725,12 @@ where
                 }
             }
 
+            Notify::FollowerLogState { target, state } => {
+                if self.engine.internal_server_state.is_leading() {
+                    self.follower_log_state.insert(target, state);
+                }
+            }
+
             Notify::HigherVote {
                 target,
                 higher,
@@ -1254,13 +2750,40 @@ where
                 }
             }
 
+            Notify::ReadConfirmed { round } => {
+                tracing::debug!(
+                    round = display(round),
+                    "received Notify::ReadConfirmed: {}",
+                    func_name!()
+                );
+
+                self.read_batch.finish_round(round);
+            }
+
             Notify::Tick { i } => {
                 // check every timer
 
                 let now = InstantOf::<C>::now();
                 tracing::debug!("received tick: {}, now: {:?}", i, now);
 
-                self.handle_tick_election();
+                self.sample_snapshot_send_rate(now);
+                self.sample_metrics();
+
+                if let Some(t) = self.apply_retry_at {
+                    if now >= t {
+                        self.apply_retry_at = None;
+                        let res = self.sm_handle.send(sm::Command::retry_apply());
+                        if let Err(e) = res {
+                            tracing::error!(error = display(e), "error sending RetryApply to sm worker");
+                        }
+                    }
+                }
+
+                let quiesced = self.check_quiesced(now);
+
+                if !quiesced {
+                    self.handle_tick_election();
+                }
 
                 // TODO: test: fixture: make isolated_nodes a single-way isolating.
 
@@ -1268,7 +2791,7 @@ where
                 let heartbeat_at = self.leader_data.as_ref().map(|x| x.next_heartbeat);
                 if let Some(t) = heartbeat_at {
                     if now >= t {
-                        if self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) {
+                        if self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) && !quiesced {
                             self.send_heartbeat("tick");
                         }
 
@@ -1277,9 +2800,18 @@ where
                             l.next_heartbeat =
                                 InstantOf::<C>::now() + Duration::from_millis(self.config.heartbeat_interval);
                         }
+
+                        // Clean up responders of the clients that are gone, once per heartbeat interval.
+                        self.remove_closed_responders();
+
+                        if self.config.persist_replication_hints {
+                            self.save_replication_hints();
+                        }
                     }
                 }
 
+                self.check_leader_idle(now);
+
                 // When a membership that removes the leader is committed,
                 // the leader continue to work for a short while before reverting to a learner.
                 // This way, let the leader replicate the `membership-log-is-committed` message to
@@ -1346,6 +2878,25 @@ where
                 }
             }
 
+            Notify::ApplyFailing { error } => {
+                match &error {
+                    Some(e) => {
+                        tracing::warn!("state machine apply failed, retry later: {}", e);
+                        let interval = Duration::from_millis(self.config.heartbeat_interval);
+                        self.apply_retry_at = Some(InstantOf::<C>::now() + interval);
+                    }
+                    None => {
+                        tracing::info!("state machine apply recovered");
+                        self.apply_retry_at = None;
+                    }
+                }
+                self.apply_error = error;
+            }
+
+            Notify::ElectionAdmitted => {
+                self.handle_election_admitted();
+            }
+
             Notify::StateMachine { command_result } => {
                 tracing::debug!("sm::StateMachine command result: {:?}", command_result);
 
@@ -1365,7 +2916,11 @@ where
                         );
                     }
                 }
-                self.command_state.finished_sm_seq = seq;
+
+                // A partially applied Apply command is not finished yet.
+                if !matches!(res, sm::Response::ApplyPartial(_)) {
+                    self.command_state.finished_sm_seq = seq;
+                }
 
                 match res {
                     sm::Response::BuildSnapshot(meta) => {
@@ -1397,7 +2952,7 @@ where
                             st.update_snapshot(meta.last_log_id);
                         }
                     }
-                    sm::Response::Apply(res) => {
+                    sm::Response::Apply(res) | sm::Response::ApplyPartial(res) => {
                         self.engine.state.io_state_mut().update_applied(Some(res.last_applied));
 
                         self.handle_apply_result(res);
@@ -1441,7 +2996,7 @@ where
             let timer_config = &self.engine.config.timer_config;
 
             let mut election_timeout = if current_vote.is_committed() {
-                timer_config.leader_lease + timer_config.election_timeout
+                timer_config.leader_lease + timer_config.leader_stickiness + timer_config.election_timeout
             } else {
                 timer_config.election_timeout
             };
@@ -1464,14 +3019,103 @@ where
                 return;
             }
 
+            // A heartbeat of an active leader may be only slightly delayed: wait for it a little
+            // longer before disrupting the leader.
+            if current_vote.is_committed()
+                && utime > Some(now - (election_timeout + timer_config.election_grace_period))
+            {
+                tracing::info!(
+                    grace_period = debug(timer_config.election_grace_period),
+                    "election timeout passed, defer election within grace period"
+                );
+                return;
+            }
+
             tracing::info!("election timeout passed, check if it is a voter for election");
         }
 
+        if self.election_admission.is_some() && !self.admitted_campaign {
+            self.request_election_admission();
+            return;
+        }
+
         // Every time elect, reset this flag.
         self.engine.reset_greater_log();
 
         tracing::info!("do trigger election");
-        self.engine.elect();
+        self.engine.elect_on_timeout();
+    }
+
+    /// Ask the election admission hook for a permit to elect, unless one is already pending.
+    ///
+    /// The election starts when the permit resolves, in [`Self::handle_election_admitted()`].
+    fn request_election_admission(&mut self) {
+        if self.admission_waiting.is_some() {
+            tracing::debug!("election permit is pending, wait for it");
+            return;
+        }
+
+        let Some(admission) = &mut self.election_admission else {
+            return;
+        };
+
+        let permit = admission.acquire(&self.id);
+        self.admission_waiting = Some(*self.engine.state.vote_ref());
+
+        tracing::info!("election timeout passed, wait for election permit");
+
+        let tx = self.tx_notify.clone();
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                permit.await;
+                let _ = tx.send(Notify::ElectionAdmitted);
+            }
+            .instrument(tracing::debug_span!("wait_election_permit")),
+        );
+    }
+
+    /// Start the election admitted by a resolved permit, unless the vote of this node changed
+    /// while waiting for it.
+    fn handle_election_admitted(&mut self) {
+        let Some(vote) = self.admission_waiting.take() else {
+            return;
+        };
+
+        let vote_changed = &vote != self.engine.state.vote_ref();
+        if vote_changed
+            || self.engine.state.server_state == ServerState::Leader
+            || !self.runtime_config.enable_elect.load(Ordering::Relaxed)
+        {
+            tracing::info!(
+                waiting_vote = display(&vote),
+                current_vote = display(self.engine.state.vote_ref()),
+                "election permit resolved, abandon election"
+            );
+            self.election_done();
+            return;
+        }
+
+        self.engine.reset_greater_log();
+
+        tracing::info!("election permit resolved, do trigger election");
+        self.engine.elect_on_timeout();
+        self.admitted_campaign = true;
+    }
+
+    /// Notify the election admission hook once the admitted election is finished, i.e., this node
+    /// is no longer a candidate.
+    fn check_admitted_campaign_done(&mut self) {
+        if self.admitted_campaign && self.engine.state.server_state != ServerState::Candidate {
+            self.admitted_campaign = false;
+            self.election_done();
+        }
+    }
+
+    fn election_done(&mut self) {
+        if let Some(admission) = &mut self.election_admission {
+            admission.on_election_done(&self.id);
+        }
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
@@ -1498,12 +3142,21 @@ where
             }
         }
 
+        if let Ok(r) = &result {
+            self.record_rpc_latency(target, InstantOf::<C>::now() - r.sending_time);
+        }
+
         // A leader may have stepped down.
         if self.engine.internal_server_state.is_leading() {
             self.engine.replication_handler().update_progress(target, request_id, result);
         }
     }
 
+    /// Record the round-trip latency of an RPC to `target`, which is reported in the metrics.
+    fn record_rpc_latency(&mut self, target: C::NodeId, latency: Duration) {
+        self.rpc_latency.entry(target).or_insert_with(LatencyWindow::new).push(latency);
+    }
+
     /// If a message is sent by a previous server state but is received by current server state,
     /// it is a stale message and should be just ignored.
     fn does_vote_match(&self, vote: &Vote<C::NodeId>, msg: impl Display) -> bool {
@@ -1543,6 +3196,40 @@ where
     }
 }
 
+impl<C, N, LS, SM> CoreAccess<C> for RaftCore<C, N, LS, SM>
+where
+    C: RaftTypeConfig,
+    N: RaftNetworkFactory<C>,
+    LS: RaftLogStorage<C>,
+    SM: RaftStateMachine<C>,
+{
+    fn engine(&mut self) -> &mut Engine<C> {
+        &mut self.engine
+    }
+
+    fn metrics_history(&self) -> &MetricsHistory<C> {
+        &self.metrics_history
+    }
+
+    fn pending_writes(&self) -> PendingWrites {
+        RaftCore::pending_writes(self)
+    }
+
+    fn prepare_shutdown(&mut self, timeout: Duration, tx: ResultSender<C, ShutdownReport<C>>) {
+        tracing::info!(timeout = debug(timeout), "prepare shutdown: {}", func_name!());
+
+        self.shutting_down = true;
+
+        // A previous request is answered with the current state.
+        if let Some((_, prev_tx)) = self.shutdown_waiter.take() {
+            let _ = prev_tx.send(Ok(self.shutdown_report()));
+        }
+
+        self.shutdown_waiter = Some((InstantOf::<C>::now() + timeout, tx));
+        self.check_shutdown_settled();
+    }
+}
+
 impl<C, N, LS, SM> RaftRuntime<C> for RaftCore<C, N, LS, SM>
 where
     C: RaftTypeConfig,
@@ -1586,10 +3273,17 @@ where
         match cmd {
             Command::BecomeLeader => {
                 debug_assert!(self.leader_data.is_none(), "can not become leader twice");
+
+                // A quorum has granted, the votes from the other peers are no longer needed.
+                self.vote_requests.cancel();
                 self.leader_data = Some(LeaderData::new());
             }
             Command::QuitLeader => {
+                if self.leader_data.is_some() {
+                    self.remove_all_replication().await;
+                }
                 self.leader_data = None;
+                self.follower_log_state.clear();
             }
             Command::AppendEntry { entry } => {
                 let log_id = *entry.get_log_id();
@@ -1607,6 +3301,11 @@ where
                 let last_log_id = *entries.last().unwrap().get_log_id();
                 tracing::debug!("AppendInputEntries: {}", DisplaySlice::<_>(&entries),);
 
+                // Entries replicated from a leader carry its timestamps.
+                if let Some(t) = entries.iter().filter_map(|e| e.get_timestamp()).max() {
+                    self.last_timestamp = std::cmp::max(self.last_timestamp, t);
+                }
+
                 self.append_to_log(entries, last_log_id).await?;
 
                 // The leader may have changed.
@@ -1619,6 +3318,9 @@ where
                 self.log_store.save_vote(&vote).await?;
                 self.engine.state.io_state_mut().update_vote(vote);
             }
+            Command::SaveReplicationHints { hints } => {
+                self.log_store.save_replication_hints(hints).await?;
+            }
             Command::PurgeLog { upto } => {
                 self.log_store.purge(upto).await?;
                 self.engine.state.io_state_mut().update_purged(Some(upto));
@@ -1627,7 +3329,11 @@ where
                 self.log_store.truncate(since).await?;
 
                 // Inform clients waiting for logs to be applied.
-                let removed = self.client_resp_channels.split_off(&since.index);
+                let mut removed = self.client_resp_channels.split_off(&since.index).into_iter().collect::<Vec<_>>();
+                self.client_submit_times.split_off(&since.index);
+                if let Some(dedup) = &mut self.write_dedup {
+                    removed.extend(dedup.remove_waiting(since.index, u64::MAX));
+                }
                 if !removed.is_empty() {
                     let leader_id = self.current_leader();
                     let leader_node = self.get_leader_node(leader_id);
@@ -1664,6 +3370,7 @@ where
                 ref upto,
             } => {
                 self.log_store.save_committed(Some(*upto)).await?;
+                self.observe_commit_latency(upto.index);
                 self.apply_to_state_machine(seq, already_committed.next_index(), upto.index).await?;
             }
             Command::Replicate { req, target } => {
@@ -1675,6 +3382,19 @@ where
                             let _ = node.tx_repl.send(Replicate::Heartbeat);
                         }
                         Inflight::Logs { id, log_id_range } => {
+                            // Write-ahead: the leader has persisted the logs before replicating them.
+                            #[cfg(debug_assertions)]
+                            if let Some(local) =
+                                self.engine.internal_server_state.leading().and_then(|l| l.progress.try_get(&self.id))
+                            {
+                                debug_assert!(
+                                    log_id_range.last.as_ref() <= local.matching.as_ref(),
+                                    "logs to replicate {} are not yet persisted by the leader: {}",
+                                    log_id_range,
+                                    local.matching.display()
+                                );
+                            }
+
                             let _ = node.tx_repl.send(Replicate::logs(RequestId::new_append_entries(id), log_id_range));
                         }
                         Inflight::Snapshot { id, last_log_id } => {
@@ -1691,6 +3411,9 @@ where
             Command::RebuildReplicationStreams { targets } => {
                 self.remove_all_replication().await;
 
+                // Forget the latency of the peers that are removed from the membership.
+                self.rpc_latency.retain(|id, _| targets.iter().any(|(t, _)| t == id));
+
                 for (target, matching) in targets.iter() {
                     let handle = self.spawn_replication_stream(*target, *matching).await;
 
@@ -1,8 +1,19 @@
 //! This mod defines external command sent by application to Raft.
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 
 use crate::core::raft_msg::ResultSender;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::TraceWriter;
+use crate::error::SetAppliedIndexError;
+use crate::raft::AppendEntriesValidator;
+use crate::raft::ApplyCoordinator;
+use crate::raft::ApplyObserver;
+use crate::raft::ElectionAdmission;
+use crate::raft::ElectionTiebreaker;
+use crate::raft::WriteDeduplicator;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 
@@ -19,6 +30,10 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     /// Send a heartbeat message, only if the node is leader, or it will be ignored.
     Heartbeat,
 
+    /// Give up the leadership without choosing a successor, only if the node is leader, or it will
+    /// be ignored.
+    StepDown,
+
     /// Initiate to build a snapshot on this node.
     Snapshot,
 
@@ -31,6 +46,68 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     ///
     /// [`max_in_snapshot_log_to_keep`]: `crate::Config::max_in_snapshot_log_to_keep`
     PurgeLog { upto: u64 },
+
+    /// Set the range in milliseconds to pick a random election timeout from.
+    ///
+    /// It takes effect when the election timeout is renewed the next time.
+    SetElectionTimeout { min: u64, max: u64 },
+
+    /// Restore the election timeout range to the one in `Config`.
+    ResetElectionTimeout,
+
+    /// Set the observer to be notified when a log entry is applied to the state machine.
+    SetApplyObserver { observer: Box<dyn ApplyObserver<C>> },
+
+    /// Set the coordinator to permit applying a log entry to the state machine.
+    SetApplyCoordinator { coordinator: Box<dyn ApplyCoordinator<C>> },
+
+    /// Set the validator to check AppendEntries requests in addition to the normal checks.
+    SetAppendEntriesValidator {
+        validator: Box<dyn AppendEntriesValidator<C>>,
+    },
+
+    /// Set the tiebreaker to prefer one of the candidates with equally up-to-date logs.
+    SetElectionTiebreaker { tiebreaker: Box<dyn ElectionTiebreaker<C>> },
+
+    /// Set the labels of the other nodes.
+    SetPeerLabels {
+        labels: BTreeMap<C::NodeId, BTreeMap<String, String>>,
+    },
+
+    /// Set the hook to consult before starting an election on election timeout.
+    SetElectionAdmission { admission: Box<dyn ElectionAdmission<C>> },
+
+    /// Set the deduplicator to skip a client write identical to the last one.
+    SetWriteDeduplicator {
+        deduplicator: Box<dyn WriteDeduplicator<C>>,
+    },
+
+    /// Write a record of every decision to `writer`, or stop recording if it is `None`.
+    #[cfg(feature = "decision-trace")]
+    SetDecisionTrace { writer: Option<Box<dyn TraceWriter>> },
+
+    /// Commit logs accepted by every surviving node instead of a quorum, or restore the normal
+    /// commit quorum if it is `None`.
+    SetUnsafeCommitQuorum { surviving: Option<BTreeSet<C::NodeId>> },
+
+    /// Stop replicating logs or snapshot to `target`, except heartbeats.
+    PauseReplication { target: C::NodeId },
+
+    /// Resume replicating logs or snapshot to a paused `target`.
+    ResumeReplication { target: C::NodeId },
+
+    /// Send the snapshot to `target` at once instead of logs, only if the node is leader, or it
+    /// will be ignored.
+    ForceSnapshotTo { target: C::NodeId },
+
+    /// Leave the degraded state entered because the term is too far ahead of the last log term.
+    ClearTermGapDegraded,
+
+    /// Skip applying entries up to `index`, because the state machine is restored out-of-band.
+    SetAppliedIndex {
+        index: u64,
+        tx: ResultSender<C, (), SetAppliedIndexError<C>>,
+    },
 }
 
 impl<C> fmt::Debug for ExternalCommand<C>
@@ -52,6 +129,9 @@ where C: RaftTypeConfig
             ExternalCommand::Heartbeat => {
                 write!(f, "Heartbeat")
             }
+            ExternalCommand::StepDown => {
+                write!(f, "StepDown")
+            }
             ExternalCommand::Snapshot => {
                 write!(f, "Snapshot")
             }
@@ -61,6 +141,55 @@ where C: RaftTypeConfig
             ExternalCommand::PurgeLog { upto } => {
                 write!(f, "PurgeLog[..={}]", upto)
             }
+            ExternalCommand::SetElectionTimeout { min, max } => {
+                write!(f, "SetElectionTimeout: [{}, {})", min, max)
+            }
+            ExternalCommand::ResetElectionTimeout => {
+                write!(f, "ResetElectionTimeout")
+            }
+            ExternalCommand::SetApplyObserver { .. } => {
+                write!(f, "SetApplyObserver")
+            }
+            ExternalCommand::SetApplyCoordinator { .. } => {
+                write!(f, "SetApplyCoordinator")
+            }
+            ExternalCommand::SetAppendEntriesValidator { .. } => {
+                write!(f, "SetAppendEntriesValidator")
+            }
+            ExternalCommand::SetElectionTiebreaker { .. } => {
+                write!(f, "SetElectionTiebreaker")
+            }
+            ExternalCommand::SetPeerLabels { labels } => {
+                write!(f, "SetPeerLabels: {} nodes", labels.len())
+            }
+            ExternalCommand::SetElectionAdmission { .. } => {
+                write!(f, "SetElectionAdmission")
+            }
+            ExternalCommand::SetWriteDeduplicator { .. } => {
+                write!(f, "SetWriteDeduplicator")
+            }
+            #[cfg(feature = "decision-trace")]
+            ExternalCommand::SetDecisionTrace { writer } => {
+                write!(f, "SetDecisionTrace: enabled: {}", writer.is_some())
+            }
+            ExternalCommand::SetUnsafeCommitQuorum { surviving } => {
+                write!(f, "SetUnsafeCommitQuorum: {:?}", surviving)
+            }
+            ExternalCommand::PauseReplication { target } => {
+                write!(f, "PauseReplication: {}", target)
+            }
+            ExternalCommand::ResumeReplication { target } => {
+                write!(f, "ResumeReplication: {}", target)
+            }
+            ExternalCommand::ForceSnapshotTo { target } => {
+                write!(f, "ForceSnapshotTo: {}", target)
+            }
+            ExternalCommand::ClearTermGapDegraded => {
+                write!(f, "ClearTermGapDegraded")
+            }
+            ExternalCommand::SetAppliedIndex { index, .. } => {
+                write!(f, "SetAppliedIndex: {}", index)
+            }
         }
     }
 }
@@ -1,13 +1,22 @@
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
 
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::error::CatchUpError;
 use crate::error::CheckIsLeaderError;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::InstallSnapshotError;
+use crate::error::NodeIsWitness;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::BoxCoreFn;
+use crate::raft::CommitWait;
+use crate::raft::Committed;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
@@ -18,6 +27,7 @@ use crate::type_config::alias::SnapshotDataOf;
 use crate::ChangeMembers;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
+use crate::StorageError;
 use crate::Vote;
 
 pub(crate) mod external_command;
@@ -34,6 +44,19 @@ pub(crate) type AppendEntriesTx<C> = ResultSender<C, AppendEntriesResponse<C>>;
 /// TX for Linearizable Read Response
 pub(crate) type ClientReadTx<C> = ResultSender<C, (Option<LogIdOf<C>>, Option<LogIdOf<C>>), CheckIsLeaderError<C>>;
 
+/// The payload of a client write request.
+pub(crate) enum ClientPayload<C>
+where C: RaftTypeConfig
+{
+    /// Application data, appended as a normal entry.
+    AppData(C::D),
+
+    /// Application data, appended as a checkpoint, see [`RaftEntry::new_checkpoint()`].
+    ///
+    /// [`RaftEntry::new_checkpoint()`]: crate::entry::RaftEntry::new_checkpoint
+    Checkpoint(C::D),
+}
+
 /// A message sent by application to the [`RaftCore`].
 ///
 /// [`RaftCore`]: crate::core::RaftCore
@@ -50,6 +73,12 @@ where C: RaftTypeConfig
         tx: VoteTx<C>,
     },
 
+    /// Migrate a received snapshot to the format version of the local state machine.
+    MigrateSnapshot {
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, Snapshot<C>, InstallSnapshotError>,
+    },
+
     InstallFullSnapshot {
         vote: Vote<C::NodeId>,
         snapshot: Snapshot<C>,
@@ -67,7 +96,15 @@ where C: RaftTypeConfig
     },
 
     ClientWriteRequest {
-        app_data: C::D,
+        payload: ClientPayload<C>,
+
+        /// Opaque application metadata to store in the entry, see [`RaftEntry::set_metadata()`].
+        ///
+        /// [`RaftEntry::set_metadata()`]: crate::entry::RaftEntry::set_metadata
+        metadata: Option<Vec<u8>>,
+
+        /// Wait for a follower in a group to acknowledge the entry before responding.
+        commit_wait: Option<CommitWait>,
         tx: ResponderOf<C>,
     },
 
@@ -75,11 +112,33 @@ where C: RaftTypeConfig
         tx: ClientReadTx<C>,
     },
 
+    /// Get the committed and the applied log id without confirming the leadership.
+    ReadBarrier {
+        tx: ResultSender<C, (Option<LogIdOf<C>>, Option<LogIdOf<C>>), NodeIsWitness<C>>,
+    },
+
+    /// Get the read log id from the leader, on a node that is not the leader.
+    GetLeaderReadLogId {
+        timeout: Duration,
+        tx: ResultSender<C, Option<LogIdOf<C>>, CatchUpError<C>>,
+    },
+
+    /// Feed the committed entries since `start` to `tx`.
+    SubscribeCommitted {
+        start: u64,
+        tx: mpsc::Sender<Result<Committed<C>, StorageError<C::NodeId>>>,
+    },
+
     Initialize {
         members: BTreeMap<C::NodeId, C::Node>,
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
+    InitializeWithSnapshot {
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, (), InitializeError<C>>,
+    },
+
     ChangeMembership {
         changes: ChangeMembers<C::NodeId, C::Node>,
 
@@ -87,6 +146,22 @@ where C: RaftTypeConfig
         /// config will be converted into learners, otherwise they will be removed.
         retain: bool,
 
+        /// If `force` is `true`, a node added within `Config::member_removal_grace` is removed
+        /// anyway.
+        force: bool,
+
+        tx: ResponderOf<C>,
+    },
+
+    /// Seal or unseal the cluster by writing a membership entry with the `sealed` flag set.
+    SetSealed {
+        sealed: bool,
+        tx: ResponderOf<C>,
+    },
+
+    /// Make voters witnesses by writing a membership entry with them added as witnesses.
+    AddWitnesses {
+        witnesses: BTreeSet<C::NodeId>,
         tx: ResponderOf<C>,
     },
 
@@ -114,19 +189,38 @@ where C: RaftTypeConfig
             RaftMsg::BeginReceivingSnapshot { .. } => {
                 write!(f, "BeginReceivingSnapshot")
             }
+            RaftMsg::MigrateSnapshot { snapshot, .. } => {
+                write!(f, "MigrateSnapshot: {}", snapshot)
+            }
             RaftMsg::InstallFullSnapshot { vote, snapshot, .. } => {
                 write!(f, "InstallFullSnapshot: vote: {}, snapshot: {}", vote, snapshot)
             }
             RaftMsg::ClientWriteRequest { .. } => write!(f, "ClientWriteRequest"),
             RaftMsg::CheckIsLeaderRequest { .. } => write!(f, "CheckIsLeaderRequest"),
+            RaftMsg::ReadBarrier { .. } => write!(f, "ReadBarrier"),
+            RaftMsg::GetLeaderReadLogId { timeout, .. } => {
+                write!(f, "GetLeaderReadLogId: timeout: {:?}", timeout)
+            }
+            RaftMsg::SubscribeCommitted { start, .. } => write!(f, "SubscribeCommitted: start: {}", start),
             RaftMsg::Initialize { members, .. } => {
                 // TODO: avoid using Debug
                 write!(f, "Initialize: {:?}", members)
             }
-            RaftMsg::ChangeMembership { changes, retain, .. } => {
+            RaftMsg::InitializeWithSnapshot { snapshot, .. } => {
+                write!(f, "InitializeWithSnapshot: {}", snapshot)
+            }
+            RaftMsg::ChangeMembership {
+                changes, retain, force, ..
+            } => {
                 // TODO: avoid using Debug
-                write!(f, "ChangeMembership: members: {:?}, retain: {}", changes, retain,)
+                write!(
+                    f,
+                    "ChangeMembership: members: {:?}, retain: {}, force: {}",
+                    changes, retain, force
+                )
             }
+            RaftMsg::SetSealed { sealed, .. } => write!(f, "SetSealed: {}", sealed),
+            RaftMsg::AddWitnesses { witnesses, .. } => write!(f, "AddWitnesses: {:?}", witnesses),
             RaftMsg::ExternalCoreRequest { .. } => write!(f, "External Request"),
             RaftMsg::ExternalCommand { cmd } => {
                 write!(f, "ExternalCommand: {}", cmd)
@@ -0,0 +1,105 @@
+use crate::core::raft_msg::ClientReadTx;
+use crate::RaftTypeConfig;
+
+/// Queue of linearizable read requests waiting for the leadership to be confirmed.
+///
+/// At most one round of leadership confirmation heartbeats is in flight.
+/// Read requests received while a round is in flight can not be served by it,
+/// because the heartbeats of that round may have been sent before the requests arrived.
+/// They are queued and will be confirmed together by the next round.
+pub(crate) struct ReadBatch<C>
+where C: RaftTypeConfig
+{
+    /// Read requests that are not yet confirmed by any round.
+    pending: Vec<ClientReadTx<C>>,
+
+    /// The id of the round being in flight, if any.
+    confirming: Option<u64>,
+
+    /// The id to assign to the next round.
+    next_round: u64,
+}
+
+impl<C> Default for ReadBatch<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            pending: vec![],
+            confirming: None,
+            next_round: 1,
+        }
+    }
+}
+
+impl<C> ReadBatch<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn push(&mut self, tx: ClientReadTx<C>) {
+        self.pending.push(tx);
+    }
+
+    /// Take all pending read requests for a new round, if there is no round in flight.
+    ///
+    /// It returns the round id and the requests to confirm.
+    pub(crate) fn start_round(&mut self) -> Option<(u64, Vec<ClientReadTx<C>>)> {
+        if self.confirming.is_some() || self.pending.is_empty() {
+            return None;
+        }
+
+        let round = self.next_round;
+        self.next_round += 1;
+        self.confirming = Some(round);
+
+        Some((round, std::mem::take(&mut self.pending)))
+    }
+
+    /// Mark a round as finished, so that the next round can be started.
+    ///
+    /// A stale round id, e.g., one that is already finished, is ignored.
+    pub(crate) fn finish_round(&mut self, round: u64) {
+        if self.confirming == Some(round) {
+            self.confirming = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::read_batch::ReadBatch;
+    use crate::engine::testing::UTConfig;
+    use crate::AsyncRuntime;
+    use crate::RaftTypeConfig;
+
+    type Batch = ReadBatch<UTConfig>;
+
+    fn tx() -> crate::core::raft_msg::ClientReadTx<UTConfig> {
+        let (tx, _rx) = <UTConfig as RaftTypeConfig>::AsyncRuntime::oneshot();
+        tx
+    }
+
+    #[test]
+    fn test_read_batch_one_round_at_a_time() {
+        let mut b = Batch::default();
+
+        assert!(b.start_round().is_none(), "nothing to confirm");
+
+        b.push(tx());
+        b.push(tx());
+
+        let (round, txs) = b.start_round().unwrap();
+        assert_eq!(1, round);
+        assert_eq!(2, txs.len());
+
+        b.push(tx());
+        assert!(b.start_round().is_none(), "round 1 is in flight");
+
+        b.finish_round(2);
+        assert!(b.start_round().is_none(), "stale round id is ignored");
+
+        b.finish_round(1);
+        let (round, txs) = b.start_round().unwrap();
+        assert_eq!(2, round);
+        assert_eq!(1, txs.len());
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use crate::type_config::alias::InstantOf;
+use crate::AsyncRuntime;
+use crate::Instant;
+use crate::RaftTypeConfig;
+
+/// Limits the number of log entries applied to the state machine in every fixed time window.
+///
+/// See [`Config::apply_rate_limit`](`crate::Config::apply_rate_limit`).
+#[derive(Debug)]
+pub(crate) struct ApplyRate<C>
+where C: RaftTypeConfig
+{
+    /// The max number of entries to apply in a window.
+    max_entries: u64,
+
+    /// The length of a window.
+    interval: Duration,
+
+    /// The start of the current window, `None` if nothing has been applied yet.
+    window_start: Option<InstantOf<C>>,
+
+    /// The number of entries applied in the current window.
+    used: u64,
+}
+
+impl<C> ApplyRate<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(max_entries: u64, interval: Duration) -> Self {
+        Self {
+            max_entries,
+            interval,
+            window_start: None,
+            used: 0,
+        }
+    }
+
+    /// Return the number of entries, at most `n`, permitted to apply in the current window.
+    ///
+    /// If the current window is used up, it waits until the next window starts. Thus it always
+    /// permits at least one entry if `n > 0`.
+    pub(crate) async fn acquire(&mut self, n: u64) -> u64 {
+        let now = InstantOf::<C>::now();
+
+        match self.window_start {
+            Some(start) if now < start + self.interval => {
+                if self.used >= self.max_entries {
+                    let next = start + self.interval;
+                    tracing::debug!(used = self.used, "apply rate limit reached, wait for the next window");
+
+                    C::AsyncRuntime::sleep_until(next).await;
+
+                    self.window_start = Some(next);
+                    self.used = 0;
+                }
+            }
+            _ => {
+                self.window_start = Some(now);
+                self.used = 0;
+            }
+        }
+
+        let permitted = std::cmp::min(n, self.max_entries - self.used);
+        self.used += permitted;
+        permitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ApplyRate;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::alias::InstantOf;
+
+    #[tokio::test]
+    async fn test_apply_rate_acquire() {
+        let mut r = ApplyRate::<UTConfig>::new(3, Duration::from_millis(100));
+
+        let now = InstantOf::<UTConfig>::now();
+
+        assert_eq!(2, r.acquire(2).await);
+        assert_eq!(1, r.acquire(5).await);
+
+        // The window is used up, the next acquire waits for the next window.
+        assert_eq!(3, r.acquire(5).await);
+        assert!(now.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -4,7 +4,10 @@ use std::fmt::Formatter;
 use crate::core::raft_msg::ResultSender;
 use crate::display_ext::DisplaySlice;
 use crate::error::Infallible;
+use crate::error::InstallSnapshotError;
 use crate::log_id::RaftLogId;
+use crate::raft::ApplyCoordinator;
+use crate::raft::ApplyObserver;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
@@ -64,6 +67,14 @@ where C: RaftTypeConfig
         Command::new(payload)
     }
 
+    pub(crate) fn migrate_snapshot(
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, Snapshot<C>, InstallSnapshotError>,
+    ) -> Self {
+        let payload = CommandPayload::MigrateSnapshot { snapshot, tx };
+        Command::new(payload)
+    }
+
     pub(crate) fn install_full_snapshot(snapshot: Snapshot<C>) -> Self {
         let payload = CommandPayload::InstallFullSnapshot { snapshot };
         Command::new(payload)
@@ -73,6 +84,21 @@ where C: RaftTypeConfig
         let payload = CommandPayload::Apply { entries };
         Command::new(payload)
     }
+
+    pub(crate) fn retry_apply() -> Self {
+        let payload = CommandPayload::RetryApply;
+        Command::new(payload)
+    }
+
+    pub(crate) fn set_apply_observer(observer: Box<dyn ApplyObserver<C>>) -> Self {
+        let payload = CommandPayload::SetApplyObserver { observer };
+        Command::new(payload)
+    }
+
+    pub(crate) fn set_apply_coordinator(coordinator: Box<dyn ApplyCoordinator<C>>) -> Self {
+        let payload = CommandPayload::SetApplyCoordinator { coordinator };
+        Command::new(payload)
+    }
 }
 
 // TODO: move to other mod, it is shared by log, sm and replication
@@ -98,6 +124,12 @@ where C: RaftTypeConfig
         tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>,
     },
 
+    /// Migrate a received snapshot to the format version of the state machine.
+    MigrateSnapshot {
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, Snapshot<C>, InstallSnapshotError>,
+    },
+
     InstallFullSnapshot {
         snapshot: Snapshot<C>,
     },
@@ -106,6 +138,22 @@ where C: RaftTypeConfig
     Apply {
         entries: Vec<C::Entry>,
     },
+
+    /// Retry the last failed apply, if there is one.
+    ///
+    /// `Apply` and `InstallFullSnapshot` commands received after a failed apply are held back by
+    /// the worker until the retry succeeds.
+    RetryApply,
+
+    /// Set the observer to be notified when a log entry is applied.
+    SetApplyObserver {
+        observer: Box<dyn ApplyObserver<C>>,
+    },
+
+    /// Set the coordinator to permit applying a log entry.
+    SetApplyCoordinator {
+        coordinator: Box<dyn ApplyCoordinator<C>>,
+    },
 }
 
 impl<C> Debug for CommandPayload<C>
@@ -115,6 +163,9 @@ where C: RaftTypeConfig
         match self {
             CommandPayload::BuildSnapshot => write!(f, "BuildSnapshot"),
             CommandPayload::GetSnapshot { .. } => write!(f, "GetSnapshot"),
+            CommandPayload::MigrateSnapshot { snapshot, .. } => {
+                write!(f, "MigrateSnapshot: meta: {:?}", snapshot.meta)
+            }
             CommandPayload::InstallFullSnapshot { snapshot } => {
                 write!(f, "InstallFullSnapshot: meta: {:?}", snapshot.meta)
             }
@@ -122,6 +173,9 @@ where C: RaftTypeConfig
                 write!(f, "BeginReceivingSnapshot")
             }
             CommandPayload::Apply { entries } => write!(f, "Apply: {}", DisplaySlice::<_>(entries)),
+            CommandPayload::RetryApply => write!(f, "RetryApply"),
+            CommandPayload::SetApplyObserver { .. } => write!(f, "SetApplyObserver"),
+            CommandPayload::SetApplyCoordinator { .. } => write!(f, "SetApplyCoordinator"),
         }
     }
 }
@@ -135,10 +189,17 @@ where C: RaftTypeConfig
             (CommandPayload::BuildSnapshot, CommandPayload::BuildSnapshot) => true,
             (CommandPayload::GetSnapshot { .. }, CommandPayload::GetSnapshot { .. }) => true,
             (CommandPayload::BeginReceivingSnapshot { .. }, CommandPayload::BeginReceivingSnapshot { .. }) => true,
+            (
+                CommandPayload::MigrateSnapshot { snapshot: s1, .. },
+                CommandPayload::MigrateSnapshot { snapshot: s2, .. },
+            ) => s1.meta == s2.meta,
             (
                 CommandPayload::InstallFullSnapshot { snapshot: s1 },
                 CommandPayload::InstallFullSnapshot { snapshot: s2 },
             ) => s1.meta == s2.meta,
+            (CommandPayload::RetryApply, CommandPayload::RetryApply) => true,
+            (CommandPayload::SetApplyObserver { .. }, CommandPayload::SetApplyObserver { .. }) => true,
+            (CommandPayload::SetApplyCoordinator { .. }, CommandPayload::SetApplyCoordinator { .. }) => true,
             (CommandPayload::Apply { entries: entries1 }, CommandPayload::Apply { entries: entries2 }) => {
                 // Entry may not be `Eq`, we just compare log id.
                 // This would be enough for testing.
@@ -0,0 +1,25 @@
+use crate::core::sm::CommandSeq;
+use crate::RaftTypeConfig;
+
+/// An apply that failed and waits for RaftCore to retry it with `CommandPayload::RetryApply`.
+///
+/// See [`Config::apply_stall_backlog`](`crate::Config::apply_stall_backlog`).
+pub(crate) struct FailedApply<C>
+where C: RaftTypeConfig
+{
+    /// The sequence number of the `Apply` command the failed entries belong to.
+    pub(crate) seq: CommandSeq,
+
+    /// The index of the first entry of the failed apply.
+    pub(crate) first: u64,
+
+    /// The index of the last entry of the failed apply.
+    ///
+    /// The entries are consumed by the failed apply, thus they are read again from the log before
+    /// retrying.
+    pub(crate) last: u64,
+
+    /// The entries of the same `Apply` command that are not yet applied, because an apply rate
+    /// limit split the command into chunks.
+    pub(crate) rest: Vec<C::Entry>,
+}
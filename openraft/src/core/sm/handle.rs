@@ -1,5 +1,9 @@
 //! State machine control handle
 
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 use tokio::sync::mpsc;
 
 use crate::core::sm;
@@ -15,6 +19,9 @@ where C: RaftTypeConfig
 {
     pub(in crate::core::sm) cmd_tx: mpsc::UnboundedSender<sm::Command<C>>,
 
+    /// Entries with an index smaller than this are skipped by the worker.
+    pub(in crate::core::sm) skip_apply_before: Arc<AtomicU64>,
+
     #[allow(dead_code)]
     pub(in crate::core::sm) join_handle: JoinHandleOf<C, ()>,
 }
@@ -27,6 +34,12 @@ where C: RaftTypeConfig
         self.cmd_tx.send(cmd)
     }
 
+    /// Let the worker skip applying entries up to `index`, including those already sent to it but
+    /// not yet applied.
+    pub(crate) fn skip_apply_upto(&self, index: u64) {
+        self.skip_apply_before.fetch_max(index + 1, Ordering::Relaxed);
+    }
+
     /// Create a [`SnapshotReader`] to get the current snapshot from the state machine.
     pub(crate) fn new_snapshot_reader(&self) -> SnapshotReader<C> {
         SnapshotReader {
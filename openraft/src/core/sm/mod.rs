@@ -4,7 +4,9 @@
 //! It is responsible for applying log entries, building/receiving snapshot  and sending responses
 //! to the RaftCore.
 
+pub(crate) mod apply_rate;
 pub(crate) mod command;
+pub(crate) mod failed_apply;
 pub(crate) mod handle;
 pub(crate) mod response;
 pub(crate) mod worker;
@@ -19,6 +19,10 @@ where C: RaftTypeConfig
 
     /// Send back applied result to RaftCore.
     Apply(ApplyResult<C>),
+
+    /// Send back the result of a part of the entries of an `Apply` command, which is not yet
+    /// finished, because the rest of the entries are waiting for the apply rate limit.
+    ApplyPartial(ApplyResult<C>),
 }
 
 /// Container of result of a command.
@@ -1,8 +1,16 @@
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use anyerror::AnyError;
 use tokio::sync::mpsc;
 
 use crate::async_runtime::AsyncOneshotSendExt;
 use crate::core::notify::Notify;
 use crate::core::raft_msg::ResultSender;
+use crate::core::sm::apply_rate::ApplyRate;
+use crate::core::sm::failed_apply::FailedApply;
 use crate::core::sm::handle::Handle;
 use crate::core::sm::Command;
 use crate::core::sm::CommandPayload;
@@ -12,46 +20,102 @@ use crate::core::sm::Response;
 use crate::core::ApplyResult;
 use crate::core::ApplyingEntry;
 use crate::display_ext::DisplayOptionExt;
+use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
+use crate::error::UnsupportedSnapshotFormat;
+use crate::raft::ApplyCoordinator;
+use crate::raft::ApplyObserver;
+use crate::storage::RaftLogReader;
 use crate::storage::RaftStateMachine;
 use crate::type_config::alias::JoinHandleOf;
 use crate::AsyncRuntime;
+use crate::LogIdOptionExt;
 use crate::RaftLogId;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 use crate::StorageError;
+use crate::StorageIOError;
 
-pub(crate) struct Worker<C, SM>
+pub(crate) struct Worker<C, SM, LR>
 where
     C: RaftTypeConfig,
     SM: RaftStateMachine<C>,
+    LR: RaftLogReader<C>,
 {
     state_machine: SM,
 
+    /// Reads the applied entries again for [`ApplyObserver`], since they are consumed by
+    /// [`RaftStateMachine::apply()`].
+    log_reader: LR,
+
+    /// The observer to notify when a log entry is applied.
+    apply_observer: Option<Box<dyn ApplyObserver<C>>>,
+
+    /// The coordinator that permits applying a log entry.
+    apply_coordinator: Option<Box<dyn ApplyCoordinator<C>>>,
+
+    /// Limits the rate of applying entries, if `Config::apply_rate_limit` is set.
+    apply_rate: Option<ApplyRate<C>>,
+
+    /// Whether a failed apply waits for RaftCore to retry it instead of shutting down the worker,
+    /// i.e., `Config::apply_stall_backlog` is set.
+    retry_failed_apply: bool,
+
+    /// The failed apply waiting for a `RetryApply` command.
+    failed_apply: Option<FailedApply<C>>,
+
+    /// `Apply` and `InstallFullSnapshot` commands received while [`Self::failed_apply`] is set.
+    /// They are handled in order once the retry succeeds.
+    deferred: VecDeque<Command<C>>,
+
+    /// Entries with an index smaller than this are not applied, because the state machine has been
+    /// restored out-of-band. It is shared with [`Handle`].
+    skip_apply_before: Arc<AtomicU64>,
+
     cmd_rx: mpsc::UnboundedReceiver<Command<C>>,
 
     resp_tx: mpsc::UnboundedSender<Notify<C>>,
 }
 
-impl<C, SM> Worker<C, SM>
+impl<C, SM, LR> Worker<C, SM, LR>
 where
     C: RaftTypeConfig,
     SM: RaftStateMachine<C>,
+    LR: RaftLogReader<C>,
 {
     /// Spawn a new state machine worker, return a controlling handle.
-    pub(crate) fn spawn(state_machine: SM, resp_tx: mpsc::UnboundedSender<Notify<C>>) -> Handle<C> {
+    pub(crate) fn spawn(
+        state_machine: SM,
+        log_reader: LR,
+        apply_rate: Option<ApplyRate<C>>,
+        retry_failed_apply: bool,
+        resp_tx: mpsc::UnboundedSender<Notify<C>>,
+    ) -> Handle<C> {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let skip_apply_before = Arc::new(AtomicU64::new(0));
 
         let worker = Worker {
             state_machine,
+            log_reader,
+            apply_observer: None,
+            apply_coordinator: None,
+            apply_rate,
+            retry_failed_apply,
+            failed_apply: None,
+            deferred: VecDeque::new(),
+            skip_apply_before: skip_apply_before.clone(),
             cmd_rx,
             resp_tx,
         };
 
         let join_handle = worker.do_spawn();
 
-        Handle { cmd_tx, join_handle }
+        Handle {
+            cmd_tx,
+            skip_apply_before,
+            join_handle,
+        }
     }
 
     fn do_spawn(mut self) -> JoinHandleOf<C, ()> {
@@ -85,6 +149,17 @@ where
 
             tracing::debug!("{}: received command: {:?}", func_name!(), cmd);
 
+            if self.failed_apply.is_some()
+                && matches!(
+                    cmd.payload,
+                    CommandPayload::Apply { .. } | CommandPayload::InstallFullSnapshot { .. }
+                )
+            {
+                tracing::debug!("{}: defer command until the failed apply is retried", func_name!());
+                self.deferred.push_back(cmd);
+                continue;
+            }
+
             match cmd.payload {
                 CommandPayload::BuildSnapshot => {
                     tracing::info!("{}: build snapshot", func_name!());
@@ -98,16 +173,25 @@ where
                     self.get_snapshot(tx).await?;
                     // GetSnapshot does not respond to RaftCore
                 }
-                CommandPayload::InstallFullSnapshot { snapshot } => {
-                    tracing::info!("{}: install complete snapshot", func_name!());
+                CommandPayload::MigrateSnapshot { snapshot, tx } => {
+                    tracing::info!("{}: migrate snapshot: {}", func_name!(), snapshot.meta);
 
-                    let meta = snapshot.meta.clone();
-                    self.state_machine.install_snapshot(&meta, snapshot.snapshot).await?;
+                    let format_version = snapshot.meta.format_version();
+                    let migrated = self.state_machine.migrate_snapshot(snapshot).await?;
 
-                    tracing::info!("Done install complete snapshot, meta: {}", meta);
+                    let res = migrated.ok_or_else(|| {
+                        UnsupportedSnapshotFormat {
+                            format_version,
+                            current: self.state_machine.snapshot_format_version(),
+                        }
+                        .into()
+                    });
 
-                    let res = CommandResult::new(cmd.seq, Ok(Response::InstallSnapshot(Some(meta))));
-                    let _ = self.resp_tx.send(Notify::sm(res));
+                    let _ = tx.send(res);
+                    // No response to RaftCore
+                }
+                CommandPayload::InstallFullSnapshot { snapshot } => {
+                    self.install_full_snapshot(cmd.seq, snapshot).await?;
                 }
                 CommandPayload::BeginReceivingSnapshot { tx } => {
                     tracing::info!("{}: BeginReceivingSnapshot", func_name!());
@@ -118,23 +202,203 @@ where
                     // No response to RaftCore
                 }
                 CommandPayload::Apply { entries } => {
-                    let resp = self.apply(entries).await?;
-                    let res = CommandResult::new(cmd.seq, Ok(Response::Apply(resp)));
-                    let _ = self.resp_tx.send(Notify::sm(res));
+                    self.apply_paced(cmd.seq, entries).await?;
+                }
+                CommandPayload::RetryApply => {
+                    self.retry_apply().await?;
+                }
+                CommandPayload::SetApplyObserver { observer } => {
+                    tracing::info!("{}: set apply observer", func_name!());
+
+                    self.apply_observer = Some(observer);
+                    // No response to RaftCore
+                }
+                CommandPayload::SetApplyCoordinator { coordinator } => {
+                    tracing::info!("{}: set apply coordinator", func_name!());
+
+                    self.apply_coordinator = Some(coordinator);
+                    // No response to RaftCore
                 }
             };
         }
     }
+
+    async fn install_full_snapshot(
+        &mut self,
+        seq: CommandSeq,
+        snapshot: Snapshot<C>,
+    ) -> Result<(), StorageError<C::NodeId>> {
+        tracing::info!("{}: install complete snapshot", func_name!());
+
+        let meta = snapshot.meta.clone();
+        self.state_machine.install_snapshot(&meta, snapshot.snapshot).await?;
+
+        if let Some(observer) = &mut self.apply_observer {
+            observer.on_install_snapshot(&meta);
+        }
+
+        tracing::info!("Done install complete snapshot, meta: {}", meta);
+
+        let res = CommandResult::new(seq, Ok(Response::InstallSnapshot(Some(meta))));
+        let _ = self.resp_tx.send(Notify::sm(res));
+        Ok(())
+    }
+
+    /// Apply entries in chunks no larger than the apply rate limit permits.
+    ///
+    /// Every chunk but the last one is responded to RaftCore with [`Response::ApplyPartial`] as
+    /// soon as it is applied, so that the progress is visible while the rest wait.
+    ///
+    /// If a chunk fails and `Config::apply_stall_backlog` is set, the failure is reported to
+    /// RaftCore with [`Notify::ApplyFailing`] and the rest of the entries wait in
+    /// [`FailedApply`] for RaftCore to retry it, instead of shutting down the worker.
+    async fn apply_paced(
+        &mut self,
+        seq: CommandSeq,
+        mut entries: Vec<C::Entry>,
+    ) -> Result<(), StorageError<C::NodeId>> {
+        loop {
+            let n = match &mut self.apply_rate {
+                None => entries.len(),
+                Some(rate) => rate.acquire(entries.len() as u64).await as usize,
+            };
+
+            let rest = entries.split_off(n);
+
+            let first = entries.first().map(|x| x.get_log_id().index).unwrap();
+            let last = entries.last().map(|x| x.get_log_id().index).unwrap();
+
+            let resp = match self.apply(entries, 0).await {
+                Ok(resp) => resp,
+                Err(err) => {
+                    if !self.retry_failed_apply {
+                        return Err(err);
+                    }
+
+                    tracing::warn!(first, last, "apply failed, wait for RaftCore to retry: {}", err);
+                    self.failed_apply = Some(FailedApply { seq, first, last, rest });
+                    let _ = self.resp_tx.send(Notify::ApplyFailing { error: Some(err) });
+                    return Ok(());
+                }
+            };
+
+            if rest.is_empty() {
+                let res = CommandResult::new(seq, Ok(Response::Apply(resp)));
+                let _ = self.resp_tx.send(Notify::sm(res));
+                return Ok(());
+            }
+
+            let res = CommandResult::new(seq, Ok(Response::ApplyPartial(resp)));
+            let _ = self.resp_tx.send(Notify::sm(res));
+
+            entries = rest;
+        }
+    }
+
+    /// Retry the failed apply, then handle the commands deferred by it, if it succeeds.
+    ///
+    /// The entries are consumed by the failed apply, thus they are read again from the log.
+    ///
+    /// A failed apply may have applied some of the entries. A retry resumes after the last applied
+    /// log id reported by [`RaftStateMachine::applied_state()`], so that no entry is applied twice.
+    async fn retry_apply(&mut self) -> Result<(), StorageError<C::NodeId>> {
+        let Some(failed) = self.failed_apply.take() else {
+            tracing::debug!("{}: no failed apply to retry", func_name!());
+            return Ok(());
+        };
+
+        let FailedApply { seq, first, last, rest } = failed;
+
+        let (last_applied, _) = self.state_machine.applied_state().await?;
+        let resume = last_applied.next_index();
+
+        let entries = self.log_reader.try_get_log_entries(first..=last).await?;
+
+        let resp = match self.apply(entries, resume).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                tracing::warn!(first, last, "retried apply failed: {}", err);
+                self.failed_apply = Some(FailedApply { seq, first, last, rest });
+                let _ = self.resp_tx.send(Notify::ApplyFailing { error: Some(err) });
+                return Ok(());
+            }
+        };
+
+        tracing::info!(first, last, "apply recovered");
+        let _ = self.resp_tx.send(Notify::ApplyFailing { error: None });
+
+        if rest.is_empty() {
+            let res = CommandResult::new(seq, Ok(Response::Apply(resp)));
+            let _ = self.resp_tx.send(Notify::sm(res));
+        } else {
+            let res = CommandResult::new(seq, Ok(Response::ApplyPartial(resp)));
+            let _ = self.resp_tx.send(Notify::sm(res));
+
+            self.apply_paced(seq, rest).await?;
+        }
+
+        while self.failed_apply.is_none() {
+            let Some(cmd) = self.deferred.pop_front() else {
+                break;
+            };
+
+            match cmd.payload {
+                CommandPayload::Apply { entries } => {
+                    self.apply_paced(cmd.seq, entries).await?;
+                }
+                CommandPayload::InstallFullSnapshot { snapshot } => {
+                    self.install_full_snapshot(cmd.seq, snapshot).await?;
+                }
+                _ => unreachable!("only Apply and InstallFullSnapshot are deferred"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply entries, except the ones before `resume`, which are already applied by a failed
+    /// apply.
     #[tracing::instrument(level = "debug", skip_all)]
-    async fn apply(&mut self, entries: Vec<C::Entry>) -> Result<ApplyResult<C>, StorageError<C::NodeId>> {
+    async fn apply(
+        &mut self,
+        mut entries: Vec<C::Entry>,
+        resume: u64,
+    ) -> Result<ApplyResult<C>, StorageError<C::NodeId>> {
         // TODO: prepare response before apply_to_state_machine,
         //       so that an Entry does not need to be Clone,
         //       and no references will be used by apply_to_state_machine
 
-        let since = entries.first().map(|x| x.get_log_id().index).unwrap();
         let end = entries.last().map(|x| x.get_log_id().index + 1).unwrap();
         let last_applied = entries.last().map(|x| *x.get_log_id()).unwrap();
 
+        let n_applied = entries.iter().take_while(|x| x.get_log_id().index < resume).count();
+        if n_applied > 0 {
+            tracing::info!(
+                resume,
+                first = display(entries[0].get_log_id()),
+                n_applied,
+                "skip entries applied by a failed apply"
+            );
+            entries.drain(..n_applied);
+        }
+
+        let skip_since = entries.first().map(|x| x.get_log_id().index).unwrap_or(end);
+
+        let skip_before = self.skip_apply_before.load(Ordering::Relaxed);
+        let n_skip = entries.iter().take_while(|x| x.get_log_id().index < skip_before).count();
+        if n_skip > 0 {
+            tracing::warn!(
+                skip_before,
+                first = display(entries[0].get_log_id()),
+                n_skip,
+                "DANGEROUS: skip applying entries, the state machine is restored out-of-band"
+            );
+            entries.drain(..n_skip);
+        }
+
+        // Skipped entries are not in the result.
+        let since = entries.first().map(|x| x.get_log_id().index).unwrap_or(end);
+
         // Fake complain: avoid using `collect()` when not needed
         #[allow(clippy::needless_collect)]
         let applying_entries = entries
@@ -143,23 +407,60 @@ where
             .collect::<Vec<_>>();
 
         let n_entries = applying_entries.len();
+        let last_timestamp = entries.iter().filter_map(|e| e.get_timestamp()).max();
+
+        let apply_results = if entries.is_empty() {
+            vec![]
+        } else {
+            // Permits are acquired in index order for the whole batch before applying it.
+            let mut permitted = vec![];
+            if let Some(coordinator) = &mut self.apply_coordinator {
+                for entry in entries.iter() {
+                    coordinator.acquire(entry).await;
+                    permitted.push(*entry.get_log_id());
+                }
+            }
+
+            let res = self.state_machine.apply(entries).await?;
 
-        let apply_results = self.state_machine.apply(entries).await?;
+            if let Some(coordinator) = &mut self.apply_coordinator {
+                for log_id in permitted.iter() {
+                    coordinator.on_applied(log_id);
+                }
+            }
+            res
+        };
 
         let n_replies = apply_results.len();
 
-        debug_assert_eq!(
-            n_entries, n_replies,
-            "n_entries: {} should equal n_replies: {}",
-            n_entries, n_replies
-        );
+        if n_entries != n_replies {
+            return Err(StorageIOError::apply(
+                last_applied,
+                AnyError::error(format!(
+                    "state machine returned {} responses for {} entries",
+                    n_replies, n_entries
+                )),
+            )
+            .into());
+        }
+
+        if n_entries > 0 {
+            if let Some(observer) = &mut self.apply_observer {
+                let applied = self.log_reader.try_get_log_entries(since..end).await?;
+                for (entry, response) in applied.iter().zip(apply_results.iter()) {
+                    observer.on_apply(entry, response);
+                }
+            }
+        }
 
         let resp = ApplyResult {
+            skip_since,
             since,
             end,
             last_applied,
             applying_entries,
             apply_results,
+            last_timestamp,
         };
 
         Ok(resp)
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::engine::Command;
+use crate::type_config::alias::InstantOf;
+use crate::RaftTypeConfig;
+
+/// Tracks consecutive failures of retryable storage commands.
+///
+/// A streak of failures starts with the first failure and ends with a success.
+/// A failure that happens more than `window` after the start of the streak starts a new streak.
+pub(crate) struct StorageFailures<C>
+where C: RaftTypeConfig
+{
+    /// The number of failures in the current streak.
+    count: u64,
+
+    /// When the current streak started.
+    first_failure: Option<InstantOf<C>>,
+
+    /// The failed command is not retried before this time.
+    retry_at: Option<InstantOf<C>>,
+
+    /// Whether the number of failures reached the threshold and the node runs in degraded mode.
+    degraded: bool,
+}
+
+impl<C> Default for StorageFailures<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            count: 0,
+            first_failure: None,
+            retry_at: None,
+            degraded: false,
+        }
+    }
+}
+
+impl<C> StorageFailures<C>
+where C: RaftTypeConfig
+{
+    /// Record a failure and schedule the next retry at `retry_at`.
+    ///
+    /// It returns the number of failures in the current streak.
+    pub(crate) fn record_failure(&mut self, now: InstantOf<C>, window: Duration, retry_at: InstantOf<C>) -> u64 {
+        match self.first_failure {
+            Some(first) if now - first <= window => {}
+            _ => {
+                self.first_failure = Some(now);
+                self.count = 0;
+            }
+        }
+
+        self.count += 1;
+        self.retry_at = Some(retry_at);
+        self.count
+    }
+
+    /// Record a success and end the current streak.
+    ///
+    /// It returns `true` if the node was degraded.
+    pub(crate) fn record_success(&mut self) -> bool {
+        let was_degraded = self.degraded;
+        *self = Self::default();
+        was_degraded
+    }
+
+    /// Whether a failed command is waiting to be retried later than `now`.
+    pub(crate) fn is_backing_off(&self, now: InstantOf<C>) -> bool {
+        self.retry_at.map_or(false, |at| now < at)
+    }
+
+    /// Whether a failed command is waiting to be retried.
+    pub(crate) fn is_retrying(&self) -> bool {
+        self.retry_at.is_some()
+    }
+
+    pub(crate) fn set_degraded(&mut self) {
+        self.degraded = true;
+    }
+
+    pub(crate) fn is_degraded(&self) -> bool {
+        self.degraded
+    }
+
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Build a copy of a command that is safe to run again if it fails, i.e., a storage write that
+/// does not consume its input.
+///
+/// Appending log entries can not be retried: the entries are moved into the log store.
+pub(crate) fn retryable_copy<C>(cmd: &Command<C>) -> Option<Command<C>>
+where C: RaftTypeConfig {
+    let c = match cmd {
+        Command::SaveVote { vote } => Command::SaveVote { vote: *vote },
+        Command::PurgeLog { upto } => Command::PurgeLog { upto: *upto },
+        Command::DeleteConflictLog { since } => Command::DeleteConflictLog { since: *since },
+        Command::Commit {
+            seq,
+            already_committed,
+            upto,
+        } => Command::Commit {
+            seq: *seq,
+            already_committed: *already_committed,
+            upto: *upto,
+        },
+        _ => return None,
+    };
+    Some(c)
+}
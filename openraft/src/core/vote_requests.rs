@@ -0,0 +1,83 @@
+use std::future::Future;
+
+use futures::future::abortable;
+use futures::future::AbortHandle;
+
+use crate::type_config::alias::AsyncRuntimeOf;
+use crate::AsyncRuntime;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// Vote requests of the current election round that are sent to peers concurrently.
+///
+/// Every request runs in its own task, so a slow peer does not delay the others. Once the
+/// candidate is granted by a quorum, the remaining outstanding requests are no longer needed and
+/// are cancelled with [`Self::cancel`].
+pub(crate) struct VoteRequests<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the current election round.
+    vote: Option<Vote<C::NodeId>>,
+
+    /// Handles to abort the requests that may still be in flight.
+    inflight: Vec<AbortHandle>,
+}
+
+impl<C> Default for VoteRequests<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            vote: None,
+            inflight: vec![],
+        }
+    }
+}
+
+impl<C> VoteRequests<C>
+where C: RaftTypeConfig
+{
+    /// Start a new election round with `vote`, cancelling requests of the previous round.
+    pub(crate) fn new_round(&mut self, vote: Vote<C::NodeId>) {
+        self.cancel();
+        self.vote = Some(vote);
+    }
+
+    /// Spawn a task to send a vote request of the current round.
+    pub(crate) fn spawn<F>(&mut self, fu: F)
+    where F: Future<Output = ()> + OptionalSend + 'static {
+        let (fu, handle) = abortable(fu);
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = AsyncRuntimeOf::<C>::spawn(async move {
+            let _ = fu.await;
+        });
+
+        self.inflight.push(handle);
+    }
+
+    /// Cancel all outstanding vote requests of the current round.
+    ///
+    /// Aborting a request that is already finished is a no-op.
+    pub(crate) fn cancel(&mut self) {
+        if self.inflight.is_empty() {
+            return;
+        }
+
+        tracing::debug!(vote = debug(&self.vote), "cancel outstanding vote requests");
+
+        for h in self.inflight.drain(..) {
+            h.abort();
+        }
+    }
+}
+
+impl<C> Drop for VoteRequests<C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use crate::raft::WriteDeduplicator;
+use crate::type_config::alias::ResponderOf;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// The last client write entry proposed by this leader, which a following identical write is
+/// deduplicated into.
+struct LastWrite<C>
+where C: RaftTypeConfig
+{
+    log_id: LogId<C::NodeId>,
+    hash: u64,
+
+    /// The response built for a duplicate, once the entry is applied.
+    response: Option<C::R>,
+}
+
+/// The state of deduplicating consecutive identical client writes on the leader.
+pub(crate) struct WriteDedup<C>
+where C: RaftTypeConfig
+{
+    deduplicator: Box<dyn WriteDeduplicator<C>>,
+
+    last: Option<LastWrite<C>>,
+
+    /// Responders of duplicate writes, waiting for the entry at the index to be applied.
+    waiting: BTreeMap<u64, Vec<ResponderOf<C>>>,
+}
+
+impl<C> WriteDedup<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(deduplicator: Box<dyn WriteDeduplicator<C>>) -> Self {
+        Self {
+            deduplicator,
+            last: None,
+            waiting: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the deduplicator and forget the last write, the hash of which may not be comparable.
+    pub(crate) fn set_deduplicator(&mut self, deduplicator: Box<dyn WriteDeduplicator<C>>) {
+        self.deduplicator = deduplicator;
+        self.last = None;
+    }
+
+    pub(crate) fn content_hash(&mut self, data: &C::D) -> u64 {
+        self.deduplicator.content_hash(data)
+    }
+
+    /// Return the log id of the last write if it is `last_log_id` and its content hash is `hash`.
+    pub(crate) fn duplicate_of(&self, hash: u64, last_log_id: Option<&LogId<C::NodeId>>) -> Option<LogId<C::NodeId>> {
+        let last = self.last.as_ref()?;
+
+        if last.hash == hash && Some(&last.log_id) == last_log_id {
+            Some(last.log_id)
+        } else {
+            None
+        }
+    }
+
+    /// Record a client write entry that is appended.
+    pub(crate) fn written(&mut self, log_id: LogId<C::NodeId>, hash: u64) {
+        self.last = Some(LastWrite {
+            log_id,
+            hash,
+            response: None,
+        });
+    }
+
+    /// Return a response for a duplicate of `log_id`, if the entry is already applied.
+    pub(crate) fn applied_response(&mut self, log_id: &LogId<C::NodeId>) -> Option<C::R> {
+        let last = self.last.as_ref().filter(|l| &l.log_id == log_id)?;
+        let original = last.response.as_ref()?;
+        Some(self.deduplicator.duplicate_response(original))
+    }
+
+    /// Wait for the entry at `index` to be applied to respond to a duplicate write.
+    pub(crate) fn wait(&mut self, index: u64, tx: ResponderOf<C>) {
+        self.waiting.entry(index).or_default().push(tx);
+    }
+
+    /// Called when the entry at `log_id` is applied with the response `original`.
+    ///
+    /// It returns the responders of the duplicates of this entry, each with its response.
+    pub(crate) fn on_applied(&mut self, log_id: &LogId<C::NodeId>, original: &C::R) -> Vec<(ResponderOf<C>, C::R)> {
+        if let Some(last) = self.last.as_mut() {
+            if &last.log_id == log_id {
+                last.response = Some(self.deduplicator.duplicate_response(original));
+            }
+        }
+
+        let waiting = self.waiting.remove(&log_id.index).unwrap_or_default();
+        waiting.into_iter().map(|tx| (tx, self.deduplicator.duplicate_response(original))).collect()
+    }
+
+    /// Remove the responders waiting for entries with index in `[since, end)`, which will not be
+    /// applied on this node.
+    pub(crate) fn remove_waiting(&mut self, since: u64, end: u64) -> Vec<(u64, ResponderOf<C>)> {
+        let mut rest = self.waiting.split_off(&since);
+        let mut after = rest.split_off(&end);
+        self.waiting.append(&mut after);
+
+        rest.into_iter().flat_map(|(index, txs)| txs.into_iter().map(move |tx| (index, tx))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::write_dedup::WriteDedup;
+    use crate::engine::testing::UTConfig;
+    use crate::raft::responder::Responder;
+    use crate::raft::WriteDeduplicator;
+    use crate::testing::log_id;
+    use crate::type_config::alias::ResponderOf;
+
+    struct ConstHash;
+
+    impl WriteDeduplicator<UTConfig> for ConstHash {
+        fn content_hash(&mut self, _data: &()) -> u64 {
+            1
+        }
+
+        fn duplicate_response(&mut self, _original: &()) {}
+    }
+
+    fn tx() -> ResponderOf<UTConfig> {
+        let (_, tx, _rx) = ResponderOf::<UTConfig>::from_app_data(());
+        tx
+    }
+
+    #[test]
+    fn test_write_dedup_duplicate_of_last_entry() {
+        let mut d = WriteDedup::<UTConfig>::new(Box::new(ConstHash));
+        assert_eq!(None, d.duplicate_of(1, Some(&log_id(1, 0, 3))), "no write yet");
+
+        d.written(log_id(1, 0, 3), 1);
+        assert_eq!(Some(log_id(1, 0, 3)), d.duplicate_of(1, Some(&log_id(1, 0, 3))));
+        assert_eq!(None, d.duplicate_of(2, Some(&log_id(1, 0, 3))), "different content");
+        assert_eq!(None, d.duplicate_of(1, Some(&log_id(1, 0, 4))), "not the last entry");
+
+        assert_eq!(None, d.applied_response(&log_id(1, 0, 3)), "not yet applied");
+        let _ = d.on_applied(&log_id(1, 0, 3), &());
+        assert_eq!(Some(()), d.applied_response(&log_id(1, 0, 3)));
+
+        d.set_deduplicator(Box::new(ConstHash));
+        assert_eq!(
+            None,
+            d.duplicate_of(1, Some(&log_id(1, 0, 3))),
+            "last write is forgotten"
+        );
+    }
+
+    #[test]
+    fn test_write_dedup_waiting() {
+        let mut d = WriteDedup::<UTConfig>::new(Box::new(ConstHash));
+
+        d.wait(3, tx());
+        d.wait(3, tx());
+        d.wait(5, tx());
+        d.wait(7, tx());
+
+        assert_eq!(2, d.on_applied(&log_id(1, 0, 3), &()).len());
+        assert_eq!(0, d.on_applied(&log_id(1, 0, 3), &()).len(), "already responded");
+
+        let removed = d.remove_waiting(4, 6).into_iter().map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(vec![5], removed);
+
+        assert_eq!(1, d.on_applied(&log_id(1, 0, 7), &()).len());
+    }
+}
@@ -0,0 +1,159 @@
+//! Compute a digest of log entries, to compare the logs of different nodes.
+
+use std::fmt;
+use std::fmt::Write;
+
+use anyerror::AnyError;
+
+use crate::storage::RaftLogReader;
+use crate::LogId;
+use crate::LogIdOptionExt;
+use crate::RaftLogId;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::StorageIOError;
+
+/// The number of entries to read from the log store at a time when computing a digest.
+const READ_BATCH: u64 = 64;
+
+/// A 64-bit FNV-1a hasher.
+///
+/// The digest only depends on the input bytes, it does not change across processes, platforms or
+/// Rust versions, unlike [`std::collections::hash_map::DefaultHasher`].
+pub(crate) struct Digest(u64);
+
+impl Default for Digest {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Digest {
+    /// The encoding of the entries fed with [`Self::update_entry`], which depends on the enabled
+    /// features.
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    const ENTRY_ENCODING: &'static str = "json";
+
+    #[cfg(not(all(feature = "serde", feature = "serde_json")))]
+    const ENTRY_ENCODING: &'static str = "display";
+
+    /// Create a digest to feed log entries into.
+    ///
+    /// The encoding of the entries is fed first, so that two nodes built with different features
+    /// never produce the same digest, even if their logs are identical.
+    pub(crate) fn for_entries() -> Self {
+        let mut d = Self::default();
+        d.update(Self::ENTRY_ENCODING.as_bytes());
+        d
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Feed an entry into the digest.
+    ///
+    /// With features `serde` and `serde_json` enabled, the JSON encoding of the entry is used,
+    /// which includes the application data. Otherwise only the [`Display`](fmt::Display) of the
+    /// entry is used, i.e., the log id and the kind of the payload.
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    pub(crate) fn update_entry<T>(&mut self, entry: &T) -> Result<(), serde_json::Error>
+    where T: serde::Serialize {
+        serde_json::to_writer(&mut *self, entry)
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "serde_json")))]
+    pub(crate) fn update_entry<T>(&mut self, entry: &T) -> Result<(), fmt::Error>
+    where T: fmt::Display {
+        write!(self, "{}", entry)
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Compute the digest of the committed entries in `[0, up_to]`, reading `log_reader` in batches.
+///
+/// If logs are purged, the digest is chained from `last_purged`, the last purged log id: the log
+/// id is fed in place of the purged entries, and the entries after it are fed.
+///
+/// It returns `None` if more logs are purged while reading, after `last_purged` is read.
+pub(crate) async fn digest_committed<C, LR>(
+    log_reader: &mut LR,
+    last_purged: Option<LogId<C::NodeId>>,
+    up_to: u64,
+) -> Result<Option<u64>, StorageError<C::NodeId>>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    let mut digest = Digest::for_entries();
+
+    if let Some(purged) = &last_purged {
+        write!(digest, "purged:{}", purged).map_err(|e| StorageIOError::read_logs(AnyError::new(&e)))?;
+    }
+
+    let mut start = last_purged.next_index();
+
+    while start <= up_to {
+        let end = std::cmp::min(start + READ_BATCH, up_to + 1);
+        let entries = log_reader.try_get_log_entries(start..end).await?;
+
+        for entry in entries.iter() {
+            // The entries before it are purged meanwhile.
+            if entry.get_log_id().index != start {
+                return Ok(None);
+            }
+
+            digest.update_entry(entry).map_err(|e| StorageIOError::read_logs(AnyError::new(&e)))?;
+            start += 1;
+        }
+
+        if start < end {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(digest.finish()))
+}
+
+impl std::io::Write for Digest {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for Digest {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Digest;
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, Digest::default().finish());
+
+        let mut d = Digest::default();
+        d.update(b"a");
+        assert_eq!(0xaf63_dc4c_8601_ec8c, d.finish());
+
+        let mut d = Digest::default();
+        d.update(b"foo");
+        d.update(b"bar");
+        assert_eq!(0x85944171f73967e8, d.finish());
+    }
+}
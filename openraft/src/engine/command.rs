@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use crate::async_runtime::AsyncOneshotSendExt;
@@ -88,6 +89,10 @@ where C: RaftTypeConfig
     /// inclusive.
     DeleteConflictLog { since: LogId<C::NodeId> },
 
+    /// Save the matching log index of every replication target, as hints for the next leadership
+    /// after restart.
+    SaveReplicationHints { hints: BTreeMap<C::NodeId, u64> },
+
     // TODO(1): current it is only used to replace BuildSnapshot, InstallSnapshot, CancelSnapshot.
     /// A command send to state machine worker [`sm::worker::Worker`].
     ///
@@ -131,6 +136,7 @@ where
             (Command::SendVote { vote_req },                   Command::SendVote { vote_req: b }, )                                            => vote_req == b,
             (Command::PurgeLog { upto },                       Command::PurgeLog { upto: b })                                                  => upto == b,
             (Command::DeleteConflictLog { since },             Command::DeleteConflictLog { since: b }, )                                      => since == b,
+            (Command::SaveReplicationHints { hints },          Command::SaveReplicationHints { hints: b })                                     => hints == b,
             (Command::Respond { when, resp: send },            Command::Respond { when: b_when, resp: b })                                     => send == b && when == b_when,
             (Command::StateMachine { command },                Command::StateMachine { command: b })                                           => command == b,
             _ => false,
@@ -155,6 +161,7 @@ where C: RaftTypeConfig
             Command::SaveVote { .. }                  => CommandKind::Log,
             Command::PurgeLog { .. }                  => CommandKind::Log,
             Command::DeleteConflictLog { .. }         => CommandKind::Log,
+            Command::SaveReplicationHints { .. }      => CommandKind::Log,
 
             Command::ReplicateCommitted { .. }        => CommandKind::Network,
             Command::Replicate { .. }                 => CommandKind::Network,
@@ -185,6 +192,7 @@ where C: RaftTypeConfig
             Command::SendVote { .. }                  => None,
             Command::PurgeLog { .. }                  => None,
             Command::DeleteConflictLog { .. }         => None,
+            Command::SaveReplicationHints { .. }      => None,
             Command::Respond { when, .. }             => when.as_ref(),
             // TODO(1): 
             Command::StateMachine { .. }              => None,
@@ -242,6 +250,22 @@ where C: RaftTypeConfig
         Respond::from(ValueSender::new(res, tx))
     }
 
+    /// Turn a granted vote response into a denied one.
+    ///
+    /// It returns `true` if it is a vote response that was granted.
+    pub(crate) fn deny_vote(&mut self) -> bool {
+        match self {
+            Respond::Vote(x) => match &mut x.value {
+                Ok(resp) if resp.vote_granted => {
+                    resp.vote_granted = false;
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
     pub(crate) fn send(self) {
         match self {
             Respond::Vote(x) => x.send(),
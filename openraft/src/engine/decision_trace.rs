@@ -0,0 +1,162 @@
+//! Record every decision of the [`Engine`](crate::engine::Engine) for replay debugging.
+
+use std::fmt;
+use std::io;
+use std::time::Instant;
+
+use crate::core::ServerState;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::LogId;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// A digest of the state of a Raft node, before or after handling a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct StateDigest<C>
+where C: RaftTypeConfig
+{
+    pub server_state: ServerState,
+    pub vote: Vote<C::NodeId>,
+    pub last_log_id: Option<LogId<C::NodeId>>,
+    pub committed: Option<LogId<C::NodeId>>,
+
+    /// The log id of the effective membership config.
+    pub membership_log_id: Option<LogId<C::NodeId>>,
+}
+
+/// An incoming message recorded in a form that can be fed to a Raft node again.
+#[derive(Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub enum TraceInput<C>
+where C: RaftTypeConfig
+{
+    VoteRequest(VoteRequest<C>),
+
+    /// A response to the vote request this node sent to `target`.
+    VoteResponse {
+        target: C::NodeId,
+        resp: VoteResponse<C>,
+    },
+
+    AppendEntries {
+        vote: Vote<C::NodeId>,
+        prev_log_id: Option<LogId<C::NodeId>>,
+
+        /// The entries in JSON, because an entry is not required to be `Clone`.
+        entries: serde_json::Value,
+
+        leader_commit: Option<LogId<C::NodeId>>,
+    },
+}
+
+/// A record of handling one incoming message: the state before, the decision and the state after.
+///
+/// Records are written by a Raft node with [`Raft::set_decision_trace()`] as lines of JSON, in the
+/// order the messages are handled. A trace can be replayed against a fresh node with
+/// [`replay_decision_trace()`].
+///
+/// [`Raft::set_decision_trace()`]: crate::Raft::set_decision_trace
+/// [`replay_decision_trace()`]: crate::testing::replay_decision_trace
+#[derive(Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct DecisionRecord<C>
+where C: RaftTypeConfig
+{
+    /// The sequence number of this record, starting from 0.
+    pub seq: u64,
+
+    /// The time in milliseconds since the recording started, when the message is handled.
+    #[serde(default)]
+    pub elapsed_ms: u64,
+
+    /// The kind of the message, such as `vote-request`.
+    pub kind: String,
+
+    /// The message in human-readable form.
+    pub message: String,
+
+    /// The message to feed when replaying, or `None` if it can not be replayed, e.g., a snapshot,
+    /// whose data is not recorded.
+    #[serde(default)]
+    pub input: Option<TraceInput<C>>,
+
+    pub before: StateDigest<C>,
+
+    /// The decision made for the message in human-readable form, such as the response.
+    pub decision: String,
+
+    pub after: StateDigest<C>,
+}
+
+/// The message, its replayable input and the state before handling it.
+pub(crate) type TraceBegin<C> = (String, Option<TraceInput<C>>, StateDigest<C>);
+
+/// A writer that decision records are written to.
+pub(crate) trait TraceWriter: io::Write + OptionalSend {}
+
+impl<T> TraceWriter for T where T: io::Write + OptionalSend {}
+
+/// Writes a [`DecisionRecord`] for every message handled by the engine.
+pub(crate) struct DecisionTrace {
+    seq: u64,
+    started_at: Instant,
+    writer: Box<dyn TraceWriter>,
+}
+
+impl fmt::Debug for DecisionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecisionTrace").field("seq", &self.seq).finish()
+    }
+}
+
+impl DecisionTrace {
+    pub(crate) fn new(writer: Box<dyn TraceWriter>) -> Self {
+        Self {
+            seq: 0,
+            started_at: Instant::now(),
+            writer,
+        }
+    }
+
+    /// Serialize a record as a line of JSON and flush it.
+    ///
+    /// A failure to write is logged and does not affect the Raft node.
+    pub(crate) fn record<C>(
+        &mut self,
+        kind: &str,
+        message: String,
+        input: Option<TraceInput<C>>,
+        before: StateDigest<C>,
+        decision: String,
+        after: StateDigest<C>,
+    ) where
+        C: RaftTypeConfig,
+    {
+        let record = DecisionRecord {
+            seq: self.seq,
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+            kind: kind.to_string(),
+            message,
+            input,
+            before,
+            decision,
+            after,
+        };
+        self.seq += 1;
+
+        let res = serde_json::to_writer(&mut self.writer, &record)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"))
+            .and_then(|_| self.writer.flush());
+
+        if let Err(e) = res {
+            tracing::warn!(error = display(&e), seq = record.seq, "failed to write decision record");
+        }
+    }
+}
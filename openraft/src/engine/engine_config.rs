@@ -1,5 +1,11 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
 use std::time::Duration;
 
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
 use crate::engine::time_state;
 use crate::type_config::alias::AsyncRuntimeOf;
 use crate::Config;
@@ -22,28 +28,107 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The minimal number of applied logs to purge in a batch.
     pub(crate) purge_batch_size: u64,
 
+    /// The maximum number of leaders to remember after their logs are purged.
+    pub(crate) term_history_size: u64,
+
+    /// The number of the most recent vote decisions to keep.
+    pub(crate) vote_log_size: u64,
+
     /// The maximum number of entries per payload allowed to be transmitted during replication
     pub(crate) max_payload_entries: u64,
 
+    /// The percentage of `max_payload_entries` a learner receives when voters are backed up.
+    pub(crate) learner_replication_budget: u64,
+
+    /// The lag behind the committed log beyond which a follower is replicated with a snapshot,
+    /// `None` means only when the logs it needs are purged.
+    pub(crate) snapshot_lag_threshold: Option<u64>,
+
+    /// The number of conflicting AppendEntries in a row beyond which replication to a follower is
+    /// considered stalled, `None` means never.
+    pub(crate) append_rejection_threshold: Option<u64>,
+
+    /// The maximum term increase accepted from a single message, `None` means unlimited.
+    pub(crate) max_term_jump: Option<u64>,
+
+    /// Whether to reject a message whose term increase exceeds `max_term_jump`.
+    pub(crate) reject_term_jump: bool,
+
+    /// Whether a candidate keeps its term in the next election if no peer responded.
+    pub(crate) keep_term_when_isolated: bool,
+
     pub(crate) timer_config: time_state::Config,
+
+    /// The range in milliseconds to pick a random election timeout from.
+    pub(crate) election_timeout_range: Range<u64>,
+
+    /// Generates the randomized timing, seeded with `Config::rand_seed` if it is set.
+    pub(crate) rng: StdRng,
+
+    /// The surviving nodes a log is committed by, in place of a quorum of voters.
+    ///
+    /// It is set only for disaster recovery, see `Config::allow_unsafe_commit_quorum`.
+    pub(crate) unsafe_commit_quorum: Option<BTreeSet<C::NodeId>>,
+
+    /// The replication targets to which a leader sends heartbeats only, but no logs or snapshot.
+    pub(crate) paused_replication: BTreeSet<C::NodeId>,
+
+    /// Whether to start replication at the matching log index saved by the leader before restart.
+    pub(crate) persist_replication_hints: bool,
+
+    /// The maximum gap between the persisted term and the last log term checked at startup.
+    pub(crate) max_term_log_gap: Option<u64>,
+
+    /// The number of heartbeats acknowledged by a quorum a new leader waits for before serving
+    /// reads, `0` means it does not wait.
+    pub(crate) read_min_heartbeats: u64,
+
+    /// Whether to respond to a successful AppendEntries with the log state of this node.
+    pub(crate) report_log_state: bool,
+
+    /// Whether this node found its term too far ahead of its last log term at startup, and
+    /// refuses to elect itself until it is cleared.
+    pub(crate) term_gap_degraded: bool,
 }
 
 impl<C> EngineConfig<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(id: C::NodeId, config: &Config) -> Self {
-        let election_timeout = Duration::from_millis(config.new_rand_election_timeout::<AsyncRuntimeOf<C>>());
+        let election_timeout_range = config.election_timeout_min..config.election_timeout_max;
+        let mut rng = config.new_timing_rng::<AsyncRuntimeOf<C>>();
+        let election_timeout = Duration::from_millis(rng.gen_range(election_timeout_range.clone()));
+
         Self {
             id,
             snapshot_policy: config.snapshot_policy.clone(),
             max_in_snapshot_log_to_keep: config.max_in_snapshot_log_to_keep,
             purge_batch_size: config.purge_batch_size,
+            term_history_size: config.term_history_size,
+            vote_log_size: config.vote_log_size,
             max_payload_entries: config.max_payload_entries,
+            learner_replication_budget: config.learner_replication_budget,
+            snapshot_lag_threshold: config.snapshot_lag_threshold,
+            append_rejection_threshold: config.append_rejection_threshold,
+            max_term_jump: config.max_term_jump,
+            reject_term_jump: config.reject_term_jump,
+            keep_term_when_isolated: config.keep_term_when_isolated,
             timer_config: time_state::Config {
                 election_timeout,
                 smaller_log_timeout: Duration::from_millis(config.election_timeout_max * 2),
                 leader_lease: Duration::from_millis(config.election_timeout_max),
+                leader_stickiness: Duration::from_millis(config.heartbeat_interval * config.leader_stickiness),
+                election_grace_period: Duration::from_millis(config.election_grace_period),
             },
+            election_timeout_range,
+            rng,
+            unsafe_commit_quorum: None,
+            paused_replication: BTreeSet::new(),
+            persist_replication_hints: config.persist_replication_hints,
+            max_term_log_gap: config.max_term_log_gap,
+            read_min_heartbeats: config.read_min_heartbeats,
+            report_log_state: config.report_log_state,
+            term_gap_degraded: false,
         }
     }
 
@@ -54,8 +139,31 @@ where C: RaftTypeConfig
             snapshot_policy: SnapshotPolicy::LogsSinceLast(5000),
             max_in_snapshot_log_to_keep: 1000,
             purge_batch_size: 256,
+            term_history_size: 0,
+            vote_log_size: 0,
             max_payload_entries: 300,
+            learner_replication_budget: 100,
+            snapshot_lag_threshold: None,
+            append_rejection_threshold: None,
+            max_term_jump: None,
+            reject_term_jump: false,
+            keep_term_when_isolated: false,
             timer_config: time_state::Config::default(),
+            election_timeout_range: 150..300,
+            rng: StdRng::seed_from_u64(0),
+            unsafe_commit_quorum: None,
+            paused_replication: BTreeSet::new(),
+            persist_replication_hints: false,
+            max_term_log_gap: None,
+            read_min_heartbeats: 0,
+            report_log_state: false,
+            term_gap_degraded: false,
         }
     }
+
+    /// Pick a new random election timeout.
+    pub(crate) fn renew_election_timeout(&mut self) {
+        let timeout = self.rng.gen_range(self.election_timeout_range.clone());
+        self.timer_config.election_timeout = Duration::from_millis(timeout);
+    }
 }
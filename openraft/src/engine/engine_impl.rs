@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::time::Duration;
+use std::time::SystemTime;
 
 use validit::Valid;
 
@@ -8,6 +11,14 @@ use crate::core::sm;
 use crate::core::ServerState;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::DecisionTrace;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::StateDigest;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::TraceBegin;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::TraceInput;
 use crate::engine::engine_config::EngineConfig;
 use crate::engine::handler::following_handler::FollowingHandler;
 use crate::engine::handler::leader_handler::LeaderHandler;
@@ -25,13 +36,19 @@ use crate::entry::RaftPayload;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::InstallSnapshotError;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
 use crate::error::RejectAppendEntries;
 use crate::internal_server_state::InternalServerState;
 use crate::membership::EffectiveMembership;
+use crate::metrics::VoteDenyReason;
+use crate::metrics::VoteLog;
+use crate::metrics::VoteRecord;
+use crate::progress::Progress;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ElectionTiebreaker;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
@@ -80,18 +97,73 @@ where C: RaftTypeConfig
 
     /// Output entry for the runtime.
     pub(crate) output: EngineOutput<C>,
+
+    /// The most recent decisions on vote requests, for diagnostic purpose.
+    pub(crate) vote_log: VoteLog<C>,
+
+    /// Prefers one of the candidates with equally up-to-date logs, if it is set.
+    pub(crate) election_tiebreaker: Option<Box<dyn ElectionTiebreaker<C>>>,
+
+    /// Records every decision for replay debugging, if it is set.
+    #[cfg(feature = "decision-trace")]
+    pub(crate) decision_trace: Option<DecisionTrace>,
 }
 
 impl<C> Engine<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(init_state: RaftState<C>, config: EngineConfig<C>) -> Self {
+        let vote_log = VoteLog::new(config.vote_log_size as usize);
         Self {
             config,
             state: Valid::new(init_state),
             seen_greater_log: false,
             internal_server_state: InternalServerState::default(),
             output: EngineOutput::new(4096),
+            vote_log,
+            election_tiebreaker: None,
+            #[cfg(feature = "decision-trace")]
+            decision_trace: None,
+        }
+    }
+
+    /// A digest of the current state, for the decision trace.
+    #[cfg(feature = "decision-trace")]
+    pub(crate) fn state_digest(&self) -> StateDigest<C> {
+        StateDigest {
+            server_state: self.state.server_state,
+            vote: *self.state.vote_ref(),
+            last_log_id: self.state.last_log_id().copied(),
+            committed: self.state.committed().copied(),
+            membership_log_id: *self.state.membership_state.effective().log_id(),
+        }
+    }
+
+    /// Take the message, its replayable input and the state before handling it, if the decision
+    /// trace is enabled.
+    #[cfg(feature = "decision-trace")]
+    fn trace_begin(
+        &self,
+        message: impl FnOnce() -> String,
+        input: impl FnOnce() -> Option<TraceInput<C>>,
+    ) -> Option<TraceBegin<C>> {
+        self.decision_trace.as_ref()?;
+        Some((message(), input(), self.state_digest()))
+    }
+
+    /// Record the decision and the state after handling a message, if the decision trace is
+    /// enabled.
+    #[cfg(feature = "decision-trace")]
+    fn trace_end(&mut self, kind: &str, begin: Option<TraceBegin<C>>, decision: impl FnOnce(&Self) -> String) {
+        let Some((message, input, before)) = begin else {
+            return;
+        };
+
+        let decision = decision(self);
+        let after = self.state_digest();
+
+        if let Some(trace) = &mut self.decision_trace {
+            trace.record(kind, message, input, before, decision, after);
         }
     }
 
@@ -105,6 +177,8 @@ where C: RaftTypeConfig
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn startup(&mut self) {
+        self.check_term_log_gap();
+
         // Allows starting up as a leader.
 
         tracing::info!(
@@ -144,6 +218,36 @@ where C: RaftTypeConfig
         );
     }
 
+    /// Enter the degraded state if the term is ahead of the last log term by more than
+    /// `max_term_log_gap`, which suggests the persisted vote is corrupted.
+    fn check_term_log_gap(&mut self) {
+        let Some(max_gap) = self.config.max_term_log_gap else {
+            return;
+        };
+
+        let term = self.state.vote_ref().leader_id().get_term();
+        let last_log_term = self.state.last_log_id().map(|x| x.leader_id.term).unwrap_or_default();
+        let gap = term.saturating_sub(last_log_term);
+
+        if gap > max_gap {
+            tracing::error!(
+                term = display(term),
+                last_log_term = display(last_log_term),
+                max_term_log_gap = display(max_gap),
+                "term is too far ahead of the last log term, the vote may be corrupted; \
+                refuse to elect until it is cleared by an operator"
+            );
+            self.config.term_gap_degraded = true;
+        }
+    }
+
+    /// Leave the degraded state entered because the term is too far ahead of the last log term.
+    pub(crate) fn clear_term_gap_degraded(&mut self) {
+        tracing::info!(degraded = display(self.config.term_gap_degraded), "{}", func_name!());
+
+        self.config.term_gap_degraded = false;
+    }
+
     /// Initialize a node by appending the first log.
     ///
     /// - The first log has to be membership config log.
@@ -160,12 +264,18 @@ where C: RaftTypeConfig
     pub(crate) fn initialize(&mut self, mut entry: C::Entry) -> Result<(), InitializeError<C>> {
         self.check_initialize()?;
 
+        // Validate the membership before making any change to the state.
+        {
+            let m = entry.get_membership().expect("the only log entry for initializing has to be membership log");
+            m.ensure_non_empty_config()?;
+            self.check_members_contain_me(m)?;
+        }
+
         self.state.assign_log_ids([&mut entry]);
         let log_id = *entry.get_log_id();
         self.state.extend_log_ids_from_same_leader(&[log_id]);
 
-        let m = entry.get_membership().expect("the only log entry for initializing has to be membership log");
-        self.check_members_contain_me(m)?;
+        let m = entry.get_membership().unwrap();
 
         tracing::debug!("update effective membership: log_id:{} {}", log_id, m);
 
@@ -182,9 +292,69 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Initialize a pristine node with a snapshot, and start to elect it as the leader.
+    ///
+    /// The membership in the snapshot becomes the initial membership, and it has to contain this
+    /// node as a voter. Logs before the snapshot do not exist on this node, thus other nodes
+    /// added to the cluster later receive the snapshot instead of logs.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn initialize_with_snapshot(&mut self, snapshot: Snapshot<C>) -> Result<(), InitializeError<C>> {
+        tracing::info!(snapshot = display(&snapshot), "{}", func_name!());
+
+        self.check_initialize()?;
+
+        {
+            let m = snapshot.meta.last_membership.membership();
+            m.ensure_non_empty_config()?;
+            self.check_members_contain_me(m)?;
+        }
+
+        // The snapshot may be built by another cluster with a greater term.
+        // The vote has to catch up with it, or this node would propose logs with a smaller term than
+        // the last log in the snapshot.
+        if let Some(last) = snapshot.meta.last_log_id {
+            let v = Vote::new(last.leader_id.term, self.config.id);
+            if &v > self.state.vote_ref() {
+                self.state.vote.update(InstantOf::<C>::now(), v);
+                self.output.push_command(Command::SaveVote { vote: v });
+            }
+        }
+
+        self.following_handler().install_full_snapshot(snapshot);
+
+        self.server_state_handler().update_server_state_if_changed();
+
+        self.elect();
+
+        Ok(())
+    }
+
+    /// Start to elect this node as leader, because the election timeout passed.
+    ///
+    /// Unlike an election requested explicitly with [`Self::elect()`], an isolated candidate does
+    /// not increase its term if `keep_term_when_isolated` is enabled.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn elect_on_timeout(&mut self) {
+        if self.config.keep_term_when_isolated && !self.config.term_gap_degraded && self.resend_vote_if_isolated() {
+            return;
+        }
+
+        self.elect();
+    }
+
     /// Start to elect this node as leader
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn elect(&mut self) {
+        if self.is_witness() {
+            tracing::info!("this node is a witness, it never elects itself");
+            return;
+        }
+
+        if self.config.term_gap_degraded {
+            tracing::warn!("term is too far ahead of the last log term, refuse to elect until it is cleared");
+            return;
+        }
+
         let v = Vote::new(self.state.vote_ref().leader_id().term + 1, self.config.id);
         tracing::info!(vote = display(&v), "{}", func_name!());
 
@@ -217,6 +387,60 @@ where C: RaftTypeConfig
         self.server_state_handler().update_server_state_if_changed();
     }
 
+    /// Re-send the vote request of the current term if no peer responded to it.
+    ///
+    /// An isolated candidate does not increase its term in this case, so that its term stays
+    /// stable until it is able to contact other nodes again.
+    ///
+    /// It returns `true` if the vote request is re-sent.
+    fn resend_vote_if_isolated(&mut self) -> bool {
+        let Some(voting) = self.internal_server_state.voting_mut() else {
+            return false;
+        };
+
+        if voting.vote_ref() != self.state.vote_ref() || voting.is_responded() {
+            return false;
+        }
+
+        tracing::info!(
+            vote = display(self.state.vote_ref()),
+            "no peer responded to the last vote request, re-send it without increasing term"
+        );
+
+        self.vote_handler().update_election_timeout();
+
+        self.output.push_command(Command::SendVote {
+            vote_req: VoteRequest::new(*self.state.vote_ref(), self.state.last_log_id().copied()),
+        });
+
+        true
+    }
+
+    /// Leader gives up its leadership without choosing a successor.
+    ///
+    /// A vote in Openraft always belongs to some node, thus the leader can not un-grant its
+    /// leadership in the current term. Instead, it moves to the next term by voting for itself
+    /// without requesting votes from others. Then a candidate of the next term from another voter
+    /// can be granted.
+    ///
+    /// The election on this node is postponed in the same way as when a greater log is seen, so
+    /// that the other voters have the chance to elect first.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn step_down(&mut self) {
+        if !self.state.is_leader(&self.config.id) {
+            tracing::info!("not a leader, ignore step down; vote: {}", self.state.vote_ref());
+            return;
+        }
+
+        let v = Vote::new(self.state.vote_ref().leader_id().term + 1, self.config.id);
+        tracing::info!(vote = display(&v), "{}", func_name!());
+
+        // Safe unwrap(): it won't reject itself
+        self.vote_handler().update_vote(&v).unwrap();
+
+        self.set_greater_log();
+    }
+
     /// Get a LeaderHandler for handling leader's operation. If it is not a leader, it send back a
     /// ForwardToLeader error through the tx.
     ///
@@ -244,8 +468,39 @@ where C: RaftTypeConfig
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_vote_req(&mut self, req: VoteRequest<C>) -> VoteResponse<C> {
+        let term = req.vote.leader_id().term;
+        let candidate = req.vote.leader_id().voted_for();
+        let candidate_last_log_id = req.last_log_id;
+
+        #[cfg(feature = "decision-trace")]
+        let trace = self.trace_begin(|| req.to_string(), || Some(TraceInput::VoteRequest(req.clone())));
+
+        let (resp, denied) = self.decide_vote_req(req);
+
+        #[cfg(feature = "decision-trace")]
+        self.trace_end("vote-request", trace, |_| match &denied {
+            None => format!("grant: {}", resp),
+            Some(reason) => format!("deny: {}: {}", reason, resp),
+        });
+
+        if let Some(candidate) = candidate {
+            self.vote_log.push(VoteRecord {
+                time: SystemTime::now(),
+                term,
+                candidate,
+                candidate_last_log_id,
+                denied,
+            });
+        }
+
+        resp
+    }
+
+    /// Decide whether to grant a vote request, and return the reason if it is denied.
+    fn decide_vote_req(&mut self, req: VoteRequest<C>) -> (VoteResponse<C>, Option<VoteDenyReason<C>>) {
         let now = InstantOf::<C>::now();
-        let lease = self.config.timer_config.leader_lease;
+        let timer_config = &self.config.timer_config;
+        let lease = timer_config.leader_lease + timer_config.leader_stickiness;
         let vote = self.state.vote_ref();
 
         // Make default vote-last-modified a low enough value, that expires leader lease.
@@ -277,11 +532,12 @@ where C: RaftTypeConfig
                     vote_utime + lease - now
                 );
 
-                return VoteResponse {
+                let resp = VoteResponse {
                     vote: *self.state.vote_ref(),
                     vote_granted: false,
                     last_log_id: self.state.last_log_id().copied(),
                 };
+                return (resp, Some(VoteDenyReason::LeaderLease));
             }
         }
 
@@ -297,13 +553,33 @@ where C: RaftTypeConfig
             );
             // The res is not used yet.
             // let _res = Err(RejectVoteRequest::ByLastLogId(self.state.last_log_id().copied()));
-            return VoteResponse {
+            let resp = VoteResponse {
                 // Return the updated vote, this way the candidate knows which vote is granted, in case
                 // the candidate's vote is changed after sending the vote request.
                 vote: *self.state.vote_ref(),
                 vote_granted: false,
                 last_log_id: self.state.last_log_id().copied(),
             };
+            let reason = VoteDenyReason::StaleLog {
+                last_log_id: self.state.last_log_id().copied(),
+            };
+            return (resp, Some(reason));
+        }
+
+        // Logs are equally up to date: this node denies the candidate if the tiebreaker prefers
+        // this node itself. Denying a vote is always safe.
+        if let Some(preferred) = self.tiebreak_vote_req(&req) {
+            tracing::info!(
+                req = display(&req),
+                preferred = display(&preferred),
+                "reject vote-request: this node is preferred by the election tiebreaker"
+            );
+            let resp = VoteResponse {
+                vote: *self.state.vote_ref(),
+                vote_granted: false,
+                last_log_id: self.state.last_log_id().copied(),
+            };
+            return (resp, Some(VoteDenyReason::Tiebreak { preferred }));
         }
 
         // Then check vote just as it does for every incoming event.
@@ -314,17 +590,87 @@ where C: RaftTypeConfig
 
         let vote_granted = res.is_ok();
 
-        VoteResponse {
+        if vote_granted {
+            self.vote_handler().update_election_timeout();
+        }
+
+        // `update_vote()` rejects a vote with a greater term only if the term jump is too large.
+        let denied = res.err().map(|_| {
+            let vote = *self.state.vote_ref();
+            let local_term = vote.leader_id().term;
+            let term = req.vote.leader_id().term;
+
+            match term.cmp(&local_term) {
+                Ordering::Less => VoteDenyReason::LowerTerm { vote },
+                Ordering::Equal => VoteDenyReason::AlreadyVoted { vote },
+                Ordering::Greater => VoteDenyReason::TermJump { vote },
+            }
+        });
+
+        let resp = VoteResponse {
             // Return the updated vote, this way the candidate knows which vote is granted, in case
             // the candidate's vote is changed after sending the vote request.
             vote: *self.state.vote_ref(),
             vote_granted,
             last_log_id: self.state.last_log_id().copied(),
+        };
+        (resp, denied)
+    }
+
+    /// Consult the election tiebreaker for a vote request whose last log id equals this node's.
+    ///
+    /// It returns the id of this node if the tiebreaker prefers it over the candidate. It is not
+    /// consulted if this node never elects itself, i.e., it is a witness or not a voter.
+    fn tiebreak_vote_req(&mut self, req: &VoteRequest<C>) -> Option<C::NodeId> {
+        let id = self.config.id;
+
+        if !self.state.membership_state.effective().is_voter(&id) || self.is_witness() {
+            return None;
+        }
+
+        let tiebreaker = self.election_tiebreaker.as_mut()?;
+
+        let candidate = req.vote.leader_id().voted_for()?;
+
+        if candidate == id || req.last_log_id.as_ref() != self.state.last_log_id() {
+            return None;
+        }
+
+        // A request that would not change the vote is left to the normal vote check.
+        if req.vote.partial_cmp(self.state.vote_ref()) != Some(Ordering::Greater) {
+            return None;
+        }
+
+        if tiebreaker.prefer(&id, &candidate) {
+            Some(id)
+        } else {
+            None
         }
     }
 
     #[tracing::instrument(level = "debug", skip(self, resp))]
     pub(crate) fn handle_vote_resp(&mut self, target: C::NodeId, resp: VoteResponse<C>) {
+        #[cfg(feature = "decision-trace")]
+        let trace = self.trace_begin(
+            || format!("from {}: {}", target, resp),
+            || {
+                Some(TraceInput::VoteResponse {
+                    target,
+                    resp: resp.clone(),
+                })
+            },
+        );
+
+        self.count_vote_resp(target, resp);
+
+        #[cfg(feature = "decision-trace")]
+        self.trace_end("vote-response", trace, |eng| {
+            format!("server_state: {:?}", eng.state.server_state)
+        });
+    }
+
+    /// Count a vote response, and establish the leadership if a quorum granted.
+    fn count_vote_resp(&mut self, target: C::NodeId, resp: VoteResponse<C>) {
         tracing::info!(
             resp = display(&resp),
             target = display(target),
@@ -343,16 +689,34 @@ where C: RaftTypeConfig
             return;
         };
 
+        voting.set_responded();
+
         if &resp.vote < self.state.vote_ref() {
             debug_assert!(!resp.vote_granted);
         }
 
+        // The effective membership is not expected to change during voting. But if it does, count
+        // the votes against the latest one.
+        let quorum_set = self.state.membership_state.effective().membership().to_quorum_set();
+        let quorum_set_changed = voting.update_quorum_set(quorum_set);
+
+        let quorum_granted = if resp.vote_granted {
+            voting.grant_by(&target)
+        } else {
+            voting.is_granted()
+        };
+
+        if quorum_set_changed {
+            self.replication_handler().rebuild_progresses();
+        }
+
+        if quorum_granted {
+            tracing::info!("a quorum granted my vote");
+            self.establish_leader();
+            return;
+        }
+
         if resp.vote_granted {
-            let quorum_granted = voting.grant_by(&target);
-            if quorum_granted {
-                tracing::info!("a quorum granted my vote");
-                self.establish_leader();
-            }
             return;
         }
 
@@ -377,15 +741,19 @@ where C: RaftTypeConfig
         }
     }
 
-    /// Append entries to follower/learner.
+    /// Append entries to follower/learner and commit up to `leader_committed`.
     ///
     /// Also clean conflicting entries and update membership state.
+    ///
+    /// If `report_log_state` is enabled, a successful response carries the committed and last log
+    /// id of this node, after the entries are appended and committed.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_append_entries(
         &mut self,
         vote: &Vote<C::NodeId>,
         prev_log_id: Option<LogId<C::NodeId>>,
         entries: Vec<C::Entry>,
+        leader_committed: Option<LogId<C::NodeId>>,
         tx: Option<AppendEntriesTx<C>>,
     ) -> bool {
         tracing::debug!(
@@ -398,11 +766,50 @@ where C: RaftTypeConfig
             func_name!()
         );
 
+        #[cfg(feature = "decision-trace")]
+        let trace = self.trace_begin(
+            || {
+                format!(
+                    "vote: {}, prev_log_id: {}, entries: {}, leader_committed: {}",
+                    vote,
+                    prev_log_id.display(),
+                    DisplaySlice::<_>(&entries),
+                    leader_committed.display()
+                )
+            },
+            || {
+                let entries = serde_json::to_value(&entries).ok()?;
+                Some(TraceInput::AppendEntries {
+                    vote: *vote,
+                    prev_log_id,
+                    entries,
+                    leader_commit: leader_committed,
+                })
+            },
+        );
+
         let res = self.append_entries(vote, prev_log_id, entries);
         let is_ok = res.is_ok();
 
+        #[cfg(feature = "decision-trace")]
+        self.trace_end("append-entries", trace, |_| match &res {
+            Ok(()) => "accept".to_string(),
+            Err(e) => format!("reject: {}", e),
+        });
+
+        if is_ok {
+            self.handle_commit_entries(leader_committed);
+        }
+
         if let Some(tx) = tx {
-            let resp: AppendEntriesResponse<C> = res.into();
+            let resp = match res {
+                Ok(()) if self.config.report_log_state => AppendEntriesResponse::SuccessWithLogState {
+                    committed: self.state.committed().copied(),
+                    last_log_id: self.state.last_log_id().copied(),
+                },
+                Ok(()) => AppendEntriesResponse::Success,
+                Err(e) => Err::<(), _>(e).into(),
+            };
             self.output.push_command(Command::Respond {
                 when: None,
                 resp: Respond::new(Ok(resp), tx),
@@ -419,7 +826,9 @@ where C: RaftTypeConfig
     ) -> Result<(), RejectAppendEntries<C>> {
         self.vote_handler().update_vote(vote)?;
 
-        // Vote is legal.
+        // Vote is legal: it is from the current leader.
+        self.vote_handler().update_election_timeout();
+        self.state.last_leader_contact = Some(InstantOf::<C>::now());
 
         let mut fh = self.following_handler();
         fh.ensure_log_consecutive(prev_log_id)?;
@@ -453,6 +862,23 @@ where C: RaftTypeConfig
     ) {
         tracing::info!(vote = display(vote), snapshot = display(&snapshot), "{}", func_name!());
 
+        #[cfg(feature = "decision-trace")]
+        let trace = self.trace_begin(|| format!("vote: {}, snapshot: {}", vote, snapshot.meta), || None);
+
+        self.install_full_snapshot(vote, snapshot, tx);
+
+        #[cfg(feature = "decision-trace")]
+        self.trace_end("install-snapshot", trace, |eng| {
+            format!("committed: {}", eng.state.committed().display())
+        });
+    }
+
+    fn install_full_snapshot(
+        &mut self,
+        vote: Vote<C::NodeId>,
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, SnapshotResponse<C>>,
+    ) {
         let vote_res = self.vote_handler().accept_vote(&vote, tx, |state, _rejected| {
             Ok(SnapshotResponse::new(*state.vote_ref()))
         });
@@ -477,6 +903,17 @@ where C: RaftTypeConfig
         });
     }
 
+    /// Migrate a received snapshot to the format version of the local state machine.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_migrate_snapshot(
+        &mut self,
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, Snapshot<C>, InstallSnapshotError>,
+    ) {
+        tracing::info!(snapshot = display(&snapshot), "{}", func_name!());
+        self.output.push_command(Command::from(sm::Command::migrate_snapshot(snapshot, tx)));
+    }
+
     /// Install a completely received snapshot on a follower.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_begin_receiving_snapshot(&mut self, tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>) {
@@ -560,6 +997,108 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Commit logs accepted by every node in `surviving`, in place of a quorum of voters, or
+    /// restore the normal commit quorum if it is `None`.
+    ///
+    /// It is only for disaster recovery and breaks the safety of raft.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn set_unsafe_commit_quorum(&mut self, surviving: Option<BTreeSet<C::NodeId>>) {
+        if let Some(s) = &surviving {
+            tracing::warn!(
+                surviving = debug(s),
+                "DANGEROUS: lower commit quorum to the surviving nodes, split-brain may happen"
+            );
+        } else {
+            tracing::info!("restore commit quorum");
+        }
+
+        self.config.unsafe_commit_quorum = surviving;
+
+        if let Ok(mut lh) = self.leader_handler() {
+            let granted = lh.leader.quorum_match_index();
+            lh.replication_handler().try_commit_quorum_accepted(granted);
+        }
+    }
+
+    /// Return true if this node is a witness in either the effective or the committed membership.
+    ///
+    /// A witness stores log metadata only, thus it must never become leader, even if a membership
+    /// that makes it a witness is not yet committed.
+    pub(crate) fn is_witness(&self) -> bool {
+        self.state.membership_state.is_witness(&self.config.id)
+    }
+
+    /// Stop sending logs or snapshot to `target`, it still receives heartbeats.
+    ///
+    /// Data already in flight to `target` is not canceled.
+    pub(crate) fn pause_replication(&mut self, target: C::NodeId) {
+        tracing::info!(target = display(target), "{}", func_name!());
+
+        self.config.paused_replication.insert(target);
+    }
+
+    /// Resume sending logs or snapshot to a paused `target`.
+    ///
+    /// If this node is a leader, it starts at once to catch up `target`.
+    pub(crate) fn resume_replication(&mut self, target: C::NodeId) {
+        tracing::info!(target = display(target), "{}", func_name!());
+
+        if !self.config.paused_replication.remove(&target) {
+            return;
+        }
+
+        if let Ok(mut lh) = self.leader_handler() {
+            lh.replication_handler().initiate_replication(SendNone::False);
+        }
+    }
+
+    /// Send the snapshot to `target` at once, instead of logs, if this node is a leader.
+    ///
+    /// It is a no-op if `target` is not a member, it is up to date, or the snapshot is not newer
+    /// than the log it already has. If data is in flight to `target`, or replication to it is
+    /// paused, the snapshot is sent after the data finishes or replication is resumed.
+    pub(crate) fn force_snapshot_to(&mut self, target: C::NodeId) {
+        tracing::info!(target = display(target), "{}", func_name!());
+
+        if target == self.config.id {
+            tracing::info!("target is this node itself, ignore");
+            return;
+        }
+
+        let Ok(mut lh) = self.leader_handler() else {
+            tracing::info!("not a leader, ignore");
+            return;
+        };
+
+        let Some(p) = lh.leader.progress.get_mut(&target) else {
+            tracing::info!(target = display(target), "target is not a member, ignore");
+            return;
+        };
+
+        if p.matching.as_ref() >= lh.state.last_log_id() {
+            tracing::info!(
+                target = display(target),
+                matching = display(p.matching.display()),
+                "target is up to date, no snapshot to send"
+            );
+            return;
+        }
+
+        if lh.state.snapshot_last_log_id() <= p.matching.as_ref() {
+            tracing::info!(
+                target = display(target),
+                matching = display(p.matching.display()),
+                snapshot = display(lh.state.snapshot_last_log_id().display()),
+                "snapshot is not newer than the matching log of target, no snapshot to send"
+            );
+            return;
+        }
+
+        p.force_snapshot = true;
+
+        lh.replication_handler().initiate_replication(SendNone::False);
+    }
+
     /// This is a to user API that triggers log purging upto `index`, inclusive.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn trigger_purge_log(&mut self, mut index: u64) {
@@ -723,7 +1262,7 @@ where C: RaftTypeConfig
 
     pub(crate) fn vote_handler(&mut self) -> VoteHandler<C> {
         VoteHandler {
-            config: &self.config,
+            config: &mut self.config,
             state: &mut self.state,
             output: &mut self.output,
             internal_server_state: &mut self.internal_server_state,
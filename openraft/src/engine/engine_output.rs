@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 
 use crate::core::sm::CommandSeq;
 use crate::engine::Command;
+use crate::engine::Respond;
 use crate::RaftTypeConfig;
 
 /// The entry of output from Engine to the runtime.
@@ -57,6 +58,7 @@ where C: RaftTypeConfig
             Command::SendVote { .. } => {}
             Command::PurgeLog { .. } => {}
             Command::DeleteConflictLog { .. } => {}
+            Command::SaveReplicationHints { .. } => {}
             Command::Respond { .. } => {}
         }
         self.commands.push_back(cmd)
@@ -75,6 +77,62 @@ where C: RaftTypeConfig
         self.commands.pop_front()
     }
 
+    /// Merge all the queued [`Command::SaveVote`] into the first one, which then saves the last
+    /// vote.
+    ///
+    /// A vote never decreases, thus every command queued after a `SaveVote`, e.g., a vote
+    /// response, still runs after a vote at least as great is saved.
+    ///
+    /// It returns the number of removed `SaveVote`.
+    pub(crate) fn coalesce_save_vote(&mut self) -> usize {
+        let last = self.commands.iter().rev().find_map(|c| match c {
+            Command::SaveVote { vote } => Some(*vote),
+            _ => None,
+        });
+
+        let Some(last) = last else {
+            return 0;
+        };
+
+        let len = self.commands.len();
+        let mut first = true;
+
+        self.commands.retain_mut(|c| match c {
+            Command::SaveVote { vote } => {
+                debug_assert!(*vote <= last, "vote must not decrease: {} > {}", vote, last);
+
+                let keep = first;
+                first = false;
+                *vote = last;
+                keep
+            }
+            _ => true,
+        });
+
+        len - self.commands.len()
+    }
+
+    /// Remove the queued granted vote responses and return them as denied.
+    pub(crate) fn take_denied_votes(&mut self) -> Vec<Respond<C>> {
+        let mut denied = vec![];
+
+        let commands = std::mem::take(&mut self.commands);
+        for cmd in commands {
+            match cmd {
+                Command::Respond { when: None, mut resp } => {
+                    if resp.deny_vote() {
+                        denied.push(resp);
+                    } else {
+                        self.commands.push_back(Command::Respond { when: None, resp });
+                    }
+                }
+                _ => self.commands.push_back(cmd),
+            }
+        }
+
+        denied
+    }
+
     /// Iterate all queued commands.
     pub(crate) fn iter_commands(&self) -> impl Iterator<Item = &Command<C>> {
         self.commands.iter()
@@ -44,6 +44,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        format_version: 0,
     };
     eng.state.server_state = eng.calc_server_state();
 
@@ -61,6 +62,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -70,6 +72,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         eng.state.snapshot_meta
     );
@@ -91,6 +94,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 5)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -100,6 +104,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         eng.state.snapshot_meta
     );
@@ -118,6 +123,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -127,6 +133,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         eng.state.snapshot_meta
     );
@@ -145,6 +152,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(4, 1, 6)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        format_version: 0,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -180,6 +188,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         };
 
         eng.state.server_state = eng.calc_server_state();
@@ -192,6 +201,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -201,6 +211,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         eng.state.snapshot_meta
     );
@@ -220,6 +231,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(5, 1, 6)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        format_version: 0,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -243,6 +255,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -252,6 +265,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         eng.state.snapshot_meta
     );
@@ -273,6 +287,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(100, 1, 100)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        format_version: 0,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -298,6 +313,7 @@ fn test_install_snapshot_update_accepted() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            format_version: 0,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -250,7 +250,7 @@ where C: RaftTypeConfig
 
         if snap_last_log_id.as_ref() <= self.state.committed() {
             tracing::info!(
-                "No need to install snapshot; snapshot last_log_id({}) <= committed({})",
+                "Ignore stale snapshot, state machine does not go backward; snapshot last_log_id({}) <= committed({})",
                 snap_last_log_id.display(),
                 self.state.committed().display()
             );
@@ -278,7 +278,10 @@ where C: RaftTypeConfig
             }
         }
 
-        self.state.update_accepted(Some(snap_last_log_id));
+        // A pristine node seeded with a snapshot has no leader to accept logs from.
+        if self.state.vote_ref().is_committed() {
+            self.state.update_accepted(Some(snap_last_log_id));
+        }
         self.state.committed = Some(snap_last_log_id);
         self.update_committed_membership(EffectiveMembership::new_from_stored_membership(
             meta.last_membership.clone(),
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use maplit::btreeset;
 #[allow(unused_imports)] use pretty_assertions::assert_eq;
@@ -7,6 +8,7 @@ use maplit::btreeset;
 
 use crate::engine::testing::UTConfig;
 use crate::engine::Engine;
+use crate::error::LeaderNotReady;
 use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::EffectiveMembership;
@@ -58,3 +60,74 @@ fn test_get_read_log_id() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_check_ready_to_read() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.read_min_heartbeats = 2;
+    eng.vote_handler().become_leading();
+
+    eng.internal_server_state.leading_mut().unwrap().noop_log_id = Some(log_id(1, 1, 2));
+
+    let not_ready = |committed, heartbeats| LeaderNotReady::<UTConfig> {
+        leader_id: 1,
+        noop_log_id: Some(log_id(1, 1, 2)),
+        committed,
+        heartbeats,
+        required: 2,
+    };
+
+    let t0 = TokioInstant::now();
+    let ack_by_quorum = |eng: &mut Engine<UTConfig>, millis: u64| {
+        let t = t0 + Duration::from_millis(millis);
+        eng.replication_handler().update_leader_clock(2, t);
+        eng.replication_handler().update_leader_clock(3, t);
+    };
+
+    // Heartbeats before the noop log is committed are not counted.
+    ack_by_quorum(&mut eng, 1);
+    assert_eq!(
+        Err(not_ready(Some(log_id(0, 1, 0)), 0)),
+        eng.leader_handler()?.check_ready_to_read()
+    );
+
+    eng.state.committed = Some(log_id(2, 1, 3));
+    eng.internal_server_state.leading_mut().unwrap().noop_committed_at = Some(t0 + Duration::from_millis(5));
+
+    // Heartbeats sent before the noop log is committed are not counted.
+    ack_by_quorum(&mut eng, 3);
+    assert_eq!(
+        Err(not_ready(Some(log_id(2, 1, 3)), 0)),
+        eng.leader_handler()?.check_ready_to_read()
+    );
+
+    // An acknowledgement by a single voter is not a quorum.
+    eng.replication_handler().update_leader_clock(2, t0 + Duration::from_millis(6));
+    assert_eq!(
+        Err(not_ready(Some(log_id(2, 1, 3)), 0)),
+        eng.leader_handler()?.check_ready_to_read()
+    );
+
+    ack_by_quorum(&mut eng, 6);
+    assert_eq!(
+        Err(not_ready(Some(log_id(2, 1, 3)), 1)),
+        eng.leader_handler()?.check_ready_to_read()
+    );
+
+    ack_by_quorum(&mut eng, 7);
+    assert_eq!(Ok(()), eng.leader_handler()?.check_ready_to_read());
+
+    Ok(())
+}
+
+#[test]
+fn test_check_ready_to_read_disabled() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.vote_handler().become_leading();
+
+    eng.internal_server_state.leading_mut().unwrap().noop_log_id = Some(log_id(1, 1, 2));
+
+    assert_eq!(Ok(()), eng.leader_handler()?.check_ready_to_read());
+
+    Ok(())
+}
@@ -4,8 +4,10 @@ use crate::engine::Command;
 use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::entry::RaftPayload;
+use crate::error::LeaderNotReady;
 use crate::internal_server_state::LeaderQuorumSet;
 use crate::leader::Leading;
+use crate::quorum::QuorumSet;
 use crate::raft_state::LogStateReader;
 use crate::type_config::alias::LogIdOf;
 use crate::RaftLogId;
@@ -41,6 +43,10 @@ where C: RaftTypeConfig
     /// If there is a membership config log entry, the caller has to guarantee the previous one is
     /// committed.
     ///
+    /// The entries are written ahead to the local log store before they are replicated:
+    /// `AppendInputEntries` is queued before any `Replicate` command, and the runtime does not run
+    /// the next command until the log store reports the entries are flushed.
+    ///
     /// TODO(xp): if vote indicates this node is not the leader, refuse append
     #[tracing::instrument(level = "debug", skip(self, entries))]
     pub(crate) fn leader_append_entries(&mut self, mut entries: Vec<C::Entry>) {
@@ -63,6 +69,7 @@ where C: RaftTypeConfig
             }
         }
 
+        // Must be queued before replication: the leader persists the entries first.
         self.output.push_command(Command::AppendInputEntries { entries });
 
         let mut rh = self.replication_handler();
@@ -78,7 +85,7 @@ where C: RaftTypeConfig
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn send_heartbeat(&mut self) -> () {
+    pub(crate) fn send_heartbeat(&mut self) {
         let mut rh = self.replication_handler();
         rh.initiate_replication(SendNone::True);
     }
@@ -92,6 +99,36 @@ where C: RaftTypeConfig
         std::cmp::max(self.leader.noop_log_id, committed)
     }
 
+    /// Check if this newly elected leader has established its authority to serve reads.
+    ///
+    /// With `read_min_heartbeats` set, the `noop` log has to be committed and that many heartbeats
+    /// after it have to be acknowledged by a quorum. A leader that alone forms a quorum only has
+    /// to commit the `noop` log.
+    pub(crate) fn check_ready_to_read(&self) -> Result<(), LeaderNotReady<C>> {
+        let required = self.config.read_min_heartbeats;
+        if required == 0 {
+            return Ok(());
+        }
+
+        let committed = self.state.committed().copied();
+        let noop_committed = self.leader.noop_log_id.is_some() && committed >= self.leader.noop_log_id;
+
+        let heartbeats = self.leader.quorum_acked_heartbeats;
+        let alone = self.state.membership_state.effective().is_quorum([self.config.id].iter());
+
+        if noop_committed && (alone || heartbeats >= required) {
+            return Ok(());
+        }
+
+        Err(LeaderNotReady {
+            leader_id: self.config.id,
+            noop_log_id: self.leader.noop_log_id,
+            committed,
+            heartbeats,
+            required,
+        })
+    }
+
     pub(crate) fn replication_handler(&mut self) -> ReplicationHandler<C> {
         ReplicationHandler {
             config: self.config,
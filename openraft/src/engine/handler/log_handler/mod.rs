@@ -41,6 +41,11 @@ where C: RaftTypeConfig
 
         let upto = *purge_upto.unwrap();
 
+        let history_size = self.config.term_history_size as usize;
+        if history_size > 0 {
+            st.keep_purged_key_log_ids(&upto, history_size);
+        }
+
         st.purge_log(&upto);
         self.output.push_command(Command::PurgeLog { upto });
     }
@@ -166,3 +166,50 @@ fn test_purge_log_to_higher_leader_lgo() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_purge_log_keep_term_history() -> anyhow::Result<()> {
+    // Disabled: purged leaders are forgotten.
+    {
+        let mut eng = eng();
+
+        let mut lh = eng.log_handler();
+        lh.state.purge_upto = Some(log_id(4, 1, 5));
+        lh.purge_log();
+
+        assert_eq!(vec![log_id(4, 1, 5)], lh.state.term_history());
+    }
+
+    // Enabled: the first log id of purged leaders are kept.
+    {
+        let mut eng = eng();
+        eng.config.term_history_size = 5;
+
+        let mut lh = eng.log_handler();
+        lh.state.purge_upto = Some(log_id(4, 1, 5));
+        lh.purge_log();
+
+        assert_eq!(log_id(4, 1, 5), lh.state.log_ids.key_log_ids()[0]);
+        assert_eq!(vec![log_id(2, 1, 2), log_id(4, 1, 4)], lh.state.term_history());
+
+        // Purging more of the same leader does not add history.
+        lh.state.purge_upto = Some(log_id(4, 1, 6));
+        lh.purge_log();
+
+        assert_eq!(vec![log_id(2, 1, 2), log_id(4, 1, 4)], lh.state.term_history());
+    }
+
+    // At most `term_history_size` leaders are kept.
+    {
+        let mut eng = eng();
+        eng.config.term_history_size = 1;
+
+        let mut lh = eng.log_handler();
+        lh.state.purge_upto = Some(log_id(4, 1, 5));
+        lh.purge_log();
+
+        assert_eq!(vec![log_id(4, 1, 4)], lh.state.term_history());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::ServerState;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,2}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.config.max_payload_entries = 10;
+
+    eng.state.log_ids = LogIdList::new([log_id(1, 0, 0), log_id(1, 0, 100)]);
+    eng.state.snapshot_meta = SnapshotMeta {
+        last_log_id: Some(log_id(1, 0, 80)),
+        last_membership: StoredMembership::new(Some(log_id(1, 0, 0)), m012()),
+        snapshot_id: "1-0-80".to_string(),
+        format_version: 0,
+    };
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 0));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 0, 0)), m012())));
+    eng.vote_handler().become_leading();
+
+    for (id, index) in [(0, 100), (1, 50), (2, 100)] {
+        let l = eng.internal_server_state.leading_mut().unwrap();
+        let _ = l.progress.update(&id, ProgressEntry::new(Some(log_id(1, 0, index))));
+    }
+
+    eng
+}
+
+#[test]
+fn test_force_snapshot_to_lagging_target() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.output.clear_commands();
+
+    eng.force_snapshot_to(1);
+
+    assert_eq!(
+        vec![Command::Replicate {
+            target: 1,
+            req: Inflight::snapshot(Some(log_id(1, 0, 80))).with_id(1),
+        },],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_force_snapshot_to_noop() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.output.clear_commands();
+
+    // Up to date
+    eng.force_snapshot_to(2);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    // Not a member
+    eng.force_snapshot_to(3);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    // The snapshot is not newer than the matching log
+    {
+        let l = eng.internal_server_state.leading_mut().unwrap();
+        let _ = l.progress.update(&1, ProgressEntry::new(Some(log_id(1, 0, 90))));
+    }
+    eng.force_snapshot_to(1);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    let l = eng.internal_server_state.leading().unwrap();
+    assert_eq!(false, l.progress.get(&1).force_snapshot);
+
+    Ok(())
+}
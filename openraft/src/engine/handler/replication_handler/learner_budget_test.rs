@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::handler::replication_handler::SendNone;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::ServerState;
+use crate::TokioInstant;
+use crate::Vote;
+
+/// Voters: {0,1}, learners: {2}
+fn m01_2() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1}], btreeset! {2})
+}
+
+/// Voters: {0,1,3}, learners: {2}
+fn m013_2() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,3}], btreeset! {2})
+}
+
+fn eng() -> Engine<UTConfig> {
+    eng_with(m01_2())
+}
+
+fn eng_with(m: Membership<UTConfig>) -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.config.max_payload_entries = 10;
+    eng.config.learner_replication_budget = 20;
+
+    eng.state.log_ids = LogIdList::new([log_id(1, 0, 0), log_id(1, 0, 100)]);
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 0));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 0, 0)), m)));
+    eng.vote_handler().become_leading();
+
+    eng
+}
+
+fn set_matching(eng: &mut Engine<UTConfig>, id: u64, index: u64) {
+    let l = eng.internal_server_state.leading_mut().unwrap();
+    let _ = l.progress.update(&id, ProgressEntry::new(Some(log_id(1, 0, index))));
+}
+
+#[test]
+fn test_learner_throttled_when_voter_backed_up() -> anyhow::Result<()> {
+    let mut eng = eng();
+    set_matching(&mut eng, 0, 100);
+    set_matching(&mut eng, 1, 50);
+    set_matching(&mut eng, 2, 50);
+
+    assert_eq!(2, eng.replication_handler().learner_max_payload_entries());
+
+    eng.output.clear_commands();
+    eng.replication_handler().initiate_replication(SendNone::False);
+
+    assert_eq!(
+        vec![
+            Command::Replicate {
+                target: 1,
+                req: Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 60))).with_id(1),
+            },
+            Command::Replicate {
+                target: 2,
+                req: Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 52))).with_id(1),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_learner_not_throttled_when_voters_caught_up() -> anyhow::Result<()> {
+    let mut eng = eng();
+    set_matching(&mut eng, 0, 100);
+    set_matching(&mut eng, 1, 100);
+    set_matching(&mut eng, 2, 50);
+
+    assert_eq!(10, eng.replication_handler().learner_max_payload_entries());
+
+    Ok(())
+}
+
+#[test]
+fn test_learner_not_throttled_when_quorum_caught_up() -> anyhow::Result<()> {
+    let mut eng = eng_with(m013_2());
+    set_matching(&mut eng, 0, 100);
+    set_matching(&mut eng, 1, 100);
+    set_matching(&mut eng, 3, 50);
+    set_matching(&mut eng, 2, 50);
+
+    assert_eq!(10, eng.replication_handler().learner_max_payload_entries());
+
+    Ok(())
+}
+
+#[test]
+fn test_learner_not_throttled_with_full_budget() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.learner_replication_budget = 100;
+    set_matching(&mut eng, 0, 100);
+    set_matching(&mut eng, 1, 50);
+    set_matching(&mut eng, 2, 50);
+
+    assert_eq!(10, eng.replication_handler().learner_max_payload_entries());
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::ops::Deref;
 
 use crate::display_ext::DisplayOptionExt;
@@ -17,6 +18,7 @@ use crate::replication::request_id::RequestId;
 use crate::replication::response::ReplicationResult;
 use crate::type_config::alias::InstantOf;
 use crate::EffectiveMembership;
+use crate::Instant;
 use crate::LogId;
 use crate::LogIdOptionExt;
 use crate::Membership;
@@ -25,7 +27,11 @@ use crate::RaftTypeConfig;
 use crate::ServerState;
 
 #[cfg(test)] mod append_membership_test;
+#[cfg(test)] mod force_snapshot_test;
+#[cfg(test)] mod learner_budget_test;
+#[cfg(test)] mod pause_replication_test;
 #[cfg(test)] mod update_matching_test;
+#[cfg(test)] mod update_progress_test;
 
 /// Handle replication operations.
 ///
@@ -165,12 +171,21 @@ where C: RaftTypeConfig
     pub(crate) fn update_leader_clock(&mut self, node_id: C::NodeId, t: InstantOf<C>) {
         tracing::debug!(target = display(node_id), t = debug(t), "{}", func_name!());
 
+        let prev_granted = *self.leader.clock_progress.granted();
+
         let granted = *self
             .leader
             .clock_progress
             .increase_to(&node_id, Some(t))
             .expect("it should always update existing progress");
 
+        // A quorum acknowledged a newer clock: count it as a heartbeat confirming the leadership,
+        // if it is sent after the blank log of this leader is committed.
+        if granted > prev_granted && self.leader.noop_committed_at.is_some() && granted >= self.leader.noop_committed_at
+        {
+            self.leader.quorum_acked_heartbeats += 1;
+        }
+
         tracing::debug!(
             granted = debug(granted),
             clock_progress = debug(&self.leader.clock_progress),
@@ -203,10 +218,7 @@ where C: RaftTypeConfig
 
         debug_assert!(log_id.is_some(), "a valid update can never set matching to None");
 
-        // The value granted by a quorum may not yet be a committed.
-        // A committed is **granted** and also is in current term.
-        let quorum_accepted = *self
-            .leader
+        self.leader
             .progress
             .update_with(&node_id, |prog_entry| {
                 let res = prog_entry.update_matching(inflight_id, log_id);
@@ -217,6 +229,10 @@ where C: RaftTypeConfig
             })
             .expect("it should always update existing progress");
 
+        // The value accepted by a quorum may not yet be a committed.
+        // A committed is **accepted** and also is in current term.
+        let quorum_accepted = self.leader.quorum_match_index();
+
         tracing::debug!(
             quorum_accepted = display(quorum_accepted.display()),
             "after updating progress"
@@ -230,6 +246,20 @@ where C: RaftTypeConfig
     /// In raft a log that is granted and in the leader term is committed.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn try_commit_quorum_accepted(&mut self, granted: Option<LogId<C::NodeId>>) {
+        let granted = if let Some(surviving) = &self.config.unsafe_commit_quorum {
+            let accepted = self.accepted_by_all(surviving);
+
+            tracing::warn!(
+                surviving = debug(surviving),
+                accepted = display(accepted.display()),
+                "DANGEROUS: commit quorum is lowered to the surviving nodes, split-brain may happen"
+            );
+
+            std::cmp::max(granted, accepted)
+        } else {
+            granted
+        };
+
         // Only when the log id is proposed by current leader, it is committed.
         if let Some(c) = granted {
             if !self.state.vote_ref().is_same_leader(c.committed_leader_id()) {
@@ -238,6 +268,13 @@ where C: RaftTypeConfig
         }
 
         if let Some(prev_committed) = self.state.update_committed(&granted) {
+            if self.leader.noop_committed_at.is_none()
+                && self.leader.noop_log_id.is_some()
+                && self.state.committed() >= self.leader.noop_log_id.as_ref()
+            {
+                self.leader.noop_committed_at = Some(InstantOf::<C>::now());
+            }
+
             self.output.push_command(Command::ReplicateCommitted {
                 committed: self.state.committed().copied(),
             });
@@ -255,6 +292,18 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Returns the greatest log id accepted by every node in `node_ids`.
+    ///
+    /// A node that is not replicated by this leader is ignored.
+    fn accepted_by_all(&self, node_ids: &BTreeSet<C::NodeId>) -> Option<LogId<C::NodeId>> {
+        node_ids
+            .iter()
+            .filter_map(|id| self.leader.progress.try_get(id))
+            .map(|p| p.matching)
+            .min()
+            .unwrap_or_default()
+    }
+
     /// Update progress when replicated data(logs or snapshot) does not match follower/learner state
     /// and is rejected.
     #[tracing::instrument(level = "debug", skip_all)]
@@ -272,6 +321,16 @@ where C: RaftTypeConfig
         );
 
         prog_entry.update_conflicting(inflight_id, conflict.index).unwrap();
+
+        if prog_entry.check_stalled(self.config.append_rejection_threshold) {
+            tracing::warn!(
+                target = display(target),
+                rejections = prog_entry.rejections,
+                stalls = prog_entry.stalls,
+                progress = display(&*prog_entry),
+                "replication is stalled: target keeps rejecting AppendEntries, fall back to snapshot"
+            );
+        }
     }
 
     /// Update replication progress when a response is received.
@@ -291,6 +350,21 @@ where C: RaftTypeConfig
             func_name!()
         );
 
+        // A replication stream rebuilt without a vote or membership change has the same session
+        // id. The response of the previous stream is stale if it does not match the data in
+        // flight.
+        if request_id != RequestId::HeartBeat {
+            let p = self.leader.progress.get(&target);
+            if !p.inflight.is_my_id(request_id) {
+                tracing::info!(
+                    request_id = display(request_id),
+                    inflight = display(&p.inflight),
+                    "ignore stale replication response"
+                );
+                return;
+            }
+        }
+
         match repl_res {
             Ok(p) => {
                 self.update_success_progress(target, request_id, p);
@@ -326,10 +400,16 @@ where C: RaftTypeConfig
 
         // initialize next replication to this target
 
+        if self.config.paused_replication.contains(&target) {
+            tracing::debug!("replication to target={target} is paused");
+            return;
+        }
+
         {
+            let max_entries = self.max_payload_entries_for(&target);
             let p = self.leader.progress.get_mut(&target).unwrap();
 
-            let r = p.next_send(self.state.deref(), self.config.max_payload_entries);
+            let r = p.next_send(self.state.deref(), max_entries, self.config.snapshot_lag_threshold);
             tracing::debug!(next_send_res = debug(&r), "next_send");
 
             if let Ok(inflight) = r {
@@ -364,14 +444,27 @@ where C: RaftTypeConfig
     pub(crate) fn initiate_replication(&mut self, send_none: SendNone) {
         tracing::debug!(progress = debug(&self.leader.progress), "{}", func_name!());
 
-        for (id, prog_entry) in self.leader.progress.iter_mut() {
+        let targets = self.leader.progress.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+
+        for id in targets.iter() {
             // TODO: update matching should be done here for leader
             //       or updating matching should be queued in commands?
             if id == &self.config.id {
                 continue;
             }
 
-            let t = prog_entry.next_send(self.state, self.config.max_payload_entries);
+            let max_entries = self.max_payload_entries_for(id);
+            let prog_entry = self.leader.progress.get_mut(id).unwrap();
+
+            // A paused target only receives heartbeats, when there is no data in flight.
+            if self.config.paused_replication.contains(id) {
+                if send_none == SendNone::True && prog_entry.inflight == Inflight::None {
+                    Self::send_to_target(self.output, id, &Inflight::None);
+                }
+                continue;
+            }
+
+            let t = prog_entry.next_send(self.state, max_entries, self.config.snapshot_lag_threshold);
             tracing::debug!(target = display(*id), send = debug(&t), "next send");
 
             match t {
@@ -397,6 +490,38 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Return the max number of entries to send to `target` in one replication request.
+    fn max_payload_entries_for(&self, target: &C::NodeId) -> u64 {
+        if self.state.membership_state.effective().is_voter(target) {
+            self.config.max_payload_entries
+        } else {
+            self.learner_max_payload_entries()
+        }
+    }
+
+    /// Return the max number of entries to send to a learner in one replication request.
+    ///
+    /// If a quorum of voters has not yet received all logs, a learner is only allowed to receive
+    /// [`EngineConfig::learner_replication_budget`] percent of `max_payload_entries`, so that
+    /// replication to voters, which affects commit, is prioritized. A single slow voter does not
+    /// throttle learners as long as the commit can advance without it.
+    pub(crate) fn learner_max_payload_entries(&self) -> u64 {
+        let max = self.config.max_payload_entries;
+        let budget = self.config.learner_replication_budget;
+
+        if budget >= 100 {
+            return max;
+        }
+
+        let quorum_backed_up = self.leader.progress.granted().as_ref() < self.state.last_log_id();
+
+        if !quorum_backed_up {
+            return max;
+        }
+
+        std::cmp::max(1, max * budget / 100)
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn send_to_target(output: &mut EngineOutput<C>, target: &C::NodeId, inflight: &Inflight<C::NodeId>) {
         output.push_command(Command::Replicate {
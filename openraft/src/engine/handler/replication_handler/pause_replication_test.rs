@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::handler::replication_handler::SendNone;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::ServerState;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,2}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.config.max_payload_entries = 10;
+
+    eng.state.log_ids = LogIdList::new([log_id(1, 0, 0), log_id(1, 0, 100)]);
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 0));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 0, 0)), m012())));
+    eng.vote_handler().become_leading();
+
+    for (id, index) in [(0, 100), (1, 50), (2, 50)] {
+        let l = eng.internal_server_state.leading_mut().unwrap();
+        let _ = l.progress.update(&id, ProgressEntry::new(Some(log_id(1, 0, index))));
+    }
+
+    eng
+}
+
+#[test]
+fn test_pause_replication_sends_heartbeat_only() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.pause_replication(2);
+
+    eng.output.clear_commands();
+    eng.replication_handler().initiate_replication(SendNone::False);
+
+    assert_eq!(
+        vec![Command::Replicate {
+            target: 1,
+            req: Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 60))).with_id(1),
+        },],
+        eng.output.take_commands()
+    );
+
+    eng.replication_handler().initiate_replication(SendNone::True);
+
+    assert_eq!(
+        vec![Command::Replicate {
+            target: 2,
+            req: Inflight::None,
+        },],
+        eng.output.take_commands(),
+        "node-1 has data in flight, node-2 receives a heartbeat"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resume_replication() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.pause_replication(2);
+
+    eng.output.clear_commands();
+    eng.resume_replication(1);
+    assert_eq!(0, eng.output.take_commands().len(), "node-1 is not paused");
+
+    eng.resume_replication(2);
+
+    assert_eq!(
+        vec![
+            Command::Replicate {
+                target: 1,
+                req: Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 60))).with_id(1),
+            },
+            Command::Replicate {
+                target: 2,
+                req: Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 60))).with_id(1),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::handler::replication_handler::SendNone;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::replication::request_id::RequestId;
+use crate::replication::response::ReplicationResult;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::ServerState;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,2}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.config.max_payload_entries = 10;
+
+    eng.state.log_ids = LogIdList::new([log_id(1, 0, 0), log_id(1, 0, 100)]);
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 0));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 0, 0)), m012())));
+    eng.vote_handler().become_leading();
+
+    for (id, index) in [(0, 100), (1, 50), (2, 50)] {
+        let l = eng.internal_server_state.leading_mut().unwrap();
+        let _ = l.progress.update(&id, ProgressEntry::new(Some(log_id(1, 0, index))));
+    }
+
+    eng
+}
+
+/// A response of a replication stream that is rebuilt, with the same session id, is ignored.
+#[test]
+fn test_update_progress_ignore_stale_response() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().initiate_replication(SendNone::False);
+    {
+        let mut rh = eng.replication_handler();
+        rh.rebuild_replication_streams();
+        rh.initiate_replication(SendNone::False);
+    }
+    eng.output.clear_commands();
+
+    let l = eng.internal_server_state.leading_mut().unwrap();
+    assert_eq!(
+        Inflight::logs(Some(log_id(1, 0, 50)), Some(log_id(1, 0, 60))).with_id(2),
+        l.progress.get(&1).inflight,
+        "the rebuilt stream sends the same logs with a new id"
+    );
+
+    let stale = ReplicationResult {
+        sending_time: TokioInstant::now(),
+        result: Ok(Some(log_id(1, 0, 60))),
+    };
+    eng.replication_handler().update_progress(1, RequestId::new_append_entries(1), Ok(stale));
+
+    let l = eng.internal_server_state.leading_mut().unwrap();
+    assert_eq!(Some(log_id(1, 0, 50)), l.progress.get(&1).matching);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    let resp = ReplicationResult {
+        sending_time: TokioInstant::now(),
+        result: Ok(Some(log_id(1, 0, 60))),
+    };
+    eng.replication_handler().update_progress(1, RequestId::new_append_entries(2), Ok(resp));
+
+    let l = eng.internal_server_state.leading_mut().unwrap();
+    assert_eq!(Some(log_id(1, 0, 60)), l.progress.get(&1).matching);
+    assert!(eng.output.take_commands().contains(&Command::Replicate {
+        target: 1,
+        req: Inflight::logs(Some(log_id(1, 0, 60)), Some(log_id(1, 0, 70))).with_id(3),
+    }));
+
+    Ok(())
+}
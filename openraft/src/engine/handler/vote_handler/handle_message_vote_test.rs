@@ -86,7 +86,7 @@ fn test_handle_message_vote_granted_equal_vote() -> anyhow::Result<()> {
 
     let mut eng = eng();
     eng.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
-    let now = TokioInstant::now();
+    let utime = eng.state.vote_last_modified();
 
     let resp = eng.vote_handler().update_vote(&Vote::new(2, 1));
 
@@ -97,6 +97,24 @@ fn test_handle_message_vote_granted_equal_vote() -> anyhow::Result<()> {
 
     assert_eq!(ServerState::Follower, eng.state.server_state);
 
+    assert_eq!(
+        utime,
+        eng.state.vote_last_modified(),
+        "equal vote does not reset election timeout"
+    );
+
+    assert!(eng.output.take_commands().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_handle_message_vote_update_election_timeout() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let now = TokioInstant::now();
+
+    eng.vote_handler().update_election_timeout();
+
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
     assert!(Some(now) <= eng.state.vote_last_modified());
     assert!(eng.state.vote_last_modified() <= Some(now + Duration::from_millis(20)));
 
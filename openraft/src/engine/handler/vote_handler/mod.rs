@@ -20,6 +20,8 @@ use crate::Vote;
 
 #[cfg(test)] mod accept_vote_test;
 #[cfg(test)] mod handle_message_vote_test;
+#[cfg(test)] mod term_jump_test;
+#[cfg(test)] mod update_election_timeout_test;
 
 /// Handle raft vote related operations
 ///
@@ -28,7 +30,7 @@ use crate::Vote;
 pub(crate) struct VoteHandler<'st, C>
 where C: RaftTypeConfig
 {
-    pub(crate) config: &'st EngineConfig<C>,
+    pub(crate) config: &'st mut EngineConfig<C>,
     pub(crate) state: &'st mut RaftState<C>,
     pub(crate) output: &'st mut EngineOutput<C>,
     pub(crate) internal_server_state: &'st mut InternalServerState<C>,
@@ -70,6 +72,8 @@ where C: RaftTypeConfig
 
             return None;
         }
+
+        self.update_election_timeout();
         Some(tx)
     }
 
@@ -82,6 +86,11 @@ where C: RaftTypeConfig
     ///
     /// Note: This method does not check last-log-id. handle-vote-request has to deal with
     /// last-log-id itself.
+    ///
+    /// Note: The election timeout is reset only when the vote changes. Receiving an equal vote
+    /// does not reset it, because it may come from a response or a rejected request. The caller
+    /// has to call [`Self::update_election_timeout`] if the message is a valid contact from the
+    /// leader or a granted vote request.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn update_vote(&mut self, vote: &Vote<C::NodeId>) -> Result<(), RejectVoteRequest<C>> {
         // Partial ord compare:
@@ -95,6 +104,8 @@ where C: RaftTypeConfig
         }
         tracing::debug!(%vote, "vote is changing to" );
 
+        self.check_term_jump(vote)?;
+
         // Grant the vote
 
         if vote > self.state.vote_ref() {
@@ -102,12 +113,8 @@ where C: RaftTypeConfig
 
             self.state.vote.update(InstantOf::<C>::now(), *vote);
             self.output.push_command(Command::SaveVote { vote: *vote });
-        } else {
-            self.state.vote.touch(InstantOf::<C>::now());
         }
 
-        // Update vote related timer and lease.
-
         tracing::debug!(now = debug(InstantOf::<C>::now()), "{}", func_name!());
 
         self.update_internal_server_state();
@@ -115,6 +122,51 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Reset the election timeout and the leader lease.
+    ///
+    /// It should only be called when a message from the current leader is accepted, i.e., an
+    /// append-entries or install-snapshot request, or when a vote request is granted.
+    ///
+    /// A new random election timeout is picked every time it is reset.
+    pub(crate) fn update_election_timeout(&mut self) {
+        tracing::debug!(now = debug(InstantOf::<C>::now()), "{}", func_name!());
+
+        self.config.renew_election_timeout();
+        self.state.vote.touch(InstantOf::<C>::now());
+    }
+
+    /// Check if the term of the input `vote` jumps too far ahead of the local term.
+    ///
+    /// A jump beyond [`EngineConfig::max_term_jump`] is logged, and is rejected if
+    /// [`EngineConfig::reject_term_jump`] is enabled.
+    fn check_term_jump(&self, vote: &Vote<C::NodeId>) -> Result<(), RejectVoteRequest<C>> {
+        let Some(max_jump) = self.config.max_term_jump else {
+            return Ok(());
+        };
+
+        let local_term = self.state.vote_ref().leader_id().term;
+        let term = vote.leader_id().term;
+
+        if term <= local_term.saturating_add(max_jump) {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            "term jump from {} to {} exceeds max_term_jump({}), vote: {}, reject: {}",
+            local_term,
+            term,
+            max_jump,
+            vote,
+            self.config.reject_term_jump
+        );
+
+        if self.config.reject_term_jump {
+            return Err(RejectVoteRequest::ByVote(*self.state.vote_ref()));
+        }
+
+        Ok(())
+    }
+
     /// Enter leading or following state by checking `vote`.
     pub(crate) fn update_internal_server_state(&mut self) {
         if self.state.is_leading(&self.config.id) {
@@ -147,13 +199,21 @@ where C: RaftTypeConfig
         // Re-create a new Leader instance.
 
         let em = &self.state.membership_state.effective();
-        let leading = Leading::new(
+        let mut leading = Leading::new(
             *self.state.vote_ref(),
             em.membership().to_quorum_set(),
             em.learner_ids(),
             self.state.last_log_id().copied(),
         );
 
+        // The hints saved before restart are only meaningful for the first leadership.
+        let hints = std::mem::take(&mut self.state.replication_hints);
+        if self.config.persist_replication_hints {
+            for (id, p) in leading.progress.iter_mut() {
+                p.hint = hints.get(id).copied();
+            }
+        }
+
         // Do not update clock_progress, until the first blank log is committed.
 
         *self.internal_server_state = InternalServerState::Leading(Box::new(leading));
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::error::RejectVoteRequest;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,2}], None)
+}
+
+fn eng(reject_term_jump: bool) -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.config.max_term_jump = Some(10);
+    eng.config.reject_term_jump = reject_term_jump;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 1));
+    eng.state.server_state = ServerState::Follower;
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m012())));
+
+    eng
+}
+
+#[test]
+fn test_update_vote_term_jump_within_cap() -> anyhow::Result<()> {
+    let mut eng = eng(true);
+
+    eng.vote_handler().update_vote(&Vote::new(12, 2))?;
+
+    assert_eq!(Vote::new(12, 2), *eng.state.vote_ref());
+    assert_eq!(
+        vec![Command::SaveVote { vote: Vote::new(12, 2) }],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_vote_term_jump_beyond_cap_rejected() -> anyhow::Result<()> {
+    let mut eng = eng(true);
+
+    let res = eng.vote_handler().update_vote(&Vote::new(13, 2));
+
+    assert_eq!(Err(RejectVoteRequest::ByVote(Vote::new_committed(2, 1))), res);
+    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_update_vote_term_jump_beyond_cap_accepted_if_not_reject() -> anyhow::Result<()> {
+    let mut eng = eng(false);
+
+    eng.vote_handler().update_vote(&Vote::new(1000, 2))?;
+
+    assert_eq!(Vote::new(1000, 2), *eng.state.vote_ref());
+    assert_eq!(
+        vec![Command::SaveVote {
+            vote: Vote::new(1000, 2)
+        }],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
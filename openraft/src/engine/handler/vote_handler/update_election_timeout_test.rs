@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::engine::EngineConfig;
+use crate::raft_state::RaftState;
+use crate::Config;
+
+fn eng(rand_seed: Option<u64>) -> Engine<UTConfig> {
+    let config = Config {
+        election_timeout_min: 150,
+        election_timeout_max: 300,
+        rand_seed,
+        ..Default::default()
+    };
+
+    let mut eng = Engine::new(RaftState::default(), EngineConfig::new(0, &config));
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+    eng
+}
+
+/// Returns the initial election timeout and the ones picked by the following `n` resets.
+fn timeouts(eng: &mut Engine<UTConfig>, n: usize) -> Vec<Duration> {
+    let mut res = vec![eng.config.timer_config.election_timeout];
+
+    for _ in 0..n {
+        eng.vote_handler().update_election_timeout();
+        res.push(eng.config.timer_config.election_timeout);
+    }
+
+    res
+}
+
+#[test]
+fn test_update_election_timeout_with_rand_seed() -> anyhow::Result<()> {
+    let a = timeouts(&mut eng(Some(7)), 20);
+    let b = timeouts(&mut eng(Some(7)), 20);
+
+    assert_eq!(a, b, "the same seed produces the same timeout sequence");
+
+    for t in a.iter() {
+        assert!(*t >= Duration::from_millis(150) && *t < Duration::from_millis(300));
+    }
+
+    let c = timeouts(&mut eng(Some(8)), 20);
+    assert_ne!(a, c, "a different seed produces a different timeout sequence");
+
+    Ok(())
+}
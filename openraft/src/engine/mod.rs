@@ -34,18 +34,22 @@ mod engine_impl;
 mod engine_output;
 mod log_id_list;
 
+#[cfg(feature = "decision-trace")] pub(crate) mod decision_trace;
+
 pub(crate) mod handler;
 pub(crate) mod time_state;
 
 #[cfg(test)]
 mod tests {
     mod append_entries_test;
+    mod coalesce_save_vote_test;
     mod elect_test;
     mod handle_vote_req_test;
     mod handle_vote_resp_test;
     mod initialize_test;
     mod log_id_list_test;
     mod startup_test;
+    mod step_down_test;
     mod trigger_purge_log_test;
 }
 #[cfg(test)] pub(crate) mod testing;
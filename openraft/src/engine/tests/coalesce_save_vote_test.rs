@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::Respond;
+use crate::raft::VoteRequest;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::AsyncRuntime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::TokioRuntime;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1,2}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 0;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(2, 1));
+    eng.state.server_state = ServerState::Follower;
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m012())));
+
+    eng
+}
+
+/// Handle a vote request and queue the response after the commands it outputs, as `RaftCore`
+/// does.
+fn handle_vote_req(eng: &mut Engine<UTConfig>, vote: Vote<u64>) {
+    let resp = eng.handle_vote_req(VoteRequest {
+        vote,
+        last_log_id: Some(log_id(1, 1, 1)),
+    });
+
+    let (tx, _rx) = TokioRuntime::oneshot();
+    eng.output.push_command(Command::Respond {
+        when: None,
+        resp: Respond::new(Ok(resp), tx),
+    });
+}
+
+fn save_vote_positions(eng: &Engine<UTConfig>) -> Vec<(usize, Vote<u64>)> {
+    eng.output
+        .iter_commands()
+        .enumerate()
+        .filter_map(|(i, c)| match c {
+            Command::SaveVote { vote } => Some((i, *vote)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn respond_positions(eng: &Engine<UTConfig>) -> Vec<usize> {
+    eng.output
+        .iter_commands()
+        .enumerate()
+        .filter_map(|(i, c)| match c {
+            Command::Respond { .. } => Some(i),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_coalesce_save_vote_rapid_term_updates() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    for (term, candidate) in [(3, 1), (4, 2), (5, 1), (6, 2)] {
+        handle_vote_req(&mut eng, Vote::new(term, candidate));
+    }
+
+    assert_eq!(4, save_vote_positions(&eng).len(), "every term update saves the vote");
+
+    let removed = eng.output.coalesce_save_vote();
+    assert_eq!(3, removed);
+
+    let saves = save_vote_positions(&eng);
+    assert_eq!(vec![(0, Vote::new(6, 2))], saves, "only the last vote is saved, first");
+
+    let responds = respond_positions(&eng);
+    assert_eq!(4, responds.len());
+    assert!(
+        responds.iter().all(|i| *i > saves[0].0),
+        "no response is sent before the vote is saved"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_coalesce_save_vote_single_or_none() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    assert_eq!(0, eng.output.coalesce_save_vote());
+
+    handle_vote_req(&mut eng, Vote::new(3, 1));
+    let before = eng.output.iter_commands().count();
+
+    assert_eq!(0, eng.output.coalesce_save_vote());
+    assert_eq!(before, eng.output.iter_commands().count());
+    assert_eq!(vec![(0, Vote::new(3, 1))], save_vote_positions(&eng));
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ use crate::engine::Engine;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
 use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
 use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::CommittedLeaderId;
@@ -178,3 +179,77 @@ fn test_elect() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_elect_keep_term_when_isolated() -> anyhow::Result<()> {
+    let new_candidate = || {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.keep_term_when_isolated = true;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 1)), m12())));
+        eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 1)]);
+
+        eng.elect_on_timeout();
+        eng.output.take_commands();
+        eng
+    };
+
+    tracing::info!("--- no peer responded: re-send vote request without increasing term");
+    {
+        let mut eng = new_candidate();
+
+        eng.elect_on_timeout();
+
+        assert_eq!(Vote::new(1, 1), *eng.state.vote_ref());
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+        assert_eq!(
+            vec![Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(1, 1), Some(log_id(1, 1, 1)))
+            },],
+            eng.output.take_commands()
+        );
+    }
+
+    tracing::info!("--- a peer responded: increase term");
+    {
+        let mut eng = new_candidate();
+
+        eng.handle_vote_resp(2, VoteResponse {
+            vote: Vote::new(1, 1),
+            vote_granted: false,
+            last_log_id: Some(log_id(1, 1, 1)),
+        });
+        eng.elect_on_timeout();
+
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+        assert_eq!(
+            vec![Command::SaveVote { vote: Vote::new(2, 1) }, Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 1, 1)))
+            },],
+            eng.output.take_commands()
+        );
+    }
+
+    tracing::info!("--- disabled: always increase term");
+    {
+        let mut eng = new_candidate();
+        eng.config.keep_term_when_isolated = false;
+
+        eng.elect_on_timeout();
+
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    }
+
+    tracing::info!("--- explicitly requested election: always increase term");
+    {
+        let mut eng = new_candidate();
+
+        eng.elect();
+
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    }
+
+    Ok(())
+}
@@ -8,6 +8,9 @@ use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
+use crate::metrics::VoteDenyReason;
+use crate::metrics::VoteLog;
+use crate::raft::PreferLowerNodeId;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::testing::log_id;
@@ -92,6 +95,31 @@ fn test_handle_vote_req_reject_smaller_vote() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_handle_vote_req_reject_smaller_vote_does_not_reset_election_timeout() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.id = 0;
+    eng.vote_handler().update_internal_server_state();
+    let utime = eng.state.vote_last_modified();
+
+    let resp = eng.handle_vote_req(VoteRequest {
+        vote: Vote::new(1, 2),
+        last_log_id: Some(log_id(2, 1, 3)),
+    });
+
+    assert!(!resp.vote_granted);
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+
+    assert_eq!(
+        utime,
+        eng.state.vote_last_modified(),
+        "rejected vote request does not reset election timeout"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_handle_vote_req_reject_smaller_last_log_id() -> anyhow::Result<()> {
     let mut eng = eng();
@@ -129,6 +157,7 @@ fn test_handle_vote_req_granted_equal_vote_and_last_log_id() -> anyhow::Result<(
     eng.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
 
     eng.output.clear_commands();
+    let now = TokioInstant::now();
 
     let resp = eng.handle_vote_req(VoteRequest {
         vote: Vote::new(2, 1),
@@ -147,6 +176,11 @@ fn test_handle_vote_req_granted_equal_vote_and_last_log_id() -> anyhow::Result<(
     assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
     assert!(eng.internal_server_state.is_following());
 
+    assert!(
+        Some(now) <= eng.state.vote_last_modified(),
+        "granted vote request resets election timeout"
+    );
+
     assert_eq!(ServerState::Follower, eng.state.server_state);
     assert!(eng.output.take_commands().is_empty());
     Ok(())
@@ -243,3 +277,167 @@ fn test_handle_vote_req_granted_follower_learner_does_not_emit_update_server_sta
     }
     Ok(())
 }
+
+#[test]
+fn test_handle_vote_req_vote_log() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.vote_log = VoteLog::new(10);
+    eng.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
+
+    let reqs = [
+        (Vote::new(1, 2), Some(log_id(2, 1, 3))),
+        (Vote::new(2, 0), Some(log_id(2, 1, 3))),
+        (Vote::new(3, 2), Some(log_id(1, 1, 3))),
+        (Vote::new(3, 2), Some(log_id(2, 1, 3))),
+    ];
+    for (vote, last_log_id) in reqs {
+        eng.handle_vote_req(VoteRequest { vote, last_log_id });
+    }
+
+    let denied = eng.vote_log.records().into_iter().map(|r| (r.term, r.candidate, r.denied)).collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            (1, 2, Some(VoteDenyReason::LowerTerm { vote: Vote::new(2, 1) })),
+            (2, 0, Some(VoteDenyReason::AlreadyVoted { vote: Vote::new(2, 1) })),
+            (
+                3,
+                2,
+                Some(VoteDenyReason::StaleLog {
+                    last_log_id: Some(log_id(2, 1, 3))
+                })
+            ),
+            (3, 2, None),
+        ],
+        denied
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_vote_req_election_tiebreaker() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.id = 0;
+    eng.vote_handler().update_internal_server_state();
+    eng.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
+    eng.election_tiebreaker = Some(Box::new(PreferLowerNodeId));
+    eng.vote_log = VoteLog::new(10);
+
+    tracing::info!("--- equal log: node-0 is preferred over candidate node-1");
+    {
+        let resp = eng.handle_vote_req(VoteRequest {
+            vote: Vote::new(3, 1),
+            last_log_id: Some(log_id(2, 1, 3)),
+        });
+
+        assert_eq!(
+            VoteResponse {
+                vote: Vote::new(2, 1),
+                vote_granted: false,
+                last_log_id: Some(log_id(2, 1, 3))
+            },
+            resp
+        );
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+        assert_eq!(
+            Some(VoteDenyReason::Tiebreak { preferred: 0 }),
+            eng.vote_log.records().last().unwrap().denied
+        );
+    }
+
+    tracing::info!("--- greater log: the tiebreaker is not consulted");
+    {
+        let resp = eng.handle_vote_req(VoteRequest {
+            vote: Vote::new(3, 1),
+            last_log_id: Some(log_id(3, 1, 4)),
+        });
+
+        assert!(resp.vote_granted);
+        assert_eq!(Vote::new(3, 1), *eng.state.vote_ref());
+    }
+
+    tracing::info!("--- equal log: candidate node-0 is preferred over node-1");
+    {
+        let mut eng1 = self::eng();
+        eng1.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
+        eng1.election_tiebreaker = Some(Box::new(PreferLowerNodeId));
+
+        let resp = eng1.handle_vote_req(VoteRequest {
+            vote: Vote::new(3, 0),
+            last_log_id: Some(log_id(2, 1, 3)),
+        });
+
+        assert!(resp.vote_granted);
+        assert_eq!(Vote::new(3, 0), *eng1.state.vote_ref());
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "decision-trace")]
+#[test]
+fn test_handle_vote_req_decision_trace() -> anyhow::Result<()> {
+    use std::io;
+    use std::sync::Mutex;
+
+    use crate::engine::decision_trace::DecisionTrace;
+    use crate::engine::decision_trace::StateDigest;
+    use crate::raft::DecisionRecord;
+
+    #[derive(Clone, Default)]
+    struct Buf(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut eng = eng();
+    eng.state.log_ids = LogIdList::new(vec![log_id(2, 1, 3)]);
+
+    let buf = Buf::default();
+    eng.decision_trace = Some(DecisionTrace::new(Box::new(buf.clone())));
+
+    eng.handle_vote_req(VoteRequest {
+        vote: Vote::new(3, 2),
+        last_log_id: Some(log_id(2, 1, 3)),
+    });
+
+    let data = buf.0.lock().unwrap().clone();
+    let lines = std::str::from_utf8(&data)?.lines().collect::<Vec<_>>();
+    assert_eq!(1, lines.len());
+
+    let record: DecisionRecord<UTConfig> = serde_json::from_str(lines[0])?;
+
+    assert_eq!(0, record.seq);
+    assert_eq!("vote-request", record.kind);
+    assert_eq!(
+        StateDigest {
+            server_state: ServerState::Candidate,
+            vote: Vote::new(2, 1),
+            last_log_id: Some(log_id(2, 1, 3)),
+            committed: None,
+            membership_log_id: Some(log_id(1, 1, 1)),
+        },
+        record.before
+    );
+    assert!(record.decision.starts_with("grant: "), "decision: {}", record.decision);
+    assert_eq!(
+        StateDigest {
+            server_state: ServerState::Follower,
+            vote: Vote::new(3, 2),
+            last_log_id: Some(log_id(2, 1, 3)),
+            committed: None,
+            membership_log_id: Some(log_id(1, 1, 1)),
+        },
+        record.after
+    );
+
+    Ok(())
+}
@@ -12,6 +12,7 @@ use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
+use crate::progress::Progress;
 use crate::raft::VoteResponse;
 use crate::raft_state::LogStateReader;
 use crate::testing::log_id;
@@ -272,3 +273,90 @@ fn test_handle_vote_resp() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// The effective membership changes during voting, the granted votes are re-counted against the
+/// latest one.
+#[test]
+fn test_handle_vote_resp_membership_changed() -> anyhow::Result<()> {
+    let candidate = |m: Membership<UTConfig>| {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(2, 1));
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m12())));
+        eng.vote_handler().become_leading();
+
+        let last_log_id = eng.state.last_log_id().copied();
+
+        eng.internal_server_state.leading_mut().map(|l| {
+            l.initialize_voting(last_log_id, TokioInstant::now());
+            l.voting_mut().unwrap().grant_by(&1)
+        });
+        eng.state.server_state = ServerState::Candidate;
+
+        // The membership changes after the voting started.
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 2)), m)));
+        eng
+    };
+
+    let granted_by_2 = || VoteResponse {
+        vote: Vote::new(2, 1),
+        vote_granted: true,
+        last_log_id: Some(log_id(1, 1, 1)),
+    };
+
+    tracing::info!("--- a quorum of the old config is not a quorum of the new joint config");
+    {
+        let mut eng = candidate(Membership::new(vec![btreeset! {1,2}, btreeset! {3,4,5}], None));
+
+        eng.handle_vote_resp(2, granted_by_2());
+
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+        assert_eq!(
+            Some(btreeset! {1,2}),
+            eng.internal_server_state.leading().map(|x| x.voting().unwrap().granters().collect::<BTreeSet<_>>())
+        );
+    }
+
+    tracing::info!("--- the grant from a removed voter is ignored");
+    {
+        let mut eng = candidate(Membership::new(vec![btreeset! {1,3,4}], None));
+
+        eng.handle_vote_resp(2, granted_by_2());
+
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+        assert_eq!(
+            Some(btreeset! {1}),
+            eng.internal_server_state.leading().map(|x| x.voting().unwrap().granters().collect::<BTreeSet<_>>())
+        );
+    }
+
+    tracing::info!("--- granted by a quorum of the new config: become leader");
+    {
+        let mut eng = candidate(m1234());
+
+        eng.handle_vote_resp(2, granted_by_2());
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+
+        eng.handle_vote_resp(3, granted_by_2());
+        assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+        assert_eq!(ServerState::Leader, eng.state.server_state);
+        assert_eq!(
+            btreeset! {1,2,3,4},
+            eng.internal_server_state
+                .leading()
+                .unwrap()
+                .progress
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<BTreeSet<_>>(),
+            "replication progress is built with the new config"
+        );
+    }
+
+    Ok(())
+}
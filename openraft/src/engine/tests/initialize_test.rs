@@ -1,3 +1,5 @@
+use std::io::Cursor;
+
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
@@ -7,6 +9,7 @@ use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
+use crate::error::EmptyMembership;
 use crate::error::InitializeError;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
@@ -18,6 +21,9 @@ use crate::vote::CommittedLeaderId;
 use crate::Entry;
 use crate::LogId;
 use crate::Membership;
+use crate::Snapshot;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
 use crate::TokioInstant;
 use crate::Vote;
 
@@ -178,6 +184,21 @@ fn test_initialize() -> anyhow::Result<()> {
         );
     }
 
+    tracing::info!("--- empty membership is rejected before any change to the state");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+
+        let m = Membership::<UTConfig>::new(vec![btreeset! {}], None);
+
+        assert_eq!(
+            Err(InitializeError::EmptyMembership(EmptyMembership {})),
+            eng.initialize(Entry::new_membership(LogId::default(), m))
+        );
+        assert_eq!(None, eng.state.last_log_id());
+        assert!(eng.output.take_commands().is_empty());
+    }
+
     tracing::info!("--- node id 0 is not in membership");
     {
         let mut eng = eng();
@@ -189,6 +210,80 @@ fn test_initialize() -> anyhow::Result<()> {
             })),
             eng.initialize(entry())
         );
+        assert_eq!(None, eng.state.last_log_id(), "no log is assigned");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_initialize_with_snapshot() -> anyhow::Result<()> {
+    let eng = || {
+        let mut eng = Engine::<UTConfig>::testing_default(0);
+        eng.state.enable_validation(false); // Disable validation for incomplete state
+
+        eng.state.server_state = eng.calc_server_state();
+        eng
+    };
+
+    let m1 = || Membership::<UTConfig>::new(vec![btreeset! {1}], None);
+    let snapshot = |m: Membership<UTConfig>| Snapshot::<UTConfig> {
+        meta: SnapshotMeta {
+            last_log_id: Some(log_id(3, 2, 10)),
+            last_membership: StoredMembership::new(Some(log_id(3, 2, 5)), m),
+            snapshot_id: "seed".to_string(),
+            format_version: 0,
+        },
+        snapshot: Box::new(Cursor::new(vec![])),
+    };
+
+    tracing::info!("--- ok: init empty node 1 with a snapshot of membership(1)");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+
+        eng.initialize_with_snapshot(snapshot(m1()))?;
+
+        assert_eq!(Some(&log_id(3, 2, 10)), eng.state.snapshot_last_log_id());
+        assert_eq!(Some(&log_id(3, 2, 10)), eng.state.purge_upto());
+        assert_eq!(&m1(), eng.state.membership_state.effective().membership());
+
+        assert_eq!(ServerState::Leader, eng.state.server_state);
+        assert_eq!(
+            &Vote::new_committed(4, 1),
+            eng.state.vote_ref(),
+            "the vote catches up with the term in the snapshot"
+        );
+        assert_eq!(Some(&log_id(4, 1, 11)), eng.state.last_log_id());
+    }
+
+    tracing::info!("--- not allowed because of vote");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(0, 1));
+
+        assert_eq!(
+            Err(InitializeError::NotAllowed(NotAllowed {
+                last_log_id: None,
+                vote: Vote::new(0, 1),
+            })),
+            eng.initialize_with_snapshot(snapshot(m1()))
+        );
+    }
+
+    tracing::info!("--- node id 0 is not in membership");
+    {
+        let mut eng = eng();
+
+        assert_eq!(
+            Err(InitializeError::NotInMembers(NotInMembers {
+                node_id: 0,
+                membership: m1()
+            })),
+            eng.initialize_with_snapshot(snapshot(m1()))
+        );
+        assert!(eng.output.take_commands().is_empty());
     }
 
     Ok(())
@@ -5,6 +5,7 @@ use maplit::btreeset;
 use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
+use crate::engine::LogIdList;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
 use crate::testing::log_id;
@@ -57,7 +58,12 @@ fn test_startup_as_leader() -> anyhow::Result<()> {
                     matching: None,
                     curr_inflight_id: 0,
                     inflight: Inflight::None,
-                    searching_end: 0
+                    searching_end: 0,
+                    hint: None,
+                    rejections: 0,
+                    stalled: false,
+                    stalls: 0,
+                    force_snapshot: false,
                 })]
             }
         ],
@@ -134,3 +140,37 @@ fn test_startup_as_learner() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_startup_term_log_gap_degraded() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.max_term_log_gap = Some(10);
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m23())));
+    eng.state.log_ids = LogIdList::new([log_id(0, 0, 0), log_id(2, 1, 3)]);
+
+    // Gap 10 is allowed
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(12, 2));
+    eng.startup();
+    assert_eq!(false, eng.config.term_gap_degraded);
+
+    // Gap 11 is not allowed
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(13, 2));
+    eng.startup();
+    assert_eq!(true, eng.config.term_gap_degraded);
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+
+    eng.output.clear_commands();
+    eng.elect();
+    assert_eq!(0, eng.output.take_commands().len(), "degraded node does not elect");
+    assert_eq!(Vote::new(13, 2), *eng.state.vote_ref());
+
+    eng.clear_term_gap_degraded();
+    assert_eq!(false, eng.config.term_gap_degraded);
+
+    eng.elect();
+    assert_eq!(Vote::new(14, 2), *eng.state.vote_ref());
+
+    Ok(())
+}
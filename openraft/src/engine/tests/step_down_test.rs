@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m01() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())));
+    eng
+}
+
+#[test]
+fn test_step_down_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 1));
+    eng.state.server_state = ServerState::Leader;
+    eng.vote_handler().become_leading();
+
+    eng.step_down();
+
+    assert_eq!(Vote::new(3, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+    assert!(eng.leader_handler().is_err(), "no longer a leader");
+    assert!(
+        eng.internal_server_state.voting_mut().is_none(),
+        "no vote request is sent"
+    );
+    assert!(eng.is_there_greater_log(), "election on this node is postponed");
+
+    assert_eq!(
+        vec![
+            //
+            Command::SaveVote { vote: Vote::new(3, 1) },
+            Command::QuitLeader,
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_step_down_not_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 0));
+    eng.state.server_state = ServerState::Follower;
+    eng.vote_handler().become_following();
+
+    eng.step_down();
+
+    assert_eq!(Vote::new_committed(2, 0), *eng.state.vote_ref());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+    assert!(!eng.is_there_greater_log());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
@@ -53,6 +53,7 @@ fn test_trigger_purge_log_already_scheduled() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        format_version: 0,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -73,6 +74,7 @@ fn test_trigger_purge_log_delete_only_in_snapshot_logs() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        format_version: 0,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -101,6 +103,7 @@ fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        format_version: 0,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -109,7 +112,7 @@ fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
     // Make it a leader and mark the logs are in flight.
     eng.vote_handler().become_leading();
     let l = eng.internal_server_state.leading_mut().unwrap();
-    let _ = l.progress.get_mut(&2).unwrap().next_send(eng.state.deref(), 10).unwrap();
+    let _ = l.progress.get_mut(&2).unwrap().next_send(eng.state.deref(), 10, None).unwrap();
 
     eng.trigger_purge_log(5);
 
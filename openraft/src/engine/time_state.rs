@@ -20,6 +20,18 @@ pub(crate) struct Config {
     /// When a follower or learner perceives an active leader, such as by receiving an AppendEntries
     /// message, it should not grant another candidate to become the leader during this period.
     pub(crate) leader_lease: Duration,
+
+    /// The additional time a follower waits for a leader that has been active, before campaigning
+    /// against it or granting another candidate.
+    ///
+    /// See [`Config::leader_stickiness`](`crate::Config::leader_stickiness`).
+    pub(crate) leader_stickiness: Duration,
+
+    /// The additional time a follower defers campaigning after the election timeout, to absorb a
+    /// slightly delayed heartbeat.
+    ///
+    /// See [`Config::election_grace_period`](`crate::Config::election_grace_period`).
+    pub(crate) election_grace_period: Duration,
 }
 
 impl Default for Config {
@@ -28,6 +40,8 @@ impl Default for Config {
             election_timeout: Duration::from_millis(150),
             smaller_log_timeout: Duration::from_millis(200),
             leader_lease: Duration::from_millis(150),
+            leader_stickiness: Duration::from_millis(0),
+            election_grace_period: Duration::from_millis(0),
         }
     }
 }
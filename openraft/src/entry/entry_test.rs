@@ -0,0 +1,35 @@
+use crate::declare_raft_types;
+use crate::entry::FromAppData;
+use crate::testing::log_id;
+use crate::Entry;
+use crate::EntryPayload;
+use crate::RaftLogId;
+
+declare_raft_types!(
+    BytesConfig:
+        D = Vec<u8>,
+        R = (),
+);
+
+/// A command serialized by the application before writing is carried as the app data, and reaches
+/// the state machine byte-for-byte.
+#[test]
+fn test_pre_serialized_app_data_round_trip() -> anyhow::Result<()> {
+    let bytes = vec![0u8, 1, b'{', 0x7f, 0x80, 0xff];
+
+    let mut entry = Entry::<BytesConfig>::from_app_data(bytes.clone());
+    entry.set_log_id(&log_id(1, 1, 3));
+
+    // A log store or a network transport encodes the entry.
+    #[cfg(feature = "serde")]
+    let entry: Entry<BytesConfig> = serde_json::from_slice(&serde_json::to_vec(&entry)?)?;
+
+    assert_eq!(&log_id(1, 1, 3), entry.get_log_id());
+
+    let EntryPayload::Normal(got) = entry.payload else {
+        panic!("expect a normal payload, got: {}", entry.payload);
+    };
+    assert_eq!(bytes, got);
+
+    Ok(())
+}
@@ -11,6 +11,8 @@ use crate::RaftTypeConfig;
 pub mod payload;
 mod traits;
 
+#[cfg(test)] mod entry_test;
+
 pub use payload::EntryPayload;
 pub use traits::FromAppData;
 pub use traits::RaftEntry;
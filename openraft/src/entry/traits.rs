@@ -35,6 +35,67 @@ where
     ///
     /// The returned instance must return `Some()` for `Self::get_membership()`.
     fn new_membership(log_id: LogId<C::NodeId>, m: Membership<C>) -> Self;
+
+    /// Create a new checkpoint log entry from app data.
+    ///
+    /// The log id is assigned when the entry is appended, as with
+    /// [`FromAppData::from_app_data()`]. An entry type that supports checkpoints marks the
+    /// returned entry so that [`Self::is_checkpoint()`] returns `true` for it. The default
+    /// implementation builds an entry with `from_app_data()`, and so does the default
+    /// [`Entry`](crate::Entry): the state machine applies it as a normal entry.
+    fn new_checkpoint(d: C::D) -> Self
+    where Self: FromAppData<C::D> + Sized {
+        Self::from_app_data(d)
+    }
+
+    /// Return `true` if this entry is built with [`Self::new_checkpoint()`].
+    ///
+    /// The default implementation returns `false`.
+    fn is_checkpoint(&self) -> bool {
+        false
+    }
+
+    /// Set the leader's wall clock time, in milliseconds since the UNIX epoch, when this entry
+    /// is appended.
+    ///
+    /// The leader stamps every entry it is asked to write. An entry type that stores the
+    /// timestamp replicates it to followers along with the entry, so that the state machine on
+    /// every node applies it with the same time. The default implementation discards it, and so
+    /// does the default [`Entry`](crate::Entry).
+    fn set_timestamp(&mut self, millis: u64) {
+        let _ = millis;
+    }
+
+    /// Return the leader's wall clock time, in milliseconds since the UNIX epoch, when this entry
+    /// is appended, or `None` if it is not stamped.
+    fn get_timestamp(&self) -> Option<u64> {
+        None
+    }
+
+    /// Set the opaque application metadata submitted along with the payload, with
+    /// [`Raft::client_write_with_metadata()`](crate::Raft::client_write_with_metadata).
+    ///
+    /// An entry type that stores the metadata replicates it along with the entry, so that the
+    /// state machine on every node can read it when applying the entry. The default implementation
+    /// discards it, and so does the default [`Entry`](crate::Entry).
+    fn set_metadata(&mut self, metadata: Vec<u8>) {
+        let _ = metadata;
+    }
+
+    /// Return the opaque application metadata of this entry, or `None` if there is none.
+    fn get_metadata(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Return the size in bytes of this entry when it is sent by the application's network, or
+    /// `None` if it is unknown.
+    ///
+    /// The leader uses it to keep an AppendEntries request within
+    /// [`Config::max_message_size`](crate::Config::max_message_size), which can only be set if
+    /// the entry type returns `Some` here. The default implementation returns `None`.
+    fn size_hint(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Build a raft log entry from app data.
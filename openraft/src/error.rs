@@ -139,6 +139,9 @@ where C: RaftTypeConfig
 pub enum InstallSnapshotError {
     #[error(transparent)]
     SnapshotMismatch(#[from] SnapshotMismatch),
+
+    #[error(transparent)]
+    UnsupportedSnapshotFormat(#[from] UnsupportedSnapshotFormat),
 }
 
 /// An error related to a is_leader request.
@@ -152,6 +155,11 @@ where C: RaftTypeConfig
 
     #[error(transparent)]
     QuorumNotEnough(#[from] QuorumNotEnough<C>),
+
+    /// The leader is newly elected and has not yet established its authority, see
+    /// [`Config::read_min_heartbeats`](crate::Config::read_min_heartbeats).
+    #[error(transparent)]
+    LeaderNotReady(#[from] LeaderNotReady<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for CheckIsLeaderError<C>
@@ -165,6 +173,56 @@ where C: RaftTypeConfig
     }
 }
 
+/// An error when a node waits to catch up with the leader's commit index before a read.
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum CatchUpError<C>
+where C: RaftTypeConfig
+{
+    /// The leader is unknown or can not be reached, or the target is no longer the leader.
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    /// The leader failed to confirm its leadership with a quorum.
+    #[error(transparent)]
+    QuorumNotEnough(#[from] QuorumNotEnough<C>),
+
+    /// The leader is newly elected and not yet ready to serve reads.
+    #[error(transparent)]
+    LeaderNotReady(#[from] LeaderNotReady<C>),
+
+    /// This node is a witness, its state machine does not have the application data.
+    #[error(transparent)]
+    IsWitness(#[from] NodeIsWitness<C>),
+
+    /// The leader did not respond, or this node did not catch up, in time.
+    #[error("timeout after {0:?} when catching up with the leader")]
+    Timeout(Duration),
+}
+
+impl<C> From<CheckIsLeaderError<C>> for CatchUpError<C>
+where C: RaftTypeConfig
+{
+    fn from(e: CheckIsLeaderError<C>) -> Self {
+        match e {
+            CheckIsLeaderError::ForwardToLeader(e) => e.into(),
+            CheckIsLeaderError::QuorumNotEnough(e) => e.into(),
+            CheckIsLeaderError::LeaderNotReady(e) => e.into(),
+        }
+    }
+}
+
+impl<C> TryAsRef<ForwardToLeader<C>> for CatchUpError<C>
+where C: RaftTypeConfig
+{
+    fn try_as_ref(&self) -> Option<&ForwardToLeader<C>> {
+        match self {
+            Self::ForwardToLeader(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
 /// An error related to a client write request.
 #[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
 #[derive(PartialEq, Eq)]
@@ -178,6 +236,40 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<C>),
+
+    /// When writing application data to a sealed cluster.
+    #[error(transparent)]
+    Sealed(#[from] Sealed<C>),
+
+    /// When writing to a node whose storage keeps failing.
+    #[error(transparent)]
+    StorageDegraded(#[from] StorageDegraded<C>),
+
+    /// When writing to a node that has no voter in its membership config.
+    #[error(transparent)]
+    NoConfiguration(#[from] NoConfiguration<C>),
+
+    /// When writing to a node whose state machine keeps failing to apply committed entries.
+    #[error(transparent)]
+    ApplyStalled(#[from] ApplyStalled<C>),
+
+    /// When writing to a node that is prepared for shutdown.
+    #[error(transparent)]
+    ShuttingDown(#[from] ShuttingDown<C>),
+
+    /// When no follower in the required group acknowledges a write with a commit wait in time.
+    #[error(transparent)]
+    CommitWaitTimeout(#[from] CommitWaitTimeout<C>),
+
+    /// When the written entry is committed but skipped by [`Raft::set_applied_index()`].
+    ///
+    /// [`Raft::set_applied_index()`]: crate::Raft::set_applied_index
+    #[error(transparent)]
+    ApplySkipped(#[from] ApplySkipped<C>),
+
+    /// When the written entry is committed and applied by a failed apply that is retried.
+    #[error(transparent)]
+    ApplyResultLost(#[from] ApplyResultLost<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -203,6 +295,15 @@ pub enum ChangeMembershipError<C: RaftTypeConfig> {
 
     #[error(transparent)]
     LearnerNotFound(#[from] LearnerNotFound<C>),
+
+    #[error(transparent)]
+    LearnerIsLagging(#[from] LearnerIsLagging<C>),
+
+    #[error(transparent)]
+    RecentlyAdded(#[from] RecentlyAdded<C>),
+
+    #[error(transparent)]
+    WitnessNotVoter(#[from] WitnessNotVoter<C>),
 }
 
 /// The set of errors which may take place when initializing a pristine Raft node.
@@ -216,6 +317,9 @@ where C: RaftTypeConfig
 
     #[error(transparent)]
     NotInMembers(#[from] NotInMembers<C>),
+
+    #[error(transparent)]
+    EmptyMembership(#[from] EmptyMembership),
 }
 
 /// Error variants related to the Replication.
@@ -562,6 +666,18 @@ pub struct SnapshotMismatch {
     pub got: SnapshotSegmentId,
 }
 
+/// The format version of a received snapshot can not be installed by the local state machine.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("unsupported snapshot format version: {format_version}, current version: {current}")]
+pub struct UnsupportedSnapshotFormat {
+    /// The format version of the received snapshot.
+    pub format_version: u32,
+
+    /// The format version of the local state machine.
+    pub current: u32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("not enough for a quorum, cluster: {cluster}, got: {got:?}")]
@@ -570,6 +686,23 @@ pub struct QuorumNotEnough<C: RaftTypeConfig> {
     pub got: BTreeSet<C::NodeId>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("leader {leader_id} is not ready to serve reads: blank log {noop_log_id:?}, committed: {committed:?}, quorum acknowledged heartbeats: {heartbeats}, required: {required}")]
+pub struct LeaderNotReady<C: RaftTypeConfig> {
+    pub leader_id: C::NodeId,
+
+    /// The blank log the leader proposed when it was elected.
+    pub noop_log_id: Option<LogId<C::NodeId>>,
+
+    pub committed: Option<LogId<C::NodeId>>,
+
+    /// The number of heartbeats acknowledged by a quorum after the blank log is committed.
+    pub heartbeats: u64,
+
+    pub required: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("the cluster is already undergoing a configuration change at log {membership_log_id:?}, last committed membership log id: {committed:?}")]
@@ -578,6 +711,88 @@ pub struct InProgress<C: RaftTypeConfig> {
     pub membership_log_id: Option<LogId<C::NodeId>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("the cluster is sealed by membership log {membership_log_id:?}, writes are rejected until it is unsealed")]
+pub struct Sealed<C: RaftTypeConfig> {
+    pub membership_log_id: Option<LogId<C::NodeId>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} is degraded after {failures} storage failures, writes are rejected until storage recovers")]
+pub struct StorageDegraded<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub failures: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} has no voter in its membership config, it must be initialized before serving requests")]
+pub struct NoConfiguration<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+}
+
+/// A committed entry is not applied, because the applied index is set past it by
+/// [`Raft::set_applied_index()`](crate::Raft::set_applied_index): the result of applying it is
+/// not available.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("entry at index {index} is committed but skipped by node {node_id}, the state machine is restored out-of-band")]
+pub struct ApplySkipped<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub index: u64,
+}
+
+/// A committed entry is applied by a failed
+/// [`RaftStateMachine::apply()`](crate::storage::RaftStateMachine::apply), which is retried after
+/// it: the entry is applied, but the result of applying it is lost.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("entry at index {index} is committed and applied by node {node_id}, but its result is lost by a failed apply")]
+pub struct ApplyResultLost<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub index: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} keeps failing to apply, {backlog} committed entries are not applied, more than {threshold}, writes are rejected until applying recovers")]
+pub struct ApplyStalled<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+
+    /// The number of committed entries that are not applied.
+    pub backlog: u64,
+
+    /// The backlog beyond which writes are rejected, i.e., `Config::apply_stall_backlog`.
+    pub threshold: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("no follower labeled {label}={value} acknowledged the log at index {log_index} on leader {node_id} within {timeout:?}, the log may still be committed")]
+pub struct CommitWaitTimeout<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+
+    /// The index of the log written.
+    pub log_index: u64,
+
+    /// The label key of the group of followers waited for.
+    pub label: String,
+
+    /// The label value of the group of followers waited for.
+    pub value: String,
+
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} is prepared for shutdown, new writes are rejected")]
+pub struct ShuttingDown<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("Learner {node_id} not found: add it as learner before adding it as a voter")]
@@ -585,6 +800,44 @@ pub struct LearnerNotFound<C: RaftTypeConfig> {
     pub node_id: C::NodeId,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("Learner {node_id} is lagging: matching: {matching:?}, distance: {distance}; wait for it to catch up before promoting it to a voter")]
+pub struct LearnerIsLagging<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub matching: Option<LogId<C::NodeId>>,
+    pub distance: u64,
+}
+
+/// A membership change removes a node added within [`Config::member_removal_grace`].
+///
+/// [`Config::member_removal_grace`]: crate::Config::member_removal_grace
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} was added {since_added:?} ago, within the removal grace period {grace:?}; force the change to remove it anyway")]
+pub struct RecentlyAdded<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub since_added: Duration,
+    pub grace: Duration,
+}
+
+/// A node to make a witness is not a voter.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} is not a voter, only a voter can be a witness")]
+pub struct WitnessNotVoter<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+}
+
+/// The node is a witness: its log and state machine have no application data, thus they can not
+/// serve reads.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} is a witness, it does not have the application data")]
+pub struct NodeIsWitness<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("not allowed to initialize due to current raft state: last_log_id: {last_log_id:?} vote: {vote}")]
@@ -608,6 +861,55 @@ where C: RaftTypeConfig
 #[error("new membership can not be empty")]
 pub struct EmptyMembership {}
 
+/// Error returned when lowering the commit quorum without enabling
+/// [`Config::allow_unsafe_commit_quorum`](crate::Config::allow_unsafe_commit_quorum).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("unsafe commit quorum is not allowed: Config::allow_unsafe_commit_quorum is disabled")]
+pub struct UnsafeCommitQuorumNotAllowed {}
+
+/// Error returned by [`Raft::set_applied_index()`](crate::Raft::set_applied_index).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum SetAppliedIndexError<C: RaftTypeConfig> {
+    /// [`Config::allow_unsafe_set_applied`](crate::Config::allow_unsafe_set_applied) is disabled.
+    #[error("setting applied index is not allowed: Config::allow_unsafe_set_applied is disabled")]
+    NotAllowed,
+
+    /// The applied index can not be set to a log that is not yet committed.
+    #[error("applied index {index} can not be set beyond committed: {committed:?}")]
+    BeyondCommitted {
+        index: u64,
+        committed: Option<LogId<C::NodeId>>,
+    },
+}
+
+/// Error returned by [`Raft::get_committed_digest()`](crate::Raft::get_committed_digest).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum CommittedDigestError<C: RaftTypeConfig> {
+    /// The digest can only be computed over committed entries.
+    #[error("can not compute digest up to {up_to}, beyond committed: {committed:?}")]
+    BeyondCommitted {
+        up_to: u64,
+        committed: Option<LogId<C::NodeId>>,
+    },
+
+    /// The entry at `up_to` is purged, the digest can only be chained from a log id before it.
+    #[error("can not compute digest up to {up_to}, logs are purged up to: {last_purged:?}")]
+    LogPurged {
+        up_to: u64,
+        last_purged: Option<LogId<C::NodeId>>,
+    },
+
+    /// This node is a witness, its log entries do not have the application data.
+    #[error(transparent)]
+    IsWitness(#[from] NodeIsWitness<C>),
+
+    #[error(transparent)]
+    StorageError(#[from] StorageError<C::NodeId>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[error("infallible")]
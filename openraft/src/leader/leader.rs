@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::leader::voting::Voting;
+use crate::metrics::ReplicationDetail;
+use crate::metrics::ReplicationPhase;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Progress;
 use crate::progress::VecProgress;
@@ -54,6 +57,13 @@ where C: RaftTypeConfig
     ///
     /// [`docs::leader_lease`]: `crate::docs::protocol::replication::leader_lease`
     pub(crate) clock_progress: VecProgress<C::NodeId, Option<InstantOf<C>>, Option<InstantOf<C>>, QS>,
+
+    /// The time when the `noop` log is committed.
+    pub(crate) noop_committed_at: Option<InstantOf<C>>,
+
+    /// The number of times the clock acknowledged by a quorum advanced to a time after the `noop`
+    /// log is committed, i.e., the rounds of heartbeats confirming this leader.
+    pub(crate) quorum_acked_heartbeats: u64,
 }
 
 impl<C, QS> Leading<C, QS>
@@ -80,6 +90,8 @@ where
                 ProgressEntry::empty(last_log_id.next_index()),
             ),
             clock_progress: VecProgress::new(quorum_set, learner_ids, None),
+            noop_committed_at: None,
+            quorum_acked_heartbeats: 0,
         }
     }
 
@@ -143,13 +155,61 @@ where
             Err(x) => *x,
         }
     }
+
+    /// Get the greatest log id that a quorum of voters, including the leader itself, has stored.
+    ///
+    /// It is the candidate to commit. The progress keeps the matching log ids of voters sorted in
+    /// descending order and the quorum position is re-calculated when a matching is updated, thus
+    /// it does not sort on every call. Learners do not count.
+    pub(crate) fn quorum_match_index(&self) -> Option<LogIdOf<C>> {
+        *self.progress.granted()
+    }
+
+    /// Build the replication detail of every follower and learner, excluding the leader itself.
+    pub(crate) fn replication_detail(&self) -> BTreeMap<C::NodeId, ReplicationDetail<C>> {
+        let me = self.vote.leader_id().voted_for();
+
+        let mut res = BTreeMap::new();
+
+        for (id, p) in self.progress.iter() {
+            if Some(*id) == me {
+                continue;
+            }
+
+            let phase = if p.inflight.is_sending_snapshot() {
+                ReplicationPhase::Snapshot
+            } else if p.matching.next_index() < p.searching_end {
+                ReplicationPhase::Searching
+            } else {
+                ReplicationPhase::Streaming
+            };
+
+            let last_ack = self.clock_progress.try_get(id).copied().flatten();
+
+            let detail = ReplicationDetail {
+                next_index: p.searching_end,
+                matching: p.matching,
+                phase,
+                inflight: if p.inflight.is_none() { 0 } else { 1 },
+                since_last_ack: last_ack.map(|t| t.elapsed()),
+            };
+
+            res.insert(*id, detail);
+        }
+
+        res
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::engine::testing::UTConfig;
     use crate::leader::Leading;
+    use crate::metrics::ReplicationPhase;
+    use crate::progress::entry::ProgressEntry;
+    use crate::progress::Inflight;
     use crate::progress::Progress;
+    use crate::testing::log_id;
     use crate::type_config::alias::InstantOf;
     use crate::Vote;
 
@@ -196,4 +256,79 @@ mod tests {
         let t = leading.last_quorum_acked_time();
         assert_eq!(Some(t2), t, "n2 and n3 acked");
     }
+
+    #[test]
+    fn test_leading_quorum_match_index() {
+        let leading_with = |voters: Vec<u64>, matching: Vec<(u64, u64)>| {
+            let mut leading =
+                Leading::<UTConfig, Vec<u64>>::new(Vote::new_committed(2, 1), voters, vec![9].into_iter(), None);
+
+            for (id, index) in matching {
+                let _ = leading.progress.update_with(&id, |e| e.matching = Some(log_id(2, 1, index)));
+            }
+            leading
+        };
+
+        // Odd: 2 of 3 voters.
+        let leading = leading_with(vec![1, 2, 3], vec![]);
+        assert_eq!(None, leading.quorum_match_index());
+
+        let leading = leading_with(vec![1, 2, 3], vec![(1, 10)]);
+        assert_eq!(None, leading.quorum_match_index(), "the leader alone is not a quorum");
+
+        let leading = leading_with(vec![1, 2, 3], vec![(1, 10), (2, 5), (3, 7)]);
+        assert_eq!(Some(log_id(2, 1, 7)), leading.quorum_match_index());
+
+        let leading = leading_with(vec![1, 2, 3, 4, 5], vec![(1, 10), (2, 3), (3, 8), (4, 6), (5, 1)]);
+        assert_eq!(Some(log_id(2, 1, 6)), leading.quorum_match_index());
+
+        // Even: 3 of 4 voters.
+        let leading = leading_with(vec![1, 2, 3, 4], vec![(1, 10), (2, 8), (3, 6), (4, 4)]);
+        assert_eq!(Some(log_id(2, 1, 6)), leading.quorum_match_index());
+
+        let leading = leading_with(vec![1, 2, 3, 4], vec![(1, 10), (2, 8)]);
+        assert_eq!(None, leading.quorum_match_index(), "half of the voters is not a quorum");
+
+        // A learner does not count.
+        let leading = leading_with(vec![1, 2, 3, 4], vec![(1, 10), (2, 8), (9, 20)]);
+        assert_eq!(None, leading.quorum_match_index());
+    }
+
+    #[test]
+    fn test_leading_replication_detail() {
+        let mut leading =
+            Leading::<UTConfig, Vec<u64>>::new(Vote::new_committed(2, 1), vec![1, 2, 3], vec![4].into_iter(), None);
+
+        let t2 = InstantOf::<UTConfig>::now();
+        let _ = leading.clock_progress.increase_to(&2, Some(t2));
+
+        *leading.progress.get_mut(&2).unwrap() = ProgressEntry::new(Some(log_id(1, 1, 3)));
+        *leading.progress.get_mut(&3).unwrap() = ProgressEntry::empty(5);
+        *leading.progress.get_mut(&4).unwrap() =
+            ProgressEntry::new(None).with_inflight(Inflight::snapshot(Some(log_id(1, 1, 2))).with_id(1));
+
+        let detail = leading.replication_detail();
+        assert_eq!(
+            vec![2, 3, 4],
+            detail.keys().copied().collect::<Vec<_>>(),
+            "leader is excluded"
+        );
+
+        let d2 = &detail[&2];
+        assert_eq!(4, d2.next_index);
+        assert_eq!(Some(log_id(1, 1, 3)), d2.matching);
+        assert_eq!(ReplicationPhase::Streaming, d2.phase);
+        assert_eq!(0, d2.inflight);
+        assert!(d2.since_last_ack.is_some());
+
+        let d3 = &detail[&3];
+        assert_eq!(5, d3.next_index);
+        assert_eq!(None, d3.matching);
+        assert_eq!(ReplicationPhase::Searching, d3.phase);
+        assert_eq!(None, d3.since_last_ack);
+
+        let d4 = &detail[&4];
+        assert_eq!(ReplicationPhase::Snapshot, d4.phase);
+        assert_eq!(1, d4.inflight);
+    }
 }
@@ -10,6 +10,13 @@ use crate::RaftTypeConfig;
 use crate::Vote;
 
 /// Voting state.
+///
+/// Votes are counted against the quorum set of the effective membership when the election
+/// starts. A candidate does not accept membership logs, which are only accepted from a leader
+/// after this node becomes a follower and the voting is dropped. If the effective membership
+/// changes anyway, the granted votes are re-counted against the latest one with
+/// [`Self::update_quorum_set`], so that the result of an election always complies with the
+/// authoritative config.
 #[derive(Clone, Debug)]
 #[derive(PartialEq, Eq)]
 pub(crate) struct Voting<C, QS>
@@ -27,6 +34,9 @@ where
 
     /// Which nodes have granted the the vote at certain time point.
     progress: VecProgress<C::NodeId, bool, bool, QS>,
+
+    /// Whether any peer has responded to the vote request, no matter granted or not.
+    responded: bool,
 }
 
 impl<C, QS> fmt::Display for Voting<C, QS>
@@ -62,6 +72,7 @@ where
             vote,
             last_log_id,
             progress: VecProgress::new(quorum_set, [], false),
+            responded: false,
         }
     }
 
@@ -73,15 +84,59 @@ where
         &self.progress
     }
 
+    /// Record that a peer has responded to the vote request.
+    pub(crate) fn set_responded(&mut self) {
+        self.responded = true;
+    }
+
+    /// Whether any peer has responded to the vote request.
+    pub(crate) fn is_responded(&self) -> bool {
+        self.responded
+    }
+
     /// Grant the vote by a node.
+    ///
+    /// A grant from a node that is not in the quorum set is ignored.
+    /// It returns whether the vote is granted by a quorum.
     pub(crate) fn grant_by(&mut self, target: &C::NodeId) -> bool {
-        let granted = *self.progress.update(target, true).expect("target not in quorum set");
+        let granted = match self.progress.update(target, true) {
+            Ok(granted) => *granted,
+            Err(_) => {
+                tracing::warn!("node-{} is not in the quorum set, ignore its grant", target);
+                self.is_granted()
+            }
+        };
 
         tracing::info!(voting = debug(&self), "{}", func_name!());
 
         granted
     }
 
+    /// Whether the vote is granted by a quorum.
+    pub(crate) fn is_granted(&self) -> bool {
+        *self.progress.granted()
+    }
+
+    /// Re-count the granted votes against another quorum set, if it is different from the
+    /// current one.
+    ///
+    /// The grants from nodes that are not in the new quorum set are discarded.
+    /// It returns whether the quorum set is changed.
+    pub(crate) fn update_quorum_set(&mut self, quorum_set: QS) -> bool
+    where QS: Clone + PartialEq {
+        if self.progress.quorum_set() == &quorum_set {
+            return false;
+        }
+
+        tracing::info!(
+            "quorum set changed from {:?} to {:?} during voting",
+            self.progress.quorum_set(),
+            quorum_set
+        );
+        self.progress = self.progress.clone().upgrade_quorum_set(quorum_set, &[], false);
+        true
+    }
+
     /// Return the node ids that has granted this vote.
     #[allow(dead_code)]
     pub(crate) fn granters(&self) -> impl Iterator<Item = C::NodeId> + '_ {
@@ -57,6 +57,7 @@ pub(crate) mod timer;
 pub(crate) mod type_config;
 pub(crate) mod utime;
 
+mod digest;
 mod display_ext;
 
 pub mod docs;
@@ -83,6 +84,7 @@ pub use crate::change_members::ChangeMembers;
 pub use crate::config::Config;
 pub use crate::config::ConfigError;
 pub use crate::config::SnapshotPolicy;
+pub use crate::config::StorageFailurePolicy;
 pub use crate::core::ServerState;
 pub use crate::entry::Entry;
 pub use crate::entry::EntryPayload;
@@ -31,6 +31,20 @@ where C: RaftTypeConfig
     ///
     /// A node-id key that is in `nodes` but is not in `configs` is a **learner**.
     nodes: BTreeMap<C::NodeId, C::Node>,
+
+    /// Whether the cluster is sealed, i.e., client writes are rejected.
+    ///
+    /// It is stored in membership so that it is replicated, persisted and included in snapshot
+    /// along with the membership config.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "std::ops::Not::not"))]
+    sealed: bool,
+
+    /// The nodes that are witnesses, which store log metadata only and never become leader.
+    ///
+    /// It is stored in membership so that every node, including a witness after a restart, knows
+    /// which nodes are witnesses. A node stays a witness until it is removed from `nodes`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeSet::is_empty"))]
+    witnesses: BTreeSet<C::NodeId>,
 }
 
 impl<C> From<BTreeMap<C::NodeId, C::Node>> for Membership<C>
@@ -87,7 +101,17 @@ where C: RaftTypeConfig
                 write!(f, "None")?;
             }
         }
-        write!(f, "]}}")?;
+        write!(f, "]")?;
+
+        if !self.witnesses.is_empty() {
+            write!(f, ", witnesses:{:?}", self.witnesses)?;
+        }
+
+        if self.sealed {
+            write!(f, ", sealed")?;
+        }
+
+        write!(f, "}}")?;
         Ok(())
     }
 }
@@ -113,7 +137,12 @@ where C: RaftTypeConfig
         let voter_ids = config.as_joint().ids().collect::<BTreeSet<_>>();
         let nodes = Self::extend_nodes(nodes.into_nodes(), &voter_ids.into_nodes());
 
-        Membership { configs: config, nodes }
+        Membership {
+            configs: config,
+            nodes,
+            sealed: false,
+            witnesses: BTreeSet::new(),
+        }
     }
 
     /// Returns reference to the joint config.
@@ -146,6 +175,24 @@ where C: RaftTypeConfig
     pub fn learner_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
         self.nodes.keys().filter(|x| !self.is_voter(x)).copied()
     }
+
+    /// Returns true if the cluster is sealed, i.e., client writes are rejected.
+    ///
+    /// See [`Raft::seal()`](`crate::Raft::seal`).
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Returns true if the given node is a witness, which stores log metadata only and never
+    /// becomes leader.
+    pub fn is_witness(&self, node_id: &C::NodeId) -> bool {
+        self.witnesses.contains(node_id)
+    }
+
+    /// Returns an Iterator of all witness node ids.
+    pub fn witness_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
+        self.witnesses.iter().copied()
+    }
 }
 
 impl<C> Membership<C>
@@ -171,7 +218,27 @@ where C: RaftTypeConfig
     pub(crate) fn new_unchecked<T>(configs: Vec<BTreeSet<C::NodeId>>, nodes: T) -> Self
     where T: IntoNodes<C::NodeId, C::Node> {
         let nodes = nodes.into_nodes();
-        Membership { configs, nodes }
+        Membership {
+            configs,
+            nodes,
+            sealed: false,
+            witnesses: BTreeSet::new(),
+        }
+    }
+
+    /// Return a new instance with the same config but the `sealed` flag set to `sealed`.
+    pub(crate) fn with_sealed(mut self, sealed: bool) -> Self {
+        self.sealed = sealed;
+        self
+    }
+
+    /// Return a new instance with the same config but the nodes in `witnesses` added as witnesses.
+    ///
+    /// A witness id that is not in `nodes` is dropped.
+    pub(crate) fn with_witnesses(mut self, witnesses: impl IntoIterator<Item = C::NodeId>) -> Self {
+        self.witnesses.extend(witnesses);
+        self.witnesses.retain(|id| self.nodes.contains_key(id));
+        self
     }
 
     /// Extends nodes btreemap with another.
@@ -266,6 +333,8 @@ where C: RaftTypeConfig
         };
 
         Membership::new_unchecked(config, nodes)
+            .with_sealed(self.sealed)
+            .with_witnesses(self.witnesses.iter().copied())
     }
 
     /// Apply a change-membership request and return a new instance.
@@ -326,6 +395,9 @@ where C: RaftTypeConfig
             }
         };
 
+        // A removed node is no longer a witness.
+        let new_membership = new_membership.with_witnesses([]);
+
         tracing::debug!(new_membership = display(&new_membership), "new membership");
 
         new_membership.ensure_valid()?;
@@ -360,6 +432,8 @@ mod tests {
         let m = Membership::<UTConfig> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>()},
+            sealed: false,
+            witnesses: btreeset! {},
         };
         assert_eq!(Err(2), m.ensure_voter_nodes());
         Ok(())
@@ -370,6 +444,8 @@ mod tests {
         let m = || Membership::<UTConfig> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>(),2=>(),3=>()},
+            sealed: false,
+            witnesses: btreeset! {},
         };
 
         // Add: no such learner
@@ -387,7 +463,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,3}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -399,7 +477,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,5}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -411,7 +491,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -429,7 +511,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -441,7 +525,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -452,12 +538,16 @@ mod tests {
             let mem = Membership::<UTConfig> {
                 configs: vec![btreeset! {1,2}, btreeset! {2}],
                 nodes: btreemap! {1=>(),2=>(),3=>()},
+                sealed: false,
+                witnesses: btreeset! {},
             };
             let res = mem.change(ChangeMembers::RemoveVoters(btreeset! {1}), false);
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {2}],
-                    nodes: btreemap! {2=>(),3=>()}
+                    nodes: btreemap! {2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -469,7 +559,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -481,7 +573,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -493,7 +587,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -505,7 +601,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -516,13 +614,17 @@ mod tests {
             let m = || Membership::<UTConfig<u64>> {
                 configs: vec![btreeset! {1,2}],
                 nodes: btreemap! {1=>1,2=>2,3=>3},
+                sealed: false,
+                witnesses: btreeset! {},
             };
 
             let res = m().change(ChangeMembers::SetNodes(btreemap! {3=>30, 4=>40}), false);
             assert_eq!(
                 Ok(Membership::<UTConfig<u64>> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40}
+                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -543,7 +645,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>()}
+                    nodes: btreemap! {1=>(),2=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -555,7 +659,9 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),4=>()}
+                    nodes: btreemap! {1=>(),2=>(),4=>()},
+                    sealed: false,
+                    witnesses: btreeset! {},
                 }),
                 res
             );
@@ -261,6 +261,91 @@ fn test_membership_next_coherent() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_membership_sealed() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], Some(btreeset! {3}));
+    assert!(!m.is_sealed());
+
+    let m = m.with_sealed(true);
+    assert!(m.is_sealed());
+    assert_eq!("{voters:[{1:(),2:()}], learners:[3:()], sealed}", m.to_string());
+
+    // The sealed flag is kept through membership changes.
+    let m = m.change(ChangeMembers::AddVoterIds(btreeset! {3}), false)?;
+    assert_eq!(&vec![btreeset! {1,2}, btreeset! {1,2,3}], m.get_joint_config());
+    assert!(m.is_sealed());
+
+    let m = m.with_sealed(false);
+    assert!(!m.is_sealed());
+    assert_eq!("{voters:[{1:(),2:()},{1:(),2:(),3:()}], learners:[]}", m.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_membership_witnesses() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new(vec![btreeset! {1,2,3}], Some(btreeset! {4}));
+    assert!(!m.is_witness(&3));
+
+    // A node not in the membership can not be a witness.
+    let m = m.with_witnesses([3, 5]);
+    assert!(m.is_witness(&3));
+    assert_eq!(vec![3], m.witness_ids().collect::<Vec<_>>());
+    assert_eq!(
+        "{voters:[{1:(),2:(),3:()}], learners:[4:()], witnesses:{3}}",
+        m.to_string()
+    );
+
+    // A witness removed from voters but retained as a learner is still a witness.
+    let m = m.change(ChangeMembers::RemoveVoters(btreeset! {3}), true)?;
+    let m = m.change(ChangeMembers::RemoveVoters(btreeset! {3}), true)?;
+    assert_eq!(&vec![btreeset! {1,2}], m.get_joint_config());
+    assert!(m.is_witness(&3));
+
+    // A removed node is no longer a witness.
+    let m = m.change(ChangeMembers::RemoveNodes(btreeset! {3}), true)?;
+    assert!(!m.is_witness(&3));
+
+    Ok(())
+}
+
+#[test]
+fn test_membership_witnesses_display_eq() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], Some(btreeset! {3}));
+
+    // Without witnesses, a membership is displayed as before.
+    assert_eq!("{voters:[{1:(),2:()}], learners:[3:()]}", m.to_string());
+    assert_eq!(m, m.clone().with_witnesses([]));
+
+    // Witnesses are part of the config: the same nodes with different witnesses are not equal.
+    assert_ne!(m, m.clone().with_witnesses([2]));
+
+    Ok(())
+}
+
+/// A membership serialized before witnesses are introduced is deserialized without witnesses,
+/// and a membership without witnesses is serialized the same way as before.
+#[cfg(feature = "serde")]
+#[test]
+fn test_membership_witnesses_serde_compat() -> anyhow::Result<()> {
+    let old = r#"{"configs":[[1,2]],"nodes":{"1":null,"2":null,"3":null}}"#;
+
+    let m: Membership<UTConfig> = serde_json::from_str(old)?;
+    assert_eq!(
+        Membership::<UTConfig>::new(vec![btreeset! {1,2}], Some(btreeset! {3})),
+        m
+    );
+    assert_eq!(0, m.witness_ids().count());
+    assert_eq!(old, serde_json::to_string(&m)?);
+
+    let m = m.with_witnesses([2]);
+    let got: Membership<UTConfig> = serde_json::from_str(&serde_json::to_string(&m)?)?;
+    assert_eq!(m, got);
+    assert!(got.is_witness(&2));
+
+    Ok(())
+}
+
 #[test]
 fn test_membership_next_coherent_with_nodes() -> anyhow::Result<()> {
     let node = |s: &str| TestNode {
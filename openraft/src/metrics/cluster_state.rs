@@ -0,0 +1,51 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::StoredMembership;
+
+/// A consistent view of the membership and the log progress of a Raft node.
+///
+/// It is returned by [`Raft::get_cluster_state()`](crate::Raft::get_cluster_state). All fields
+/// are read at the same instant in a single run of `RaftCore`, unlike the fields of
+/// [`RaftMetrics`](crate::RaftMetrics), which may be observed after several changes are merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ClusterState<C: RaftTypeConfig> {
+    /// The last committed membership config.
+    ///
+    /// Its log id is never greater than [`Self::committed`].
+    pub membership: Arc<StoredMembership<C>>,
+
+    /// The id of the last committed log known by this node.
+    pub committed: Option<LogId<C::NodeId>>,
+
+    /// The id of the last log applied to the state machine.
+    ///
+    /// It is never greater than [`Self::committed`].
+    pub last_applied: Option<LogId<C::NodeId>>,
+
+    /// The current term of this node.
+    pub current_term: u64,
+
+    /// The current leader, if known.
+    pub current_leader: Option<C::NodeId>,
+}
+
+impl<C> fmt::Display for ClusterState<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{membership:{}, committed:{}, last_applied:{}, current_term:{}, current_leader:{}}}",
+            self.membership,
+            self.committed.display(),
+            self.last_applied.display(),
+            self.current_term,
+            self.current_leader.display()
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// The distribution of the latency of recent client writes on the leader, from being appended
+/// to the leader's log to being committed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CommitLatency {
+    /// The median latency of the recent writes.
+    pub p50: Duration,
+
+    /// The 99th percentile latency of the recent writes.
+    pub p99: Duration,
+
+    /// The max latency of the recent writes.
+    pub max: Duration,
+
+    /// The number of recent writes these values are computed from.
+    pub samples: u64,
+}
+
+impl fmt::Display for CommitLatency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{p50:{:?}, p99:{:?}, max:{:?}, samples:{}}}",
+            self.p50, self.p99, self.max, self.samples
+        )
+    }
+}
+
+/// Keeps the commit latency of the most recent client writes and the [`CommitLatency`] of them.
+#[derive(Clone, Debug)]
+pub(crate) struct CommitLatencyWindow {
+    samples: VecDeque<Duration>,
+    latency: Option<CommitLatency>,
+}
+
+impl CommitLatencyWindow {
+    /// The number of the most recent samples to keep.
+    pub(crate) const SIZE: usize = 256;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(Self::SIZE),
+            latency: None,
+        }
+    }
+
+    /// Add latency samples, evict the oldest ones if the window is full, and update the
+    /// [`CommitLatency`].
+    pub(crate) fn extend(&mut self, latencies: impl IntoIterator<Item = Duration>) {
+        let mut added = false;
+
+        for latency in latencies {
+            if self.samples.len() == Self::SIZE {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(latency);
+            added = true;
+        }
+
+        if !added {
+            return;
+        }
+
+        let mut sorted = self.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort();
+
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100) - 1];
+
+        self.latency = Some(CommitLatency {
+            p50: percentile(50),
+            p99: percentile(99),
+            max: sorted[sorted.len() - 1],
+            samples: sorted.len() as u64,
+        });
+    }
+
+    /// Return the distribution of the recent samples, or `None` if there is no sample.
+    pub(crate) fn latency(&self) -> Option<CommitLatency> {
+        self.latency
+    }
+}
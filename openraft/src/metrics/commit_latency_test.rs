@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::metrics::commit_latency::CommitLatencyWindow;
+use crate::metrics::CommitLatency;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn test_commit_latency_window() -> anyhow::Result<()> {
+    let mut w = CommitLatencyWindow::new();
+    assert_eq!(None, w.latency());
+
+    w.extend([]);
+    assert_eq!(None, w.latency());
+
+    w.extend((1..=100).map(ms));
+    assert_eq!(
+        Some(CommitLatency {
+            p50: ms(50),
+            p99: ms(99),
+            max: ms(100),
+            samples: 100,
+        }),
+        w.latency()
+    );
+
+    w.extend([ms(1)]);
+    assert_eq!(Some(ms(50)), w.latency().map(|x| x.p50));
+    assert_eq!(Some(101), w.latency().map(|x| x.samples));
+
+    // Fill the window with smaller samples, the old ones are evicted.
+    w.extend((0..CommitLatencyWindow::SIZE).map(|_| ms(2)));
+    assert_eq!(
+        Some(CommitLatency {
+            p50: ms(2),
+            p99: ms(2),
+            max: ms(2),
+            samples: CommitLatencyWindow::SIZE as u64,
+        }),
+        w.latency()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// The log state of a follower or learner, reported to the leader in every successful
+/// `AppendEntries` response, including heartbeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct FollowerLogState<C: RaftTypeConfig> {
+    /// The last log id committed on the target.
+    pub committed: Option<LogId<C::NodeId>>,
+
+    /// The last log id on the target.
+    pub last_log_id: Option<LogId<C::NodeId>>,
+}
+
+impl<C> fmt::Display for FollowerLogState<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{committed:{}, last_log_id:{}}}",
+            self.committed.display(),
+            self.last_log_id.display()
+        )
+    }
+}
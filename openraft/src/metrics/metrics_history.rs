@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use crate::RaftMetrics;
+use crate::RaftTypeConfig;
+
+/// A [`RaftMetrics`] sampled at a point in time.
+///
+/// It is returned by [`Raft::get_metrics_history()`](crate::Raft::get_metrics_history).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct MetricsSample<C: RaftTypeConfig> {
+    /// The wall clock time when the sample is taken.
+    pub time: SystemTime,
+
+    pub metrics: RaftMetrics<C>,
+}
+
+/// The most recent metrics samples of a node, the oldest one is evicted when it is full.
+pub(crate) struct MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    capacity: usize,
+    samples: VecDeque<MetricsSample<C>>,
+}
+
+impl<C> MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn push(&mut self, sample: MetricsSample<C>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Return the samples from the oldest to the newest.
+    pub(crate) fn samples(&self) -> Vec<MetricsSample<C>> {
+        self.samples.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    use super::MetricsHistory;
+    use super::MetricsSample;
+    use crate::engine::testing::UTConfig;
+    use crate::RaftMetrics;
+
+    fn sample(secs: u64) -> MetricsSample<UTConfig> {
+        MetricsSample {
+            time: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            metrics: RaftMetrics::new_initial(0),
+        }
+    }
+
+    #[test]
+    fn test_metrics_history_push() {
+        let mut h = MetricsHistory::<UTConfig>::new(0);
+        h.push(sample(1));
+        assert_eq!(Vec::<MetricsSample<UTConfig>>::new(), h.samples());
+
+        let mut h = MetricsHistory::<UTConfig>::new(2);
+        h.push(sample(1));
+        assert_eq!(vec![sample(1)], h.samples());
+
+        h.push(sample(2));
+        h.push(sample(3));
+        assert_eq!(vec![sample(2), sample(3)], h.samples());
+    }
+}
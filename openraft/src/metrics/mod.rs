@@ -27,20 +27,47 @@
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
 
+mod cluster_state;
+mod commit_latency;
+mod follower_log_state;
 mod metric;
+mod metrics_history;
+mod pending_writes;
 mod raft_metrics;
+mod replication_detail;
+mod rpc_latency;
+mod shutdown_report;
+mod vote_log;
 mod wait;
 
+#[cfg(test)] mod commit_latency_test;
 mod metric_display;
+#[cfg(test)] mod rpc_latency_test;
 mod wait_condition;
 #[cfg(test)] mod wait_test;
 
 use std::collections::BTreeMap;
 
+pub use cluster_state::ClusterState;
+pub use commit_latency::CommitLatency;
+pub(crate) use commit_latency::CommitLatencyWindow;
+pub use follower_log_state::FollowerLogState;
 pub use metric::Metric;
+pub(crate) use metrics_history::MetricsHistory;
+pub use metrics_history::MetricsSample;
+pub use pending_writes::PendingWrites;
+pub(crate) use raft_metrics::DisplayLabels;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
+pub use replication_detail::ReplicationDetail;
+pub use replication_detail::ReplicationPhase;
+pub(crate) use rpc_latency::LatencyWindow;
+pub use rpc_latency::RPCLatency;
+pub use shutdown_report::ShutdownReport;
+pub use vote_log::VoteDenyReason;
+pub(crate) use vote_log::VoteLog;
+pub use vote_log::VoteRecord;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
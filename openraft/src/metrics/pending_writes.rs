@@ -0,0 +1,59 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::display_ext::DisplayOptionExt;
+
+/// The client write requests on a Raft node that are waiting for their entries to be committed
+/// and applied, for debugging a stalled write pipeline.
+///
+/// It is returned by [`Raft::get_pending_writes()`](crate::Raft::get_pending_writes).
+///
+/// Writes piling up in [`Self::awaiting_commit`] indicate a commit stall, e.g., the leader lost
+/// the quorum; writes piling up in [`Self::awaiting_apply`] indicate an apply stall, e.g., a slow
+/// state machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PendingWrites {
+    /// The number of client writes whose log entries are not yet committed.
+    ///
+    /// Entries no client waits for, such as the blank entry a leader appends, are not counted.
+    pub awaiting_commit: u64,
+
+    /// The number of client writes whose log entries are committed but not yet applied.
+    pub awaiting_apply: u64,
+
+    /// The time since the oldest pending write was submitted.
+    pub oldest_age: Option<Duration>,
+
+    /// The log index of the first pending write.
+    pub first_index: Option<u64>,
+
+    /// The log index of the last pending write.
+    pub last_index: Option<u64>,
+}
+
+impl PendingWrites {
+    /// The total number of pending writes.
+    pub fn len(&self) -> u64 {
+        self.awaiting_commit + self.awaiting_apply
+    }
+
+    /// Return `true` if there is no pending write.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for PendingWrites {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{awaiting_commit:{}, awaiting_apply:{}, oldest_age:{} ms, index:[{}, {}]}}",
+            self.awaiting_commit,
+            self.awaiting_apply,
+            self.oldest_age.map(|d| d.as_millis()).display(),
+            self.first_index.display(),
+            self.last_index.display(),
+        )
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -5,6 +6,9 @@ use crate::core::ServerState;
 use crate::display_ext::DisplayOption;
 use crate::display_ext::DisplayOptionExt;
 use crate::error::Fatal;
+use crate::metrics::CommitLatency;
+use crate::metrics::FollowerLogState;
+use crate::metrics::RPCLatency;
 use crate::metrics::ReplicationMetrics;
 use crate::LogId;
 use crate::RaftTypeConfig;
@@ -20,6 +24,9 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The ID of the Raft node.
     pub id: C::NodeId,
 
+    /// The static labels of this node, set with [`Config::labels`](`crate::Config::labels`).
+    pub labels: Arc<BTreeMap<String, String>>,
+
     // ---
     // --- data ---
     // ---
@@ -35,6 +42,12 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The last log index has been applied to this Raft node's state machine.
     pub last_applied: Option<LogId<C::NodeId>>,
 
+    /// The number of committed log entries that are not yet applied to the state machine.
+    ///
+    /// It grows when entries are committed faster than they are applied, for example when
+    /// [`Config::apply_rate_limit`](`crate::Config::apply_rate_limit`) is set.
+    pub apply_backlog: u64,
+
     /// The id of the last log included in snapshot.
     /// If there is no snapshot, it is (0,0).
     pub snapshot: Option<LogId<C::NodeId>>,
@@ -69,6 +82,36 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// being partitioned from the cluster.
     pub millis_since_quorum_ack: Option<u64>,
 
+    /// For a follower or learner, it is the elapsed time in milliseconds since an `AppendEntries`
+    /// request from the leader was last accepted.
+    ///
+    /// It is `None` if this node is leader, or has never heard from a leader.
+    ///
+    /// A value greater than the election timeout strongly suggests this node is partitioned from
+    /// the leader.
+    pub millis_since_leader_contact: Option<u64>,
+
+    /// Whether this node runs in degraded mode because its storage keeps failing.
+    ///
+    /// A degraded node refuses client writes until storage recovers.
+    /// See [`StorageFailurePolicy::Degrade`](`crate::StorageFailurePolicy::Degrade`).
+    pub storage_degraded: bool,
+
+    /// Whether this node runs in degraded mode because its term is too far ahead of its last log
+    /// term at startup.
+    ///
+    /// A degraded node refuses to elect itself until it is cleared.
+    /// See [`Config::max_term_log_gap`](`crate::Config::max_term_log_gap`).
+    pub term_gap_degraded: bool,
+
+    /// Whether this node has no voter in its membership config.
+    ///
+    /// Such a node can neither elect a leader nor commit a log: it refuses client writes with
+    /// [`NoConfiguration`](`crate::error::NoConfiguration`) until it is initialized with
+    /// [`Raft::initialize`](`crate::Raft::initialize`) or receives a membership config from a
+    /// leader.
+    pub no_configuration: bool,
+
     /// The current membership config of the cluster.
     pub membership_config: Arc<StoredMembership<C>>,
 
@@ -77,6 +120,53 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     // ---
     /// The replication states. It is Some() only when this node is leader.
     pub replication: Option<ReplicationMetrics<C::NodeId>>,
+
+    /// The round-trip latency of `AppendEntries` and `Vote` RPCs sent to each peer.
+    ///
+    /// RPCs are sent when this node is a leader or a candidate. The latency of a peer is kept
+    /// until it is updated by the next response from it, and is removed when the peer is removed
+    /// from the membership.
+    pub rpc_latency: BTreeMap<C::NodeId, RPCLatency>,
+
+    /// The log state of each follower and learner, as reported in its most recent `AppendEntries`
+    /// response, including heartbeats. It is empty when this node is not a leader.
+    ///
+    /// A target reports it only if [`Config::report_log_state`] is enabled on it.
+    ///
+    /// [`Config::report_log_state`]: crate::Config::report_log_state
+    pub follower_log_state: BTreeMap<C::NodeId, FollowerLogState<C>>,
+
+    /// The number of times replication to each follower or learner became stalled, i.e., it
+    /// rejected [`Config::append_rejection_threshold`] AppendEntries in a row.
+    ///
+    /// Only targets that have stalled at least once are included. It is empty when this node is
+    /// not a leader.
+    ///
+    /// [`Config::append_rejection_threshold`]: crate::Config::append_rejection_threshold
+    pub replication_stalls: BTreeMap<C::NodeId, u64>,
+
+    /// The peers whose timing config hash, received in AppendEntries requests, differs from this
+    /// node's, mapped to the peer's hash.
+    ///
+    /// It is only filled if [`Config::exchange_config_hash`] is enabled on the peers.
+    ///
+    /// [`Config::exchange_config_hash`]: crate::Config::exchange_config_hash
+    pub config_mismatches: BTreeMap<C::NodeId, u64>,
+
+    /// The distribution of the latency of the recent client writes on this leader, from being
+    /// appended to the leader's log to being committed.
+    ///
+    /// It is `None` when this node is not a leader, or no client write is committed yet in the
+    /// current leadership.
+    pub commit_latency: Option<CommitLatency>,
+
+    /// The rate in bytes per second this node sends snapshot data to all targets, sampled every
+    /// tick.
+    ///
+    /// It is `0` if no snapshot is being sent. Only snapshots sent in chunks, i.e., by the default
+    /// [`RaftNetwork::full_snapshot()`](`crate::network::RaftNetwork::full_snapshot`), are
+    /// measured.
+    pub snapshot_send_rate: u64,
 }
 
 impl<C> fmt::Display for RaftMetrics<C>
@@ -87,30 +177,62 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "id:{}, {:?}, term:{}, vote:{}, last_log:{}, last_applied:{}, leader:{}(since_last_ack:{} ms)",
+            "id:{}, {:?}, term:{}, vote:{}, last_log:{}, last_applied:{}, apply_backlog:{}, leader:{}(since_last_ack:{} ms, since_contact:{} ms)",
             self.id,
             self.state,
             self.current_term,
             self.vote,
             DisplayOption(&self.last_log_index),
             DisplayOption(&self.last_applied),
+            self.apply_backlog,
             DisplayOption(&self.current_leader),
             DisplayOption(&self.millis_since_quorum_ack),
+            DisplayOption(&self.millis_since_leader_contact),
         )?;
 
         write!(f, ", ")?;
         write!(
             f,
-            "membership:{}, snapshot:{}, purged:{}, replication:{{{}}}",
+            "membership:{}, snapshot:{}, purged:{}, storage_degraded:{}, term_gap_degraded:{}, replication:{{{}}}",
             self.membership_config,
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
+            self.storage_degraded,
+            self.term_gap_degraded,
             self.replication
                 .as_ref()
                 .map(|x| { x.iter().map(|(k, v)| format!("{}:{}", k, DisplayOption(v))).collect::<Vec<_>>().join(",") })
                 .unwrap_or_default(),
         )?;
 
+        write!(
+            f,
+            ", rpc_latency:{{{}}}",
+            self.rpc_latency.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<_>>().join(",")
+        )?;
+
+        write!(
+            f,
+            ", follower_log_state:{{{}}}",
+            self.follower_log_state.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<_>>().join(",")
+        )?;
+
+        write!(
+            f,
+            ", replication_stalls:{{{}}}",
+            self.replication_stalls.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<_>>().join(",")
+        )?;
+
+        write!(
+            f,
+            ", config_mismatches:{{{}}}",
+            self.config_mismatches.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<_>>().join(",")
+        )?;
+
+        write!(f, ", commit_latency:{}", DisplayOption(&self.commit_latency))?;
+
+        write!(f, ", labels:{{{}}}", DisplayLabels(&self.labels))?;
+
         write!(f, "}}")?;
         Ok(())
     }
@@ -123,19 +245,31 @@ where C: RaftTypeConfig
         Self {
             running_state: Ok(()),
             id,
+            labels: Arc::new(BTreeMap::new()),
 
             current_term: 0,
             vote: Vote::default(),
             last_log_index: None,
             last_applied: None,
+            apply_backlog: 0,
             snapshot: None,
             purged: None,
 
             state: ServerState::Follower,
             current_leader: None,
             millis_since_quorum_ack: None,
+            millis_since_leader_contact: None,
+            storage_degraded: false,
+            term_gap_degraded: false,
+            no_configuration: true,
             membership_config: Arc::new(StoredMembership::default()),
             replication: None,
+            rpc_latency: BTreeMap::new(),
+            follower_log_state: BTreeMap::new(),
+            replication_stalls: BTreeMap::new(),
+            config_mismatches: BTreeMap::new(),
+            commit_latency: None,
+            snapshot_send_rate: 0,
         }
     }
 }
@@ -164,6 +298,12 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// being partitioned from the cluster.
     pub millis_since_quorum_ack: Option<u64>,
 
+    /// For a follower or learner, it is the elapsed time in milliseconds since an `AppendEntries`
+    /// request from the leader was last accepted.
+    ///
+    /// See [`RaftMetrics::millis_since_leader_contact`].
+    pub millis_since_leader_contact: Option<u64>,
+
     pub replication: Option<ReplicationMetrics<C::NodeId>>,
 }
 
@@ -175,12 +315,13 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}, quorum_acked(leader):{} ms before, replication:{{{}}}",
+            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}, quorum_acked(leader):{} ms before, leader_contact:{} ms before, replication:{{{}}}",
             DisplayOption(&self.last_log),
             DisplayOption(&self.last_applied),
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
             self.millis_since_quorum_ack.display(),
+            self.millis_since_leader_contact.display(),
             self.replication
                 .as_ref()
                 .map(|x| { x.iter().map(|(k, v)| format!("{}:{}", k, DisplayOption(v))).collect::<Vec<_>>().join(",") })
@@ -197,6 +338,7 @@ where C: RaftTypeConfig
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct RaftServerMetrics<C: RaftTypeConfig> {
     pub id: C::NodeId,
+    pub labels: Arc<BTreeMap<String, String>>,
     pub vote: Vote<C::NodeId>,
     pub state: ServerState,
     pub current_leader: Option<C::NodeId>,
@@ -212,15 +354,31 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "id:{}, {:?}, vote:{}, leader:{}, membership:{}",
+            "id:{}, {:?}, vote:{}, leader:{}, membership:{}, labels:{{{}}}",
             self.id,
             self.state,
             self.vote,
             DisplayOption(&self.current_leader),
             self.membership_config,
+            DisplayLabels(&self.labels),
         )?;
 
         write!(f, "}}")?;
         Ok(())
     }
 }
+
+/// Display node labels as `key=value` pairs separated by `,`.
+pub(crate) struct DisplayLabels<'a>(pub(crate) &'a BTreeMap<String, String>);
+
+impl<'a> fmt::Display for DisplayLabels<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (k, v)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", k, v)?;
+        }
+        Ok(())
+    }
+}
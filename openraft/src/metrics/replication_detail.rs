@@ -0,0 +1,72 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// The phase of the replication to a follower or learner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ReplicationPhase {
+    /// The leader is searching for the last log matching the leader's log on the target.
+    Searching,
+
+    /// The matching log is found and logs following it are being replicated.
+    Streaming,
+
+    /// A snapshot is being replicated.
+    Snapshot,
+}
+
+impl fmt::Display for ReplicationPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationPhase::Searching => write!(f, "Searching"),
+            ReplicationPhase::Streaming => write!(f, "Streaming"),
+            ReplicationPhase::Snapshot => write!(f, "Snapshot"),
+        }
+    }
+}
+
+/// Detailed replication state of a follower or learner on the leader, for debugging.
+///
+/// It is returned by [`Raft::get_replication_detail()`](crate::Raft::get_replication_detail).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReplicationDetail<C: RaftTypeConfig> {
+    /// The index of the next log to send to the target.
+    ///
+    /// While [`ReplicationPhase::Searching`], it is the upper bound of the search.
+    pub next_index: u64,
+
+    /// The id of the last log that is known to match the leader's log on the target.
+    pub matching: Option<LogId<C::NodeId>>,
+
+    /// The current replication phase.
+    pub phase: ReplicationPhase,
+
+    /// The number of replication requests sent to the target and waiting for a response.
+    pub inflight: u64,
+
+    /// Time elapsed since the target acknowledged the leader the last time.
+    ///
+    /// It is `None` if the target has never acknowledged this leader.
+    pub since_last_ack: Option<Duration>,
+}
+
+impl<C> fmt::Display for ReplicationDetail<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{next_index:{}, matching:{}, phase:{}, inflight:{}, since_last_ack:{:?}}}",
+            self.next_index,
+            self.matching.display(),
+            self.phase,
+            self.inflight,
+            self.since_last_ack
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// Round-trip latency of the RPCs sent to a peer, i.e., `AppendEntries` and `Vote`.
+///
+/// Only the RPCs that receive a response are measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RPCLatency {
+    /// The average latency of the recent RPCs.
+    pub average: Duration,
+
+    /// The max latency of the recent RPCs.
+    pub max: Duration,
+}
+
+impl fmt::Display for RPCLatency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{avg:{:?}, max:{:?}}}", self.average, self.max)
+    }
+}
+
+/// Keeps the latency of the most recent RPCs sent to a peer and the [`RPCLatency`] of them.
+#[derive(Clone, Debug)]
+pub(crate) struct LatencyWindow {
+    samples: VecDeque<Duration>,
+    sum: Duration,
+    latency: RPCLatency,
+}
+
+impl LatencyWindow {
+    /// The number of the most recent samples to keep.
+    pub(crate) const SIZE: usize = 32;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(Self::SIZE),
+            sum: Duration::default(),
+            latency: RPCLatency::default(),
+        }
+    }
+
+    /// Add a latency sample and evict the oldest one if the window is full.
+    pub(crate) fn push(&mut self, latency: Duration) {
+        if self.samples.len() == Self::SIZE {
+            let oldest = self.samples.pop_front().unwrap();
+            self.sum -= oldest;
+        }
+
+        self.samples.push_back(latency);
+        self.sum += latency;
+
+        self.latency = RPCLatency {
+            average: self.sum / self.samples.len() as u32,
+            max: self.samples.iter().max().copied().unwrap_or_default(),
+        };
+    }
+
+    pub(crate) fn latency(&self) -> RPCLatency {
+        self.latency
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use crate::metrics::rpc_latency::LatencyWindow;
+use crate::metrics::RPCLatency;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn test_latency_window() -> anyhow::Result<()> {
+    let mut w = LatencyWindow::new();
+    assert_eq!(RPCLatency::default(), w.latency());
+
+    w.push(ms(10));
+    w.push(ms(30));
+    assert_eq!(
+        RPCLatency {
+            average: ms(20),
+            max: ms(30),
+        },
+        w.latency()
+    );
+
+    // Fill the window with smaller samples, the old ones are evicted.
+    for _ in 0..LatencyWindow::SIZE {
+        w.push(ms(2));
+    }
+    assert_eq!(
+        RPCLatency {
+            average: ms(2),
+            max: ms(2),
+        },
+        w.latency()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::metrics::PendingWrites;
+use crate::RaftTypeConfig;
+
+/// The operations a Raft node has not finished when it is prepared for shutdown.
+///
+/// It is returned by [`Raft::prepare_shutdown()`](crate::Raft::prepare_shutdown). Every field is
+/// empty if all in-flight operations settled before the timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ShutdownReport<C: RaftTypeConfig> {
+    /// The client writes whose entries are not yet committed or applied.
+    pub pending_writes: PendingWrites,
+
+    /// The replication targets that have not yet received all the logs of this leader.
+    pub replicating: BTreeSet<C::NodeId>,
+
+    /// The replication targets a snapshot is being sent to.
+    pub sending_snapshot: BTreeSet<C::NodeId>,
+
+    /// Whether this node is building a snapshot.
+    pub building_snapshot: bool,
+}
+
+impl<C> ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    /// Return `true` if there is no unfinished operation.
+    pub fn is_settled(&self) -> bool {
+        self.pending_writes.is_empty()
+            && self.replicating.is_empty()
+            && self.sending_snapshot.is_empty()
+            && !self.building_snapshot
+    }
+}
+
+impl<C> fmt::Display for ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{pending_writes:{}, replicating:{:?}, sending_snapshot:{:?}, building_snapshot:{}}}",
+            self.pending_writes, self.replicating, self.sending_snapshot, self.building_snapshot
+        )
+    }
+}
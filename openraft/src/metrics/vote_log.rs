@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::SystemTime;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// The reason a vote request is denied by this node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum VoteDenyReason<C: RaftTypeConfig> {
+    /// The lease of the leader this node knows has not yet expired.
+    LeaderLease,
+
+    /// The candidate's last log id is smaller than this node's `last_log_id`.
+    StaleLog { last_log_id: Option<LogId<C::NodeId>> },
+
+    /// The candidate's term is lower than this node's `vote`.
+    LowerTerm { vote: Vote<C::NodeId> },
+
+    /// This node has already voted, as `vote`, in the candidate's term.
+    AlreadyVoted { vote: Vote<C::NodeId> },
+
+    /// The candidate's term exceeds this node's term by more than `Config::max_term_jump`.
+    TermJump { vote: Vote<C::NodeId> },
+
+    /// The candidate's log is as up to date as this node's, and the election tiebreaker prefers
+    /// `preferred`, i.e., this node, over the candidate.
+    Tiebreak { preferred: C::NodeId },
+}
+
+impl<C> fmt::Display for VoteDenyReason<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoteDenyReason::LeaderLease => write!(f, "LeaderLease"),
+            VoteDenyReason::StaleLog { last_log_id } => write!(f, "StaleLog(local: {})", last_log_id.display()),
+            VoteDenyReason::LowerTerm { vote } => write!(f, "LowerTerm(local: {})", vote),
+            VoteDenyReason::AlreadyVoted { vote } => write!(f, "AlreadyVoted(local: {})", vote),
+            VoteDenyReason::TermJump { vote } => write!(f, "TermJump(local: {})", vote),
+            VoteDenyReason::Tiebreak { preferred } => write!(f, "Tiebreak(preferred: {})", preferred),
+        }
+    }
+}
+
+/// The decision this node made on a vote request.
+///
+/// They are returned by [`Raft::get_vote_log()`](crate::Raft::get_vote_log).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct VoteRecord<C: RaftTypeConfig> {
+    /// The wall clock time when the vote request is handled.
+    pub time: SystemTime,
+
+    /// The term the candidate requested a vote in.
+    pub term: u64,
+
+    /// The candidate that sent the vote request.
+    pub candidate: C::NodeId,
+
+    /// The last log id of the candidate.
+    pub candidate_last_log_id: Option<LogId<C::NodeId>>,
+
+    /// Why the vote is denied, `None` if it is granted.
+    pub denied: Option<VoteDenyReason<C>>,
+}
+
+impl<C> VoteRecord<C>
+where C: RaftTypeConfig
+{
+    /// Returns `true` if the vote is granted to the candidate.
+    pub fn is_granted(&self) -> bool {
+        self.denied.is_none()
+    }
+}
+
+impl<C> fmt::Display for VoteRecord<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{term: {}, candidate: {}, candidate_last_log_id: {}, ",
+            self.term,
+            self.candidate,
+            self.candidate_last_log_id.display()
+        )?;
+
+        match &self.denied {
+            None => write!(f, "granted}}"),
+            Some(reason) => write!(f, "denied: {}}}", reason),
+        }
+    }
+}
+
+/// The most recent vote decisions of a node, the oldest one is evicted when it is full.
+#[derive(Debug)]
+pub(crate) struct VoteLog<C>
+where C: RaftTypeConfig
+{
+    capacity: usize,
+    records: VecDeque<VoteRecord<C>>,
+}
+
+impl<C> VoteLog<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: VoteRecord<C>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// Return the records from the oldest to the newest.
+    pub(crate) fn records(&self) -> Vec<VoteRecord<C>> {
+        self.records.iter().cloned().collect()
+    }
+}
@@ -251,19 +251,31 @@ where C: RaftTypeConfig {
     let init = RaftMetrics {
         running_state: Ok(()),
         id: NodeIdOf::<C>::default(),
+        labels: Default::default(),
         state: ServerState::Learner,
         current_term: 0,
         vote: Vote::default(),
         last_log_index: None,
         last_applied: None,
+        apply_backlog: 0,
         purged: None,
 
         current_leader: None,
         millis_since_quorum_ack: None,
+        millis_since_leader_contact: None,
+        storage_degraded: false,
+        term_gap_degraded: false,
+        no_configuration: true,
         membership_config: Arc::new(StoredMembership::new(None, Membership::new(vec![btreeset! {}], None))),
 
         snapshot: None,
         replication: None,
+        rpc_latency: Default::default(),
+        follower_log_state: Default::default(),
+        replication_stalls: Default::default(),
+        config_mismatches: Default::default(),
+        commit_latency: None,
+        snapshot_send_rate: 0,
     };
     let (tx, rx) = watch::channel(init.clone());
     let w = Wait {
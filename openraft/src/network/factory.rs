@@ -30,4 +30,20 @@ where C: RaftTypeConfig
     /// The method is intentionally async to give the implementation a chance to use asynchronous
     /// sync primitives to serialize access to the common internal object, if needed.
     async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network;
+
+    /// Register the address of a node that is added to the effective membership, or whose
+    /// [`Node`] is changed, e.g., by [`ChangeMembers::AddNodes`] or [`ChangeMembers::SetNodes`].
+    ///
+    /// It is called on every Raft node, including followers and learners, before any client is
+    /// created for `target` with the new `node`. It is also called for every node in the
+    /// membership when a Raft node starts. This node itself is not registered.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// [`Node`]: crate::Node
+    /// [`ChangeMembers::AddNodes`]: crate::ChangeMembers::AddNodes
+    /// [`ChangeMembers::SetNodes`]: crate::ChangeMembers::SetNodes
+    async fn register_node(&mut self, target: C::NodeId, node: &C::Node) {
+        let _ = (target, node);
+    }
 }
@@ -5,6 +5,7 @@ mod factory;
 #[allow(clippy::module_inception)] mod network;
 mod rpc_option;
 mod rpc_type;
+mod snapshot_bandwidth;
 
 pub mod snapshot_transport;
 
@@ -13,3 +14,4 @@ pub use factory::RaftNetworkFactory;
 pub use network::RaftNetwork;
 pub use rpc_option::RPCOption;
 pub use rpc_type::RPCTypes;
+pub(crate) use snapshot_bandwidth::SnapshotBandwidth;
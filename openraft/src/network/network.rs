@@ -1,20 +1,26 @@
 use std::future::Future;
 use std::time::Duration;
 
+use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
 use crate::error::Fatal;
 use crate::error::RPCError;
 use crate::error::RaftError;
 use crate::error::ReplicationClosed;
 use crate::error::StreamingError;
+use crate::error::Unreachable;
 use crate::network::rpc_option::RPCOption;
 use crate::network::Backoff;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ClientWriteResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
+use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
@@ -125,6 +131,46 @@ where C: RaftTypeConfig
         Ok(resp)
     }
 
+    /// Forward a client write to the target, which is the current leader.
+    ///
+    /// It is called only if [`Config::forward_client_write`] is enabled, when
+    /// [`Raft::client_write()`] is called on a node that is not the leader. The target should
+    /// handle it with [`Raft::client_write()`] and send back the result.
+    ///
+    /// The default implementation returns an [`Unreachable`] error, in which case the client
+    /// receives a `ForwardToLeader` error as if forwarding is disabled.
+    ///
+    /// [`Config::forward_client_write`]: crate::Config::forward_client_write
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    async fn client_write(
+        &mut self,
+        _app_data: C::D,
+        _option: RPCOption,
+    ) -> Result<ClientWriteResponse<C>, RPCError<C, RaftError<C, ClientWriteError<C>>>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "forwarding client write is not implemented",
+        ))))
+    }
+
+    /// Get the read log id from the target, which is the current leader.
+    ///
+    /// It is called by [`Raft::ensure_caught_up()`] on a node that is not the leader. The target
+    /// should handle it with [`Raft::get_read_log_id()`] and send back the `read_log_id`.
+    ///
+    /// The default implementation returns an [`Unreachable`] error, in which case the caller
+    /// receives a `ForwardToLeader` error.
+    ///
+    /// [`Raft::ensure_caught_up()`]: crate::Raft::ensure_caught_up
+    /// [`Raft::get_read_log_id()`]: crate::Raft::get_read_log_id
+    async fn get_read_log_id(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<LogId<C::NodeId>>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "getting read log id is not implemented",
+        ))))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
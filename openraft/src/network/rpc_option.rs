@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::network::SnapshotBandwidth;
+
 /// An additional argument to the [`RaftNetwork`] methods to allow applications to customize
 /// networking behaviors.
 ///
@@ -13,6 +15,9 @@ pub struct RPCOption {
 
     /// The size of the snapshot chunk.
     pub(crate) snapshot_chunk_size: Option<usize>,
+
+    /// Limits the bandwidth of snapshot transfer.
+    pub(crate) snapshot_bandwidth: Option<SnapshotBandwidth>,
 }
 
 impl RPCOption {
@@ -20,6 +25,7 @@ impl RPCOption {
         Self {
             hard_ttl,
             snapshot_chunk_size: None,
+            snapshot_bandwidth: None,
         }
     }
 
@@ -0,0 +1,109 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Limits and measures the bandwidth of the snapshot transfers of a node.
+///
+/// It is shared by all replication streams of a node. The aggregate limit is divided evenly
+/// among the transfers in progress.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SnapshotBandwidth {
+    /// The max bytes per second of a single transfer.
+    per_transfer: Option<u64>,
+
+    /// The max bytes per second of all transfers of this node.
+    total: Option<u64>,
+
+    /// The number of transfers in progress.
+    transfers: Arc<AtomicU64>,
+
+    /// The total bytes of snapshot data sent by this node.
+    sent_bytes: Arc<AtomicU64>,
+}
+
+impl SnapshotBandwidth {
+    pub(crate) fn new(per_transfer: Option<u64>, total: Option<u64>) -> Self {
+        Self {
+            per_transfer,
+            total,
+            ..Default::default()
+        }
+    }
+
+    /// Register a transfer in progress, until the returned guard is dropped.
+    pub(crate) fn begin_transfer(&self) -> TransferGuard {
+        self.transfers.fetch_add(1, Ordering::Relaxed);
+        TransferGuard {
+            transfers: self.transfers.clone(),
+        }
+    }
+
+    /// Record `n` bytes sent, and return how long sending them should take at the current rate
+    /// limit.
+    ///
+    /// It returns zero if there is no limit.
+    pub(crate) fn pace(&self, n: u64) -> Duration {
+        self.sent_bytes.fetch_add(n, Ordering::Relaxed);
+
+        match self.rate() {
+            None => Duration::ZERO,
+            Some(rate) => Duration::from_secs_f64(n as f64 / rate as f64),
+        }
+    }
+
+    /// The max bytes per second a transfer can send now, or `None` if unlimited.
+    fn rate(&self) -> Option<u64> {
+        let transfers = std::cmp::max(1, self.transfers.load(Ordering::Relaxed));
+        let share = self.total.map(|t| std::cmp::max(1, t / transfers));
+
+        match (self.per_transfer, share) {
+            (Some(a), Some(b)) => Some(std::cmp::min(a, b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// The total bytes of snapshot data sent by this node.
+    pub(crate) fn sent_bytes(&self) -> u64 {
+        self.sent_bytes.load(Ordering::Relaxed)
+    }
+}
+
+/// Unregisters a transfer from [`SnapshotBandwidth`] when dropped.
+pub(crate) struct TransferGuard {
+    transfers: Arc<AtomicU64>,
+}
+
+impl Drop for TransferGuard {
+    fn drop(&mut self) {
+        self.transfers.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::SnapshotBandwidth;
+
+    #[test]
+    fn test_snapshot_bandwidth_pace() {
+        let b = SnapshotBandwidth::new(None, None);
+        assert_eq!(Duration::ZERO, b.pace(1000));
+
+        let b = SnapshotBandwidth::new(Some(1000), None);
+        assert_eq!(Duration::from_millis(500), b.pace(500));
+
+        // The aggregate limit is shared by transfers in progress.
+        let b = SnapshotBandwidth::new(Some(1000), Some(1000));
+        let g1 = b.begin_transfer();
+        assert_eq!(Duration::from_millis(500), b.pace(500));
+        let g2 = b.begin_transfer();
+        assert_eq!(Duration::from_millis(1000), b.pace(500));
+        drop(g1);
+        drop(g2);
+        assert_eq!(Duration::from_millis(500), b.pace(500));
+
+        assert_eq!(1500, b.sent_bytes());
+    }
+}
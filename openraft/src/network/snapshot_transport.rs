@@ -19,9 +19,12 @@ use crate::network::RPCOption;
 use crate::raft::InstallSnapshotRequest;
 use crate::raft::SnapshotResponse;
 use crate::type_config::alias::AsyncRuntimeOf;
+use crate::type_config::alias::InstantOf;
 use crate::AsyncRuntime;
 use crate::ErrorSubject;
 use crate::ErrorVerb;
+use crate::Instant;
+use crate::LogId;
 use crate::OptionalSend;
 use crate::Raft;
 use crate::RaftNetwork;
@@ -67,6 +70,11 @@ pub trait SnapshotTransport<C: RaftTypeConfig> {
     /// - And it depends on `Raft::begin_receiving_snapshot()` to create a `SnapshotData` for
     /// receiving data.
     ///
+    /// A snapshot stream in progress is cancelled when another snapshot begins, unless the new one
+    /// is older, i.e., it includes fewer logs and is not sent by a greater leader, in which case
+    /// the new one is rejected. The `SnapshotData` of a cancelled stream is dropped without being
+    /// installed.
+    ///
     /// Example usage:
     /// ```ignore
     /// struct App<C> {
@@ -112,6 +120,12 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
         let mut offset = 0;
         let end = snapshot.snapshot.seek(SeekFrom::End(0)).await.sto_res(subject_verb)?;
 
+        // Sending is paced so that the data sent since `start` does not exceed the bandwidth
+        // limit, i.e., a chunk is not sent before `start + paced`.
+        let _transfer = option.snapshot_bandwidth.as_ref().map(|b| b.begin_transfer());
+        let start = InstantOf::<C>::now();
+        let mut paced = Duration::ZERO;
+
         let mut c = std::pin::pin!(cancel);
         loop {
             // If canceled, return at once
@@ -185,6 +199,11 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
             }
 
             offset += n_read as u64;
+
+            if let Some(bandwidth) = &option.snapshot_bandwidth {
+                paced += bandwidth.pace(n_read as u64);
+                AsyncRuntimeOf::<C>::sleep_until(start + paced).await;
+            }
         }
     }
 
@@ -216,6 +235,38 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
                 return Err(RaftError::APIError(mismatch));
             }
 
+            if let Some(s) = streaming.as_ref() {
+                if s.is_newer_than(&req) {
+                    tracing::info!(
+                        streaming = display(s.snapshot_id()),
+                        req = display(&req),
+                        "reject snapshot older than the one being received"
+                    );
+                    let mismatch =
+                        crate::error::InstallSnapshotError::SnapshotMismatch(crate::error::SnapshotMismatch {
+                            expect: crate::SnapshotSegmentId {
+                                id: s.snapshot_id.clone(),
+                                offset: s.offset,
+                            },
+                            got: crate::SnapshotSegmentId {
+                                id: snapshot_id.clone(),
+                                offset: req.offset,
+                            },
+                        });
+                    return Err(RaftError::APIError(mismatch));
+                }
+
+                tracing::info!(
+                    streaming = display(s.snapshot_id()),
+                    req = display(&req),
+                    "cancel receiving snapshot, start receiving a new one"
+                );
+            }
+
+            // Drop the cancelled stream before creating a new one, so that the partially received
+            // data is released.
+            *streaming = None;
+
             // Changed to another stream. re-init snapshot state.
             let snapshot_data = raft.begin_receiving_snapshot().await.map_err(|e| {
                 // Safe unwrap: `RaftError<Infallible>` is always a Fatal.
@@ -261,6 +312,12 @@ where C: RaftTypeConfig
 
     /// A handle to the snapshot writer.
     snapshot_data: Box<C::SnapshotData>,
+
+    /// The vote of the leader sending the snapshot, updated when a chunk is received.
+    vote: Option<Vote<C::NodeId>>,
+
+    /// The last log id included in the snapshot, updated when a chunk is received.
+    last_log_id: Option<LogId<C::NodeId>>,
 }
 
 impl<C> Streaming<C>
@@ -271,9 +328,23 @@ where C: RaftTypeConfig
             offset: 0,
             snapshot_id,
             snapshot_data,
+            vote: None,
+            last_log_id: None,
         }
     }
 
+    /// Return `true` if the snapshot being received is newer than the one in `req`, i.e., it
+    /// includes more logs and is sent by the same or a greater leader.
+    ///
+    /// A stream that is not newer is cancelled when a new one begins.
+    fn is_newer_than(&self, req: &InstallSnapshotRequest<C>) -> bool {
+        let Some(vote) = self.vote else {
+            return false;
+        };
+
+        req.vote <= vote && req.meta.last_log_id < self.last_log_id
+    }
+
     pub fn snapshot_id(&self) -> &SnapshotId {
         &self.snapshot_id
     }
@@ -305,6 +376,9 @@ where
             self.offset = req.offset;
         }
 
+        self.vote = Some(req.vote);
+        self.last_log_id = req.meta.last_log_id;
+
         // Write the next segment & update offset.
         let res = self.snapshot_data.as_mut().write_all(&req.data).await;
         if let Err(err) = res {
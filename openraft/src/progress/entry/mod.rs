@@ -30,6 +30,36 @@ pub(crate) struct ProgressEntry<NID: NodeId> {
 
     /// One plus the max log index on the following node that might match the leader log.
     pub(crate) searching_end: u64,
+
+    /// A hint of the matching log index on the following node, e.g., saved by this leader before
+    /// restart.
+    ///
+    /// If it is in the searching range, the next AppendEntries starts right after it instead of
+    /// at the middle of the range. It is used only once: a conflict just continues the binary
+    /// search below it.
+    pub(crate) hint: Option<u64>,
+
+    /// The number of AppendEntries the target rejected in a row because of a log conflict.
+    ///
+    /// It is reset when the target accepts logs, but not when it accepts a snapshot.
+    pub(crate) rejections: u64,
+
+    /// Whether the target is stalled, i.e., `rejections` reached
+    /// `Config::append_rejection_threshold`.
+    ///
+    /// A stalled target is replicated with a snapshot, if the snapshot is newer than the matching
+    /// log. It is cleared when the target accepts logs.
+    pub(crate) stalled: bool,
+
+    /// The number of times the target became stalled, for diagnostic purpose.
+    pub(crate) stalls: u64,
+
+    /// Whether the next replication to the target is forced to send a snapshot, if the snapshot
+    /// is newer than the matching log.
+    ///
+    /// It is cleared once the matching log reaches the snapshot, so that a failed snapshot
+    /// replication is retried with a snapshot too.
+    pub(crate) force_snapshot: bool,
 }
 
 impl<NID: NodeId> ProgressEntry<NID> {
@@ -40,6 +70,11 @@ impl<NID: NodeId> ProgressEntry<NID> {
             curr_inflight_id: 0,
             inflight: Inflight::None,
             searching_end: matching.next_index(),
+            hint: None,
+            rejections: 0,
+            stalled: false,
+            stalls: 0,
+            force_snapshot: false,
         }
     }
 
@@ -52,6 +87,11 @@ impl<NID: NodeId> ProgressEntry<NID> {
             curr_inflight_id: 0,
             inflight: Inflight::None,
             searching_end: end,
+            hint: None,
+            rejections: 0,
+            stalled: false,
+            stalls: 0,
+            force_snapshot: false,
         }
     }
 
@@ -85,6 +125,12 @@ impl<NID: NodeId> ProgressEntry<NID> {
         }
     }
 
+    /// Update `matching` with the response to the request `request_id`.
+    ///
+    /// A response is correlated with the request by `request_id`. A response to an earlier
+    /// request, e.g., one that is delayed and arrives out of order, is stale and is ignored, so
+    /// that it never moves `matching` backward. A response to a request that is not sent yet is an
+    /// error.
     pub(crate) fn update_matching(
         &mut self,
         request_id: u64,
@@ -97,8 +143,25 @@ impl<NID: NodeId> ProgressEntry<NID> {
             "update_matching"
         );
 
+        if self.inflight.get_id() != Some(request_id) && request_id <= self.curr_inflight_id {
+            tracing::warn!(
+                self = display(&self),
+                request_id = display(request_id),
+                matching = display(matching.display()),
+                "ignore stale response to a finished request"
+            );
+            return Ok(());
+        }
+
+        let logs_accepted = matches!(self.inflight, Inflight::Logs { .. });
+
         self.inflight.ack(request_id, matching)?;
 
+        if logs_accepted {
+            self.rejections = 0;
+            self.stalled = false;
+        }
+
         debug_assert!(matching >= self.matching);
         self.matching = matching;
 
@@ -129,6 +192,7 @@ impl<NID: NodeId> ProgressEntry<NID> {
         );
 
         self.inflight.conflict(request_id, conflict)?;
+        self.rejections += 1;
 
         debug_assert!(conflict < self.searching_end);
         self.searching_end = conflict;
@@ -165,11 +229,18 @@ impl<NID: NodeId> ProgressEntry<NID> {
     ///
     /// If there is an action in progress, i.e., `inflight` is not None, it returns an `Err`
     /// containing the current `inflight` data
+    ///
+    /// If `snapshot_lag_threshold` is `Some`, a target that falls behind the committed log by more
+    /// than it is replicated with snapshot, if the snapshot includes logs the target lacks.
+    ///
+    /// A stalled target, or a target forced to receive a snapshot, is replicated with snapshot
+    /// too, if the snapshot is newer than the matching log.
     #[allow(dead_code)]
     pub(crate) fn next_send(
         &mut self,
         log_state: &impl LogStateReader<NID>,
         max_entries: u64,
+        snapshot_lag_threshold: Option<u64>,
     ) -> Result<&Inflight<NID>, &Inflight<NID>> {
         if !self.inflight.is_none() {
             return Err(&self.inflight);
@@ -190,9 +261,16 @@ impl<NID: NodeId> ProgressEntry<NID> {
 
         // `searching_end` is the max value for `start`.
 
-        // The log the follower needs is purged.
-        // Replicate by snapshot.
-        if self.searching_end < purge_upto_next {
+        if self.force_snapshot && log_state.snapshot_last_log_id() <= self.matching.as_ref() {
+            self.force_snapshot = false;
+        }
+
+        // The log the follower needs is purged, or the follower is too far behind, stalled, or
+        // forced to receive a snapshot. Replicate by snapshot.
+        if self.searching_end < purge_upto_next
+            || self.is_too_far_behind(log_state, snapshot_lag_threshold)
+            || ((self.stalled || self.force_snapshot) && log_state.snapshot_last_log_id() > self.matching.as_ref())
+        {
             self.curr_inflight_id += 1;
             let snapshot_last = log_state.snapshot_last_log_id();
             self.inflight = Inflight::snapshot(snapshot_last.copied()).with_id(self.curr_inflight_id);
@@ -201,7 +279,14 @@ impl<NID: NodeId> ProgressEntry<NID> {
 
         // Replicate by logs.
         // Run a binary search to find the matching log id, if matching log id is not determined.
-        let mut start = Self::calc_mid(self.matching.next_index(), self.searching_end);
+        let matching_next = self.matching.next_index();
+        let mut start = match self.hint.take() {
+            // Start right after the hinted log, but send at least one log in the searching range.
+            Some(hint) if matching_next <= hint + 1 && matching_next < self.searching_end => {
+                std::cmp::min(hint + 1, self.searching_end - 1)
+            }
+            _ => Self::calc_mid(matching_next, self.searching_end),
+        };
         if start < purge_upto_next {
             start = purge_upto_next;
         }
@@ -222,6 +307,41 @@ impl<NID: NodeId> ProgressEntry<NID> {
         Ok(&self.inflight)
     }
 
+    /// Return if the target falls behind the committed log by more than `snapshot_lag_threshold`
+    /// and the snapshot includes logs the target lacks.
+    ///
+    /// `searching_end - 1` is the max index that may match on the target, thus the lag is at least
+    /// `committed + 1 - searching_end`.
+    fn is_too_far_behind(&self, log_state: &impl LogStateReader<NID>, snapshot_lag_threshold: Option<u64>) -> bool {
+        let Some(threshold) = snapshot_lag_threshold else {
+            return false;
+        };
+
+        if log_state.snapshot_last_log_id().next_index() <= self.searching_end {
+            return false;
+        }
+
+        let lag = log_state.committed().next_index().saturating_sub(self.searching_end);
+        lag > threshold
+    }
+
+    /// Mark the target as stalled if it has rejected `threshold` AppendEntries in a row.
+    ///
+    /// It returns `true` if the target just becomes stalled.
+    pub(crate) fn check_stalled(&mut self, threshold: Option<u64>) -> bool {
+        let Some(threshold) = threshold else {
+            return false;
+        };
+
+        if self.stalled || self.rejections < threshold {
+            return false;
+        }
+
+        self.stalled = true;
+        self.stalls += 1;
+        true
+    }
+
     /// Return the index range(`[start,end]`) of the first log in the next AppendEntries.
     ///
     /// The returned range is left close and right close.
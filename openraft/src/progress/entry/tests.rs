@@ -65,6 +65,53 @@ fn test_update_matching() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_update_matching_out_of_order() -> anyhow::Result<()> {
+    // A stale response to an earlier request does not move matching backward
+    {
+        let mut pe = ProgressEntry::empty(20).with_curr_inflight_id(5);
+        pe.matching = Some(log_id(8));
+        pe.inflight = inflight_logs(8, 10).with_id(5);
+
+        pe.update_matching(4, Some(log_id(6)))?;
+        assert_eq!(Some(log_id(8)), pe.matching);
+        assert_eq!(
+            inflight_logs(8, 10).with_id(5),
+            pe.inflight,
+            "the current request is inflight"
+        );
+        assert_eq!(20, pe.searching_end);
+
+        pe.update_matching(5, Some(log_id(10)))?;
+        assert_eq!(Some(log_id(10)), pe.matching);
+        assert_eq!(Inflight::None, pe.inflight);
+    }
+
+    // A stale response arriving after the current request is finished is ignored
+    {
+        let mut pe = ProgressEntry::empty(20).with_curr_inflight_id(5);
+        pe.matching = Some(log_id(10));
+
+        pe.update_matching(4, Some(log_id(6)))?;
+        assert_eq!(Some(log_id(10)), pe.matching);
+        assert_eq!(Inflight::None, pe.inflight);
+    }
+
+    // A response to a request not sent yet is rejected
+    {
+        let mut pe = ProgressEntry::empty(20).with_curr_inflight_id(5);
+        pe.matching = Some(log_id(8));
+        pe.inflight = inflight_logs(8, 10).with_id(5);
+
+        let res = pe.update_matching(6, Some(log_id(10)));
+        assert!(res.is_err());
+        assert_eq!(Some(log_id(8)), pe.matching);
+        assert_eq!(inflight_logs(8, 10).with_id(5), pe.inflight);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_update_conflicting() -> anyhow::Result<()> {
     let mut pe = ProgressEntry::empty(20);
@@ -81,6 +128,7 @@ fn test_update_conflicting() -> anyhow::Result<()> {
 /// LogStateReader impl for testing
 struct LogState {
     last: Option<LogId<u64>>,
+    committed: Option<LogId<u64>>,
     snap_last: Option<LogId<u64>>,
     purge_upto: Option<LogId<u64>>,
     purged: Option<LogId<u64>>,
@@ -90,6 +138,7 @@ impl LogState {
     fn new(purge_upto: u64, snap_last: u64, last: u64) -> Self {
         Self {
             last: Some(log_id(last)),
+            committed: Some(log_id(last)),
             snap_last: Some(log_id(snap_last)),
             // `next_send()` only checks purge_upto, but not purged,
             // We just fake a purged
@@ -114,7 +163,7 @@ impl LogStateReader<u64> for LogState {
     }
 
     fn committed(&self) -> Option<&LogId<u64>> {
-        unimplemented!("testing")
+        self.committed.as_ref()
     }
 
     fn io_applied(&self) -> Option<&LogId<u64>> {
@@ -148,7 +197,7 @@ fn test_next_send() -> anyhow::Result<()> {
     {
         let mut pe = ProgressEntry::empty(20);
         pe.inflight = inflight_logs(10, 11);
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Err(&inflight_logs(10, 11)), res);
     }
 
@@ -163,7 +212,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(4);
         pe.matching = Some(log_id(4));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(1)), res);
     }
     {
@@ -177,7 +226,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(6);
         pe.matching = Some(log_id(4));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(1)), res);
     }
 
@@ -192,7 +241,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(7);
         pe.matching = Some(log_id(4));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
     }
 
@@ -207,7 +256,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(20);
         pe.matching = Some(log_id(4));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
     }
 
@@ -224,7 +273,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(7);
         pe.matching = Some(log_id(6));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
     }
 
@@ -239,7 +288,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(8);
         pe.matching = Some(log_id(6));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
     }
 
@@ -254,7 +303,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(20);
         pe.matching = Some(log_id(6));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
     }
 
@@ -269,7 +318,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(20);
         pe.matching = Some(log_id(7));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(7, 20).with_id(1)), res);
     }
 
@@ -284,7 +333,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(8);
         pe.matching = Some(log_id(7));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Ok(&inflight_logs(7, 20).with_id(1)), res);
     }
 
@@ -299,7 +348,7 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(21);
         pe.matching = Some(log_id(20));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
         assert_eq!(Err(&Inflight::None), res, "nothing to send");
     }
 
@@ -315,8 +364,156 @@ fn test_next_send() -> anyhow::Result<()> {
         let mut pe = ProgressEntry::empty(20);
         pe.matching = Some(log_id(7));
 
-        let res = pe.next_send(&LogState::new(6, 10, 20), 5);
+        let res = pe.next_send(&LogState::new(6, 10, 20), 5, None);
         assert_eq!(Ok(&inflight_logs(7, 12).with_id(1)), res);
     }
     Ok(())
 }
+
+#[test]
+fn test_next_send_with_hint() -> anyhow::Result<()> {
+    // The hint is in the searching range: start right after it.
+    {
+        let mut pe = ProgressEntry::empty(20);
+        pe.hint = Some(15);
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
+        assert_eq!(Ok(&inflight_logs(15, 20).with_id(1)), res);
+        assert_eq!(None, pe.hint, "hint is used only once");
+    }
+
+    // The hint is below the matching log: ignore it.
+    {
+        let mut pe = ProgressEntry::empty(20);
+        pe.matching = Some(log_id(12));
+        pe.hint = Some(8);
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
+        assert_eq!(Ok(&inflight_logs(12, 20).with_id(1)), res);
+        assert_eq!(None, pe.hint);
+    }
+
+    // The hint is not below the searching end: send only the last log in the searching range.
+    {
+        let mut pe = ProgressEntry::empty(20);
+        pe.hint = Some(25);
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
+        assert_eq!(Ok(&inflight_logs(18, 20).with_id(1)), res);
+        assert_eq!(None, pe.hint);
+    }
+
+    // The hinted log is purged: replicate from the first present log.
+    {
+        let mut pe = ProgressEntry::empty(20);
+        pe.hint = Some(2);
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, None);
+        assert_eq!(Ok(&inflight_logs(6, 20).with_id(1)), res);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_next_send_with_snapshot_lag_threshold() -> anyhow::Result<()> {
+    // Far behind, replicate by snapshot
+    {
+        let mut pe = ProgressEntry::empty(8);
+        pe.matching = Some(log_id(7));
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, Some(5));
+        assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(1)), res);
+    }
+
+    // Within the threshold, replicate by logs
+    {
+        let mut pe = ProgressEntry::empty(8);
+        pe.matching = Some(log_id(7));
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, Some(15));
+        assert_eq!(Ok(&inflight_logs(7, 20).with_id(1)), res);
+    }
+
+    // Far behind, but the snapshot does not include any log the target lacks
+    {
+        let mut pe = ProgressEntry::empty(12);
+        pe.matching = Some(log_id(11));
+
+        let res = pe.next_send(&LogState::new(6, 10, 20), 100, Some(5));
+        assert_eq!(Ok(&inflight_logs(11, 20).with_id(1)), res);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stalled() -> anyhow::Result<()> {
+    let mut pe = ProgressEntry::empty(20);
+
+    // Rejected twice in a row
+    pe.inflight = inflight_logs(15, 20);
+    pe.update_conflicting(pe.inflight.id(), 15)?;
+    pe.inflight = inflight_logs(12, 15);
+    pe.update_conflicting(pe.inflight.id(), 12)?;
+    assert_eq!(2, pe.rejections);
+
+    assert_eq!(false, pe.check_stalled(None));
+    assert_eq!(false, pe.check_stalled(Some(3)));
+    assert_eq!(true, pe.check_stalled(Some(2)));
+    assert_eq!(false, pe.check_stalled(Some(2)), "already stalled");
+    assert_eq!(1, pe.stalls);
+
+    // A stalled target is replicated by snapshot
+    let res = pe.next_send(&LogState::new(1, 10, 20), 100, None);
+    assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(1)), res);
+
+    // Accepting a snapshot does not clear the stalled state, but no more snapshot is sent.
+    pe.update_matching(pe.inflight.id(), Some(log_id(10)))?;
+    assert_eq!(true, pe.stalled);
+    assert_eq!(2, pe.rejections);
+
+    let res = pe.next_send(&LogState::new(1, 10, 20), 100, None);
+    assert_eq!(Ok(&inflight_logs(10, 20).with_id(2)), res);
+
+    // Accepting logs clears the stalled state
+    pe.update_matching(pe.inflight.id(), Some(log_id(20)))?;
+    assert_eq!(false, pe.stalled);
+    assert_eq!(0, pe.rejections);
+    assert_eq!(1, pe.stalls);
+
+    Ok(())
+}
+
+#[test]
+fn test_force_snapshot() -> anyhow::Result<()> {
+    let mut pe = ProgressEntry::new(Some(log_id(5)));
+
+    // The snapshot is not newer than the matching log: replicate by logs
+    pe.force_snapshot = true;
+    let res = pe.next_send(&LogState::new(1, 5, 20), 100, None);
+    assert_eq!(Ok(&inflight_logs(5, 20).with_id(1)), res);
+    assert_eq!(false, pe.force_snapshot);
+
+    pe.inflight = Inflight::None;
+
+    // Replicate by snapshot, and retry by snapshot if it fails
+    pe.force_snapshot = true;
+    let res = pe.next_send(&LogState::new(1, 10, 20), 100, None);
+    assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(2)), res);
+
+    pe.inflight = Inflight::None;
+
+    let res = pe.next_send(&LogState::new(1, 10, 20), 100, None);
+    assert_eq!(Ok(&Inflight::snapshot(Some(log_id(10))).with_id(3)), res);
+    assert_eq!(true, pe.force_snapshot);
+
+    // Replicate by logs once the snapshot is installed
+    pe.update_matching(pe.inflight.id(), Some(log_id(10)))?;
+
+    let res = pe.next_send(&LogState::new(1, 10, 20), 100, None);
+    assert_eq!(Ok(&inflight_logs(10, 20).with_id(4)), res);
+    assert_eq!(false, pe.force_snapshot);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Validate AppendEntries requests with application defined rules.
+
+use crate::raft::AppendEntriesRequest;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// An application defined check on AppendEntries requests received by a follower or learner.
+///
+/// It is registered with [`Raft::set_append_entries_validator()`] and runs in `RaftCore`.
+///
+/// It is meant as an extra safety check, e.g., to reject a leader that the application considers
+/// stale according to its own clock or lease, in rare dual-leader scenarios caused by clock
+/// issues. It never bypasses the normal checks: it is called only for a request whose vote is not
+/// rejected, and a request it approves is still subject to the log consistency check.
+///
+/// A rejected request is responded with [`AppendEntriesResponse::HigherVote`] carrying the local
+/// vote, which is not greater than the leader's, and the local vote, log and election timer are
+/// left untouched. The leader backs off and retries later.
+///
+/// [`Raft::set_append_entries_validator()`]: crate::Raft::set_append_entries_validator
+/// [`AppendEntriesResponse::HigherVote`]: crate::raft::AppendEntriesResponse::HigherVote
+pub trait AppendEntriesValidator<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Return `true` to accept the request, or `false` to reject it.
+    fn validate(&mut self, req: &AppendEntriesRequest<C>) -> bool;
+}
@@ -0,0 +1,67 @@
+//! Coordinate applying log entries across multiple Raft groups.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::LogId;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// A coordinator that defers applying a log entry until a sequencer shared by several Raft groups
+/// permits it.
+///
+/// It is registered with [`Raft::set_apply_coordinator()`] and runs in the state machine worker
+/// task. It is meant for multi-raft systems in which groups share one state machine and the
+/// entries of different groups have to be applied in a global order.
+///
+/// Entries are still applied in batches. Before applying a batch, the state machine worker waits,
+/// in index order, for the permit returned by [`acquire()`] for every entry of the batch. After
+/// the batch is applied, [`on_applied()`] is called for every entry of it, in index order.
+///
+/// A permit that never resolves blocks the state machine of this group, including the responses
+/// to client writes.
+///
+/// [`Raft::set_apply_coordinator()`]: crate::Raft::set_apply_coordinator
+/// [`acquire()`]: ApplyCoordinator::acquire
+/// [`on_applied()`]: ApplyCoordinator::on_applied
+pub trait ApplyCoordinator<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Return a permit that resolves when the entry is allowed to be applied.
+    fn acquire(&mut self, entry: &C::Entry) -> ApplyPermit;
+
+    /// Called after the entry with `log_id` is applied to the state machine.
+    fn on_applied(&mut self, log_id: &LogId<C::NodeId>) {
+        let _ = log_id;
+    }
+}
+
+/// A future returned by [`ApplyCoordinator::acquire()`] that resolves when an entry is allowed to
+/// be applied.
+pub struct ApplyPermit {
+    #[cfg(not(feature = "singlethreaded"))]
+    inner: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+    #[cfg(feature = "singlethreaded")]
+    inner: Pin<Box<dyn Future<Output = ()> + 'static>>,
+}
+
+impl ApplyPermit {
+    pub fn new(fut: impl Future<Output = ()> + OptionalSend + 'static) -> Self {
+        Self { inner: Box::pin(fut) }
+    }
+
+    /// A permit that allows applying at once.
+    pub fn ready() -> Self {
+        Self::new(std::future::ready(()))
+    }
+}
+
+impl Future for ApplyPermit {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.as_mut().poll(cx)
+    }
+}
@@ -0,0 +1,40 @@
+//! Observe log entries being applied to the state machine.
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::SnapshotMeta;
+
+/// An observer that is notified when a log entry is applied to the state machine.
+///
+/// It is registered with [`Raft::set_apply_observer()`] and runs in the state machine worker task,
+/// on every node, not only on the leader.
+///
+/// The observer is guaranteed to see every applied entry exactly once, in strictly increasing
+/// index order. When a snapshot is installed, the entries included in it are not applied one by
+/// one: [`on_install_snapshot()`] is called instead, and the next entry observed is the one
+/// right after the snapshot.
+///
+/// Entries passed to the state machine before the observer is registered are not observed.
+/// Entries are still applied in batches: the observer is called once for every entry of a batch,
+/// after [`RaftStateMachine::apply()`] returns for the whole batch.
+///
+/// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+///
+/// [`Raft::set_apply_observer()`]: crate::Raft::set_apply_observer
+/// [`on_install_snapshot()`]: ApplyObserver::on_install_snapshot
+pub trait ApplyObserver<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Called with a log entry and the response returned for it by the state machine, after
+    /// [`RaftStateMachine::apply()`] returns `Ok` for the batch containing it.
+    ///
+    /// Entries of a batch whose apply fails are not observed.
+    ///
+    /// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+    fn on_apply(&mut self, entry: &C::Entry, response: &C::R);
+
+    /// Called after a snapshot is installed to the state machine.
+    fn on_install_snapshot(&mut self, meta: &SnapshotMeta<C>) {
+        let _ = meta;
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Whether a log index is committed on this node.
+///
+/// It is returned by [`Raft::is_committed()`](crate::Raft::is_committed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CommitStatus {
+    /// The index is committed and the entry is in the log.
+    Committed,
+
+    /// The index is not yet committed on this node.
+    ///
+    /// The leader may have committed it but this node has not learned about it yet.
+    NotCommitted,
+
+    /// The index is covered by the snapshot, thus it is committed, but the entry may have been
+    /// purged from the log.
+    Compacted,
+}
+
+impl CommitStatus {
+    /// Return `true` if the index is committed, i.e., it is [`Committed`](Self::Committed) or
+    /// [`Compacted`](Self::Compacted).
+    pub fn is_committed(&self) -> bool {
+        match self {
+            Self::Committed | Self::Compacted => true,
+            Self::NotCommitted => false,
+        }
+    }
+}
+
+impl fmt::Display for CommitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Committed => write!(f, "Committed"),
+            Self::NotCommitted => write!(f, "NotCommitted"),
+            Self::Compacted => write!(f, "Compacted"),
+        }
+    }
+}
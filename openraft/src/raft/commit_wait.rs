@@ -0,0 +1,46 @@
+//! Require a client write to be acknowledged by a follower in a group of nodes.
+
+use std::fmt;
+use std::time::Duration;
+
+/// An option of a client write, requiring its entry to be acknowledged by at least one follower
+/// whose label `label` is `value`, in addition to being committed by a quorum.
+///
+/// E.g., a write that must be durable in a second datacenter before it is acknowledged to the
+/// client, with nodes labeled by datacenter. The labels of the other nodes are set on the leader
+/// with [`Raft::set_peer_labels()`].
+///
+/// It is used with [`Raft::client_write_with_commit_wait()`]. If no such follower acknowledges
+/// the entry within `timeout` since it is submitted, the write returns a
+/// [`CommitWaitTimeout`](crate::error::CommitWaitTimeout) error, although the entry may still be
+/// committed and applied.
+///
+/// [`Raft::set_peer_labels()`]: crate::Raft::set_peer_labels
+/// [`Raft::client_write_with_commit_wait()`]: crate::Raft::client_write_with_commit_wait
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitWait {
+    /// The label key defining the group, e.g., `dc`.
+    pub label: String,
+
+    /// The label value of the nodes in the group, e.g., `us-west`.
+    pub value: String,
+
+    /// How long to wait for a follower in the group to acknowledge the entry.
+    pub timeout: Duration,
+}
+
+impl CommitWait {
+    pub fn new(label: impl ToString, value: impl ToString, timeout: Duration) -> Self {
+        Self {
+            label: label.to_string(),
+            value: value.to_string(),
+            timeout,
+        }
+    }
+}
+
+impl fmt::Display for CommitWait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}(timeout:{:?})", self.label, self.value, self.timeout)
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::Stream;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
+
+use crate::storage::RaftLogReader;
+use crate::LogId;
+use crate::LogIdOptionExt;
+use crate::RaftLogId;
+use crate::RaftMetrics;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// An item of a [`CommittedStream`].
+#[derive(Debug, Clone)]
+pub enum Committed<C>
+where C: RaftTypeConfig
+{
+    /// A committed entry, with its log id and payload.
+    Entry(C::Entry),
+
+    /// The entries up to this log id have been purged from the log.
+    ///
+    /// The consumer should read the snapshot, which includes at least these entries, with
+    /// [`Raft::get_snapshot()`](crate::Raft::get_snapshot). The stream goes on with the entry
+    /// right after this log id.
+    Snapshot(LogId<C::NodeId>),
+}
+
+impl<C> fmt::Display for Committed<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entry(entry) => write!(f, "Entry({})", entry),
+            Self::Snapshot(log_id) => write!(f, "Snapshot({})", log_id),
+        }
+    }
+}
+
+/// A stream of the committed entries on this node, returned by
+/// [`Raft::subscribe_committed()`](crate::Raft::subscribe_committed).
+///
+/// It yields the entries already committed first, then follows the newly committed ones. It ends
+/// after yielding an error, or when the [`Raft`](crate::Raft) shuts down.
+pub struct CommittedStream<C>
+where C: RaftTypeConfig
+{
+    rx: mpsc::Receiver<Result<Committed<C>, StorageError<C::NodeId>>>,
+}
+
+impl<C> CommittedStream<C>
+where C: RaftTypeConfig
+{
+    /// The number of items buffered before the consumer reads them.
+    pub(crate) const BUFFER: usize = 64;
+
+    pub(crate) fn new(rx: mpsc::Receiver<Result<Committed<C>, StorageError<C::NodeId>>>) -> Self {
+        Self { rx }
+    }
+}
+
+impl<C> Stream for CommittedStream<C>
+where C: RaftTypeConfig
+{
+    type Item = Result<Committed<C>, StorageError<C::NodeId>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Feed the committed entries since `start` to `tx`, until `tx` is closed or `rx_metrics` is
+/// closed.
+///
+/// An entry is fed once it is applied to the state machine on this node, which implies it is
+/// committed. If the next entry to feed has been purged, it feeds [`Committed::Snapshot`] with the
+/// last log id in the snapshot, and goes on after it.
+pub(crate) async fn feed_committed<C, LR>(
+    start: u64,
+    mut log_reader: LR,
+    mut rx_metrics: watch::Receiver<RaftMetrics<C>>,
+    tx: mpsc::Sender<Result<Committed<C>, StorageError<C::NodeId>>>,
+) where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    let mut next = start;
+
+    loop {
+        let (applied, purged, snapshot) = {
+            let m = rx_metrics.borrow_and_update();
+            (m.last_applied, m.purged, m.snapshot)
+        };
+
+        if Some(next) <= purged.index() {
+            // The snapshot always includes the purged entries.
+            let snapshot = std::cmp::max(snapshot, purged).unwrap();
+
+            tracing::debug!(
+                next,
+                snapshot = display(&snapshot),
+                "committed stream: redirect to snapshot"
+            );
+
+            if tx.send(Ok(Committed::Snapshot(snapshot))).await.is_err() {
+                return;
+            }
+            next = snapshot.index + 1;
+            continue;
+        }
+
+        if Some(next) <= applied.index() {
+            let end = applied.next_index().min(next + CommittedStream::<C>::BUFFER as u64);
+
+            let entries = match log_reader.try_get_log_entries(next..end).await {
+                Ok(x) => x,
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                    return;
+                }
+            };
+
+            // If the entries are purged after the metrics are read, re-check the metrics after
+            // they are updated.
+            if entries.first().map(|e| e.get_log_id().index) == Some(next) {
+                for entry in entries {
+                    if entry.get_log_id().index != next {
+                        break;
+                    }
+                    next += 1;
+
+                    if tx.send(Ok(Committed::Entry(entry))).await.is_err() {
+                        return;
+                    }
+                }
+                continue;
+            }
+        }
+
+        tokio::select! {
+            res = rx_metrics.changed() => {
+                if res.is_err() {
+                    return;
+                }
+            }
+            _ = tx.closed() => {
+                return;
+            }
+        }
+    }
+}
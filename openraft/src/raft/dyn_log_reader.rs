@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::pin::Pin;
+
+use crate::storage::RaftLogReader;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::Vote;
+
+#[cfg(not(feature = "singlethreaded"))]
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+#[cfg(feature = "singlethreaded")]
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+type StorageResult<C, T> = Result<T, StorageError<<C as RaftTypeConfig>::NodeId>>;
+
+/// The object safe form of [`RaftLogReader`].
+///
+/// It is implemented for every [`RaftLogReader`], so that [`Raft`](crate::Raft), which does not
+/// know the type of the log store, can read the log with a [`BoxLogReader`].
+trait DynLogReader<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    fn dyn_try_get_log_entries(
+        &mut self,
+        range: (Bound<u64>, Bound<u64>),
+    ) -> BoxFuture<'_, StorageResult<C, Vec<C::Entry>>>;
+
+    fn dyn_try_get_log_entries_rev(
+        &mut self,
+        high: u64,
+        limit: usize,
+    ) -> BoxFuture<'_, StorageResult<C, Vec<C::Entry>>>;
+
+    fn dyn_term_of_index(&mut self, index: u64) -> BoxFuture<'_, StorageResult<C, Option<u64>>>;
+
+    fn dyn_read_vote(&mut self) -> BoxFuture<'_, StorageResult<C, Option<Vote<C::NodeId>>>>;
+}
+
+impl<C, LR> DynLogReader<C> for LR
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    fn dyn_try_get_log_entries(
+        &mut self,
+        range: (Bound<u64>, Bound<u64>),
+    ) -> BoxFuture<'_, StorageResult<C, Vec<C::Entry>>> {
+        Box::pin(self.try_get_log_entries(range))
+    }
+
+    fn dyn_try_get_log_entries_rev(
+        &mut self,
+        high: u64,
+        limit: usize,
+    ) -> BoxFuture<'_, StorageResult<C, Vec<C::Entry>>> {
+        Box::pin(self.try_get_log_entries_rev(high, limit))
+    }
+
+    fn dyn_term_of_index(&mut self, index: u64) -> BoxFuture<'_, StorageResult<C, Option<u64>>> {
+        Box::pin(self.term_of_index(index))
+    }
+
+    fn dyn_read_vote(&mut self) -> BoxFuture<'_, StorageResult<C, Option<Vote<C::NodeId>>>> {
+        Box::pin(self.read_vote())
+    }
+}
+
+/// A [`RaftLogReader`] with the type of the log store erased.
+///
+/// Every method is forwarded to the wrapped reader, including the ones it overrides.
+pub(crate) struct BoxLogReader<C>
+where C: RaftTypeConfig
+{
+    inner: Box<dyn DynLogReader<C>>,
+}
+
+impl<C> BoxLogReader<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new<LR>(log_reader: LR) -> Self
+    where LR: RaftLogReader<C> {
+        Self {
+            inner: Box::new(log_reader),
+        }
+    }
+}
+
+impl<C> RaftLogReader<C> for BoxLogReader<C>
+where C: RaftTypeConfig
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        self.inner.dyn_try_get_log_entries(range).await
+    }
+
+    async fn try_get_log_entries_rev(
+        &mut self,
+        high: u64,
+        limit: usize,
+    ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>> {
+        self.inner.dyn_try_get_log_entries_rev(high, limit).await
+    }
+
+    async fn term_of_index(&mut self, index: u64) -> Result<Option<u64>, StorageError<C::NodeId>> {
+        self.inner.dyn_term_of_index(index).await
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<Vote<C::NodeId>>, StorageError<C::NodeId>> {
+        self.inner.dyn_read_vote().await
+    }
+}
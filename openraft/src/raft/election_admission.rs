@@ -0,0 +1,69 @@
+//! Throttle elections across multiple Raft groups.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// A hook a node consults before it starts an election, so that a coordinator shared by several
+/// Raft groups can limit how many of them campaign at once.
+///
+/// It is registered with [`Raft::set_election_admission()`] and is called by `RaftCore`. When the
+/// election timeout passes, the node asks for a permit with [`acquire()`] and starts the election
+/// only when the permit resolves: the hook can delay an election but not deny it. While waiting,
+/// the node stays in its current state and does not ask again.
+///
+/// If the vote of this node changes while waiting, e.g., a leader is seen, the election is not
+/// started when the permit resolves. Either way, [`on_election_done()`] is called once for every
+/// resolved permit, when the election it admits is finished, i.e., this node is no longer a
+/// candidate, or when it is abandoned. A candidate that elects again after a split vote does not
+/// ask for another permit.
+///
+/// An election triggered by [`Trigger::elect()`] is not subject to it.
+///
+/// [`Raft::set_election_admission()`]: crate::Raft::set_election_admission
+/// [`acquire()`]: ElectionAdmission::acquire
+/// [`on_election_done()`]: ElectionAdmission::on_election_done
+/// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
+pub trait ElectionAdmission<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Return a permit that resolves when the node `node_id` is allowed to start an election.
+    fn acquire(&mut self, node_id: &C::NodeId) -> ElectionPermit;
+
+    /// Called when the election admitted by a permit is finished or abandoned.
+    fn on_election_done(&mut self, node_id: &C::NodeId) {
+        let _ = node_id;
+    }
+}
+
+/// A future returned by [`ElectionAdmission::acquire()`] that resolves when a node is allowed to
+/// start an election.
+pub struct ElectionPermit {
+    #[cfg(not(feature = "singlethreaded"))]
+    inner: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+    #[cfg(feature = "singlethreaded")]
+    inner: Pin<Box<dyn Future<Output = ()> + 'static>>,
+}
+
+impl ElectionPermit {
+    pub fn new(fut: impl Future<Output = ()> + OptionalSend + 'static) -> Self {
+        Self { inner: Box::pin(fut) }
+    }
+
+    /// A permit that allows electing at once.
+    pub fn ready() -> Self {
+        Self::new(std::future::ready(()))
+    }
+}
+
+impl Future for ElectionPermit {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.as_mut().poll(cx)
+    }
+}
@@ -0,0 +1,53 @@
+//! Prefer one of the candidates with equally up-to-date logs in an election.
+
+use std::fmt;
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// An application defined preference among candidates whose logs are equally up to date.
+///
+/// It is registered with [`Raft::set_election_tiebreaker()`] and runs in `RaftCore`.
+///
+/// Without it, which of two candidates with identical logs becomes the leader depends on timing
+/// alone. With it, when a voter receives a vote request whose last log id equals its own, it
+/// consults [`prefer()`](Self::prefer) with itself and the candidate. If the voter itself is
+/// preferred, the vote is denied with [`VoteDenyReason::Tiebreak`], and the voter leaves its vote
+/// and election timer untouched, so that it campaigns itself when the timer fires.
+///
+/// It only denies votes that would otherwise be granted, thus it never makes a node vote twice in
+/// a term. It is not consulted by a witness, or by a node that is not a voter, because such a node
+/// never elects itself.
+///
+/// A node preferred by the tiebreaker must be able to elect itself, e.g., `Config::enable_elect`
+/// is not disabled on it. Otherwise it keeps denying the other candidates and no leader is elected
+/// while logs are equal.
+///
+/// [`Raft::set_election_tiebreaker()`]: crate::Raft::set_election_tiebreaker
+/// [`VoteDenyReason::Tiebreak`]: crate::metrics::VoteDenyReason::Tiebreak
+pub trait ElectionTiebreaker<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Return `true` if `a` is preferred over `b` to become the leader.
+    fn prefer(&mut self, a: &C::NodeId, b: &C::NodeId) -> bool;
+}
+
+impl<C> fmt::Debug for dyn ElectionTiebreaker<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ElectionTiebreaker")
+    }
+}
+
+/// An [`ElectionTiebreaker`] that prefers the candidate with the lower node id.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferLowerNodeId;
+
+impl<C> ElectionTiebreaker<C> for PreferLowerNodeId
+where C: RaftTypeConfig
+{
+    fn prefer(&mut self, a: &C::NodeId, b: &C::NodeId) -> bool {
+        a < b
+    }
+}
@@ -1,15 +1,36 @@
 //! Defines API for application to send request to access Raft core.
 
+use std::time::Duration;
+
+use crate::core::raft_msg::ResultSender;
+use crate::engine::Engine;
+use crate::metrics::MetricsHistory;
+use crate::metrics::PendingWrites;
+use crate::metrics::ShutdownReport;
 use crate::OptionalSend;
-use crate::RaftState;
 use crate::RaftTypeConfig;
 
-pub trait BoxCoreFnInternal<C>: FnOnce(&RaftState<C>) + OptionalSend
+/// Access to the data owned by `RaftCore`, for a request function run in the `RaftCore` task.
+pub(crate) trait CoreAccess<C>
+where C: RaftTypeConfig
+{
+    fn engine(&mut self) -> &mut Engine<C>;
+
+    fn metrics_history(&self) -> &MetricsHistory<C>;
+
+    fn pending_writes(&self) -> PendingWrites;
+
+    /// Stop accepting client writes and respond to `tx` once the in-flight operations settle, or
+    /// `timeout` expires.
+    fn prepare_shutdown(&mut self, timeout: Duration, tx: ResultSender<C, ShutdownReport<C>>);
+}
+
+pub(crate) trait BoxCoreFnInternal<C>: FnOnce(&mut dyn CoreAccess<C>) + OptionalSend
 where C: RaftTypeConfig
 {
 }
 
-impl<C: RaftTypeConfig, T: FnOnce(&RaftState<C>) + OptionalSend> BoxCoreFnInternal<C> for T {}
+impl<C: RaftTypeConfig, T: FnOnce(&mut dyn CoreAccess<C>) + OptionalSend> BoxCoreFnInternal<C> for T {}
 
 /// Boxed trait object for external request function run in `RaftCore` task.
 pub(crate) type BoxCoreFn<C> = Box<dyn BoxCoreFnInternal<C> + 'static>;
@@ -2,6 +2,8 @@
 //! Blocking mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use std::collections::BTreeSet;
+
 use maplit::btreemap;
 
 use crate::core::raft_msg::RaftMsg;
@@ -44,17 +46,46 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ///
     /// If it loses leadership or crashed before committing the second **uniform** config log, the
     /// cluster is left in the **joint** config.
+    ///
+    /// If [`Config::member_removal_grace`] is set, removing a node added within the grace period
+    /// fails with [`ChangeMembershipError::RecentlyAdded`]. Use
+    /// [`force_change_membership()`](Self::force_change_membership) to remove it anyway.
+    ///
+    /// [`Config::member_removal_grace`]: crate::Config::member_removal_grace
+    /// [`ChangeMembershipError::RecentlyAdded`]: crate::error::ChangeMembershipError::RecentlyAdded
     #[tracing::instrument(level = "info", skip_all)]
     pub async fn change_membership(
         &self,
         members: impl Into<ChangeMembers<C::NodeId, C::Node>>,
         retain: bool,
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        let changes: ChangeMembers<C::NodeId, C::Node> = members.into();
+        self.do_change_membership(members.into(), retain, false).await
+    }
+
+    /// Propose a cluster configuration change, like [`change_membership()`], but remove a node even
+    /// if it is added within [`Config::member_removal_grace`].
+    ///
+    /// [`change_membership()`]: Self::change_membership
+    /// [`Config::member_removal_grace`]: crate::Config::member_removal_grace
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn force_change_membership(
+        &self,
+        members: impl Into<ChangeMembers<C::NodeId, C::Node>>,
+        retain: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.do_change_membership(members.into(), retain, true).await
+    }
 
+    async fn do_change_membership(
+        &self,
+        changes: ChangeMembers<C::NodeId, C::Node>,
+        retain: bool,
+        force: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         tracing::info!(
             changes = debug(&changes),
             retain = display(retain),
+            force = display(force),
             "change_membership: start to commit joint config"
         );
 
@@ -68,6 +99,7 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
                 RaftMsg::ChangeMembership {
                     changes: changes.clone(),
                     retain,
+                    force,
                     tx,
                 },
                 rx,
@@ -92,7 +124,18 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
 
         let (tx, rx) = oneshot_channel::<C>();
 
-        let res = self.inner.call_core(RaftMsg::ChangeMembership { changes, retain, tx }, rx).await;
+        let res = self
+            .inner
+            .call_core(
+                RaftMsg::ChangeMembership {
+                    changes,
+                    retain,
+                    force,
+                    tx,
+                },
+                rx,
+            )
+            .await;
 
         if let Err(e) = &res {
             tracing::error!("the second step error: {}", e);
@@ -132,6 +175,7 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
         let msg = RaftMsg::ChangeMembership {
             changes: ChangeMembers::AddNodes(btreemap! {id=>node}),
             retain: true,
+            force: false,
             tx,
         };
 
@@ -166,6 +210,56 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
 
         Ok(resp)
     }
+
+    /// Seal the cluster, i.e., make it read-only, and block until the seal is committed.
+    ///
+    /// A sealed cluster rejects client writes with a [`Sealed`](`crate::error::Sealed`) error,
+    /// while reads are still served. Membership changes are still allowed.
+    ///
+    /// Sealing is done by proposing a membership entry with a `sealed` flag. Thus the sealed state
+    /// is replicated, survives leader changes and restarts, until [`Raft::unseal()`] is committed.
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn seal(&self) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = oneshot_channel::<C>();
+        self.inner.call_core(RaftMsg::SetSealed { sealed: true, tx }, rx).await
+    }
+
+    /// Make voters witnesses, and block until the change is committed.
+    ///
+    /// A witness votes and counts toward the commit quorum, but stores only log metadata: a
+    /// leader replicates every entry to a witness as a blank entry with the same log id, except
+    /// membership entries, and a witness never elects itself. It provides an odd-sized quorum
+    /// without the cost of a full replica. A witness that is too far behind still receives a full
+    /// snapshot.
+    ///
+    /// Witnesses are stored in the membership, thus they are replicated and survive restarts. A
+    /// node that is not a voter can not be made a witness, and a witness stays a witness until it
+    /// is removed from the cluster: its log does not have the payloads. It must start with empty
+    /// storage to join the cluster again.
+    ///
+    /// The state machine of a witness applies the blank entries, thus it does not have the
+    /// application data and must not serve reads. [`Raft::read_barrier()`],
+    /// [`Raft::ensure_caught_up()`] and [`Raft::get_committed_digest()`] return a
+    /// [`NodeIsWitness`] error on a witness.
+    ///
+    /// [`NodeIsWitness`]: crate::error::NodeIsWitness
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn add_witnesses(
+        &self,
+        witnesses: BTreeSet<C::NodeId>,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = oneshot_channel::<C>();
+        self.inner.call_core(RaftMsg::AddWitnesses { witnesses, tx }, rx).await
+    }
+
+    /// Unseal the cluster sealed by [`Raft::seal()`], and block until the unseal is committed.
+    ///
+    /// Client writes are accepted again once the unseal is committed.
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn unseal(&self) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = oneshot_channel::<C>();
+        self.inner.call_core(RaftMsg::SetSealed { sealed: false, tx }, rx).await
+    }
 }
 
 fn oneshot_channel<C>() -> (OneshotResponder<C>, OneshotReceiverOf<C, ClientWriteResult<C>>)
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::display_ext::DisplaySlice;
+use crate::entry::RaftEntry;
+use crate::storage::RaftLogReader;
+use crate::LogId;
+use crate::RaftLogId;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// The number of entries to read from the log store at a time when reading a log segment.
+const READ_BATCH: u64 = 64;
+
+/// A segment of committed log entries, returned by
+/// [`Raft::get_log_segment()`](crate::Raft::get_log_segment).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogSegment<C: RaftTypeConfig> {
+    /// The committed entries from the requested start index, in index order, without gap.
+    ///
+    /// It is empty if the start index is not yet committed. Otherwise the next segment starts at
+    /// the index after the last entry.
+    Entries(Vec<C::Entry>),
+
+    /// The requested start index is at or below `purged`, the last purged log id: these entries
+    /// are only in the snapshot.
+    ///
+    /// The caller should install the snapshot first, and continue from the index after `purged`.
+    BelowSnapshot { purged: LogId<C::NodeId> },
+}
+
+impl<C> fmt::Display for LogSegment<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entries(entries) => write!(f, "Entries({})", DisplaySlice::<_>(entries)),
+            Self::BelowSnapshot { purged } => write!(f, "BelowSnapshot(purged: {})", purged),
+        }
+    }
+}
+
+/// Read the entries in `[from, to)` from `log_reader`, until their total
+/// [`size_hint()`](crate::entry::RaftEntry::size_hint) exceeds `max_bytes`.
+///
+/// At least one entry is returned if the range is not empty, so that a caller paging through the
+/// log always makes progress. An entry whose size is unknown is counted as 0 bytes.
+///
+/// The returned entries always start at `from` and are contiguous: if entries are removed while
+/// reading, the ones read before the removed ones are returned, which may be none.
+pub(crate) async fn read_log_segment<C, LR>(
+    log_reader: &mut LR,
+    from: u64,
+    to: u64,
+    max_bytes: u64,
+) -> Result<Vec<C::Entry>, StorageError<C::NodeId>>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    let mut entries = vec![];
+    let mut size = 0;
+    let mut start = from;
+
+    while start < to {
+        let end = std::cmp::min(start + READ_BATCH, to);
+        let batch = log_reader.try_get_log_entries(start..end).await?;
+        let complete = batch.len() as u64 == end - start;
+
+        for ent in batch {
+            // The entries before it are removed, e.g., purged meanwhile.
+            if ent.get_log_id().index != start {
+                return Ok(entries);
+            }
+
+            let ent_size = ent.size_hint().unwrap_or_default();
+            if !entries.is_empty() && size + ent_size > max_bytes {
+                return Ok(entries);
+            }
+
+            size += ent_size;
+            entries.push(ent);
+            start += 1;
+        }
+
+        // The rest of the entries are removed, e.g., purged meanwhile.
+        if !complete {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
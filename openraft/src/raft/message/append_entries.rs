@@ -22,6 +22,46 @@ pub struct AppendEntriesRequest<C: RaftTypeConfig> {
 
     /// The leader's committed log id.
     pub leader_commit: Option<LogId<C::NodeId>>,
+
+    /// The [`Config::timing_hash()`] of the leader, if [`Config::exchange_config_hash`] is
+    /// enabled on it.
+    ///
+    /// [`Config::timing_hash()`]: crate::Config::timing_hash
+    /// [`Config::exchange_config_hash`]: crate::Config::exchange_config_hash
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub config_hash: Option<u64>,
+}
+
+impl<C: RaftTypeConfig> AppendEntriesRequest<C> {
+    pub fn new(
+        vote: Vote<C::NodeId>,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        entries: Vec<C::Entry>,
+        leader_commit: Option<LogId<C::NodeId>>,
+    ) -> Self {
+        Self {
+            vote,
+            prev_log_id,
+            entries,
+            leader_commit,
+            config_hash: None,
+        }
+    }
+
+    /// Attach the [`Config::timing_hash()`] of the leader to this request.
+    ///
+    /// [`Config::timing_hash()`]: crate::Config::timing_hash
+    pub fn with_config_hash(mut self, config_hash: u64) -> Self {
+        self.config_hash = Some(config_hash);
+        self
+    }
+
+    /// Return the [`Config::timing_hash()`] of the leader, if it is attached.
+    ///
+    /// [`Config::timing_hash()`]: crate::Config::timing_hash
+    pub fn config_hash(&self) -> Option<u64> {
+        self.config_hash
+    }
 }
 
 impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C>
@@ -33,6 +73,7 @@ where C::D: fmt::Debug
             .field("prev_log_id", &self.prev_log_id)
             .field("entries", &self.entries)
             .field("leader_commit", &self.leader_commit)
+            .field("config_hash", &self.config_hash)
             .finish()
     }
 }
@@ -66,6 +107,23 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// Successfully replicated all log entries to the target node.
     Success,
 
+    /// Successfully replicated all log entries to the target node, with the log state of the
+    /// target node after handling the request.
+    ///
+    /// Openraft responds with it instead of [`Success`](Self::Success) if
+    /// [`Config::report_log_state`] is enabled on the target, so that the leader keeps track of the
+    /// target even when only heartbeats are sent. `Success` is still accepted, e.g., when it is
+    /// built by an application's network implementation.
+    ///
+    /// [`Config::report_log_state`]: crate::Config::report_log_state
+    SuccessWithLogState {
+        /// The last log id committed on the target node.
+        committed: Option<LogId<C::NodeId>>,
+
+        /// The last log id on the target node.
+        last_log_id: Option<LogId<C::NodeId>>,
+    },
+
     /// Successfully sent the first portion of log entries.
     ///
     /// [`RaftNetwork::append_entries`] can return a partial success.
@@ -91,6 +149,12 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// Seen a vote `v` that does not hold `mine_vote >= v`.
     /// And a leader's vote(committed vote) must be total order with other vote.
     /// Therefore it has to be a higher vote: `mine_vote < v`
+    ///
+    /// A request rejected by the [`AppendEntriesValidator`] on the target node is responded with
+    /// the target's vote, which is **not** greater than the leader's. The leader takes it as a
+    /// rejection and retries later.
+    ///
+    /// [`AppendEntriesValidator`]: crate::raft::AppendEntriesValidator
     HigherVote(Vote<C::NodeId>),
 }
 
@@ -98,7 +162,10 @@ impl<C> AppendEntriesResponse<C>
 where C: RaftTypeConfig
 {
     pub fn is_success(&self) -> bool {
-        matches!(*self, AppendEntriesResponse::Success)
+        matches!(
+            *self,
+            AppendEntriesResponse::Success | AppendEntriesResponse::SuccessWithLogState { .. }
+        )
     }
 
     pub fn is_conflict(&self) -> bool {
@@ -112,6 +179,14 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppendEntriesResponse::Success => write!(f, "Success"),
+            AppendEntriesResponse::SuccessWithLogState { committed, last_log_id } => {
+                write!(
+                    f,
+                    "Success(committed:{}, last_log_id:{})",
+                    committed.display(),
+                    last_log_id.display()
+                )
+            }
             AppendEntriesResponse::PartialSuccess(m) => {
                 write!(f, "PartialSuccess({})", m.display())
             }
@@ -53,6 +53,17 @@ where C: RaftTypeConfig
         &self.data
     }
 
+    /// Return a fencing token for this write.
+    ///
+    /// The token is the index of the committed log entry. Indexes of committed entries are
+    /// globally monotonic, even across leader changes, thus an external resource can reject a
+    /// request carrying a token smaller than the greatest one it has seen.
+    ///
+    /// See: [`Raft::get_fencing_token()`](crate::Raft::get_fencing_token)
+    pub fn fencing_token(&self) -> u64 {
+        self.log_id.index
+    }
+
     /// Return membership config if the log entry is a change-membership entry.
     #[since(version = "0.9.5")]
     pub fn membership(&self) -> &Option<Membership<C>> {
@@ -1,18 +1,32 @@
 //! Public Raft interface and data types.
 
+mod append_entries_validator;
+mod apply_coordinator;
+mod apply_observer;
+mod commit_status;
+mod commit_wait;
+mod committed_stream;
 #[cfg(test)] mod declare_raft_types_test;
+mod dyn_log_reader;
+mod election_admission;
+mod election_tiebreaker;
 mod external_request;
 mod impl_raft_blocking_write;
+mod log_segment;
 pub(crate) mod message;
 mod raft_inner;
 pub mod responder;
+mod rpc_span;
 mod runtime_config_handle;
 pub mod trigger;
+mod write_deduplicator;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::error::Error;
 
 pub(crate) use self::external_request::BoxCoreFn;
+pub(crate) use self::external_request::CoreAccess;
 
 pub(in crate::raft) mod core_state;
 
@@ -21,7 +35,23 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use anyerror::AnyError;
+pub use append_entries_validator::AppendEntriesValidator;
+pub use apply_coordinator::ApplyCoordinator;
+pub use apply_coordinator::ApplyPermit;
+pub use apply_observer::ApplyObserver;
+pub use commit_status::CommitStatus;
+pub use commit_wait::CommitWait;
+pub(crate) use committed_stream::feed_committed;
+pub use committed_stream::Committed;
+pub use committed_stream::CommittedStream;
 use core_state::CoreState;
+pub use election_admission::ElectionAdmission;
+pub use election_admission::ElectionPermit;
+pub use election_tiebreaker::ElectionTiebreaker;
+pub use election_tiebreaker::PreferLowerNodeId;
+pub(crate) use log_segment::read_log_segment;
+pub use log_segment::LogSegment;
 pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
 pub use message::ClientWriteResponse;
@@ -37,51 +67,92 @@ use tokio::sync::Mutex;
 use tracing::trace_span;
 use tracing::Instrument;
 use tracing::Level;
+pub use write_deduplicator::WriteDeduplicator;
 
 use crate::async_runtime::AsyncOneshotSendExt;
 use crate::config::Config;
 use crate::config::RuntimeConfig;
 use crate::core::command_state::CommandState;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::raft_msg::ClientPayload;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::replication_lag;
+use crate::core::sm::apply_rate::ApplyRate;
 use crate::core::sm::worker;
 use crate::core::RaftCore;
 use crate::core::Tick;
+use crate::digest::digest_committed;
+#[cfg(feature = "decision-trace")]
+pub use crate::engine::decision_trace::DecisionRecord;
+#[cfg(feature = "decision-trace")]
+pub use crate::engine::decision_trace::StateDigest;
+#[cfg(feature = "decision-trace")]
+pub use crate::engine::decision_trace::TraceInput;
+#[cfg(feature = "decision-trace")]
+use crate::engine::decision_trace::TraceWriter;
 use crate::engine::Engine;
 use crate::engine::EngineConfig;
+use crate::entry::RaftEntry;
+use crate::error::CatchUpError;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
+use crate::error::CommittedDigestError;
 use crate::error::Fatal;
+use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::InstallSnapshotError;
+use crate::error::NodeIsWitness;
 use crate::error::RaftError;
+use crate::error::SetAppliedIndexError;
+use crate::error::UnsafeCommitQuorumNotAllowed;
+use crate::error::UnsupportedSnapshotFormat;
 use crate::membership::IntoNodes;
+use crate::metrics::ClusterState;
+use crate::metrics::DisplayLabels;
+use crate::metrics::MetricsHistory;
+use crate::metrics::MetricsSample;
+use crate::metrics::PendingWrites;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::ReplicationDetail;
+use crate::metrics::ShutdownReport;
+use crate::metrics::VoteRecord;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
 use crate::network::RaftNetworkFactory;
+use crate::network::SnapshotBandwidth;
+use crate::raft::dyn_log_reader::BoxLogReader;
 use crate::raft::raft_inner::RaftInner;
 use crate::raft::responder::Responder;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
 use crate::raft::trigger::Trigger;
+use crate::raft_state::LogStateReader;
+use crate::storage::LastApplied;
+use crate::storage::LogGap;
+use crate::storage::RaftLogReader;
+use crate::storage::RaftLogReaderExt;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
 use crate::type_config::alias::AsyncRuntimeOf;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::ResponderReceiverOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::AsyncRuntime;
+use crate::Instant;
 use crate::LogId;
 use crate::LogIdOptionExt;
 use crate::OptionalSend;
+use crate::RaftLogId;
 use crate::RaftState;
 pub use crate::RaftTypeConfig;
 use crate::Snapshot;
+use crate::StorageError;
 use crate::StorageHelper;
+use crate::StorageIOError;
 use crate::Vote;
 
 /// Define types for a Raft type configuration.
@@ -217,6 +288,16 @@ where C: RaftTypeConfig
     /// ### `storage`
     /// An implementation of the [`RaftLogStorage`] and [`RaftStateMachine`] trait which will be
     /// used by Raft for data storage.
+    ///
+    /// Before it returns, the state recovered from the storage is loaded: the vote, the log state,
+    /// the committed log id, the last applied log id, the membership config and the snapshot.
+    /// Committed logs that are not yet applied are re-applied. Thus a request to the returned
+    /// `Raft` is always handled with the recovered state.
+    ///
+    /// It panics if [`Config::max_message_size`] is set but the log entry type does not provide
+    /// its size with [`RaftEntry::size_hint()`].
+    ///
+    /// [`RaftEntry::size_hint()`]: crate::entry::RaftEntry::size_hint
     #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
     pub async fn new<LS, N, SM>(
         id: C::NodeId,
@@ -230,11 +311,24 @@ where C: RaftTypeConfig
         LS: RaftLogStorage<C>,
         SM: RaftStateMachine<C>,
     {
+        assert!(
+            config.max_message_size == 0 || C::Entry::new_blank(LogId::default()).size_hint().is_some(),
+            "max_message_size is set but the log entry type does not implement RaftEntry::size_hint()"
+        );
+
         let (tx_api, rx_api) = mpsc::unbounded_channel();
         let (tx_notify, rx_notify) = mpsc::unbounded_channel();
-        let (tx_metrics, rx_metrics) = watch::channel(RaftMetrics::new_initial(id));
+        let labels = Arc::new(config.label_map());
+
+        let (tx_metrics, rx_metrics) = watch::channel(RaftMetrics {
+            labels: labels.clone(),
+            ..RaftMetrics::new_initial(id)
+        });
         let (tx_data_metrics, rx_data_metrics) = watch::channel(RaftDataMetrics::default());
-        let (tx_server_metrics, rx_server_metrics) = watch::channel(RaftServerMetrics::default());
+        let (tx_server_metrics, rx_server_metrics) = watch::channel(RaftServerMetrics {
+            labels: labels.clone(),
+            ..RaftServerMetrics::default()
+        });
         let (tx_shutdown, rx_shutdown) = C::AsyncRuntime::oneshot();
 
         let tick_handle = Tick::spawn(
@@ -250,7 +344,8 @@ where C: RaftTypeConfig
             Level::DEBUG,
             "RaftCore",
             id = display(id),
-            cluster = display(&config.cluster_name)
+            cluster = display(&config.cluster_name),
+            labels = display(DisplayLabels(&labels))
         );
 
         let eng_config = EngineConfig::new(id, config.as_ref());
@@ -262,7 +357,18 @@ where C: RaftTypeConfig
 
         let engine = Engine::new(state, eng_config);
 
-        let sm_handle = worker::Worker::spawn(state_machine, tx_notify.clone());
+        let snapshot_format_version = state_machine.snapshot_format_version();
+        let apply_rate = config
+            .apply_rate_limit
+            .map(|n| ApplyRate::new(n, Duration::from_millis(config.apply_rate_interval)));
+        let log_reader = BoxLogReader::new(log_store.get_log_reader().await);
+        let sm_handle = worker::Worker::spawn(
+            state_machine,
+            log_store.get_log_reader().await,
+            apply_rate,
+            config.apply_stall_backlog.is_some(),
+            tx_notify.clone(),
+        );
 
         let core: RaftCore<C, N, LS, SM> = RaftCore {
             id,
@@ -275,8 +381,26 @@ where C: RaftTypeConfig
             engine,
 
             client_resp_channels: BTreeMap::new(),
+            responder_sweep_cursor: 0,
+            client_submit_times: BTreeMap::new(),
+            shutting_down: false,
+            shutdown_waiter: None,
+            apply_error: None,
+            apply_retry_at: None,
+            last_timestamp: 0,
 
             leader_data: None,
+            read_batch: Default::default(),
+            storage_failures: Default::default(),
+            vote_requests: Default::default(),
+            quiescence: Default::default(),
+            metrics_history: MetricsHistory::new(config.metrics_history_size as usize),
+            append_entries_validator: None,
+            election_admission: None,
+            admission_waiting: None,
+            admitted_campaign: false,
+            write_dedup: None,
+            registered_nodes: (None, BTreeMap::new()),
 
             tx_api: tx_api.clone(),
             rx_api,
@@ -287,6 +411,18 @@ where C: RaftTypeConfig
             tx_metrics,
             tx_data_metrics,
             tx_server_metrics,
+            rpc_latency: BTreeMap::new(),
+            follower_log_state: BTreeMap::new(),
+            config_mismatches: BTreeMap::new(),
+            snapshot_bandwidth: SnapshotBandwidth::new(
+                config.snapshot_max_bytes_per_sec,
+                config.snapshot_max_total_bytes_per_sec,
+            ),
+            snapshot_sent_sample: None,
+            snapshot_send_rate: 0,
+            labels,
+            peer_labels: BTreeMap::new(),
+            commit_waits: Default::default(),
 
             command_state: CommandState::default(),
             span: core_span,
@@ -309,6 +445,8 @@ where C: RaftTypeConfig
             core_state: Mutex::new(CoreState::Running(core_handle)),
 
             snapshot: Mutex::new(None),
+            snapshot_format_version,
+            log_reader: Mutex::new(log_reader),
         };
 
         Ok(Self { inner: Arc::new(inner) })
@@ -345,16 +483,198 @@ where C: RaftTypeConfig
         Trigger::new(self.inner.as_ref())
     }
 
+    /// Set an observer to be notified when a log entry is applied to the state machine on this
+    /// node. It replaces the previously set observer, if any.
+    ///
+    /// It returns at once. Entries applied after the observer is installed in the state machine
+    /// worker are observed. See [`ApplyObserver`] for the guarantees it provides.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_apply_observer(&self, observer: impl ApplyObserver<C>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetApplyObserver {
+            observer: Box::new(observer),
+        };
+        self.inner.send_external_command(cmd, "set_apply_observer").await
+    }
+
+    /// Set a coordinator that defers applying every log entry on this node until it permits. It
+    /// replaces the previously set coordinator, if any.
+    ///
+    /// It returns at once. Entries applied after the coordinator is installed in the state machine
+    /// worker are coordinated. See [`ApplyCoordinator`] for the guarantees it provides.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_apply_coordinator(&self, coordinator: impl ApplyCoordinator<C>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetApplyCoordinator {
+            coordinator: Box::new(coordinator),
+        };
+        self.inner.send_external_command(cmd, "set_apply_coordinator").await
+    }
+
+    /// Set a validator to check every AppendEntries request received by this node, in addition to
+    /// the normal checks. It replaces the previously set validator, if any.
+    ///
+    /// It returns at once. See [`AppendEntriesValidator`] for how it is applied.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_append_entries_validator(
+        &self,
+        validator: impl AppendEntriesValidator<C>,
+    ) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetAppendEntriesValidator {
+            validator: Box::new(validator),
+        };
+        self.inner.send_external_command(cmd, "set_append_entries_validator").await
+    }
+
+    /// Set a tiebreaker to prefer one of the candidates whose logs are equally up to date, when
+    /// this node handles a vote request. It replaces the previously set tiebreaker, if any.
+    ///
+    /// It returns at once. See [`ElectionTiebreaker`] for how it is applied.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_election_tiebreaker(&self, tiebreaker: impl ElectionTiebreaker<C>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetElectionTiebreaker {
+            tiebreaker: Box::new(tiebreaker),
+        };
+        self.inner.send_external_command(cmd, "set_election_tiebreaker").await
+    }
+
+    /// Set a hook to consult before this node starts an election on election timeout. It replaces
+    /// the previously set one, if any.
+    ///
+    /// It returns at once. See [`ElectionAdmission`] for how it is applied.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_election_admission(&self, admission: impl ElectionAdmission<C>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetElectionAdmission {
+            admission: Box::new(admission),
+        };
+        self.inner.send_external_command(cmd, "set_election_admission").await
+    }
+
+    /// Write a [`DecisionRecord`] of every incoming message handled by this node to `writer`, as
+    /// a line of JSON, in the order the messages are handled. Pass `None` to stop recording.
+    ///
+    /// A record contains the message, the state before handling it, the decision and the state
+    /// after. It is meant for examining a hard-to-reproduce issue offline.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    #[cfg(feature = "decision-trace")]
+    pub async fn set_decision_trace(
+        &self,
+        writer: Option<impl std::io::Write + OptionalSend + 'static>,
+    ) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetDecisionTrace {
+            writer: writer.map(|w| Box::new(w) as Box<dyn TraceWriter>),
+        };
+        self.inner.send_external_command(cmd, "set_decision_trace").await
+    }
+
+    /// Set the labels of the other nodes, which are used by this node as a leader to find the
+    /// followers a write with a [`CommitWait`] waits for. It replaces the previously set labels.
+    ///
+    /// The labels of this node itself are set with [`Config::labels`].
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_peer_labels(&self, labels: BTreeMap<C::NodeId, BTreeMap<String, String>>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetPeerLabels { labels };
+        self.inner.send_external_command(cmd, "set_peer_labels").await
+    }
+
+    /// Set a deduplicator to skip a client write identical to the last one, when this node is a
+    /// leader. It replaces the previously set deduplicator, if any.
+    ///
+    /// It returns at once. See [`WriteDeduplicator`] for how writes are deduplicated.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn set_write_deduplicator(&self, deduplicator: impl WriteDeduplicator<C>) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::SetWriteDeduplicator {
+            deduplicator: Box::new(deduplicator),
+        };
+        self.inner.send_external_command(cmd, "set_write_deduplicator").await
+    }
+
+    /// **DANGEROUS**: lower the commit quorum to the `surviving` nodes, or restore the normal
+    /// commit quorum if it is `None`.
+    ///
+    /// This is only for disaster recovery, when a majority of the voters is permanently lost. While
+    /// it is set, the leader commits a log once every surviving node has accepted it, instead of a
+    /// quorum of voters. It breaks the safety of raft: committed logs may be lost, and two
+    /// leaders may commit conflicting logs. A warning is logged every time a log is committed this
+    /// way.
+    ///
+    /// It returns [`UnsafeCommitQuorumNotAllowed`] if
+    /// [`Config::allow_unsafe_commit_quorum`] is not enabled. Restoring the normal commit quorum is
+    /// always allowed.
+    #[tracing::instrument(level = "warn", skip(self))]
+    pub async fn set_unsafe_commit_quorum(
+        &self,
+        surviving: Option<BTreeSet<C::NodeId>>,
+    ) -> Result<(), RaftError<C, UnsafeCommitQuorumNotAllowed>> {
+        if surviving.is_some() && !self.inner.config.allow_unsafe_commit_quorum {
+            return Err(RaftError::APIError(UnsafeCommitQuorumNotAllowed {}));
+        }
+
+        let cmd = ExternalCommand::SetUnsafeCommitQuorum { surviving };
+        self.inner.send_external_command(cmd, "set_unsafe_commit_quorum").await?;
+        Ok(())
+    }
+
+    /// **DANGEROUS**: tell Raft that the state machine has been restored out-of-band up to log
+    /// `index`, so that entries up to it are not applied again.
+    ///
+    /// It is only for manual recovery, e.g., when an operator restores the state machine from a
+    /// backup while the application can not report it with
+    /// [`RaftStateMachine::applied_state()`]. Committed entries up to `index` that are not yet
+    /// applied are skipped, including those already sent to the state machine worker. A client
+    /// waiting for the response of a skipped entry receives an [`ApplySkipped`] error, since the
+    /// result of applying it is not available.
+    ///
+    /// The skip only lasts for the lifetime of this process, it is not persisted. After a restart,
+    /// committed entries after the last applied log id reported by
+    /// [`RaftStateMachine::applied_state()`] are applied again, thus the restored state machine
+    /// should report the restored applied log id.
+    ///
+    /// `index` must not be greater than the committed index, otherwise
+    /// [`SetAppliedIndexError::BeyondCommitted`] is returned. An `index` that is already applied
+    /// is ignored. It returns [`SetAppliedIndexError::NotAllowed`] if
+    /// [`Config::allow_unsafe_set_applied`] is not enabled.
+    ///
+    /// [`RaftStateMachine::applied_state()`]: crate::storage::RaftStateMachine::applied_state
+    /// [`ApplySkipped`]: crate::error::ApplySkipped
+    #[tracing::instrument(level = "warn", skip(self))]
+    pub async fn set_applied_index(&self, index: u64) -> Result<(), RaftError<C, SetAppliedIndexError<C>>> {
+        if !self.inner.config.allow_unsafe_set_applied {
+            return Err(RaftError::APIError(SetAppliedIndexError::NotAllowed));
+        }
+
+        tracing::warn!(index, "Raft::set_applied_index()");
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::SetAppliedIndex { index, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
     /// Submit an AppendEntries RPC to this Raft node.
     ///
     /// These RPCs are sent by the cluster leader to replicate log entries (§5.3), and are also
     /// used as heartbeats (§5.2).
+    ///
+    /// An RPC received while this node is still starting up is queued and is handled once
+    /// `RaftCore` finishes loading its state. It is never dropped, thus the leader does not need
+    /// to retry it.
     #[tracing::instrument(level = "debug", skip(self, rpc))]
     pub async fn append_entries(&self, rpc: AppendEntriesRequest<C>) -> Result<AppendEntriesResponse<C>, RaftError<C>> {
         tracing::debug!(rpc = display(&rpc), "Raft::append_entries");
 
+        let span = rpc_span::append_entries(self.inner.id, &rpc);
+
         let (tx, rx) = C::AsyncRuntime::oneshot();
-        self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).await
+        let res = self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).instrument(span.clone()).await;
+
+        rpc_span::record_append_entries(&span, &res);
+        res
     }
 
     /// Submit a VoteRequest (RequestVote in the spec) RPC to this Raft node.
@@ -365,8 +685,13 @@ where C: RaftTypeConfig
     pub async fn vote(&self, rpc: VoteRequest<C>) -> Result<VoteResponse<C>, RaftError<C>> {
         tracing::info!(rpc = display(&rpc), "Raft::vote()");
 
+        let span = rpc_span::vote(self.inner.id, &rpc);
+
         let (tx, rx) = C::AsyncRuntime::oneshot();
-        self.inner.call_core(RaftMsg::RequestVote { rpc, tx }, rx).await
+        let res = self.inner.call_core(RaftMsg::RequestVote { rpc, tx }, rx).instrument(span.clone()).await;
+
+        rpc_span::record_vote(&span, &res);
+        res
     }
 
     /// Get the latest snapshot from the state machine.
@@ -397,6 +722,14 @@ where C: RaftTypeConfig
     /// This method is used to implement a totally application defined snapshot transmission.
     /// The application receives a snapshot from the leader, in chunks or a stream, and
     /// then rebuild a snapshot, then pass the snapshot to Raft to install.
+    ///
+    /// The snapshot must be of the format version of the local state machine. A snapshot received
+    /// from a node of another version should be passed through [`Raft::migrate_snapshot()`]
+    /// first.
+    ///
+    /// A snapshot whose `last_log_id` is not greater than the committed log id of this node, e.g.,
+    /// one sent by a stale leader or delivered out of order, is ignored, so that the state machine
+    /// never goes backward. The response still carries the current vote of this node.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn install_full_snapshot(
         &self,
@@ -405,15 +738,64 @@ where C: RaftTypeConfig
     ) -> Result<SnapshotResponse<C>, Fatal<C>> {
         tracing::info!("Raft::install_full_snapshot()");
 
-        let (tx, rx) = C::AsyncRuntime::oneshot();
-        let res = self.inner.call_core(RaftMsg::InstallFullSnapshot { vote, snapshot, tx }, rx).await;
-        match res {
-            Ok(x) => Ok(x),
-            Err(e) => {
-                // Safe unwrap: `RaftError<Infallible>` must be a Fatal.
-                Err(e.into_fatal().unwrap())
+        let span = rpc_span::install_snapshot::<C>(self.inner.id, &vote);
+
+        let res = async {
+            let (tx, rx) = C::AsyncRuntime::oneshot();
+            let res = self.inner.call_core(RaftMsg::InstallFullSnapshot { vote, snapshot, tx }, rx).await;
+            match res {
+                Ok(x) => Ok(x),
+                Err(e) => {
+                    // Safe unwrap: `RaftError<Infallible>` must be a Fatal.
+                    Err(e.into_fatal().unwrap())
+                }
             }
         }
+        .instrument(span.clone())
+        .await;
+
+        rpc_span::record_result(&span, &res);
+        res
+    }
+
+    /// Bring a received snapshot to the format version of the local state machine.
+    ///
+    /// A snapshot with an older [`SnapshotMeta::format_version()`] is migrated by
+    /// [`RaftStateMachine::migrate_snapshot()`]; a snapshot of the current version is returned
+    /// as is. If it can not be migrated, or it is newer than the state machine supports, it returns
+    /// [`InstallSnapshotError::UnsupportedSnapshotFormat`].
+    ///
+    /// Snapshots received with [`Raft::install_snapshot()`] are migrated automatically.
+    ///
+    /// [`SnapshotMeta::format_version()`]: crate::SnapshotMeta::format_version
+    pub async fn migrate_snapshot(
+        &self,
+        snapshot: Snapshot<C>,
+    ) -> Result<Snapshot<C>, RaftError<C, InstallSnapshotError>> {
+        let format_version = snapshot.meta.format_version();
+        let current = self.inner.snapshot_format_version;
+
+        if format_version == current {
+            return Ok(snapshot);
+        }
+
+        if format_version > current {
+            tracing::warn!(
+                format_version,
+                current,
+                "snapshot format version is newer than supported"
+            );
+            let err = UnsupportedSnapshotFormat {
+                format_version,
+                current,
+            };
+            return Err(RaftError::APIError(err.into()));
+        }
+
+        tracing::info!(format_version, current, "migrate snapshot");
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        self.inner.call_core(RaftMsg::MigrateSnapshot { snapshot, tx }, rx).await
     }
 
     /// Receive an `InstallSnapshotRequest`.
@@ -434,7 +816,7 @@ where C: RaftTypeConfig
     pub async fn install_snapshot(
         &self,
         req: InstallSnapshotRequest<C>,
-    ) -> Result<InstallSnapshotResponse<C>, RaftError<C, crate::error::InstallSnapshotError>>
+    ) -> Result<InstallSnapshotResponse<C>, RaftError<C, InstallSnapshotError>>
     where
         C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
     {
@@ -465,6 +847,7 @@ where C: RaftTypeConfig
         };
 
         if let Some(snapshot) = finished_snapshot {
+            let snapshot = self.migrate_snapshot(snapshot).await?;
             let resp = self.install_full_snapshot(req_vote, snapshot).await?;
             return Ok(resp.into());
         }
@@ -481,6 +864,19 @@ where C: RaftTypeConfig
         self.metrics().borrow().current_leader
     }
 
+    /// Get the time elapsed since this node last accepted an `AppendEntries` request from the
+    /// leader.
+    ///
+    /// It is `None` if this node is the leader or has never heard from a leader. Unlike
+    /// [`RaftMetrics::millis_since_leader_contact`], which is refreshed periodically, it is
+    /// evaluated when it is called. A duration greater than the election timeout strongly
+    /// suggests this node is partitioned from the leader, and it can be used in a health check or
+    /// to route client requests.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn time_since_leader_contact(&self) -> Result<Option<Duration>, Fatal<C>> {
+        self.with_raft_state(|st| st.time_since_leader_contact()).await
+    }
+
     /// Check to ensure this node is still the cluster leader, in order to guard against stale reads
     /// (§8).
     ///
@@ -535,6 +931,90 @@ where C: RaftTypeConfig
         Ok(read_log_id)
     }
 
+    /// Waits for the state machine to apply all the logs that are committed on this node at the
+    /// time of invocation, and returns the committed log id.
+    ///
+    /// A read performed after this method reflects every write committed before this call, as
+    /// long as this node has already learned about the commit.
+    ///
+    /// Unlike [`ensure_linearizable()`](Raft::ensure_linearizable), it does not confirm the
+    /// leadership. A stale leader or a lagging follower may not have seen the latest committed
+    /// logs, therefore the read is **not** linearizable across the cluster. It is only suitable
+    /// for reads that tolerate staleness but want to see the local writes.
+    ///
+    /// It returns [`NodeIsWitness`] on a witness, whose state machine does not have the
+    /// application data.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn read_barrier(&self) -> Result<Option<LogId<C::NodeId>>, RaftError<C, NodeIsWitness<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let (committed, applied) = self.inner.call_core(RaftMsg::ReadBarrier { tx }, rx).await?;
+
+        if committed.index() > applied.index() {
+            self.wait(None)
+                .applied_index_at_least(committed.index(), "read_barrier")
+                .await
+                .map_err(|e| match e {
+                    WaitError::Timeout(_, _) => {
+                        unreachable!("did not specify timeout")
+                    }
+                    WaitError::ShuttingDown => Fatal::Stopped,
+                })?;
+        }
+        Ok(committed)
+    }
+
+    /// Waits for this node to catch up with the leader's commit index, and returns the
+    /// `read_log_id`, so that a read on this node, which may be a follower or a learner, is
+    /// linearizable.
+    ///
+    /// On the leader, it is the same as [`ensure_linearizable()`](Raft::ensure_linearizable).
+    /// On other nodes, it asks the leader for the `read_log_id` with
+    /// [`RaftNetwork::get_read_log_id()`], then waits for the local state machine to apply up to
+    /// it.
+    ///
+    /// It returns [`CatchUpError::Timeout`] if it does not finish within `timeout`,
+    /// [`CatchUpError::ForwardToLeader`] if the leader is unknown or can not be reached, and
+    /// [`CatchUpError::IsWitness`] on a witness, whose state machine does not have the application
+    /// data.
+    ///
+    /// [`RaftNetwork::get_read_log_id()`]: crate::network::RaftNetwork::get_read_log_id
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn ensure_caught_up(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<LogId<C::NodeId>>, RaftError<C, CatchUpError<C>>> {
+        let start = InstantOf::<C>::now();
+
+        let is_leader = self.metrics().borrow().current_leader == Some(self.inner.id);
+
+        let read_log_id = if is_leader {
+            let res = C::AsyncRuntime::timeout(timeout, self.get_read_log_id())
+                .await
+                .map_err(|_elapsed| RaftError::APIError(CatchUpError::Timeout(timeout)))?;
+
+            let (read_log_id, _applied) = res.map_err(|e| match e {
+                RaftError::APIError(e) => RaftError::APIError(e.into()),
+                RaftError::Fatal(f) => RaftError::Fatal(f),
+            })?;
+            read_log_id
+        } else {
+            let (tx, rx) = C::AsyncRuntime::oneshot();
+            self.inner.call_core(RaftMsg::GetLeaderReadLogId { timeout, tx }, rx).await?
+        };
+
+        self.wait(Some(timeout.saturating_sub(start.elapsed())))
+            .applied_index_at_least(read_log_id.index(), "ensure_caught_up")
+            .await
+            .map_err(|e| match e {
+                WaitError::Timeout(_, _) => RaftError::APIError(CatchUpError::Timeout(timeout)),
+                WaitError::ShuttingDown => RaftError::Fatal(Fatal::Stopped),
+            })?;
+
+        Ok(read_log_id)
+    }
+
     /// Ensures this node is leader and returns the log id up to which the state machine should
     /// apply to ensure a read can be linearizable across the cluster.
     ///
@@ -573,6 +1053,25 @@ where C: RaftTypeConfig
         Ok((read_log_id, applied))
     }
 
+    /// Ensures this node is leader and returns a fencing token.
+    ///
+    /// The token is the index of the `read_log_id` returned by
+    /// [`get_read_log_id()`](Self::get_read_log_id), i.e., it is no less than the index of any
+    /// log committed before this call. It is monotonic with the tokens returned by
+    /// [`ClientWriteResponse::fencing_token()`]: a leader elected later always returns a greater
+    /// token, because its `read_log_id` is at least the blank log it appended when elected.
+    ///
+    /// An external resource protected by the token should reject a request carrying a token
+    /// smaller than the greatest one it has seen, so that a stale leader can not overwrite it.
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_fencing_token(&self) -> Result<u64, RaftError<C, CheckIsLeaderError<C>>> {
+        let (read_log_id, _applied) = self.get_read_log_id().await?;
+        // A leader always has the blank log appended when elected, thus `read_log_id` is `Some`.
+        Ok(read_log_id.index().unwrap_or_default())
+    }
+
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
     ///
     /// It will be appended to the log, committed to the cluster, and then applied to the
@@ -591,6 +1090,20 @@ where C: RaftTypeConfig
     ///
     /// These are application specific requirements, and must be implemented by the application
     /// which is being built on top of Raft.
+    ///
+    /// Openraft has no separate path for a pre-serialized command: `app_data` is stored in the log
+    /// and handed to the state machine as it is. An application that already holds the encoded
+    /// command, and wants to avoid encoding it again, defines [`RaftTypeConfig::D`] as the bytes,
+    /// e.g., `Vec<u8>`, and decodes them in [`RaftStateMachine::apply`].
+    ///
+    /// The leader stamps the entry with its wall clock time, see
+    /// [`RaftEntry::set_timestamp()`](crate::entry::RaftEntry::set_timestamp), which never goes
+    /// backward across leaders. The default [`Entry`](crate::Entry) discards the timestamp: an
+    /// application that reads it when applying has to define its own [`RaftTypeConfig::Entry`].
+    ///
+    /// If this node is not the leader, a `ForwardToLeader` error is returned, unless
+    /// [`Config::forward_client_write`] is enabled, in which case the request is forwarded to the
+    /// leader with [`RaftNetwork::client_write()`](crate::network::RaftNetwork::client_write).
     #[tracing::instrument(level = "debug", skip(self, app_data))]
     pub async fn client_write<E>(
         &self,
@@ -600,12 +1113,123 @@ where C: RaftTypeConfig
         ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
         E: Error + OptionalSend,
     {
-        let rx = self.client_write_ff(app_data).await?;
+        self.do_client_write(app_data, false, None, None).await
+    }
 
-        let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+    /// Submit a mutating client request along with opaque `metadata`, and wait for it to be
+    /// applied.
+    ///
+    /// It is the same as [`Raft::client_write`], except that `metadata` is stored in the log entry
+    /// with [`RaftEntry::set_metadata()`], replicated along with it, and can be read with
+    /// [`RaftEntry::get_metadata()`] when the entry is applied on every node. E.g., a trace id to
+    /// correlate applying the entry with the client request. Openraft does not interpret it.
+    ///
+    /// The default [`Entry`](crate::Entry) discards the metadata: an application that needs it
+    /// uses an entry type that stores it.
+    ///
+    /// A write with metadata is never forwarded to the leader: if this node is not the leader, a
+    /// `ForwardToLeader` error is returned.
+    ///
+    /// [`RaftEntry::set_metadata()`]: crate::entry::RaftEntry::set_metadata
+    /// [`RaftEntry::get_metadata()`]: crate::entry::RaftEntry::get_metadata
+    #[tracing::instrument(level = "debug", skip(self, app_data, metadata))]
+    pub async fn client_write_with_metadata<E>(
+        &self,
+        app_data: C::D,
+        metadata: Vec<u8>,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        self.do_client_write(app_data, false, Some(metadata), None).await
+    }
 
-        let client_write_response = res.map_err(|e| RaftError::APIError(e))?;
-        Ok(client_write_response)
+    /// Submit a mutating client request, and wait for it to be applied and acknowledged by a
+    /// follower in the group given by `commit_wait`.
+    ///
+    /// It is the same as [`Raft::client_write`], except that the response is returned only after
+    /// at least one follower whose label `commit_wait.label` is `commit_wait.value`, as set with
+    /// [`Raft::set_peer_labels()`], has also received the entry. If it does not happen within
+    /// `commit_wait.timeout`, a [`CommitWaitTimeout`](crate::error::CommitWaitTimeout) error is
+    /// returned, even though the entry may be committed and applied.
+    ///
+    /// Such a write is never forwarded to the leader: if this node is not the leader, a
+    /// `ForwardToLeader` error is returned.
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_with_commit_wait<E>(
+        &self,
+        app_data: C::D,
+        commit_wait: CommitWait,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        self.do_client_write(app_data, false, None, Some(commit_wait)).await
+    }
+
+    /// Submit a checkpoint entry carrying `app_data`, and wait for it to be applied.
+    ///
+    /// It is committed and applied like an entry submitted with [`Raft::client_write`], but the
+    /// entry is built with [`RaftEntry::new_checkpoint()`], so that an entry type that supports
+    /// checkpoints lets the state machine tell it apart with [`RaftEntry::is_checkpoint()`] and
+    /// act on it, e.g., compact its internal data. With the default [`Entry`](crate::Entry) it is
+    /// applied as a normal entry. Openraft does nothing else with it.
+    ///
+    /// A checkpoint is never forwarded to the leader: if this node is not the leader, a
+    /// `ForwardToLeader` error is returned.
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_checkpoint<E>(
+        &self,
+        app_data: C::D,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        self.do_client_write(app_data, true, None, None).await
+    }
+
+    async fn do_client_write<E>(
+        &self,
+        app_data: C::D,
+        checkpoint: bool,
+        metadata: Option<Vec<u8>>,
+        commit_wait: Option<CommitWait>,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let span = rpc_span::client_write::<C>(self.inner.id);
+
+        let res = async {
+            let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
+            let payload = if checkpoint {
+                ClientPayload::Checkpoint(app_data)
+            } else {
+                ClientPayload::AppData(app_data)
+            };
+            self.inner
+                .send_msg(RaftMsg::ClientWriteRequest {
+                    payload,
+                    metadata,
+                    commit_wait,
+                    tx,
+                })
+                .await?;
+
+            let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+
+            let client_write_response = res.map_err(|e| RaftError::APIError(e))?;
+            Ok(client_write_response)
+        }
+        .instrument(span.clone())
+        .await;
+
+        rpc_span::record_client_write(&span, &res);
+        res
     }
 
     /// Submit a mutating client request to Raft to update the state machine, returns an application
@@ -618,11 +1242,29 @@ where C: RaftTypeConfig
     pub async fn client_write_ff(&self, app_data: C::D) -> Result<ResponderReceiverOf<C>, Fatal<C>> {
         let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
 
-        self.inner.send_msg(RaftMsg::ClientWriteRequest { app_data, tx }).await?;
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                payload: ClientPayload::AppData(app_data),
+                metadata: None,
+                commit_wait: None,
+                tx,
+            })
+            .await?;
 
         Ok(rx)
     }
 
+    /// Return the first log id of every leader known by this node, in index order.
+    ///
+    /// The log at index `i` is proposed by the leader of the last returned log id whose index is
+    /// `<= i`. It is meant for debugging: leaders whose logs are purged are included only if
+    /// [`Config::term_history_size`] is not 0.
+    pub async fn term_history(&self) -> Result<Vec<LogId<C::NodeId>>, Fatal<C>> {
+        let history = self.with_raft_state(|st| st.term_history()).await?;
+
+        Ok(history)
+    }
+
     /// Return `true` if this node is already initialized and can not be initialized again with
     /// [`Raft::initialize`]
     pub async fn is_initialized(&self) -> Result<bool, Fatal<C>> {
@@ -667,6 +1309,33 @@ where C: RaftTypeConfig
             .await
     }
 
+    /// Initialize a pristine Raft node with a snapshot, e.g., one built from an existing dataset.
+    ///
+    /// It is a fast way to bootstrap a cluster with a large initial state: the node installs the
+    /// snapshot, takes the membership in [`SnapshotMeta::last_membership`] as the initial
+    /// membership, and starts to elect itself as the leader, just like [`Raft::initialize()`].
+    /// The membership has to contain this node as a voter.
+    ///
+    /// Since logs before the snapshot do not exist, members added later with
+    /// [`Raft::add_learner()`] are brought up by `InstallSnapshot` instead of replaying logs.
+    ///
+    /// The snapshot is expected to be built by the local state machine and is not migrated: its
+    /// [`SnapshotMeta::format_version()`] should be the current one.
+    /// It returns [`InitializeError::NotAllowed`] if the node is already initialized.
+    ///
+    /// [`SnapshotMeta::last_membership`]: crate::SnapshotMeta::last_membership
+    /// [`SnapshotMeta::format_version()`]: crate::SnapshotMeta::format_version
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn initialize_with_snapshot(
+        &self,
+        snapshot: Snapshot<C>,
+    ) -> Result<(), RaftError<C, InitializeError<C>>> {
+        tracing::info!(snapshot = display(&snapshot), "Raft::initialize_with_snapshot()");
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        self.inner.call_core(RaftMsg::InitializeWithSnapshot { snapshot, tx }, rx).await
+    }
+
     /// Returns Ok() with the latest known matched log id if it should quit waiting: leader change,
     /// node removed, or replication becomes upto date.
     ///
@@ -717,6 +1386,334 @@ where C: RaftTypeConfig
         Err(())
     }
 
+    /// Read at most `limit` log entries backward from index `high`, in descending index order.
+    ///
+    /// This is a diagnostic API, e.g., for audit tooling to find the most recent entries matching
+    /// some condition without loading the whole log. Reading stops at the first purged index.
+    ///
+    /// See [`RaftLogReader::try_get_log_entries_rev()`].
+    ///
+    /// [`RaftLogReader::try_get_log_entries_rev()`]: crate::storage::RaftLogReader::try_get_log_entries_rev
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_log_entries_rev(
+        &self,
+        high: u64,
+        limit: usize,
+    ) -> Result<Vec<C::Entry>, RaftError<C, StorageError<C::NodeId>>> {
+        // Entries that are purged but may not yet be removed from the storage are not returned.
+        let first = self.with_raft_state(|st| st.last_purged_log_id().next_index()).await?;
+
+        let mut log_reader = self.inner.log_reader.lock().await;
+        let mut entries = log_reader.try_get_log_entries_rev(high, limit).await.map_err(RaftError::APIError)?;
+        entries.retain(|ent| ent.get_log_id().index >= first);
+        Ok(entries)
+    }
+
+    /// Get the term of the log entry at `index` on this node.
+    ///
+    /// This is a diagnostic API, e.g., for an external consistency check to compare the logs of
+    /// the nodes. It returns `None` if the entry is not in the local log: it is purged and covered
+    /// by the snapshot, or it is beyond the last log.
+    ///
+    /// See [`RaftLogReader::term_of_index()`].
+    ///
+    /// [`RaftLogReader::term_of_index()`]: crate::storage::RaftLogReader::term_of_index
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn term_of_index(&self, index: u64) -> Result<Option<u64>, RaftError<C, StorageError<C::NodeId>>> {
+        // An entry that is purged but may not yet be removed from the storage has no term.
+        let first = self.with_raft_state(|st| st.last_purged_log_id().next_index()).await?;
+        if index < first {
+            return Ok(None);
+        }
+
+        let mut log_reader = self.inner.log_reader.lock().await;
+        log_reader.term_of_index(index).await.map_err(RaftError::APIError)
+    }
+
+    /// Get the committed log entries in `[from, to)` on this node, in index order, up to about
+    /// `max_bytes` in total.
+    ///
+    /// It is meant for tooling that copies the log elsewhere, e.g., a replication bridge, to page
+    /// through the committed log: the next call starts at the index after the last returned
+    /// entry. The size of an entry is its [`RaftEntry::size_hint()`]. At least one entry is
+    /// returned if `from` is committed, even if it is larger than `max_bytes`. Entries that are
+    /// not yet committed on this node are not returned.
+    ///
+    /// If `from` is purged, i.e., the entries are only in the snapshot,
+    /// [`LogSegment::BelowSnapshot`] is returned with the last purged log id.
+    ///
+    /// [`RaftEntry::size_hint()`]: crate::entry::RaftEntry::size_hint
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_log_segment(
+        &self,
+        from: u64,
+        to: u64,
+        max_bytes: u64,
+    ) -> Result<LogSegment<C>, RaftError<C, StorageError<C::NodeId>>> {
+        let (purged, committed) =
+            self.with_raft_state(|st| (st.last_purged_log_id().copied(), st.committed().next_index())).await?;
+
+        if let Some(purged) = purged {
+            if from <= purged.index {
+                return Ok(LogSegment::BelowSnapshot { purged });
+            }
+        }
+
+        let to = std::cmp::min(to, committed);
+        if from >= to {
+            return Ok(LogSegment::Entries(vec![]));
+        }
+
+        let entries = {
+            let mut log_reader = self.inner.log_reader.lock().await;
+            read_log_segment(&mut *log_reader, from, to, max_bytes).await.map_err(RaftError::APIError)?
+        };
+
+        // `from` is committed, thus it is missing only if it is purged after `purged` is read.
+        if entries.first().map(|e| e.get_log_id().index) != Some(from) {
+            let purged = self.with_raft_state(|st| st.last_purged_log_id().copied()).await?;
+
+            if let Some(purged) = purged {
+                if from <= purged.index {
+                    return Ok(LogSegment::BelowSnapshot { purged });
+                }
+            }
+
+            let err = StorageIOError::read_logs(AnyError::error(format!("committed log at {} is not found", from)));
+            return Err(RaftError::APIError(err.into()));
+        }
+
+        Ok(LogSegment::Entries(entries))
+    }
+
+    /// Get the last log entry applied to the state machine on this node.
+    ///
+    /// This is a diagnostic API, e.g., to inspect the most recently applied command, or to find
+    /// where to resume after a restart. It reads the local state only and does not involve the
+    /// other nodes, thus the entry may be behind the leader.
+    ///
+    /// It returns `None` if nothing has been applied yet, or
+    /// [`LastApplied::CoveredBySnapshot`] with only the log id, if the entry has been purged from
+    /// the log.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_last_applied(&self) -> Result<Option<LastApplied<C>>, RaftError<C, StorageError<C::NodeId>>> {
+        let (applied, purged) =
+            self.with_raft_state(|st| (st.io_applied().copied(), st.last_purged_log_id().copied())).await?;
+
+        let Some(applied) = applied else {
+            return Ok(None);
+        };
+
+        if Some(applied) <= purged {
+            return Ok(Some(LastApplied::CoveredBySnapshot(applied)));
+        }
+
+        let mut log_reader = self.inner.log_reader.lock().await;
+        let mut entries = log_reader
+            .try_get_log_entries(applied.index..applied.index + 1)
+            .await
+            .map_err(RaftError::APIError)?;
+
+        let last_applied = match entries.pop() {
+            Some(entry) if entry.get_log_id() == &applied => LastApplied::Entry(entry),
+            // Purged after the state is read.
+            _ => LastApplied::CoveredBySnapshot(applied),
+        };
+        Ok(Some(last_applied))
+    }
+
+    /// Check whether the log at `index` is committed on this node.
+    ///
+    /// It is served from the local commit index without subscribing or contacting other nodes,
+    /// thus a follower may return [`CommitStatus::NotCommitted`] for an index the leader has
+    /// already committed. An index covered by the snapshot is committed and it returns
+    /// [`CommitStatus::Compacted`].
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn is_committed(&self, index: u64) -> Result<CommitStatus, Fatal<C>> {
+        self.with_raft_state(move |st| {
+            if Some(index) <= st.snapshot_last_log_id().index() {
+                CommitStatus::Compacted
+            } else if Some(index) <= st.committed().index() {
+                CommitStatus::Committed
+            } else {
+                CommitStatus::NotCommitted
+            }
+        })
+        .await
+    }
+
+    /// Subscribe to the committed entries on this node, starting from the entry at `start`.
+    ///
+    /// The returned [`CommittedStream`] yields the entries already committed first, read from the
+    /// log, then follows the newly committed ones. An entry is yielded once it is applied to the
+    /// local state machine, thus it may be behind the leader. If the next entry to yield has been
+    /// purged, it yields [`Committed::Snapshot`] instead, and the consumer should read the
+    /// snapshot before going on with the following entries.
+    ///
+    /// Dropping the stream cancels the subscription.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn subscribe_committed(&self, start: u64) -> Result<CommittedStream<C>, Fatal<C>> {
+        let (tx, rx) = mpsc::channel(CommittedStream::<C>::BUFFER);
+        self.inner.send_msg(RaftMsg::SubscribeCommitted { start, tx }).await?;
+        Ok(CommittedStream::new(rx))
+    }
+
+    /// Scan the log for missing entries, from the last purged log to the last log.
+    ///
+    /// This is a diagnostic API to catch storage corruption early: a correct log store never has
+    /// a gap between present entries. It returns every range of missing indexes in index order,
+    /// or an empty `Vec` if the log is contiguous.
+    ///
+    /// The scan can also be run at startup with [`Config::check_log_gaps_at_startup`].
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn check_log_gaps(&self) -> Result<Vec<LogGap>, RaftError<C, StorageError<C::NodeId>>> {
+        let (start, last) =
+            self.with_raft_state(|st| (st.last_purged_log_id().next_index(), st.last_log_id().copied())).await?;
+
+        let Some(last) = last else {
+            return Ok(vec![]);
+        };
+
+        let mut log_reader = self.inner.log_reader.lock().await;
+        log_reader.find_log_gaps(start, last.index).await.map_err(RaftError::APIError)
+    }
+
+    /// Compute a digest over the committed log entries in `[0, up_to]`.
+    ///
+    /// This is a diagnostic API for safety verification, e.g., a test harness compares the digests
+    /// of all nodes to detect divergence of committed entries. The digest is deterministic: nodes
+    /// with identical committed entries, built with the same features, produce the same digest.
+    ///
+    /// With features `serde` and `serde_json` enabled, the digest covers the whole serialized
+    /// entry including the application data. Otherwise it only covers the log id and the kind of
+    /// the payload of every entry. The encoding is included in the digest, thus nodes built with
+    /// different features always produce different digests.
+    ///
+    /// If logs are purged, the digest is chained from the last purged log id, which is covered by
+    /// the snapshot: the log id is included in place of the purged entries. Thus the digests of
+    /// two nodes are comparable only if they have purged logs up to the same log id, e.g., the
+    /// ones that keep all logs, or the ones that purge at the same indexes.
+    ///
+    /// It returns [`CommittedDigestError::BeyondCommitted`] if `up_to` is not yet committed on
+    /// this node, [`CommittedDigestError::LogPurged`] if the log at `up_to` has been purged, or
+    /// [`CommittedDigestError::IsWitness`] on a witness, which stores blank entries in place of
+    /// the application data.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_committed_digest(&self, up_to: u64) -> Result<u64, RaftError<C, CommittedDigestError<C>>> {
+        let id = self.inner.id;
+
+        loop {
+            let (is_witness, committed, last_purged) = self
+                .with_raft_state(move |st| {
+                    (
+                        st.membership_state.is_witness(&id),
+                        st.committed().copied(),
+                        st.last_purged_log_id().copied(),
+                    )
+                })
+                .await?;
+
+            // A witness stores blank entries in place of the application data.
+            if is_witness {
+                return Err(RaftError::APIError(NodeIsWitness { node_id: id }.into()));
+            }
+
+            if Some(up_to) > committed.index() {
+                return Err(RaftError::APIError(CommittedDigestError::BeyondCommitted {
+                    up_to,
+                    committed,
+                }));
+            }
+
+            if Some(up_to) <= last_purged.index() {
+                return Err(RaftError::APIError(CommittedDigestError::LogPurged {
+                    up_to,
+                    last_purged,
+                }));
+            }
+
+            let digest = {
+                let mut log_reader = self.inner.log_reader.lock().await;
+                digest_committed(&mut *log_reader, last_purged, up_to)
+                    .await
+                    .map_err(|e| RaftError::APIError(e.into()))?
+            };
+
+            // `None` means more logs are purged meanwhile, compute it again from the new last
+            // purged log id.
+            if let Some(digest) = digest {
+                return Ok(digest);
+            }
+        }
+    }
+
+    /// Get the most recent metrics samples of this node, from the oldest to the newest.
+    ///
+    /// A sample is taken on every tick and at most [`Config::metrics_history_size`] samples are
+    /// kept. It returns an empty `Vec` if the history is disabled.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_metrics_history(&self) -> Result<Vec<MetricsSample<C>>, Fatal<C>> {
+        self.with_core(|core| core.metrics_history().samples()).await
+    }
+
+    /// Get the most recent decisions this node made on vote requests, from the oldest to the
+    /// newest.
+    ///
+    /// Every record has the candidate, the term and, if the vote is denied, the reason. At most
+    /// [`Config::vote_log_size`] records are kept. It returns an empty `Vec` if it is disabled.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_vote_log(&self) -> Result<Vec<VoteRecord<C>>, Fatal<C>> {
+        self.with_core(|core| core.engine().vote_log.records()).await
+    }
+
+    /// Get the committed membership, the commit index, the last applied log id, the current term
+    /// and the current leader of this node, all read at the same instant.
+    ///
+    /// Unlike composing several queries, or reading [`RaftMetrics`], the returned fields are
+    /// consistent with each other: e.g., the log id of the membership and the last applied log id
+    /// are never greater than the committed log id.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_cluster_state(&self) -> Result<ClusterState<C>, Fatal<C>> {
+        self.with_raft_state(|st| ClusterState {
+            membership: st.membership_state.committed().stored_membership().clone(),
+            committed: st.committed().copied(),
+            last_applied: st.io_applied().copied(),
+            current_term: st.vote_ref().leader_id().get_term(),
+            current_leader: st.current_leader(),
+        })
+        .await
+    }
+
+    /// Get the client write requests on this node that are waiting for their entries to be
+    /// committed and applied, for debugging a stalled write pipeline.
+    ///
+    /// It returns how many writes are awaiting commit and how many are committed but awaiting
+    /// apply, the age of the oldest one and the range of log indexes they occupy. Writes piling up
+    /// awaiting commit indicate the leader can not reach a quorum, while writes piling up awaiting
+    /// apply indicate a slow state machine.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_pending_writes(&self) -> Result<PendingWrites, Fatal<C>> {
+        self.with_core(|core| core.pending_writes()).await
+    }
+
+    /// Get the replication detail of every follower and learner, for debugging replication stalls.
+    ///
+    /// For each target it returns the next index to send, the last matching log id, the
+    /// replication phase, the number of inflight requests and the time since the last
+    /// acknowledgement. It is richer than [`RaftMetrics::replication`] and is meant for interactive
+    /// debugging.
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_replication_detail(
+        &self,
+    ) -> Result<BTreeMap<C::NodeId, ReplicationDetail<C>>, RaftError<C, ForwardToLeader<C>>> {
+        let res = self
+            .with_core(|core| core.engine().leader_handler().map(|lh| lh.leader.replication_detail()))
+            .await?;
+        res.map_err(RaftError::APIError)
+    }
+
     /// Provides read-only access to [`RaftState`] through a user-provided function.
     ///
     /// The function `func` is applied to the current [`RaftState`]. The result of this function,
@@ -736,15 +1733,29 @@ where C: RaftTypeConfig
     where
         F: FnOnce(&RaftState<C>) -> V + OptionalSend + 'static,
         V: OptionalSend + 'static,
+    {
+        self.with_core(|core| func(&core.engine().state)).await
+    }
+
+    /// Run `func` in the `RaftCore` task with access to the data owned by `RaftCore`, and return
+    /// its result.
+    ///
+    /// It is the crate internal form of [`Self::with_raft_state`], for the queries that need more
+    /// than [`RaftState`].
+    pub(crate) async fn with_core<F, V>(&self, func: F) -> Result<V, Fatal<C>>
+    where
+        F: FnOnce(&mut dyn CoreAccess<C>) -> V + OptionalSend + 'static,
+        V: OptionalSend + 'static,
     {
         let (tx, rx) = C::AsyncRuntime::oneshot();
 
-        self.external_request(|st| {
-            let result = func(st);
+        let req: BoxCoreFn<C> = Box::new(move |core: &mut dyn CoreAccess<C>| {
+            let result = func(core);
             if let Err(_err) = tx.send(result) {
                 tracing::error!("{}: to-Raft tx send error", func_name!());
             }
         });
+        let _ignore_error = self.inner.tx_api.send(RaftMsg::ExternalCoreRequest { req });
 
         match rx.await {
             Ok(res) => Ok(res),
@@ -772,7 +1783,7 @@ where C: RaftTypeConfig
     /// destroyed right away and not called at all.
     pub fn external_request<F>(&self, req: F)
     where F: FnOnce(&RaftState<C>) + OptionalSend + 'static {
-        let req: BoxCoreFn<C> = Box::new(req);
+        let req: BoxCoreFn<C> = Box::new(move |core: &mut dyn CoreAccess<C>| req(&core.engine().state));
         let _ignore_error = self.inner.tx_api.send(RaftMsg::ExternalCoreRequest { req });
     }
 
@@ -822,6 +1833,49 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Wait until this node knows a current leader, and return the leader id.
+    ///
+    /// The leader can be this node itself or another node. It returns at once if a leader is
+    /// already known, or returns [`WaitError::Timeout`] if no leader is known within `timeout`.
+    /// `None` means to wait forever.
+    ///
+    /// ```ignore
+    /// let leader_id = r.await_leader(Some(Duration::from_secs(3))).await?;
+    /// ```
+    pub async fn await_leader(&self, timeout: Option<Duration>) -> Result<C::NodeId, WaitError> {
+        let metrics = self.wait(timeout).metrics(|m| m.current_leader.is_some(), "await leader").await?;
+
+        // Safe unwrap(): the waiting condition ensures it is Some.
+        Ok(metrics.current_leader.unwrap())
+    }
+
+    /// Prepare this Raft node for shutdown: stop accepting new work and wait for in-flight
+    /// operations to settle, up to `timeout`.
+    ///
+    /// New client writes and membership changes are rejected with [`ShuttingDown`] since it is
+    /// called. It then waits for the pending client writes to be committed and applied, the
+    /// replication to catch up and the snapshot transfers and building to finish. It returns a
+    /// [`ShutdownReport`] of the operations that did not finish in time, which is empty if all of
+    /// them settled, see [`ShutdownReport::is_settled()`].
+    ///
+    /// The node keeps running until [`Raft::shutdown()`] is called.
+    ///
+    /// [`ShuttingDown`]: crate::error::ShuttingDown
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn prepare_shutdown(&self, timeout: Duration) -> Result<ShutdownReport<C>, Fatal<C>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        self.with_core(move |core| core.prepare_shutdown(timeout, tx)).await?;
+
+        match rx.await {
+            Ok(res) => res.map_err(|e| match e {}),
+            Err(_) => {
+                let fatal =
+                    self.inner.get_core_stopped_error("receiving ShutdownReport from RaftCore", None::<u64>).await;
+                Err(fatal)
+            }
+        }
+    }
+
     /// Shutdown this Raft node.
     ///
     /// It sends a shutdown signal and waits until `RaftCore` returns.
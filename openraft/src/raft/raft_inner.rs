@@ -17,6 +17,7 @@ use crate::error::RaftError;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::raft::core_state::CoreState;
+use crate::raft::dyn_log_reader::BoxLogReader;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::AsyncRuntime;
@@ -46,6 +47,12 @@ where C: RaftTypeConfig
 
     /// The ongoing snapshot transmission.
     pub(in crate::raft) snapshot: Mutex<Option<crate::network::snapshot_transport::Streaming<C>>>,
+
+    /// The snapshot format version of the state machine.
+    pub(in crate::raft) snapshot_format_version: u32,
+
+    /// Reads the log for the read-only queries, without going through `RaftCore`.
+    pub(in crate::raft) log_reader: Mutex<BoxLogReader<C>>,
 }
 
 impl<C> RaftInner<C>
@@ -47,4 +47,8 @@ where C: RaftTypeConfig
             tracing::warn!("OneshotConsumer.tx.send: is_ok: {}", res.is_ok());
         }
     }
+
+    fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
 }
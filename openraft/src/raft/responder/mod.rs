@@ -1,6 +1,9 @@
 //! API to consumer a response when a client write request is completed.
 
 pub(crate) mod impls;
+
+use std::collections::BTreeMap;
+
 pub use impls::OneshotResponder;
 
 use crate::raft::message::ClientWriteResult;
@@ -36,4 +39,85 @@ where C: RaftTypeConfig
     ///
     /// This method is called by the `RaftCore` once the request has been applied to state machine.
     fn send(self, result: ClientWriteResult<C>);
+
+    /// Returns `true` if nobody is waiting for the response any more, e.g., the client dropped the
+    /// receiver.
+    ///
+    /// `RaftCore` periodically drops closed responders of pending requests. The requests are still
+    /// committed and applied, only the response is not sent.
+    ///
+    /// The default implementation returns `false`, i.e., a responder is kept until the request is
+    /// completed.
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+/// Remove responders whose receiver is closed from `responders`, checking at most `limit` of them
+/// with an index `>= since`.
+///
+/// It returns the indexes of the removed responders, and the index to start the next sweep from.
+/// The next sweep starts from the beginning, i.e., `0`, when this one reaches the end.
+pub(crate) fn remove_closed<C, R>(responders: &mut BTreeMap<u64, R>, since: u64, limit: usize) -> (Vec<u64>, u64)
+where
+    C: RaftTypeConfig,
+    R: Responder<C>,
+{
+    let mut closed = vec![];
+    let mut next = 0;
+
+    for (i, (index, r)) in responders.range(since..).enumerate() {
+        if i == limit {
+            next = *index;
+            break;
+        }
+        if r.is_closed() {
+            closed.push(*index);
+        }
+    }
+
+    for index in closed.iter() {
+        responders.remove(index);
+    }
+
+    (closed, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::engine::testing::UTConfig;
+    use crate::raft::responder::remove_closed;
+    use crate::raft::responder::OneshotResponder;
+    use crate::raft::responder::Responder;
+
+    #[test]
+    fn test_remove_closed() {
+        let mut responders = BTreeMap::new();
+        let mut receivers = vec![];
+
+        for i in 0..10 {
+            let (_, r, rx) = OneshotResponder::<UTConfig>::from_app_data(());
+            responders.insert(i, r);
+            receivers.push(rx);
+        }
+
+        assert_eq!(
+            (vec![], 0),
+            remove_closed(&mut responders, 0, 100),
+            "all clients are waiting"
+        );
+
+        // Clients of the even indexes are gone.
+        let receivers = receivers.into_iter().enumerate().filter(|(i, _)| i % 2 == 1).collect::<Vec<_>>();
+
+        assert_eq!((vec![0, 2], 4), remove_closed(&mut responders, 0, 4));
+        assert_eq!((vec![4, 6, 8], 0), remove_closed(&mut responders, 4, 4));
+        assert_eq!(vec![1, 3, 5, 7, 9], responders.keys().copied().collect::<Vec<_>>());
+
+        drop(receivers);
+        assert_eq!((vec![1, 3, 5, 7, 9], 0), remove_closed(&mut responders, 0, 100));
+        assert!(responders.is_empty());
+    }
 }
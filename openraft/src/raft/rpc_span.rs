@@ -0,0 +1,118 @@
+//! Structured spans for handling RPCs and client write requests.
+//!
+//! The spans are only created if feature `tracing-spans` is enabled. A span is created in the
+//! caller's context, thus it is a child of the span in which the application's network layer calls
+//! into `Raft`, e.g., a span carrying a trace context propagated from the remote peer.
+
+use tracing::Span;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::error::RaftError;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::ClientWriteResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+const ENABLED: bool = cfg!(feature = "tracing-spans");
+
+pub(crate) fn vote<C>(id: C::NodeId, req: &VoteRequest<C>) -> Span
+where C: RaftTypeConfig {
+    if !ENABLED {
+        return Span::none();
+    }
+
+    tracing::info_span!(
+        "raft.vote",
+        node_id = display(id),
+        term = req.vote.leader_id().get_term(),
+        candidate = display(req.vote.leader_id().voted_for().display()),
+        outcome = tracing::field::Empty,
+    )
+}
+
+pub(crate) fn append_entries<C>(id: C::NodeId, req: &AppendEntriesRequest<C>) -> Span
+where C: RaftTypeConfig {
+    if !ENABLED {
+        return Span::none();
+    }
+
+    tracing::info_span!(
+        "raft.append_entries",
+        node_id = display(id),
+        term = req.vote.leader_id().get_term(),
+        leader = display(req.vote.leader_id().voted_for().display()),
+        entries = req.entries.len(),
+        outcome = tracing::field::Empty,
+    )
+}
+
+pub(crate) fn install_snapshot<C>(id: C::NodeId, vote: &Vote<C::NodeId>) -> Span
+where C: RaftTypeConfig {
+    if !ENABLED {
+        return Span::none();
+    }
+
+    tracing::info_span!(
+        "raft.install_snapshot",
+        node_id = display(id),
+        term = vote.leader_id().get_term(),
+        leader = display(vote.leader_id().voted_for().display()),
+        outcome = tracing::field::Empty,
+    )
+}
+
+pub(crate) fn client_write<C>(id: C::NodeId) -> Span
+where C: RaftTypeConfig {
+    if !ENABLED {
+        return Span::none();
+    }
+
+    tracing::info_span!(
+        "raft.client_write",
+        node_id = display(id),
+        log_index = tracing::field::Empty,
+        outcome = tracing::field::Empty,
+    )
+}
+
+pub(crate) fn record_vote<C, E>(span: &Span, res: &Result<VoteResponse<C>, E>)
+where C: RaftTypeConfig {
+    let outcome = match res {
+        Ok(resp) if resp.vote_granted => "granted",
+        Ok(_) => "rejected",
+        Err(_) => "error",
+    };
+    span.record("outcome", outcome);
+}
+
+pub(crate) fn record_append_entries<C, E>(span: &Span, res: &Result<AppendEntriesResponse<C>, E>)
+where C: RaftTypeConfig {
+    let outcome = match res {
+        Ok(AppendEntriesResponse::Success | AppendEntriesResponse::SuccessWithLogState { .. }) => "success",
+        Ok(AppendEntriesResponse::PartialSuccess(_)) => "partial_success",
+        Ok(AppendEntriesResponse::Conflict) => "conflict",
+        Ok(AppendEntriesResponse::HigherVote(_)) => "higher_vote",
+        Err(_) => "error",
+    };
+    span.record("outcome", outcome);
+}
+
+pub(crate) fn record_result<T, E>(span: &Span, res: &Result<T, E>) {
+    span.record("outcome", if res.is_ok() { "ok" } else { "error" });
+}
+
+pub(crate) fn record_client_write<C, E>(span: &Span, res: &Result<ClientWriteResponse<C>, RaftError<C, E>>)
+where C: RaftTypeConfig {
+    match res {
+        Ok(resp) => {
+            span.record("log_index", resp.log_id.index);
+            span.record("outcome", "committed");
+        }
+        Err(_) => {
+            span.record("outcome", "error");
+        }
+    }
+}
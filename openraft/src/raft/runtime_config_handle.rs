@@ -2,7 +2,11 @@
 
 use std::sync::atomic::Ordering;
 
+use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::error::Fatal;
+use crate::error::RaftError;
 use crate::raft::RaftInner;
+use crate::ConfigError;
 use crate::RaftTypeConfig;
 
 /// RuntimeConfigHandle is an interface to update runtime config.
@@ -42,4 +46,30 @@ where C: RaftTypeConfig
     pub fn elect(&self, enabled: bool) {
         self.raft_inner.runtime_config.enable_elect.store(enabled, Ordering::Relaxed);
     }
+
+    /// Set the range `[min, max)` in milliseconds to pick a random election timeout from.
+    ///
+    /// It is meant to temporarily widen the election timeout during a known-unstable period, such
+    /// as a rolling deploy. It takes effect the next time the election timeout is renewed, e.g.,
+    /// when a heartbeat from the leader is received. Use
+    /// [`reset_election_timeout()`](Self::reset_election_timeout) to restore the range in
+    /// [`Config`](crate::Config).
+    ///
+    /// It returns a [`ConfigError`] if the range is invalid, or if `min` is not greater than
+    /// [`Config::heartbeat_interval`](crate::Config::heartbeat_interval).
+    pub async fn election_timeout(&self, min: u64, max: u64) -> Result<(), RaftError<C, ConfigError>> {
+        self.raft_inner.config.validate_election_timeout(min, max).map_err(RaftError::APIError)?;
+
+        let cmd = ExternalCommand::SetElectionTimeout { min, max };
+        self.raft_inner.send_external_command(cmd, "set_election_timeout").await?;
+        Ok(())
+    }
+
+    /// Restore the election timeout range to the one in [`Config`](crate::Config).
+    ///
+    /// It takes effect the next time the election timeout is renewed.
+    pub async fn reset_election_timeout(&self) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::ResetElectionTimeout;
+        self.raft_inner.send_external_command(cmd, "reset_election_timeout").await
+    }
 }
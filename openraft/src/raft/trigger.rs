@@ -40,6 +40,20 @@ where C: RaftTypeConfig
         self.raft_inner.send_external_command(ExternalCommand::Heartbeat, "trigger_heartbeat").await
     }
 
+    /// Make the leader give up its leadership at once and return at once.
+    ///
+    /// The leader reverts to a non-leader state in the next term without choosing a successor, and
+    /// stops replication. It postpones its own election, so that another voter is likely to be
+    /// elected as the new leader. It is ignored if this node is not a leader.
+    ///
+    /// This is useful for testing failover or for operational control. To hand over leadership to
+    /// a specific node, trigger [`elect()`](Self::elect) on that node instead.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn step_down(&self) -> Result<(), Fatal<C>> {
+        self.raft_inner.send_external_command(ExternalCommand::StepDown, "trigger_step_down").await
+    }
+
     /// Trigger to build a snapshot at once and return at once.
     ///
     /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
@@ -65,4 +79,57 @@ where C: RaftTypeConfig
     pub async fn purge_log(&self, upto: u64) -> Result<(), Fatal<C>> {
         self.raft_inner.send_external_command(ExternalCommand::PurgeLog { upto }, "purge_log").await
     }
+
+    /// Stop the leader from replicating logs or snapshot to `target`, e.g., for maintenance on
+    /// it, without removing it from the membership. Return at once.
+    ///
+    /// A paused target is still a member and still receives heartbeats, but not new logs. Logs are
+    /// committed as long as a quorum of the other voters accept them. The pause is a setting of
+    /// this node, which applies whenever this node is a leader, and is not persisted.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn pause_replication(&self, target: C::NodeId) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::PauseReplication { target };
+        self.raft_inner.send_external_command(cmd, "pause_replication").await
+    }
+
+    /// Resume replicating to a `target` paused by [`pause_replication()`](Self::pause_replication)
+    /// and return at once.
+    ///
+    /// The leader catches up the target at once, with a snapshot if the logs it lacks are
+    /// purged or it is too far behind.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn resume_replication(&self, target: C::NodeId) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::ResumeReplication { target };
+        self.raft_inner.send_external_command(cmd, "resume_replication").await
+    }
+
+    /// Make the leader send its snapshot to `target` at once, instead of logs, and return at once.
+    ///
+    /// This is useful when an operator knows `target` is too far behind to catch up by logs. It is
+    /// ignored if this node is not a leader or `target` is not a member. It is a no-op, with an
+    /// info log, if `target` is up to date or the snapshot is not newer than the log `target`
+    /// already has; to send a fresh snapshot, build one with [`snapshot()`](Self::snapshot)
+    /// first.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    pub async fn force_snapshot_to(&self, target: C::NodeId) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::ForceSnapshotTo { target };
+        self.raft_inner.send_external_command(cmd, "force_snapshot_to").await
+    }
+
+    /// Clear the degraded state this node entered at startup because its term is too far ahead of
+    /// its last log term, and return at once. See [`Config::max_term_log_gap`].
+    ///
+    /// An operator should call it only after verifying the persisted vote is not corrupted. The
+    /// node then takes part in elections normally.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    ///
+    /// [`Config::max_term_log_gap`]: crate::Config::max_term_log_gap
+    pub async fn clear_term_gap_degraded(&self) -> Result<(), Fatal<C>> {
+        let cmd = ExternalCommand::ClearTermGapDegraded;
+        self.raft_inner.send_external_command(cmd, "clear_term_gap_degraded").await
+    }
 }
@@ -0,0 +1,31 @@
+//! Deduplicate consecutive identical client writes with application defined content hash.
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// An application defined rule to deduplicate client writes on the leader.
+///
+/// It is registered with [`Raft::set_write_deduplicator()`] and runs in `RaftCore`.
+///
+/// Before a leader appends the app data of a [`Raft::client_write()`], it compares the
+/// [`content_hash()`](Self::content_hash) of the data with the hash of the last log entry, if
+/// the last entry is also a client write proposed by this leader. If they are equal, no entry is
+/// appended: the client receives the log id of the last entry and a response built by
+/// [`duplicate_response()`](Self::duplicate_response) from the result of applying the last entry.
+///
+/// Checkpoint writes and writes forwarded to another node are never deduplicated.
+///
+/// [`Raft::set_write_deduplicator()`]: crate::Raft::set_write_deduplicator
+/// [`Raft::client_write()`]: crate::Raft::client_write
+pub trait WriteDeduplicator<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Return the hash of the content of `data`.
+    ///
+    /// Two writes with the same hash are considered identical.
+    fn content_hash(&mut self, data: &C::D) -> u64;
+
+    /// Build the response to a deduplicated write, from the response of applying the entry it
+    /// duplicates.
+    fn duplicate_response(&mut self, original: &C::R) -> C::R;
+}
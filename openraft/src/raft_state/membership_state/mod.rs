@@ -77,6 +77,20 @@ where C: RaftTypeConfig
         self.effective.membership().is_voter(id)
     }
 
+    /// Return true if the given node is a witness in either the effective or the committed
+    /// membership.
+    pub(crate) fn is_witness(&self, id: &C::NodeId) -> bool {
+        self.effective.membership().is_witness(id) || self.committed.membership().is_witness(id)
+    }
+
+    /// Return true if the effective membership has at least one voter.
+    ///
+    /// A node without any voter, e.g., a pristine node that is not yet initialized, can neither
+    /// elect a leader nor commit a log.
+    pub(crate) fn is_configured(&self) -> bool {
+        self.effective.voter_ids().next().is_some()
+    }
+
     /// Update membership state if the specified committed_log_id is greater than `self.effective`
     pub(crate) fn commit(&mut self, committed_log_id: &Option<LogId<C::NodeId>>) {
         if committed_log_id >= self.effective().log_id() {
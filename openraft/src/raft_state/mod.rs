@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
 use std::error::Error;
 use std::ops::Deref;
+use std::time::Duration;
 
 use validit::Validate;
 
@@ -8,6 +11,7 @@ use crate::entry::RaftEntry;
 use crate::error::ForwardToLeader;
 use crate::log_id::RaftLogId;
 use crate::utime::UTime;
+use crate::Instant;
 use crate::LogId;
 use crate::LogIdOptionExt;
 use crate::RaftTypeConfig;
@@ -88,6 +92,20 @@ where C: RaftTypeConfig
     /// If a log is in use by a replication task, the purge is postponed and is stored in this
     /// field.
     pub(crate) purge_upto: Option<LogId<C::NodeId>>,
+
+    /// The first log id of the leaders whose logs are purged, in index order.
+    ///
+    /// It is kept only for diagnosis and is not persisted. At most `Config::term_history_size` of
+    /// them are kept.
+    pub(crate) purged_key_log_ids: VecDeque<LogId<C::NodeId>>,
+
+    /// The last time an AppendEntries request from the current leader is accepted.
+    pub(crate) last_leader_contact: Option<InstantOf<C>>,
+
+    /// The matching log index of every replication target saved by the leader before restart.
+    ///
+    /// They are consumed by the first election of this node after startup.
+    pub(crate) replication_hints: BTreeMap<C::NodeId, u64>,
 }
 
 impl<C> Default for RaftState<C>
@@ -106,6 +124,9 @@ where C: RaftTypeConfig
             io_state: IOState::default(),
             snapshot_streaming: None,
             purge_upto: None,
+            purged_key_log_ids: VecDeque::new(),
+            last_leader_contact: None,
+            replication_hints: BTreeMap::new(),
         }
     }
 }
@@ -202,6 +223,37 @@ where C: RaftTypeConfig
         self.vote.utime()
     }
 
+    /// Return the time elapsed since an AppendEntries request from the leader was last accepted.
+    ///
+    /// It is `None` if this node is the leader or has never heard from a leader.
+    pub fn time_since_leader_contact(&self) -> Option<Duration> {
+        if self.server_state == ServerState::Leader {
+            return None;
+        }
+        self.last_leader_contact.map(|t| t.elapsed())
+    }
+
+    /// Return the id of the leader this node has granted a committed vote to, if it is a voter.
+    pub(crate) fn current_leader(&self) -> Option<C::NodeId> {
+        let vote = self.vote_ref();
+
+        if !vote.is_committed() {
+            return None;
+        }
+
+        // Safe unwrap(): vote that is committed has to already have voted for some node.
+        let id = vote.leader_id().voted_for().unwrap();
+
+        // TODO: `is_voter()` is slow, maybe cache `current_leader`,
+        //       e.g., only update it when membership or vote changes
+        if self.membership_state.effective().is_voter(&id) {
+            Some(id)
+        } else {
+            tracing::debug!("id={} is not a voter", id);
+            None
+        }
+    }
+
     pub(crate) fn is_initialized(&self) -> bool {
         // initialize() writes a membership config log entry.
         // If there are logs, it is already initialized.
@@ -309,6 +361,41 @@ where C: RaftTypeConfig
         l
     }
 
+    /// Returns the first log id of every leader, in index order, including the leaders whose logs
+    /// are purged.
+    ///
+    /// A log at index `i` is proposed by the leader of the last log id whose index is `<= i`.
+    /// Purged leaders are remembered only if `Config::term_history_size` is not 0.
+    pub fn term_history(&self) -> Vec<LogIdOf<C>> {
+        let mut history = self.purged_key_log_ids.iter().copied().collect::<Vec<_>>();
+
+        for log_id in self.log_ids.key_log_ids() {
+            if history.last().map(|x| x.leader_id) != Some(log_id.leader_id) {
+                history.push(*log_id);
+            }
+        }
+
+        history
+    }
+
+    /// Remember the first log id of the leaders whose logs are to be purged upto `upto`,
+    /// and keep at most `max` of them.
+    pub(crate) fn keep_purged_key_log_ids(&mut self, upto: &LogIdOf<C>, max: usize) {
+        for log_id in self.log_ids.key_log_ids() {
+            if log_id.index > upto.index {
+                break;
+            }
+
+            if self.purged_key_log_ids.back().map(|x| x.leader_id) != Some(log_id.leader_id) {
+                self.purged_key_log_ids.push_back(*log_id);
+            }
+        }
+
+        while self.purged_key_log_ids.len() > max {
+            self.purged_key_log_ids.pop_front();
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn purge_log(&mut self, upto: &LogIdOf<C>) {
         self.purged_next = upto.index + 1;
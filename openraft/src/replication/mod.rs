@@ -28,6 +28,8 @@ use crate::config::Config;
 use crate::core::notify::Notify;
 use crate::core::sm::handle::SnapshotReader;
 use crate::display_ext::DisplayOptionExt;
+use crate::entry::RaftEntry;
+use crate::entry::RaftPayload;
 use crate::error::HigherVote;
 use crate::error::PayloadTooLarge;
 use crate::error::RPCError;
@@ -35,13 +37,16 @@ use crate::error::RaftError;
 use crate::error::ReplicationClosed;
 use crate::error::ReplicationError;
 use crate::error::Timeout;
+use crate::error::Unreachable;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id_range::LogIdRange;
+use crate::metrics::FollowerLogState;
 use crate::network::Backoff;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::network::RaftNetwork;
 use crate::network::RaftNetworkFactory;
+use crate::network::SnapshotBandwidth;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::replication::callbacks::SnapshotCallback;
@@ -88,6 +93,9 @@ where
     /// The ID of the target Raft node which replication events are to be sent to.
     target: C::NodeId,
 
+    /// Whether the target is a witness, which is replicated with log metadata only.
+    witness: bool,
+
     /// Identifies which session this replication belongs to.
     session_id: ReplicationSessionId<C::NodeId>,
 
@@ -113,6 +121,10 @@ where
     /// Snapshot transmitting is a long running task, and is processed in a separate task.
     snapshot_network: Arc<Mutex<N::Network>>,
 
+    /// Limits and measures the bandwidth of snapshot transfers, shared by replication streams of
+    /// this node.
+    snapshot_bandwidth: SnapshotBandwidth,
+
     /// The current snapshot replication state.
     ///
     /// It includes a cancel signaler and the join handle of the snapshot replication task.
@@ -159,12 +171,14 @@ where
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn spawn(
         target: C::NodeId,
+        witness: bool,
         session_id: ReplicationSessionId<C::NodeId>,
         config: Arc<Config>,
         committed: Option<LogId<C::NodeId>>,
         matching: Option<LogId<C::NodeId>>,
         network: N::Network,
         snapshot_network: N::Network,
+        snapshot_bandwidth: SnapshotBandwidth,
         log_reader: LS::LogReader,
         snapshot_reader: SnapshotReader<C>,
         tx_raft_core: mpsc::UnboundedSender<Notify<C>>,
@@ -183,9 +197,11 @@ where
 
         let this = Self {
             target,
+            witness,
             session_id,
             network,
             snapshot_network: Arc::new(Mutex::new(snapshot_network)),
+            snapshot_bandwidth,
             snapshot_state: None,
             backoff: None,
             log_reader,
@@ -352,6 +368,52 @@ where
         }
     }
 
+    /// Replace every entry with a blank entry of the same log id, except membership entries, which
+    /// a witness needs to vote.
+    fn strip_payloads(logs: Vec<C::Entry>) -> Vec<C::Entry> {
+        logs.into_iter()
+            .map(|ent| {
+                if ent.get_membership().is_some() {
+                    ent
+                } else {
+                    C::Entry::new_blank(*ent.get_log_id())
+                }
+            })
+            .collect()
+    }
+
+    /// Truncate `logs` to the longest prefix whose total [`RaftEntry::size_hint()`] fits in
+    /// [`Config::max_message_size`].
+    ///
+    /// At least one entry is kept so that replication always makes progress. The remaining entries
+    /// are sent in the following requests.
+    fn fit_max_message_size(&self, logs: &mut Vec<C::Entry>) {
+        let max = self.config.max_message_size;
+        if max == 0 {
+            return;
+        }
+
+        let mut size = 0;
+
+        for (i, ent) in logs.iter().enumerate() {
+            let Some(ent_size) = ent.size_hint() else {
+                return;
+            };
+
+            size += ent_size;
+
+            if size > max && i > 0 {
+                tracing::debug!(
+                    max_message_size = max,
+                    entries = i,
+                    "truncate AppendEntries to fit max_message_size"
+                );
+                logs.truncate(i);
+                return;
+            }
+        }
+    }
+
     /// Send an AppendEntries RPC to the target.
     ///
     /// This request will timeout if no response is received within the
@@ -393,7 +455,7 @@ where
                 let r = LogIdRange::new(rng.prev, rng.prev);
                 (vec![], r)
             } else {
-                let logs = self.log_reader.try_get_log_entries(start..end).await?;
+                let mut logs = self.log_reader.try_get_log_entries(start..end).await?;
                 debug_assert_eq!(
                     logs.len(),
                     (end - start) as usize,
@@ -405,6 +467,12 @@ where
                     logs.last().map(|ent| ent.get_log_id()).display()
                 );
 
+                if self.witness {
+                    logs = Self::strip_payloads(logs);
+                }
+
+                self.fit_max_message_size(&mut logs);
+
                 let last_log_id = logs.last().map(|ent| *ent.get_log_id());
 
                 let r = LogIdRange::new(rng.prev, last_log_id);
@@ -415,12 +483,10 @@ where
         let leader_time = InstantOf::<C>::now();
 
         // Build the heartbeat frame to be sent to the follower.
-        let payload = AppendEntriesRequest {
-            vote: self.session_id.vote,
-            prev_log_id: sending_range.prev,
-            leader_commit: self.committed,
-            entries: logs,
-        };
+        let mut payload = AppendEntriesRequest::new(self.session_id.vote, sending_range.prev, logs, self.committed);
+        if self.config.exchange_config_hash {
+            payload = payload.with_config_hash(self.config.timing_hash());
+        }
 
         // Send the payload.
         tracing::debug!(
@@ -460,18 +526,37 @@ where
                 let next = self.finish_success_append(matching, leader_time, log_ids);
                 Ok(next)
             }
+            AppendEntriesResponse::SuccessWithLogState { committed, last_log_id } => {
+                let _ = self.tx_raft_core.send(Notify::FollowerLogState {
+                    target: self.target,
+                    state: FollowerLogState { committed, last_log_id },
+                });
+
+                let matching = sending_range.last;
+                let next = self.finish_success_append(matching, leader_time, log_ids);
+                Ok(next)
+            }
             AppendEntriesResponse::PartialSuccess(matching) => {
                 Self::debug_assert_partial_success(&sending_range, &matching);
                 let next = self.finish_success_append(matching, leader_time, log_ids);
                 Ok(next)
             }
             AppendEntriesResponse::HigherVote(vote) => {
-                debug_assert!(
-                    vote > self.session_id.vote,
-                    "higher vote({}) should be greater than leader's vote({})",
-                    vote,
-                    self.session_id.vote,
-                );
+                // A leader's vote is committed and has a total order with other votes: a vote that
+                // is not greater is responded by the `AppendEntriesValidator` on the target.
+                if vote <= self.session_id.vote {
+                    // The target refuses this leader by its own rule. Back off and retry later as
+                    // if it were unreachable, the rule may change, e.g., when the clock catches up.
+                    tracing::warn!(
+                        target = display(self.target),
+                        "append_entries is rejected by target validator"
+                    );
+
+                    return Err(ReplicationError::RPCError(RPCError::Unreachable(Unreachable::new(
+                        &AnyError::error("AppendEntries is rejected by target validator"),
+                    ))));
+                }
+
                 tracing::debug!(%vote, "append entries failed. converting to follower");
 
                 Err(ReplicationError::HigherVote(HigherVote {
@@ -729,7 +814,8 @@ where
         };
 
         let mut option = RPCOption::new(self.config.install_snapshot_timeout());
-        option.snapshot_chunk_size = Some(self.config.snapshot_max_chunk_size as usize);
+        option.snapshot_chunk_size = Some(self.config.snapshot_chunk_size() as usize);
+        option.snapshot_bandwidth = Some(self.snapshot_bandwidth.clone());
 
         let (tx_cancel, rx_cancel) = oneshot::channel();
 
@@ -64,6 +64,7 @@ where
         let vote = vote.unwrap_or_default();
 
         let mut committed = self.log_store.read_committed().await?;
+        let replication_hints = self.log_store.read_replication_hints().await?;
 
         let st = self.log_store.get_log_state().await?;
         let mut last_purged_log_id = st.last_purged_log_id;
@@ -162,6 +163,7 @@ where
             log_ids,
             membership_state: mem_state,
             snapshot_meta,
+            replication_hints,
 
             // -- volatile fields: they are not persisted.
             server_state: Default::default(),
@@ -169,6 +171,8 @@ where
             io_state,
             snapshot_streaming: None,
             purge_upto: last_purged_log_id,
+            purged_key_log_ids: Default::default(),
+            last_leader_contact: None,
         })
     }
 
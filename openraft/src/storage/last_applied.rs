@@ -0,0 +1,45 @@
+use std::fmt;
+
+use crate::LogId;
+use crate::RaftLogId;
+use crate::RaftTypeConfig;
+
+/// The last log entry applied to the state machine on this node.
+///
+/// It is returned by [`Raft::get_last_applied()`](crate::Raft::get_last_applied).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum LastApplied<C>
+where C: RaftTypeConfig
+{
+    /// The last applied entry, with its log id and payload, read from the log.
+    Entry(C::Entry),
+
+    /// The last applied entry has been purged from the log, it is covered by the snapshot.
+    ///
+    /// Only its log id is known.
+    CoveredBySnapshot(LogId<C::NodeId>),
+}
+
+impl<C> LastApplied<C>
+where C: RaftTypeConfig
+{
+    /// Return the log id of the last applied entry.
+    pub fn log_id(&self) -> &LogId<C::NodeId> {
+        match self {
+            Self::Entry(entry) => entry.get_log_id(),
+            Self::CoveredBySnapshot(log_id) => log_id,
+        }
+    }
+}
+
+impl<C> fmt::Display for LastApplied<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entry(entry) => write!(f, "Entry({})", entry),
+            Self::CoveredBySnapshot(log_id) => write!(f, "CoveredBySnapshot({})", log_id),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::collections::Bound;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::io::Read;
+use std::io::Write;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::storage::LogFlushed;
+use crate::storage::RaftLogStorage;
+use crate::LogId;
+use crate::LogState;
+use crate::OptionalSend;
+use crate::RaftLogId;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::Vote;
+
+/// The most recently appended log entries, kept in memory.
+///
+/// The cached entries are always a contiguous tail of the log in the store: appending advances
+/// it, truncating and purging remove the affected entries from it.
+pub(crate) struct LogCache<C>
+where C: RaftTypeConfig
+{
+    capacity: usize,
+    entries: VecDeque<C::Entry>,
+
+    /// Number of reads served by the cache.
+    pub(crate) hits: u64,
+
+    /// Number of reads that have to go to the store.
+    pub(crate) misses: u64,
+}
+
+impl<C> LogCache<C>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn first_index(&self) -> Option<u64> {
+        self.entries.front().map(|e| e.get_log_id().index)
+    }
+
+    fn last_index(&self) -> Option<u64> {
+        self.entries.back().map(|e| e.get_log_id().index)
+    }
+
+    /// Add entries that are just appended to the store, evicting the oldest ones if it is full.
+    ///
+    /// An entry overrides the cached ones since its index. If there is a gap between the cached
+    /// entries and the appended one, the cache restarts from the appended one.
+    pub(crate) fn append(&mut self, entries: impl IntoIterator<Item = C::Entry>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        for ent in entries {
+            let index = ent.get_log_id().index;
+
+            match self.last_index() {
+                Some(last) if index <= last => self.truncate(index),
+                Some(last) if index > last + 1 => self.entries.clear(),
+                _ => {}
+            }
+
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(ent);
+        }
+    }
+
+    /// Remove cached entries since `index`, inclusive.
+    pub(crate) fn truncate(&mut self, index: u64) {
+        while self.last_index().map_or(false, |last| last >= index) {
+            self.entries.pop_back();
+        }
+    }
+
+    /// Remove cached entries upto `index`, inclusive.
+    pub(crate) fn purge(&mut self, index: u64) {
+        while self.first_index().map_or(false, |first| first <= index) {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Remove all cached entries.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Get the entries in `range` if the start of it is cached.
+    ///
+    /// Since the cache is a tail of the log, the entries after the last cached one do not exist
+    /// in the store either.
+    pub(crate) fn get<RB: RangeBounds<u64>>(&mut self, range: &RB) -> Option<Vec<C::Entry>> {
+        let start = match range.start_bound() {
+            Bound::Included(i) => Some(*i),
+            Bound::Excluded(i) => Some(*i + 1),
+            Bound::Unbounded => None,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(i) => i.saturating_add(1),
+            Bound::Excluded(i) => *i,
+            Bound::Unbounded => u64::MAX,
+        };
+
+        let (Some(first), Some(start)) = (self.first_index(), start) else {
+            self.misses += 1;
+            return None;
+        };
+
+        if start < first {
+            self.misses += 1;
+            return None;
+        }
+
+        self.hits += 1;
+
+        let entries = self
+            .entries
+            .iter()
+            .skip((start - first) as usize)
+            .take_while(|e| e.get_log_id().index < end)
+            .cloned()
+            .collect();
+
+        Some(entries)
+    }
+}
+
+/// A [`RaftLogStorage`] wrapper that keeps the most recently appended log entries in memory.
+///
+/// Replication and applying mostly read the entries that are just appended. Serving these reads
+/// from memory saves the reads to a disk based log store. For an in-memory store there is no
+/// benefit.
+///
+/// A capacity of `0` disables the cache. All of the writes to the log must go through this
+/// wrapper, otherwise the cached entries become stale.
+///
+/// The cache is opt-in and is not maintained by [`Raft`](crate::Raft) with a `Config` value:
+/// serving an entry from the cache requires cloning it, while [`RaftTypeConfig::Entry`] is not
+/// required to be `Clone`. An application whose entry type is `Clone` wraps its log store before
+/// passing it to [`Raft::new()`](crate::Raft::new):
+///
+/// ```ignore
+/// let log_store = CachedLogStore::new(log_store, 1024);
+/// let raft = Raft::new(id, config, network, log_store, state_machine).await?;
+/// ```
+pub struct CachedLogStore<C, LS>
+where C: RaftTypeConfig
+{
+    inner: LS,
+    cache: Arc<Mutex<LogCache<C>>>,
+}
+
+impl<C, LS> CachedLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    LS: RaftLogStorage<C>,
+{
+    /// Wrap a log store with a cache of the most recent `capacity` entries.
+    pub fn new(inner: LS, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(LogCache::new(capacity))),
+        }
+    }
+
+    /// Return the wrapped log store.
+    pub fn into_inner(self) -> LS {
+        self.inner
+    }
+}
+
+/// The [`RaftLogReader`] of a [`CachedLogStore`], sharing the cache with it.
+pub struct CachedLogReader<C, LR>
+where C: RaftTypeConfig
+{
+    inner: LR,
+    cache: Arc<Mutex<LogCache<C>>>,
+}
+
+impl<C, LS> RaftLogReader<C> for CachedLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    LS: RaftLogStorage<C>,
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>> {
+        let cached = self.cache.lock().unwrap().get(&range);
+        match cached {
+            Some(entries) => Ok(entries),
+            None => self.inner.try_get_log_entries(range).await,
+        }
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<Vote<C::NodeId>>, StorageError<C::NodeId>> {
+        self.inner.read_vote().await
+    }
+}
+
+impl<C, LR> RaftLogReader<C> for CachedLogReader<C, LR>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    LR: RaftLogReader<C>,
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>> {
+        let cached = self.cache.lock().unwrap().get(&range);
+        match cached {
+            Some(entries) => Ok(entries),
+            None => self.inner.try_get_log_entries(range).await,
+        }
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<Vote<C::NodeId>>, StorageError<C::NodeId>> {
+        self.inner.read_vote().await
+    }
+}
+
+impl<C, LS> RaftLogStorage<C> for CachedLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    LS: RaftLogStorage<C>,
+{
+    type LogReader = CachedLogReader<C, LS::LogReader>;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C::NodeId>> {
+        self.inner.get_log_state().await
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        CachedLogReader {
+            inner: self.inner.get_log_reader().await,
+            cache: self.cache.clone(),
+        }
+    }
+
+    async fn save_vote(&mut self, vote: &Vote<C::NodeId>) -> Result<(), StorageError<C::NodeId>> {
+        self.inner.save_vote(vote).await
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogId<C::NodeId>>) -> Result<(), StorageError<C::NodeId>> {
+        self.inner.save_committed(committed).await
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogId<C::NodeId>>, StorageError<C::NodeId>> {
+        self.inner.read_committed().await
+    }
+
+    async fn save_replication_hints(&mut self, hints: BTreeMap<C::NodeId, u64>) -> Result<(), StorageError<C::NodeId>> {
+        self.inner.save_replication_hints(hints).await
+    }
+
+    async fn read_replication_hints(&mut self) -> Result<BTreeMap<C::NodeId, u64>, StorageError<C::NodeId>> {
+        self.inner.read_replication_hints().await
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: LogFlushed<C>) -> Result<(), StorageError<C::NodeId>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let capacity = self.cache.lock().unwrap().capacity();
+        if capacity == 0 {
+            return self.inner.append(entries, callback).await;
+        }
+
+        let entries = entries.into_iter().collect::<Vec<_>>();
+
+        // Only the last `capacity` entries can be kept.
+        let to_cache = entries[entries.len().saturating_sub(capacity)..].to_vec();
+
+        self.inner.append(entries, callback).await?;
+        self.cache.lock().unwrap().append(to_cache);
+        Ok(())
+    }
+
+    async fn truncate(&mut self, log_id: LogId<C::NodeId>) -> Result<(), StorageError<C::NodeId>> {
+        self.cache.lock().unwrap().truncate(log_id.index);
+        self.inner.truncate(log_id).await
+    }
+
+    async fn purge(&mut self, log_id: LogId<C::NodeId>) -> Result<(), StorageError<C::NodeId>> {
+        self.cache.lock().unwrap().purge(log_id.index);
+        self.inner.purge(log_id).await
+    }
+
+    async fn export_state<W>(&mut self, w: W) -> Result<(), StorageError<C::NodeId>>
+    where W: Write + OptionalSend {
+        self.inner.export_state(w).await
+    }
+
+    async fn import_state<R>(&mut self, r: R) -> Result<(), StorageError<C::NodeId>>
+    where R: Read + OptionalSend {
+        // The whole log is replaced, none of the cached entries is valid any more.
+        self.cache.lock().unwrap().clear();
+        self.inner.import_state(r).await
+    }
+}
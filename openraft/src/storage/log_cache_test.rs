@@ -0,0 +1,78 @@
+use crate::engine::testing::UTConfig;
+use crate::storage::log_cache::LogCache;
+use crate::testing::blank_ent;
+use crate::Entry;
+use crate::RaftLogId;
+
+fn indexes(entries: Option<Vec<Entry<UTConfig>>>) -> Option<Vec<u64>> {
+    entries.map(|x| x.iter().map(|e| e.get_log_id().index).collect())
+}
+
+#[test]
+fn test_log_cache_hit_and_evict() -> anyhow::Result<()> {
+    let mut c = LogCache::<UTConfig>::new(3);
+
+    c.append([blank_ent(1, 1, 1), blank_ent(1, 1, 2)]);
+
+    assert_eq!(Some(vec![1, 2]), indexes(c.get(&(1..3))));
+    assert_eq!(Some(vec![2]), indexes(c.get(&(2..=2))));
+    assert_eq!(Some(vec![2]), indexes(c.get(&(2..))));
+    assert_eq!((3, 0), (c.hits, c.misses));
+
+    // Evict the oldest when full.
+    c.append([blank_ent(1, 1, 3), blank_ent(1, 1, 4)]);
+    assert_eq!(None, indexes(c.get(&(1..3))));
+    assert_eq!(Some(vec![2, 3, 4]), indexes(c.get(&(2..5))));
+    assert_eq!(None, indexes(c.get(&(..))));
+    assert_eq!((4, 2), (c.hits, c.misses));
+
+    // A gap restarts the cache.
+    c.append([blank_ent(1, 1, 6)]);
+    assert_eq!(None, indexes(c.get(&(4..))));
+    assert_eq!(Some(vec![6]), indexes(c.get(&(6..))));
+
+    Ok(())
+}
+
+#[test]
+fn test_log_cache_truncate_and_purge() -> anyhow::Result<()> {
+    let mut c = LogCache::<UTConfig>::new(10);
+
+    c.append([
+        blank_ent(1, 1, 1),
+        blank_ent(1, 1, 2),
+        blank_ent(1, 1, 3),
+        blank_ent(1, 1, 4),
+    ]);
+
+    c.truncate(3);
+    assert_eq!(Some(vec![1, 2]), indexes(c.get(&(1..5))));
+
+    // Entries that override the cached ones replace them.
+    c.append([blank_ent(2, 1, 2), blank_ent(2, 1, 3)]);
+    let got = c.get(&(1..5)).unwrap();
+    assert_eq!(
+        vec![blank_ent::<UTConfig>(1, 1, 1), blank_ent(2, 1, 2), blank_ent(2, 1, 3)],
+        got
+    );
+
+    c.purge(1);
+    assert_eq!(None, indexes(c.get(&(1..5))));
+    assert_eq!(Some(vec![2, 3]), indexes(c.get(&(2..5))));
+
+    c.truncate(0);
+    assert_eq!(None, indexes(c.get(&(2..5))));
+
+    Ok(())
+}
+
+#[test]
+fn test_log_cache_disabled() -> anyhow::Result<()> {
+    let mut c = LogCache::<UTConfig>::new(0);
+
+    c.append([blank_ent(1, 1, 1)]);
+    assert_eq!(None, indexes(c.get(&(1..2))));
+    assert_eq!((0, 1), (c.hits, c.misses));
+
+    Ok(())
+}
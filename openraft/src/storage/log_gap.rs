@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// A range of log indexes missing from the log store, between present log entries.
+///
+/// It is found by [`Raft::check_log_gaps()`](crate::Raft::check_log_gaps). Both ends are
+/// inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LogGap {
+    /// The first missing index.
+    pub first: u64,
+
+    /// The last missing index.
+    pub last: u64,
+}
+
+impl fmt::Display for LogGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.first, self.last)
+    }
+}
@@ -4,6 +4,7 @@ use std::ops::RangeBounds;
 use openraft_macros::add_async_trait;
 
 use crate::defensive::check_range_matches_entries;
+use crate::storage::LogGap;
 use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
@@ -45,6 +46,41 @@ where C: RaftTypeConfig
 
         Ok(*entries[0].get_log_id())
     }
+
+    /// Find the ranges of missing log entries in `[start, end]`, in index order.
+    ///
+    /// Entries are read in chunks, thus it does not load the whole range into memory.
+    async fn find_log_gaps(&mut self, start: u64, end: u64) -> Result<Vec<LogGap>, StorageError<C::NodeId>> {
+        const CHUNK: u64 = 1024;
+
+        let mut gaps = vec![];
+        // The index of the next entry expected to be present.
+        let mut next = start;
+
+        let mut from = start;
+        while from <= end {
+            let to = std::cmp::min(end, from + CHUNK - 1);
+
+            for entry in self.try_get_log_entries(from..=to).await? {
+                let index = entry.get_log_id().index;
+                if index > next {
+                    gaps.push(LogGap {
+                        first: next,
+                        last: index - 1,
+                    });
+                }
+                next = std::cmp::max(next, index + 1);
+            }
+
+            from = to + 1;
+        }
+
+        if next <= end {
+            gaps.push(LogGap { first: next, last: end });
+        }
+
+        Ok(gaps)
+    }
 }
 
 impl<C, LR> RaftLogReaderExt<C> for LR
@@ -2,6 +2,10 @@
 
 mod callback;
 mod helper;
+mod last_applied;
+mod log_cache;
+#[cfg(test)] mod log_cache_test;
+mod log_gap;
 mod log_store_ext;
 mod snapshot_signature;
 mod v2;
@@ -11,6 +15,10 @@ use std::fmt::Debug;
 use std::ops::RangeBounds;
 
 pub use helper::StorageHelper;
+pub use last_applied::LastApplied;
+pub use log_cache::CachedLogReader;
+pub use log_cache::CachedLogStore;
+pub use log_gap::LogGap;
 pub use log_store_ext::RaftLogReaderExt;
 use openraft_macros::add_async_trait;
 pub use snapshot_signature::SnapshotSignature;
@@ -25,6 +33,7 @@ pub use crate::storage::callback::LogFlushed;
 use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
+use crate::RaftLogId;
 use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::StoredMembership;
@@ -38,13 +47,22 @@ where C: RaftTypeConfig
     /// Log entries upto which this snapshot includes, inclusive.
     pub last_log_id: Option<LogId<C::NodeId>>,
 
-    /// The last applied membership config.
+    /// The last applied membership config, i.e., the membership as of `last_log_id`.
+    ///
+    /// A node installing this snapshot adopts it as the committed membership, so that a node
+    /// recovering from a snapshot knows its peers without the purged membership logs.
     pub last_membership: StoredMembership<C>,
 
     /// To identify a snapshot when transferring.
     /// Caveat: even when two snapshot is built with the same `last_log_id`, they still could be
     /// different in bytes.
     pub snapshot_id: SnapshotId,
+
+    /// The format version of the snapshot data, `0` if it is not set.
+    ///
+    /// See [`Self::with_format_version()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub format_version: u32,
 }
 
 impl<C> fmt::Display for SnapshotMeta<C>
@@ -53,10 +71,11 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{snapshot_id: {}, last_log:{}, last_membership: {}}}",
+            "{{snapshot_id: {}, last_log:{}, last_membership: {}, format_version: {}}}",
             self.snapshot_id,
             DisplayOption(&self.last_log_id),
-            self.last_membership
+            self.last_membership,
+            self.format_version
         )
     }
 }
@@ -64,6 +83,36 @@ where C: RaftTypeConfig
 impl<C> SnapshotMeta<C>
 where C: RaftTypeConfig
 {
+    pub fn new(
+        last_log_id: Option<LogId<C::NodeId>>,
+        last_membership: StoredMembership<C>,
+        snapshot_id: SnapshotId,
+    ) -> Self {
+        Self {
+            last_log_id,
+            last_membership,
+            snapshot_id,
+            format_version: 0,
+        }
+    }
+
+    /// Set the format version of the snapshot data.
+    ///
+    /// A state machine that versions its snapshots should set it to
+    /// [`RaftStateMachine::snapshot_format_version()`] when building a snapshot. A snapshot with an
+    /// older version is migrated with [`Raft::migrate_snapshot()`] before being installed.
+    ///
+    /// [`Raft::migrate_snapshot()`]: crate::Raft::migrate_snapshot
+    pub fn with_format_version(mut self, format_version: u32) -> Self {
+        self.format_version = format_version;
+        self
+    }
+
+    /// Returns the format version of the snapshot data.
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
     pub fn signature(&self) -> SnapshotSignature<C::NodeId> {
         SnapshotSignature {
             last_log_id: self.last_log_id,
@@ -149,10 +198,48 @@ where C: RaftTypeConfig
         range: RB,
     ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>>;
 
+    /// Get at most `limit` log entries backward from index `high`, in descending index order.
+    ///
+    /// It returns the present entries with index in `[high - limit + 1, high]`. Since purged
+    /// entries are absent, the result stops at the first purged index.
+    ///
+    /// It is meant for tools that look for the most recent entries, without reading the whole log.
+    /// The default implementation reads the range with [`Self::try_get_log_entries`] and reverses
+    /// it. An implementation can override it to iterate backward directly.
+    async fn try_get_log_entries_rev(
+        &mut self,
+        high: u64,
+        limit: usize,
+    ) -> Result<Vec<C::Entry>, StorageError<C::NodeId>> {
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let low = high.saturating_sub(limit as u64 - 1);
+        let mut entries = self.try_get_log_entries(low..=high).await?;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// Get the term of the log entry at `index`.
+    ///
+    /// It returns `None` if the entry is not present, i.e., it is purged, or it is beyond the
+    /// last log.
+    ///
+    /// The default implementation reads the entry with [`Self::try_get_log_entries`]. An
+    /// implementation can override it to read the term without loading the whole entry.
+    async fn term_of_index(&mut self, index: u64) -> Result<Option<u64>, StorageError<C::NodeId>> {
+        let mut entries = self.try_get_log_entries(index..=index).await?;
+        Ok(entries.pop().map(|ent| ent.get_log_id().leader_id.term))
+    }
+
     /// Return the last saved vote by [`RaftLogStorage::save_vote`].
     ///
     /// A log reader must also be able to read the last saved vote by [`RaftLogStorage::save_vote`],
     /// See: [log-stream](`crate::docs::protocol::replication::log_stream`)
+    ///
+    /// A vote saved by an older version of the application, in an older layout, must be migrated to
+    /// the current [`Vote`], see [`RaftLogStorage::save_vote`].
     async fn read_vote(&mut self) -> Result<Option<Vote<C::NodeId>>, StorageError<C::NodeId>>;
 }
 
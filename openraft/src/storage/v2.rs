@@ -5,6 +5,11 @@
 
 mod raft_log_storage_ext;
 
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::io::Write;
+
+use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 pub use raft_log_storage_ext::RaftLogStorageExt;
 
@@ -19,6 +24,7 @@ use crate::RaftTypeConfig;
 use crate::Snapshot;
 use crate::SnapshotMeta;
 use crate::StorageError;
+use crate::StorageIOError;
 use crate::StoredMembership;
 use crate::Vote;
 
@@ -64,6 +70,15 @@ where C: RaftTypeConfig
     /// ### To ensure correctness:
     ///
     /// The vote must be persisted on disk before returning.
+    ///
+    /// ### Compatibility:
+    ///
+    /// The vote must remain readable by [`RaftLogReader::read_vote`] after the application is
+    /// upgraded to a newer version, which may change the layout of [`Vote`]. Thus the stored vote
+    /// should be tagged with the version of its layout, and an older layout should be migrated to
+    /// the current one when it is read, e.g., by storing a
+    /// [`compat::VersionedVote`](`crate::compat::VersionedVote`) with feature flags `compat` and `serde`. A
+    /// storage that does not persist data across restarts, such as an in-memory store, is exempt.
     async fn save_vote(&mut self, vote: &Vote<C::NodeId>) -> Result<(), StorageError<C::NodeId>>;
 
     /// Saves the last committed log id to storage.
@@ -90,6 +105,32 @@ where C: RaftTypeConfig
         Ok(None)
     }
 
+    /// Saves a hint of the matching log index on every follower and learner, reported by the
+    /// leader on this node.
+    ///
+    /// # Optional feature
+    ///
+    /// It is called only if [`Config::persist_replication_hints`] is enabled. When this node is
+    /// re-elected after a restart, the hints read by [`Self::read_replication_hints`] let it
+    /// start replicating to a target near its last known position, instead of searching for
+    /// the matching log from scratch. Hints are advisory: a stale hint only costs extra
+    /// AppendEntries round trips, thus they do not need to be flushed to disk before returning.
+    ///
+    /// [`Config::persist_replication_hints`]: crate::Config::persist_replication_hints
+    async fn save_replication_hints(
+        &mut self,
+        _hints: BTreeMap<C::NodeId, u64>,
+    ) -> Result<(), StorageError<C::NodeId>> {
+        // By default replication hints are not saved
+        Ok(())
+    }
+
+    /// Return the last saved replication hints by [`Self::save_replication_hints`].
+    async fn read_replication_hints(&mut self) -> Result<BTreeMap<C::NodeId, u64>, StorageError<C::NodeId>> {
+        // By default replication hints are not saved and this method just return an empty map.
+        Ok(BTreeMap::new())
+    }
+
     /// Append log entries and call the `callback` once logs are persisted on disk.
     ///
     /// It should returns immediately after saving the input log entries in memory, and calls the
@@ -126,6 +167,37 @@ where C: RaftTypeConfig
     ///
     /// - It must not leave a **hole** in logs.
     async fn purge(&mut self, log_id: LogId<C::NodeId>) -> Result<(), StorageError<C::NodeId>>;
+
+    /// Write the complete durable state of the log store, i.e., the vote, the committed log id and
+    /// the logs, to `w`.
+    ///
+    /// # Optional feature
+    ///
+    /// It is used to move a node to another host without replicating the logs again: the output
+    /// is loaded into an empty log store on the new host with [`Self::import_state`]. Together
+    /// with [`RaftStateMachine::export_state`], it covers the complete persistent state of a node.
+    ///
+    /// The default implementation returns an error.
+    async fn export_state<W>(&mut self, w: W) -> Result<(), StorageError<C::NodeId>>
+    where W: Write + OptionalSend {
+        let _ = w;
+        Err(StorageIOError::read(AnyError::error("export_state is not supported by this log store")).into())
+    }
+
+    /// Replace the durable state of the log store with the one written by [`Self::export_state`].
+    ///
+    /// # Optional feature
+    ///
+    /// A committed vote must be loaded as a non-committed vote with the same term, so that the
+    /// imported node starts as a follower instead of resuming the leadership of the exporting
+    /// node.
+    ///
+    /// The default implementation returns an error.
+    async fn import_state<R>(&mut self, r: R) -> Result<(), StorageError<C::NodeId>>
+    where R: Read + OptionalSend {
+        let _ = r;
+        Err(StorageIOError::write(AnyError::error("import_state is not supported by this log store")).into())
+    }
 }
 
 /// API for state machine and snapshot.
@@ -180,6 +252,13 @@ where C: RaftTypeConfig
     /// - An implementation with persistent snapshot: `apply_to_state_machine()` does not have to
     ///   persist state on disk. But every snapshot has to be persistent. And when starting up the
     ///   application, the state machine should be rebuilt from the last snapshot.
+    ///
+    /// If it returns an error and [`Config::apply_stall_backlog`] is set, applying is retried
+    /// from the entry after the last applied log id returned by [`Self::applied_state()`]. Thus
+    /// an implementation that may apply some of the entries before failing must record the last
+    /// applied log id for every applied entry, so that no entry is applied twice.
+    ///
+    /// [`Config::apply_stall_backlog`]: crate::Config::apply_stall_backlog
     async fn apply<I>(&mut self, entries: I) -> Result<Vec<C::R>, StorageError<C::NodeId>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
@@ -200,6 +279,11 @@ where C: RaftTypeConfig
     ///
     /// Openraft will use this handle to receive snapshot data.
     ///
+    /// If receiving is cancelled, e.g., a newer snapshot arrives or the leader changes, the handle
+    /// is dropped without being installed. An implementation that writes the data to a temporary
+    /// file should remove it when the handle is dropped, which also happens if the receiving task
+    /// is aborted.
+    ///
     /// See the [storage chapter of the guide][sto] for details on log compaction / snapshotting.
     ///
     /// [sto]: crate::docs::getting_started#3-implement-raftlogstorage-and-raftstatemachine
@@ -212,6 +296,15 @@ where C: RaftTypeConfig
     /// - the input snapshot should be saved, i.e., [`Self::get_current_snapshot`] should return it.
     /// - and all other snapshots should be deleted at this point.
     ///
+    /// The installation must be atomic: if it returns an error or is interrupted, e.g., the
+    /// returned future is dropped or the process crashes, the previous state machine and snapshot
+    /// must be left intact. An implementation usually decodes the snapshot completely before
+    /// swapping it in.
+    ///
+    /// Log entries covered by the snapshot are not removed by this method. Openraft purges them
+    /// with [`RaftLogStorage::purge`] after the snapshot is installed, and it is safe to be
+    /// interrupted in between, because the snapshot covers all of the purged entries.
+    ///
     /// ### snapshot
     ///
     /// A snapshot created from an earlier call to `begin_receiving_snapshot` which provided the
@@ -222,6 +315,37 @@ where C: RaftTypeConfig
         snapshot: Box<C::SnapshotData>,
     ) -> Result<(), StorageError<C::NodeId>>;
 
+    /// Returns the format version of the snapshots this state machine builds and installs.
+    ///
+    /// A snapshot built by this state machine should store this value with
+    /// [`SnapshotMeta::with_format_version()`]. It is read once when [`Raft`] is created.
+    ///
+    /// [`Raft`]: crate::Raft
+    fn snapshot_format_version(&self) -> u32 {
+        0
+    }
+
+    /// Migrate a received snapshot with an older format version to the current format version.
+    ///
+    /// It is called by [`Raft::migrate_snapshot()`] for a snapshot whose
+    /// [`SnapshotMeta::format_version()`] is less than [`Self::snapshot_format_version()`]. The
+    /// returned snapshot can then be installed.
+    ///
+    /// Return `Ok(None)` if the version can not be migrated, and `Raft::migrate_snapshot()` returns
+    /// [`UnsupportedSnapshotFormat`].
+    ///
+    /// The default implementation does not support any migration.
+    ///
+    /// [`Raft::migrate_snapshot()`]: crate::Raft::migrate_snapshot
+    /// [`UnsupportedSnapshotFormat`]: crate::error::UnsupportedSnapshotFormat
+    async fn migrate_snapshot(
+        &mut self,
+        snapshot: Snapshot<C>,
+    ) -> Result<Option<Snapshot<C>>, StorageError<C::NodeId>> {
+        let _ = snapshot;
+        Ok(None)
+    }
+
     /// Get a readable handle to the current snapshot.
     ///
     /// ### implementation algorithm
@@ -236,4 +360,31 @@ where C: RaftTypeConfig
     /// last-applied-membership config as part of the snapshot, which should be decoded for
     /// creating this method's response data.
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C::NodeId>>;
+
+    /// Write the complete durable state of the state machine, including the current snapshot, to
+    /// `w`.
+    ///
+    /// # Optional feature
+    ///
+    /// See [`RaftLogStorage::export_state`]. The output is loaded into an empty state machine with
+    /// [`Self::import_state`].
+    ///
+    /// The default implementation returns an error.
+    async fn export_state<W>(&mut self, w: W) -> Result<(), StorageError<C::NodeId>>
+    where W: Write + OptionalSend {
+        let _ = w;
+        Err(StorageIOError::read(AnyError::error("export_state is not supported by this state machine")).into())
+    }
+
+    /// Replace the durable state of the state machine with the one written by
+    /// [`Self::export_state`].
+    ///
+    /// # Optional feature
+    ///
+    /// The default implementation returns an error.
+    async fn import_state<R>(&mut self, r: R) -> Result<(), StorageError<C::NodeId>>
+    where R: Read + OptionalSend {
+        let _ = r;
+        Err(StorageIOError::write(AnyError::error("import_state is not supported by this state machine")).into())
+    }
 }
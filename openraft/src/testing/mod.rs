@@ -1,10 +1,14 @@
 //! Testing utilities for OpenRaft.
 
+#[cfg(feature = "decision-trace")] mod replay;
 mod store_builder;
 mod suite;
 
 use std::collections::BTreeSet;
 
+#[cfg(feature = "decision-trace")] pub use replay::read_decision_trace;
+#[cfg(feature = "decision-trace")] pub use replay::replay_decision_trace;
+#[cfg(feature = "decision-trace")] pub use replay::ReplayError;
 pub use store_builder::StoreBuilder;
 pub use suite::Suite;
 
@@ -0,0 +1,185 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::Fatal;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::DecisionRecord;
+use crate::raft::TraceInput;
+use crate::type_config::alias::AsyncRuntimeOf;
+use crate::type_config::alias::InstantOf;
+use crate::AsyncRuntime;
+use crate::Instant;
+use crate::Raft;
+use crate::RaftTypeConfig;
+
+/// An error that occurs when replaying a decision trace with [`replay_decision_trace()`].
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError<C>
+where C: RaftTypeConfig
+{
+    /// The record has no input to feed, e.g., a snapshot, or the response to a vote request of an
+    /// election started by the recorded node's own timer.
+    #[error("record {seq}({kind}) can not be replayed")]
+    Unreplayable { seq: u64, kind: String },
+
+    /// The trace can not be decoded.
+    #[error("invalid trace at record {seq}: {error}")]
+    InvalidTrace { seq: u64, error: String },
+
+    /// The replayed node made a different decision or reached a different state.
+    #[error("replay diverges at record {seq}: {reason}")]
+    Diverged { seq: u64, reason: String },
+
+    #[error(transparent)]
+    Fatal(#[from] Fatal<C>),
+}
+
+/// Parse a decision trace written by [`Raft::set_decision_trace()`], one JSON record per line.
+///
+/// [`Raft::set_decision_trace()`]: crate::Raft::set_decision_trace
+pub fn read_decision_trace<C>(trace: &str) -> Result<Vec<DecisionRecord<C>>, serde_json::Error>
+where C: RaftTypeConfig {
+    trace.lines().filter(|l| !l.trim().is_empty()).map(serde_json::from_str).collect()
+}
+
+/// Feed the recorded messages in `records` to a fresh `raft` node and assert it makes the same
+/// decisions and reaches the same states.
+///
+/// Every message is fed as long after the replay starts as it was handled after the recording
+/// started, so that time-dependent decisions, such as rejecting a vote request within a leader
+/// lease, are reproduced. `raft` has to start from the same state as the recorded node, and should
+/// not receive other messages, e.g., by disabling the election with [`Config::enable_elect`].
+///
+/// It returns the records of the replay, or the first divergence from `records`. A message this
+/// node did not receive from a peer, e.g., the response to a vote request sent by its own
+/// election, can not be replayed.
+///
+/// [`Config::enable_elect`]: crate::Config::enable_elect
+pub async fn replay_decision_trace<C>(
+    raft: &Raft<C>,
+    records: &[DecisionRecord<C>],
+) -> Result<Vec<DecisionRecord<C>>, ReplayError<C>>
+where
+    C: RaftTypeConfig,
+{
+    let buf = TraceBuf::default();
+    raft.set_decision_trace(Some(buf.clone())).await?;
+
+    let started_at = InstantOf::<C>::now();
+
+    for record in records {
+        let Some(input) = &record.input else {
+            return Err(ReplayError::Unreplayable {
+                seq: record.seq,
+                kind: record.kind.clone(),
+            });
+        };
+
+        AsyncRuntimeOf::<C>::sleep_until(started_at + Duration::from_millis(record.elapsed_ms)).await;
+
+        match input {
+            TraceInput::VoteRequest(req) => {
+                raft.vote(req.clone()).await.map_err(|e| e.into_fatal().unwrap())?;
+            }
+            TraceInput::AppendEntries {
+                vote,
+                prev_log_id,
+                entries,
+                leader_commit,
+            } => {
+                let entries = serde_json::from_value(entries.clone()).map_err(|e| ReplayError::InvalidTrace {
+                    seq: record.seq,
+                    error: e.to_string(),
+                })?;
+
+                let req = AppendEntriesRequest::new(*vote, *prev_log_id, entries, *leader_commit);
+                raft.append_entries(req).await.map_err(|e| e.into_fatal().unwrap())?;
+            }
+            TraceInput::VoteResponse { .. } => {
+                return Err(ReplayError::Unreplayable {
+                    seq: record.seq,
+                    kind: record.kind.clone(),
+                });
+            }
+        }
+    }
+
+    raft.set_decision_trace(None::<TraceBuf>).await?;
+
+    let data = buf.0.lock().unwrap().clone();
+    let replayed = std::str::from_utf8(&data)
+        .map_err(|e| e.to_string())
+        .and_then(|s| read_decision_trace::<C>(s).map_err(|e| e.to_string()))
+        .map_err(|error| ReplayError::InvalidTrace { seq: 0, error })?;
+
+    for (i, expected) in records.iter().enumerate() {
+        let Some(got) = replayed.get(i) else {
+            return Err(ReplayError::Diverged {
+                seq: expected.seq,
+                reason: "no decision is made".to_string(),
+            });
+        };
+
+        if let Some(reason) = diverge(expected, got) {
+            return Err(ReplayError::Diverged {
+                seq: expected.seq,
+                reason,
+            });
+        }
+    }
+
+    if replayed.len() > records.len() {
+        return Err(ReplayError::Diverged {
+            seq: records.len() as u64,
+            reason: format!("{} more decisions are made", replayed.len() - records.len()),
+        });
+    }
+
+    Ok(replayed)
+}
+
+/// Describe how the replayed record `got` differs from the recorded one, if it does.
+fn diverge<C>(expected: &DecisionRecord<C>, got: &DecisionRecord<C>) -> Option<String>
+where C: RaftTypeConfig {
+    if expected.kind != got.kind {
+        return Some(format!("kind: expect: {}, got: {}", expected.kind, got.kind));
+    }
+    if expected.message != got.message {
+        return Some(format!("message: expect: {}, got: {}", expected.message, got.message));
+    }
+    if expected.before != got.before {
+        return Some(format!(
+            "state before: expect: {:?}, got: {:?}",
+            expected.before, got.before
+        ));
+    }
+    if expected.decision != got.decision {
+        return Some(format!(
+            "decision: expect: {}, got: {}",
+            expected.decision, got.decision
+        ));
+    }
+    if expected.after != got.after {
+        return Some(format!(
+            "state after: expect: {:?}, got: {:?}",
+            expected.after, got.after
+        ));
+    }
+    None
+}
+
+/// An in-memory buffer the replayed records are written to.
+#[derive(Clone, Default)]
+struct TraceBuf(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for TraceBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
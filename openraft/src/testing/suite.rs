@@ -100,6 +100,8 @@ where
         run_fut(run_test(builder, Self::save_vote))?;
         run_fut(run_test(builder, Self::get_log_entries))?;
         run_fut(run_test(builder, Self::try_get_log_entry))?;
+        run_fut(run_test(builder, Self::try_get_log_entries_rev))?;
+        run_fut(run_test(builder, Self::term_of_index))?;
         run_fut(run_test(builder, Self::initial_logs))?;
         run_fut(run_test(builder, Self::get_log_state))?;
         run_fut(run_test(builder, Self::get_log_id))?;
@@ -739,6 +741,90 @@ where
         Ok(())
     }
 
+    pub async fn try_get_log_entries_rev(mut store: LS, mut sm: SM) -> Result<(), StorageError<C::NodeId>> {
+        Self::feed_10_logs_vote_self(&mut store).await?;
+
+        tracing::info!("--- read backward from the last log");
+        {
+            let logs = store.try_get_log_entries_rev(10, 3).await?;
+            assert_eq!(
+                vec![log_id_0(1, 10), log_id_0(1, 9), log_id_0(1, 8)],
+                logs.iter().map(|x| *x.get_log_id()).collect::<Vec<_>>()
+            );
+        }
+
+        tracing::info!("--- read backward beyond the last log");
+        {
+            let logs = store.try_get_log_entries_rev(12, 4).await?;
+            assert_eq!(
+                vec![log_id_0(1, 10), log_id_0(1, 9)],
+                logs.iter().map(|x| *x.get_log_id()).collect::<Vec<_>>()
+            );
+        }
+
+        tracing::info!("--- zero limit");
+        {
+            let logs = store.try_get_log_entries_rev(10, 0).await?;
+            assert!(logs.is_empty());
+        }
+
+        store.purge(log_id_0(1, 5)).await?;
+
+        // `purge()` does not have to do the purge at once.
+        // The implementation may choose to do it in the background.
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        tracing::info!("--- stop at the purged log");
+        {
+            let logs = store.try_get_log_entries_rev(8, 100).await?;
+            assert_eq!(
+                vec![log_id_0(1, 8), log_id_0(1, 7), log_id_0(1, 6)],
+                logs.iter().map(|x| *x.get_log_id()).collect::<Vec<_>>()
+            );
+
+            let logs = store.try_get_log_entries_rev(5, 100).await?;
+            assert!(logs.is_empty(), "all purged");
+        }
+
+        Ok(())
+    }
+
+    pub async fn term_of_index(mut store: LS, mut sm: SM) -> Result<(), StorageError<C::NodeId>> {
+        append(&mut store, [blank_ent_0::<C>(0, 0)]).await?;
+        append(&mut store, [blank_ent_0::<C>(1, 1), blank_ent_0::<C>(1, 2)]).await?;
+        append(&mut store, [blank_ent_0::<C>(2, 3), blank_ent_0::<C>(2, 4)]).await?;
+        append(&mut store, [blank_ent_0::<C>(3, 5)]).await?;
+        Self::default_vote(&mut store).await?;
+
+        tracing::info!("--- term of present entries");
+        {
+            assert_eq!(Some(0), store.term_of_index(0).await?);
+            assert_eq!(Some(1), store.term_of_index(2).await?);
+            assert_eq!(Some(2), store.term_of_index(3).await?);
+            assert_eq!(Some(3), store.term_of_index(5).await?);
+        }
+
+        tracing::info!("--- no term beyond the last log");
+        {
+            assert_eq!(None, store.term_of_index(6).await?);
+        }
+
+        store.purge(log_id_0(2, 3)).await?;
+
+        // `purge()` does not have to do the purge at once.
+        // The implementation may choose to do it in the background.
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        tracing::info!("--- no term for purged entries");
+        {
+            assert_eq!(None, store.term_of_index(1).await?);
+            assert_eq!(None, store.term_of_index(3).await?);
+            assert_eq!(Some(2), store.term_of_index(4).await?);
+        }
+
+        Ok(())
+    }
+
     pub async fn initial_logs(mut store: LS, mut sm: SM) -> Result<(), StorageError<C::NodeId>> {
         let ent = store.try_get_log_entry(0).await?;
         assert!(ent.is_none(), "store initialized");
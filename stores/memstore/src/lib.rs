@@ -4,10 +4,15 @@
 #[cfg(test)] mod test;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::io::Cursor;
+use std::io::Read;
+use std::io::Write;
 use std::ops::RangeBounds;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -19,6 +24,7 @@ use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftSnapshotBuilder;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::AnyError;
 use openraft::Entry;
 use openraft::EntryPayload;
 use openraft::LogId;
@@ -81,6 +87,12 @@ openraft::declare_raft_types!(
         Node = (),
 );
 
+/// The format version of the snapshots built by [`MemStateMachine`].
+///
+/// Snapshots of version 0 are built before format versioning is introduced. They have the same
+/// data layout and are migrated by updating the version.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
 /// The application snapshot type which the `MemStore` works with.
 #[derive(Debug)]
 pub struct MemStoreSnapshot {
@@ -112,12 +124,27 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+    /// Delay saving vote, before the vote is written. If it is set failing, no vote is written.
+    SaveVote,
+    /// Delay installing a snapshot, after the snapshot is decoded and before the state machine is
+    /// replaced.
+    InstallSnapshot,
+    /// Saving the committed log id. It can only be set failing.
+    SaveCommitted,
+    /// Applying entries to the state machine. It can only be set failing: no entry is applied.
+    Apply,
+    /// Applying entries to the state machine. It can only be set failing: only the first entry of
+    /// every call is applied before it fails.
+    ApplyPartially,
+    /// Delay reading log entries, before the log is read.
+    ReadLog,
 }
 
 /// Block operations for testing purposes.
 #[derive(Clone, Debug, Default)]
 pub struct BlockConfig {
     inner: Arc<Mutex<BTreeMap<BlockOperation, Duration>>>,
+    failing: Arc<Mutex<BTreeSet<BlockOperation>>>,
 }
 
 impl BlockConfig {
@@ -132,9 +159,24 @@ impl BlockConfig {
     }
 
     /// Clear a blocking flag for an operation.
-    pub fn clear_blocking(&mut self, block: BlockOperation) {
+    pub fn clear_blocking(&self, block: BlockOperation) {
         self.inner.lock().unwrap().remove(&block);
     }
+
+    /// Make an operation return an error for testing purposes, until it is cleared.
+    pub fn set_failing(&self, op: BlockOperation) {
+        self.failing.lock().unwrap().insert(op);
+    }
+
+    /// Check if an operation is set failing.
+    pub fn is_failing(&self, op: &BlockOperation) -> bool {
+        self.failing.lock().unwrap().contains(op)
+    }
+
+    /// Clear the failing flag for an operation.
+    pub fn clear_failing(&self, op: BlockOperation) {
+        self.failing.lock().unwrap().remove(&op);
+    }
 }
 
 /// An in-memory log storage implementing the `RaftLogStorage` trait.
@@ -151,6 +193,9 @@ pub struct MemLogStore {
 
     /// The current hard state.
     vote: RwLock<Option<Vote<MemNodeId>>>,
+
+    /// The matching log index of every replication target, saved by the leader.
+    replication_hints: RwLock<BTreeMap<MemNodeId, u64>>,
 }
 
 impl MemLogStore {
@@ -163,8 +208,25 @@ impl MemLogStore {
             log,
             block,
             vote: RwLock::new(None),
+            replication_hints: RwLock::new(BTreeMap::new()),
         }
     }
+
+    /// Remove the log entry at `index`, leaving a gap in the log.
+    ///
+    /// This method is only used for testing purposes.
+    pub async fn remove_log_entry(&self, index: u64) {
+        self.log.write().await.remove(&index);
+    }
+}
+
+/// The serialized form of the durable state of a [`MemLogStore`].
+#[derive(Serialize, Deserialize, Debug)]
+struct MemLogStoreDump {
+    vote: Option<Vote<MemNodeId>>,
+    committed: Option<LogId<MemNodeId>>,
+    last_purged_log_id: Option<LogId<MemNodeId>>,
+    log: BTreeMap<u64, String>,
 }
 
 /// An in-memory key-value storage implementing the `RaftStateMachine` trait.
@@ -179,6 +241,9 @@ pub struct MemStateMachine {
 
     /// Block operations for testing purposes.
     pub block: BlockConfig,
+
+    /// The number of entries applied, to check that no entry is applied twice.
+    applied_count: Arc<AtomicU64>,
 }
 
 impl MemStateMachine {
@@ -191,9 +256,15 @@ impl MemStateMachine {
             snapshot_idx: Arc::new(Mutex::new(0)),
             current_snapshot,
             block,
+            applied_count: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Get the number of entries applied since this state machine is created.
+    pub fn applied_count(&self) -> u64 {
+        self.applied_count.load(Ordering::Relaxed)
+    }
+
     /// Remove the current snapshot.
     ///
     /// This method is only used for testing purposes.
@@ -214,6 +285,13 @@ impl MemStateMachine {
     }
 }
 
+/// The serialized form of the durable state of a [`MemStateMachine`].
+#[derive(Serialize, Deserialize, Debug)]
+struct MemStateMachineDump {
+    sm: MemStoreStateMachine,
+    snapshot: Option<(SnapshotMeta<TypeConfig>, Vec<u8>)>,
+}
+
 pub fn new_mem_store() -> (Arc<MemLogStore>, Arc<MemStateMachine>) {
     let block = BlockConfig::default();
     (
@@ -227,6 +305,11 @@ impl RaftLogReader<TypeConfig> for Arc<MemLogStore> {
         &mut self,
         range: RB,
     ) -> Result<Vec<Entry<TypeConfig>>, StorageError<MemNodeId>> {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::ReadLog) {
+            tracing::info!(?d, "block reading log");
+            tokio::time::sleep(d).await;
+        }
+
         let mut entries = vec![];
         {
             let log = self.log.read().await;
@@ -239,6 +322,39 @@ impl RaftLogReader<TypeConfig> for Arc<MemLogStore> {
         Ok(entries)
     }
 
+    async fn try_get_log_entries_rev(
+        &mut self,
+        high: u64,
+        limit: usize,
+    ) -> Result<Vec<Entry<TypeConfig>>, StorageError<MemNodeId>> {
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let low = high.saturating_sub(limit as u64 - 1);
+
+        let mut entries = vec![];
+        {
+            let log = self.log.read().await;
+            for (_, serialized) in log.range(low..=high).rev() {
+                let ent = serde_json::from_str(serialized).map_err(|e| StorageIOError::read_logs(&e))?;
+                entries.push(ent);
+            }
+        };
+
+        Ok(entries)
+    }
+
+    async fn term_of_index(&mut self, index: u64) -> Result<Option<u64>, StorageError<MemNodeId>> {
+        let log = self.log.read().await;
+        let Some(serialized) = log.get(&index) else {
+            return Ok(None);
+        };
+
+        let ent: Entry<TypeConfig> = serde_json::from_str(serialized).map_err(|e| StorageIOError::read_logs(&e))?;
+        Ok(Some(ent.log_id.leader_id.term))
+    }
+
     async fn read_vote(&mut self) -> Result<Option<Vote<MemNodeId>>, StorageError<MemNodeId>> {
         Ok(*self.vote.read().await)
     }
@@ -284,11 +400,8 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id)
+            .with_format_version(SNAPSHOT_FORMAT_VERSION);
 
         let snapshot = MemStoreSnapshot {
             meta: meta.clone(),
@@ -345,6 +458,16 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip(self))]
     async fn save_vote(&mut self, vote: &Vote<MemNodeId>) -> Result<(), StorageError<MemNodeId>> {
         tracing::debug!(?vote, "save_vote");
+
+        if let Some(d) = self.block.get_blocking(&BlockOperation::SaveVote) {
+            tracing::info!(?d, "delay saving vote");
+            tokio::time::sleep(d).await;
+        }
+
+        if self.block.is_failing(&BlockOperation::SaveVote) {
+            return Err(StorageIOError::write_vote(AnyError::error("injected failure: save_vote")).into());
+        }
+
         let mut h = self.vote.write().await;
 
         *h = Some(*vote);
@@ -353,6 +476,11 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
 
     async fn save_committed(&mut self, committed: Option<LogId<MemNodeId>>) -> Result<(), StorageError<MemNodeId>> {
         tracing::debug!(?committed, "save_committed");
+
+        if self.block.is_failing(&BlockOperation::SaveCommitted) {
+            return Err(StorageIOError::write(AnyError::error("injected failure: save_committed")).into());
+        }
+
         let mut c = self.committed.write().await;
         *c = committed;
         Ok(())
@@ -362,6 +490,18 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
         Ok(*self.committed.read().await)
     }
 
+    async fn save_replication_hints(&mut self, hints: BTreeMap<MemNodeId, u64>) -> Result<(), StorageError<MemNodeId>> {
+        tracing::debug!(?hints, "save_replication_hints");
+
+        let mut h = self.replication_hints.write().await;
+        *h = hints;
+        Ok(())
+    }
+
+    async fn read_replication_hints(&mut self) -> Result<BTreeMap<MemNodeId, u64>, StorageError<MemNodeId>> {
+        Ok(self.replication_hints.read().await.clone())
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: LogFlushed<TypeConfig>) -> Result<(), StorageError<MemNodeId>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend {
@@ -418,6 +558,37 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
 
         Ok(())
     }
+
+    async fn export_state<W>(&mut self, w: W) -> Result<(), StorageError<MemNodeId>>
+    where W: Write + OptionalSend {
+        let dump = MemLogStoreDump {
+            vote: *self.vote.read().await,
+            committed: *self.committed.read().await,
+            last_purged_log_id: *self.last_purged_log_id.read().await,
+            log: self.log.read().await.clone(),
+        };
+
+        serde_json::to_writer(w, &dump).map_err(|e| StorageIOError::write(&e))?;
+        Ok(())
+    }
+
+    async fn import_state<R>(&mut self, r: R) -> Result<(), StorageError<MemNodeId>>
+    where R: Read + OptionalSend {
+        let dump: MemLogStoreDump = serde_json::from_reader(r).map_err(|e| StorageIOError::read(&e))?;
+
+        // A committed vote is imported as a non-committed one, so that the node starts as a
+        // follower.
+        let vote = dump.vote.map(|v| Vote {
+            leader_id: v.leader_id,
+            committed: false,
+        });
+
+        *self.vote.write().await = vote;
+        *self.committed.write().await = dump.committed;
+        *self.last_purged_log_id.write().await = dump.last_purged_log_id;
+        *self.log.write().await = dump.log;
+        Ok(())
+    }
 }
 
 impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
@@ -436,6 +607,12 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
         I::IntoIter: OptionalSend,
     {
+        if self.block.is_failing(&BlockOperation::Apply) {
+            return Err(StorageIOError::write_state_machine(AnyError::error("injected failure: apply")).into());
+        }
+
+        let apply_partially = self.block.is_failing(&BlockOperation::ApplyPartially);
+
         let mut res = Vec::new();
 
         let mut sm = self.sm.write().await;
@@ -443,7 +620,14 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         for entry in entries {
             tracing::debug!(%entry.log_id, "replicate to sm");
 
+            if apply_partially && !res.is_empty() {
+                return Err(
+                    StorageIOError::write_state_machine(AnyError::error("injected failure: apply partially")).into(),
+                );
+            }
+
             sm.last_applied_log = Some(entry.log_id);
+            self.applied_count.fetch_add(1, Ordering::Relaxed);
 
             match entry.payload {
                 EntryPayload::Blank => res.push(ClientResponse(None)),
@@ -499,20 +683,44 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             tracing::debug!("JSON SNAP DATA:{}", y);
         }
 
-        // Update the state machine.
-        {
-            let new_sm: MemStoreStateMachine = serde_json::from_slice(&new_snapshot.data)
-                .map_err(|e| StorageIOError::read_snapshot(Some(new_snapshot.meta.signature()), &e))?;
-            let mut sm = self.sm.write().await;
-            *sm = new_sm;
+        // Decode the snapshot before touching any state, so that a failed or interrupted
+        // installation leaves the previous state machine and snapshot intact.
+        let new_sm: MemStoreStateMachine = serde_json::from_slice(&new_snapshot.data)
+            .map_err(|e| StorageIOError::read_snapshot(Some(new_snapshot.meta.signature()), &e))?;
+
+        if let Some(d) = self.block.get_blocking(&BlockOperation::InstallSnapshot) {
+            tracing::info!(?d, "delay installing snapshot");
+            tokio::time::sleep(d).await;
         }
 
-        // Update current snapshot.
+        // Replace the state machine and the current snapshot while holding both locks, so that no
+        // reader observes one without the other.
+        let mut sm = self.sm.write().await;
         let mut current_snapshot = self.current_snapshot.write().await;
+
+        *sm = new_sm;
         *current_snapshot = Some(new_snapshot);
         Ok(())
     }
 
+    fn snapshot_format_version(&self) -> u32 {
+        SNAPSHOT_FORMAT_VERSION
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, snapshot))]
+    async fn migrate_snapshot(
+        &mut self,
+        mut snapshot: Snapshot<TypeConfig>,
+    ) -> Result<Option<Snapshot<TypeConfig>>, StorageError<MemNodeId>> {
+        match snapshot.meta.format_version() {
+            0 => {
+                snapshot.meta = snapshot.meta.with_format_version(SNAPSHOT_FORMAT_VERSION);
+                Ok(Some(snapshot))
+            }
+            _ => Ok(None),
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<MemNodeId>> {
         match &*self.current_snapshot.read().await {
@@ -526,4 +734,26 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             None => Ok(None),
         }
     }
+
+    async fn export_state<W>(&mut self, w: W) -> Result<(), StorageError<MemNodeId>>
+    where W: Write + OptionalSend {
+        let snapshot = self.current_snapshot.read().await.as_ref().map(|s| (s.meta.clone(), s.data.clone()));
+
+        let dump = MemStateMachineDump {
+            sm: self.sm.read().await.clone(),
+            snapshot,
+        };
+
+        serde_json::to_writer(w, &dump).map_err(|e| StorageIOError::write(&e))?;
+        Ok(())
+    }
+
+    async fn import_state<R>(&mut self, r: R) -> Result<(), StorageError<MemNodeId>>
+    where R: Read + OptionalSend {
+        let dump: MemStateMachineDump = serde_json::from_reader(r).map_err(|e| StorageIOError::read(&e))?;
+
+        *self.sm.write().await = dump.sm;
+        *self.current_snapshot.write().await = dump.snapshot.map(|(meta, data)| MemStoreSnapshot { meta, data });
+        Ok(())
+    }
 }
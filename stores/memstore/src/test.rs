@@ -1,9 +1,23 @@
+use std::io::Cursor;
 use std::sync::Arc;
+use std::time::Duration;
 
+use maplit::btreeset;
+use openraft::storage::CachedLogStore;
+use openraft::storage::RaftLogReader;
+use openraft::storage::RaftLogStorage;
+use openraft::storage::RaftLogStorageExt;
+use openraft::storage::RaftSnapshotBuilder;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::blank_ent;
+use openraft::testing::log_id;
+use openraft::testing::membership_ent;
 use openraft::testing::StoreBuilder;
 use openraft::testing::Suite;
 use openraft::StorageError;
+use openraft::Vote;
 
+use crate::BlockOperation;
 use crate::MemLogStore;
 use crate::MemNodeId;
 use crate::MemStateMachine;
@@ -23,3 +37,181 @@ pub fn test_mem_store() -> Result<(), StorageError<MemNodeId>> {
     Suite::test_all(MemStoreBuilder {})?;
     Ok(())
 }
+
+struct CachedMemStoreBuilder {}
+
+impl StoreBuilder<TypeConfig, CachedLogStore<TypeConfig, Arc<MemLogStore>>, Arc<MemStateMachine>, ()>
+    for CachedMemStoreBuilder
+{
+    async fn build(
+        &self,
+    ) -> Result<((), CachedLogStore<TypeConfig, Arc<MemLogStore>>, Arc<MemStateMachine>), StorageError<MemNodeId>> {
+        let (log_store, sm) = crate::new_mem_store();
+        Ok(((), CachedLogStore::new(log_store, 3), sm))
+    }
+}
+
+#[test]
+pub fn test_cached_mem_store() -> Result<(), StorageError<MemNodeId>> {
+    Suite::test_all(CachedMemStoreBuilder {})?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_import() -> Result<(), StorageError<MemNodeId>> {
+    let (mut log_store, mut sm) = crate::new_mem_store();
+
+    let entries = [
+        blank_ent::<TypeConfig>(0, 0, 0),
+        membership_ent::<TypeConfig>(1, 1, 1, vec![btreeset! {1,2,3}]),
+        blank_ent::<TypeConfig>(1, 1, 2),
+    ];
+
+    {
+        let mut log = log_store.log.write().await;
+        for ent in entries.iter() {
+            log.insert(ent.log_id.index, serde_json::to_string(ent).unwrap());
+        }
+    }
+    log_store.save_vote(&Vote::new_committed(1, 1)).await?;
+    log_store.save_committed(Some(log_id(1, 1, 2))).await?;
+    log_store.purge(log_id(0, 0, 0)).await?;
+
+    sm.apply(entries.clone()).await?;
+    sm.build_snapshot().await?;
+
+    let mut log_buf = vec![];
+    let mut sm_buf = vec![];
+    log_store.export_state(&mut log_buf).await?;
+    sm.export_state(&mut sm_buf).await?;
+
+    let (mut imported_log_store, mut imported_sm) = crate::new_mem_store();
+    imported_log_store.import_state(log_buf.as_slice()).await?;
+    imported_sm.import_state(sm_buf.as_slice()).await?;
+
+    assert_eq!(
+        log_store.get_log_state().await?,
+        imported_log_store.get_log_state().await?
+    );
+    assert_eq!(*log_store.log.read().await, *imported_log_store.log.read().await);
+    // Log at index 0 is purged.
+    assert_eq!(2, imported_log_store.try_get_log_entries(..).await?.len());
+    assert_eq!(
+        log_store.read_committed().await?,
+        imported_log_store.read_committed().await?
+    );
+
+    // A committed vote is imported as non-committed, so that the node starts as a follower.
+    assert_eq!(Some(Vote::new(1, 1)), imported_log_store.read_vote().await?);
+
+    assert_eq!(sm.applied_state().await?, imported_sm.applied_state().await?);
+    assert_eq!(
+        sm.get_current_snapshot().await?.map(|s| s.meta),
+        imported_sm.get_current_snapshot().await?.map(|s| s.meta)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_import_through_cached_log_store() -> Result<(), StorageError<MemNodeId>> {
+    let (log_store, _) = crate::new_mem_store();
+    let mut log_store = CachedLogStore::new(log_store, 8);
+
+    log_store
+        .blocking_append([
+            blank_ent::<TypeConfig>(0, 0, 0),
+            membership_ent::<TypeConfig>(1, 1, 1, vec![btreeset! {1,2,3}]),
+            blank_ent::<TypeConfig>(1, 1, 2),
+        ])
+        .await?;
+    log_store.save_vote(&Vote::new_committed(1, 1)).await?;
+    log_store.save_committed(Some(log_id(1, 1, 2))).await?;
+
+    let mut log_buf = vec![];
+    log_store.export_state(&mut log_buf).await?;
+
+    // The cache of the importing store holds entries that are replaced by the import.
+    let (imported_log_store, _) = crate::new_mem_store();
+    let mut imported_log_store = CachedLogStore::new(imported_log_store, 8);
+    imported_log_store
+        .blocking_append([
+            blank_ent::<TypeConfig>(0, 0, 0),
+            blank_ent::<TypeConfig>(2, 2, 1),
+            blank_ent::<TypeConfig>(2, 2, 2),
+        ])
+        .await?;
+
+    imported_log_store.import_state(log_buf.as_slice()).await?;
+
+    assert_eq!(
+        log_store.get_log_state().await?,
+        imported_log_store.get_log_state().await?
+    );
+    assert_eq!(
+        vec![log_id(0, 0, 0), log_id(1, 1, 1), log_id(1, 1, 2)],
+        imported_log_store.try_get_log_entries(0..3).await?.iter().map(|e| e.log_id).collect::<Vec<_>>()
+    );
+
+    let mut log_reader = imported_log_store.get_log_reader().await;
+    assert_eq!(
+        vec![log_id(1, 1, 1), log_id(1, 1, 2)],
+        log_reader.try_get_log_entries(1..3).await?.iter().map(|e| e.log_id).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interrupted_install_snapshot_keeps_prior_state() -> Result<(), StorageError<MemNodeId>> {
+    let (_, mut sm) = crate::new_mem_store();
+    sm.apply([
+        blank_ent::<TypeConfig>(0, 0, 0),
+        membership_ent::<TypeConfig>(1, 1, 1, vec![btreeset! {1,2,3}]),
+    ])
+    .await?;
+    sm.build_snapshot().await?;
+
+    let (_, mut leader_sm) = crate::new_mem_store();
+    leader_sm
+        .apply([
+            blank_ent::<TypeConfig>(0, 0, 0),
+            membership_ent::<TypeConfig>(1, 1, 1, vec![btreeset! {1,2}]),
+            blank_ent::<TypeConfig>(2, 1, 2),
+        ])
+        .await?;
+    let leader_snapshot = leader_sm.build_snapshot().await?;
+
+    let prior_state = sm.applied_state().await?;
+    let prior_snapshot = sm.get_current_snapshot().await?.map(|s| s.meta);
+
+    // Interrupt the installation after the snapshot is decoded and before it is swapped in.
+    {
+        sm.block.set_blocking(BlockOperation::InstallSnapshot, Duration::from_millis(1_000));
+
+        let handle = {
+            let mut sm = sm.clone();
+            let snapshot = leader_snapshot.snapshot.clone();
+            tokio::spawn(async move { sm.install_snapshot(&leader_snapshot.meta, snapshot).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+
+        assert_eq!(prior_state, sm.applied_state().await?);
+        assert_eq!(prior_snapshot, sm.get_current_snapshot().await?.map(|s| s.meta));
+    }
+
+    // A snapshot that can not be decoded is rejected without changing any state.
+    {
+        let meta = leader_sm.get_current_snapshot().await?.unwrap().meta;
+        let res = sm.install_snapshot(&meta, Box::new(Cursor::new(b"corrupted".to_vec()))).await;
+        assert!(res.is_err());
+
+        assert_eq!(prior_state, sm.applied_state().await?);
+        assert_eq!(prior_snapshot, sm.get_current_snapshot().await?.map(|s| s.meta));
+    }
+
+    Ok(())
+}
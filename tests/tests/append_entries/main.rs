@@ -17,4 +17,7 @@ mod t30_replication_1_voter_to_isolated_learner;
 mod t60_enable_heartbeat;
 mod t61_heartbeat_reject_vote;
 mod t61_large_heartbeat;
+mod t62_quiesce_idle_group;
+mod t63_append_entries_validator;
+mod t64_leader_write_ahead;
 mod t90_issue_216_stale_last_log_id;
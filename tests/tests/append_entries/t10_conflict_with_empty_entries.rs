@@ -52,12 +52,12 @@ async fn conflict_with_empty_entries() -> Result<()> {
 
     // Expect conflict even if the message contains no entries.
 
-    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-        vote: Vote::new_committed(1, 1),
-        prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
-        entries: vec![],
-        leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
-    };
+    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+        Vote::new_committed(1, 1),
+        Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
+        vec![],
+        Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
+    );
 
     let option = RPCOption::new(Duration::from_millis(1_000));
     let resp = router.new_client(0, &()).await.append_entries(rpc, option).await?;
@@ -66,10 +66,10 @@ async fn conflict_with_empty_entries() -> Result<()> {
 
     // Feed logs
 
-    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-        vote: Vote::new_committed(1, 1),
-        prev_log_id: None,
-        entries: vec![blank_ent(0, 0, 0), blank_ent(1, 0, 1), Entry {
+    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+        Vote::new_committed(1, 1),
+        None,
+        vec![blank_ent(0, 0, 0), blank_ent(1, 0, 1), Entry {
             log_id: LogId::new(CommittedLeaderId::new(1, 0), 2),
             payload: EntryPayload::Normal(ClientRequest {
                 client: "foo".to_string(),
@@ -77,8 +77,8 @@ async fn conflict_with_empty_entries() -> Result<()> {
                 status: "bar".to_string(),
             }),
         }],
-        leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
-    };
+        Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
+    );
 
     let option = RPCOption::new(Duration::from_millis(1_000));
 
@@ -88,12 +88,12 @@ async fn conflict_with_empty_entries() -> Result<()> {
 
     // Expect a conflict with prev_log_index == 3
 
-    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-        vote: Vote::new_committed(1, 1),
-        prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 3)),
-        entries: vec![],
-        leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
-    };
+    let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+        Vote::new_committed(1, 1),
+        Some(LogId::new(CommittedLeaderId::new(1, 0), 3)),
+        vec![],
+        Some(LogId::new(CommittedLeaderId::new(1, 0), 5)),
+    );
 
     let option = RPCOption::new(Duration::from_millis(1_000));
 
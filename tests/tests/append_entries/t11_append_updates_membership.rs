@@ -43,10 +43,10 @@ async fn append_updates_membership() -> Result<()> {
 
     tracing::info!("--- append-entries update membership");
     {
-        let req = AppendEntriesRequest {
-            vote: Vote::new_committed(1, 1),
-            prev_log_id: None,
-            entries: vec![
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(1, 1),
+            None,
+            vec![
                 blank_ent(0, 0, 0),
                 blank_ent(1, 0, 1),
                 Entry {
@@ -60,8 +60,8 @@ async fn append_updates_membership() -> Result<()> {
                 },
                 blank_ent(1, 0, 5),
             ],
-            leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
-        };
+            Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+        );
 
         let resp = r0.append_entries(req).await?;
         assert!(resp.is_success());
@@ -72,12 +72,12 @@ async fn append_updates_membership() -> Result<()> {
 
     tracing::info!("--- delete inconsistent logs update membership");
     {
-        let req = AppendEntriesRequest {
-            vote: Vote::new_committed(2, 2),
-            prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
-            entries: vec![blank_ent(2, 0, 3)],
-            leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
-        };
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(2, 2),
+            Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+            vec![blank_ent(2, 0, 3)],
+            Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+        );
 
         let resp = r0.append_entries(req).await?;
         assert!(resp.is_success());
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `quiesce_timeout`, an idle group stops heartbeating without followers timing out into an
+/// election, and it resumes on the next client write.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn quiesce_idle_group() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            quiesce_timeout: Some(500),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let term = router.get_raft_handle(&0)?.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- wait for the group to be idle");
+    {
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the quiesced group sends no heartbeat and does not elect"
+    );
+    let recorder = {
+        let recorder = router.record_rpc();
+        tokio::time::sleep(Duration::from_millis(1_500)).await;
+
+        assert_eq!(0, recorder.append_requests().count(), "no heartbeat is sent");
+        assert_eq!(0, recorder.vote_requests().count(), "no election is started");
+
+        for id in [0, 1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().clone();
+            assert_eq!(term, m.current_term);
+            assert_eq!(Some(0), m.current_leader);
+        }
+        assert_eq!(
+            ServerState::Leader,
+            router.get_raft_handle(&0)?.metrics().borrow().state
+        );
+
+        recorder
+    };
+
+    tracing::info!(log_index, "--- a client write wakes up the group");
+    {
+        router.send_client_request(0, ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write is applied").await?;
+        }
+
+        recorder.clear();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert!(recorder.append_requests().count() > 0, "heartbeats are resumed");
+        assert_eq!(0, recorder.vote_requests().count(), "no election is started");
+
+        for id in [0, 1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().clone();
+            assert_eq!(term, m.current_term);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,127 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::raft::AppendEntriesValidator;
+use openraft::testing::blank_ent;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Rejects AppendEntries from node-2 and counts the validated requests.
+struct RejectNode2 {
+    validated: Arc<AtomicU64>,
+}
+
+impl AppendEntriesValidator<TypeConfig> for RejectNode2 {
+    fn validate(&mut self, req: &AppendEntriesRequest<TypeConfig>) -> bool {
+        self.validated.fetch_add(1, Ordering::Relaxed);
+        req.vote.leader_id().voted_for() != Some(2)
+    }
+}
+
+/// A request rejected by the `AppendEntriesValidator` is responded with the local vote and changes
+/// nothing, while the vote check still takes precedence and an approved request is handled
+/// normally.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn append_entries_validator() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    router.wait_for_log(&btreeset![0], None, None, "empty").await?;
+    router.wait_for_state(&btreeset![0], ServerState::Learner, None, "empty").await?;
+
+    let (r0, _sto0, _sm0) = router.remove_node(0).unwrap();
+
+    let validated = Arc::new(AtomicU64::new(0));
+    r0.set_append_entries_validator(RejectNode2 {
+        validated: validated.clone(),
+    })
+    .await?;
+
+    tracing::info!("--- approved by validator, accepted by vote");
+    {
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(1, 1),
+            None,
+            vec![blank_ent(0, 0, 0), blank_ent(1, 1, 1), blank_ent(1, 1, 2)],
+            None,
+        );
+
+        let resp = r0.append_entries(req).await?;
+        assert!(resp.is_success());
+        assert_eq!(1, validated.load(Ordering::Relaxed));
+
+        r0.wait(timeout()).log_index(Some(2), "logs are appended").await?;
+    }
+
+    tracing::info!("--- rejected by validator, nothing changes");
+    {
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(2, 2),
+            Some(log_id(1, 1, 2)),
+            vec![blank_ent(2, 2, 3)],
+            None,
+        );
+
+        let resp = r0.append_entries(req).await?;
+        assert_eq!(
+            AppendEntriesResponse::HigherVote(Vote::new_committed(1, 1)),
+            resp,
+            "responded with the local vote, not greater than the leader's"
+        );
+        assert_eq!(2, validated.load(Ordering::Relaxed));
+
+        let m = r0.metrics().borrow().clone();
+        assert_eq!(Vote::new_committed(1, 1), m.vote, "vote is not updated");
+        assert_eq!(Some(2), m.last_log_index, "log is not appended");
+    }
+
+    tracing::info!("--- a smaller vote is rejected by vote, before the validator");
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(0, 2), Some(log_id(1, 1, 2)), vec![], None);
+
+        let resp = r0.append_entries(req).await?;
+        assert_eq!(AppendEntriesResponse::HigherVote(Vote::new_committed(1, 1)), resp);
+        assert_eq!(2, validated.load(Ordering::Relaxed), "validator is not called");
+    }
+
+    tracing::info!("--- approved by validator, still checked for log consistency");
+    {
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(1, 1),
+            Some(log_id(1, 1, 5)),
+            vec![blank_ent(1, 1, 6)],
+            None,
+        );
+
+        let resp = r0.append_entries(req).await?;
+        assert_eq!(AppendEntriesResponse::Conflict, resp);
+        assert_eq!(3, validated.load(Ordering::Relaxed));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::RPCTypes;
+use openraft::Config;
+use openraft::RaftLogReader;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// The leader writes entries ahead to its log store: when an AppendEntries RPC is sent, every
+/// entry in it is already in the leader's storage.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leader_write_ahead() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let (leader_log_store, _) = router.get_storage_handle(&0)?;
+
+    // `(last_index_in_rpc, last_index_in_leader_store)` of every non-empty AppendEntries.
+    let checked = Arc::new(Mutex::new(Vec::new()));
+
+    {
+        let checked = checked.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, from, _to| {
+            let RPCRequest::AppendEntries(req) = req else {
+                unreachable!("only AppendEntries is hooked");
+            };
+
+            if from != 0 || req.entries.is_empty() {
+                return Ok(());
+            }
+
+            let last = req.entries.last().unwrap().log_id.index;
+
+            let mut sto = leader_log_store.clone();
+            let stored = futures::executor::block_on(sto.try_get_log_entries(last..last + 1)).unwrap();
+            let stored_last = stored.last().map(|e| e.log_id.index);
+
+            checked.lock().unwrap().push((last, stored_last));
+            Ok(())
+        });
+    }
+
+    tracing::info!(log_index, "--- write to the leader");
+    {
+        log_index += router.client_request_many(0, "foo", 20).await?;
+        router.wait_for_log(&btreeset! {0,1,2}, Some(log_index), None, "replicated").await?;
+    }
+
+    tracing::info!(log_index, "--- every replicated entry is in the leader store when sent");
+    {
+        let checked = checked.lock().unwrap();
+        assert!(!checked.is_empty());
+
+        for (last, stored_last) in checked.iter() {
+            assert_eq!(Some(*last), *stored_last, "entry {} is persisted before sent", last);
+        }
+    }
+
+    Ok(())
+}
@@ -13,7 +13,27 @@ mod t12_trigger_purge_log;
 mod t13_begin_receiving_snapshot;
 mod t13_get_snapshot;
 mod t13_install_full_snapshot;
+mod t13_install_snapshot_format_version;
 mod t13_trigger_snapshot;
+mod t14_get_log_entries_rev;
+mod t15_read_barrier;
 mod t16_with_raft_state;
+mod t17_seal;
+mod t18_forward_client_write;
+mod t19_term_history;
+mod t20_await_leader;
+mod t21_fencing_token;
+mod t22_storage_failure_policy;
+mod t23_committed_digest;
+mod t24_no_configuration;
+mod t25_client_write_checkpoint;
+mod t26_resolve_committed_batch;
+mod t27_write_deduplicator;
+mod t28_ensure_caught_up;
+mod t29_is_committed;
+mod t30_pending_writes;
+mod t31_read_lease;
+mod t32_read_leader_not_ready;
+mod t34_commit_wait;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
@@ -65,6 +65,55 @@ async fn client_reads() -> Result<()> {
     Ok(())
 }
 
+/// Concurrent linearizable reads are confirmed in batches:
+/// one round of heartbeats confirms the leadership for all of the reads queued before it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn client_reads_batched() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let n_reads = 100;
+    let before = router.get_rpc_count().get(&RPCTypes::AppendEntries).copied().unwrap_or_default();
+
+    tracing::info!(log_index, "--- issue {} concurrent reads", n_reads);
+    {
+        let reads = (0..n_reads).map(|_| {
+            let n0 = n0.clone();
+            async move { n0.ensure_linearizable().await }
+        });
+
+        for res in futures::future::join_all(reads).await {
+            let read_log_id = res?;
+            assert_eq!(Some(log_index), read_log_id.index());
+        }
+    }
+
+    let after = router.get_rpc_count().get(&RPCTypes::AppendEntries).copied().unwrap_or_default();
+    let sent = after - before;
+
+    tracing::info!(sent, "--- AppendEntries sent for {} reads", n_reads);
+    assert!(
+        sent < n_reads / 5,
+        "expect far fewer heartbeats than reads: sent {} for {} reads",
+        sent,
+        n_reads
+    );
+
+    Ok(())
+}
+
 /// - A leader that has not yet committed any log entries returns leader initialization log id(blank
 ///   log id).
 /// - Return the last committed log id if the leader has committed any log entries.
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::InstallSnapshotError;
+use openraft::error::RaftError;
+use openraft::error::UnsupportedSnapshotFormat;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+use openraft_memstore::SNAPSHOT_FORMAT_VERSION;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A snapshot with an older format version is migrated by `Raft::migrate_snapshot()` and then
+/// installed, while a snapshot with an unsupported format version is rejected.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn install_snapshot_format_version() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- isolate node 2 so that it can receive snapshot");
+    router.set_unreachable(2, true);
+
+    tracing::info!(log_index, "--- write to make node-0,1 have more logs");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write more log").await?;
+    }
+
+    let snap;
+
+    tracing::info!(log_index, "--- trigger and get snapshot from node-0");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        snap = n0.get_snapshot().await?.unwrap();
+        assert_eq!(SNAPSHOT_FORMAT_VERSION, snap.meta.format_version());
+    }
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- migrate snapshot of a newer format version is rejected");
+    {
+        let mut newer = snap.clone();
+        newer.meta = newer.meta.with_format_version(SNAPSHOT_FORMAT_VERSION + 1);
+
+        let err = n2.migrate_snapshot(newer).await.unwrap_err();
+        assert_eq!(
+            RaftError::APIError(InstallSnapshotError::UnsupportedSnapshotFormat(
+                UnsupportedSnapshotFormat {
+                    format_version: SNAPSHOT_FORMAT_VERSION + 1,
+                    current: SNAPSHOT_FORMAT_VERSION,
+                }
+            )),
+            err
+        );
+
+        n2.with_raft_state(|state| {
+            assert_eq!(
+                None, state.snapshot_meta.last_log_id,
+                "node-2 snapshot is not installed"
+            );
+        })
+        .await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- snapshot of an older format version is migrated and installed"
+    );
+    {
+        let mut older = snap.clone();
+        older.meta = older.meta.with_format_version(0);
+
+        let migrated = n2.migrate_snapshot(older).await?;
+        assert_eq!(SNAPSHOT_FORMAT_VERSION, migrated.meta.format_version());
+
+        let resp = n2.install_full_snapshot(Vote::new_committed(1, 0), migrated).await?;
+        assert_eq!(Vote::new_committed(1, 0), resp.vote);
+
+        n2.with_raft_state(move |state| {
+            assert_eq!(Some(log_id(1, 0, log_index)), state.snapshot_meta.last_log_id);
+            assert_eq!(SNAPSHOT_FORMAT_VERSION, state.snapshot_meta.format_version());
+        })
+        .await?;
+
+        let installed = n2.get_snapshot().await?.unwrap();
+        assert_eq!(
+            SNAPSHOT_FORMAT_VERSION,
+            installed.meta.format_version(),
+            "the installed snapshot is migrated"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::RaftLogId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Call `Raft::get_log_entries_rev()` to read logs backward, which stops at the purged logs.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn get_log_entries_rev() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            // Disable building snapshot by policy.
+            snapshot_policy: SnapshotPolicy::Never,
+            // Disable auto purge by policy.
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write some logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- read backward from the last log");
+    {
+        let entries = n0.get_log_entries_rev(log_index, 3).await?;
+        assert_eq!(
+            vec![log_index, log_index - 1, log_index - 2],
+            entries.iter().map(|x| x.get_log_id().index).collect::<Vec<_>>()
+        );
+    }
+
+    tracing::info!(log_index, "--- build snapshot and purge logs");
+    let snapshot_index = log_index - 5;
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(snapshot_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, snapshot_index)), "node-0 purged").await?;
+    }
+
+    tracing::info!(log_index, "--- read backward across the purged boundary");
+    {
+        let entries = n0.get_log_entries_rev(log_index - 1, 100).await?;
+        assert_eq!(
+            (snapshot_index + 1..log_index).rev().collect::<Vec<_>>(),
+            entries.iter().map(|x| x.get_log_id().index).collect::<Vec<_>>(),
+            "stop at the first purged log"
+        );
+
+        let entries = n0.get_log_entries_rev(snapshot_index, 100).await?;
+        assert!(entries.is_empty(), "all purged");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
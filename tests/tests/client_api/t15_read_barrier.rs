@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::read_barrier()` waits for the committed logs to be applied, and a read after it
+/// reflects all the writes committed before it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn read_barrier() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- block applying by holding the state machine in snapshot building"
+    );
+    {
+        sm0.block.set_blocking(BlockOperation::BuildSnapshot, Duration::from_millis(1_000));
+        n0.trigger().snapshot().await?;
+
+        // Wait for the snapshot building to start and acquire the state machine lock.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let n = 5;
+    tracing::info!(log_index, "--- write {} logs without waiting for them to be applied", n);
+    {
+        for i in 0..n {
+            let _rx = n0.client_write_ff(ClientRequest::make_request(format!("c{}", i), 1)).await?;
+        }
+        log_index += n;
+
+        n0.wait(timeout()).log_index(Some(log_index), "logs are appended").await?;
+
+        loop {
+            let committed = n0.with_raft_state(|st| st.committed.index()).await?;
+            if committed == Some(log_index) {
+                let applied = n0.metrics().borrow().last_applied.index();
+                assert!(applied < Some(log_index), "applying is blocked");
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- read barrier waits for all writes to be applied");
+    {
+        let committed = n0.read_barrier().await?;
+        assert_eq!(Some(log_index), committed.index());
+
+        let sm = sm0.get_state_machine().await;
+        for i in 0..n {
+            assert_eq!(
+                Some("request-1"),
+                sm.client_status.get(&format!("c{}", i)).map(|x| x.as_str()),
+                "write c{} is visible",
+                i
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A committed seal makes the cluster reject writes, on the original leader and on a newly
+/// elected leader, while reads are still served. Unseal restores writes.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn seal_and_unseal() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- seal the cluster on node-0");
+    {
+        let resp = n0.seal().await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index);
+        assert!(resp.membership.unwrap().is_sealed());
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "seal is applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- writes are rejected by node-0, reads are served");
+    {
+        let res = router.send_client_request(0, ClientRequest::make_request("foo", 1)).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        match err {
+            ClientWriteError::Sealed(sealed) => {
+                assert_eq!(Some(log_index), sealed.membership_log_id.map(|x| x.index));
+            }
+            _ => unreachable!("expect Sealed, got: {:?}", err),
+        }
+
+        n0.ensure_linearizable().await?;
+    }
+
+    tracing::info!(log_index, "--- elect node-1, writes are still rejected");
+    {
+        // Let the leader lease expire
+        tokio::time::sleep(Duration::from_millis(700)).await;
+
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+        log_index += 1; // blank log of the new leader
+
+        n1.wait(timeout()).applied_index(Some(log_index), "blank log is committed").await?;
+
+        let res = router.send_client_request(1, ClientRequest::make_request("foo", 1)).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert!(
+            matches!(err, ClientWriteError::Sealed(_)),
+            "expect Sealed, got: {:?}",
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- unseal on node-1, writes are accepted");
+    {
+        let resp = n1.unseal().await?;
+        log_index += 1;
+
+        assert!(!resp.membership.unwrap().is_sealed());
+
+        router.send_client_request(1, ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write is applied").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
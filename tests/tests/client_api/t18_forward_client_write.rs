@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `Config::forward_client_write` enabled, a write sent to a follower is forwarded to the
+/// leader, and the response is returned through the follower.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn forward_client_write() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            forward_client_write: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write to follower 1");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let resp = n1.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index);
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write is applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let sm = sm.get_state_machine().await;
+            assert_eq!(
+                Some("request-1"),
+                sm.client_status.get("foo").map(|x| x.as_str()),
+                "write is applied on node-{}",
+                id
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- leader is unreachable, follower returns ForwardToLeader");
+    {
+        router.remove_node(0);
+
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write(ClientRequest::make_request("foo", 2)).await;
+
+        let err = res.unwrap_err();
+        let RaftError::APIError(ClientWriteError::ForwardToLeader(fwd)) = err else {
+            panic!("expect ForwardToLeader error, got: {}", err);
+        };
+        assert_eq!(Some(0), fwd.leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
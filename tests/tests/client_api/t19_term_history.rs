@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::LogId;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft::SnapshotPolicy;
+use openraft_memstore::MemNodeId;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `Config::term_history_size` enabled, `Raft::term_history()` still reports the leader of a
+/// log after the log is purged.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn term_history() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: u64::MAX,
+            term_history_size: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs by leader-0 at term 1");
+    let term_1_index = log_index + 1;
+    {
+        log_index += router.client_request_many(0, "0", 5).await?;
+    }
+
+    tracing::info!(log_index, "--- elect node-1 and write logs at term 2");
+    {
+        // Let the leader lease expire
+        tokio::time::sleep(Duration::from_millis(700)).await;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+        log_index += 1;
+
+        log_index += router.client_request_many(1, "0", 5).await?;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 applied all logs").await?;
+    }
+
+    tracing::info!(log_index, "--- build snapshot and purge all logs on node-0");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(2, 1, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(2, 1, log_index)), "node-0 purged all logs").await?;
+    }
+
+    tracing::info!(log_index, "--- term history reports the leader of purged logs");
+    {
+        let (mut sto0, _sm0) = router.get_storage_handle(&0)?;
+        let logs = sto0.try_get_log_entries(term_1_index..=term_1_index).await?;
+        assert!(logs.is_empty(), "log at {} is purged", term_1_index);
+
+        let history = router.get_raft_handle(&0)?.term_history().await?;
+
+        assert_eq!(1, leader_of(&history, term_1_index).unwrap().leader_id.term);
+        assert_eq!(2, leader_of(&history, log_index).unwrap().leader_id.term);
+    }
+
+    Ok(())
+}
+
+/// Find the first log id of the leader that proposed the log at `index`.
+fn leader_of(history: &[LogId<MemNodeId>], index: u64) -> Option<&LogId<MemNodeId>> {
+    history.iter().rev().find(|log_id| log_id.index <= index)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
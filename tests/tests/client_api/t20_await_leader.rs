@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::metrics::WaitError;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::await_leader()` returns once a leader is elected, or returns a timeout error if no leader
+/// is elected.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn await_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    for id in [0, 1, 2] {
+        router.new_raft_node(id).await;
+    }
+
+    tracing::info!("--- no leader in an uninitialized cluster");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.await_leader(Some(Duration::from_millis(500))).await;
+
+        assert!(matches!(res, Err(WaitError::Timeout(_, _))), "got: {:?}", res);
+    }
+
+    tracing::info!("--- returns once a leader is elected");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let waiting = tokio::spawn(async move { n1.await_leader(Some(Duration::from_millis(3_000))).await });
+
+        router.initialize(0).await?;
+
+        let leader_id = waiting.await??;
+        assert_eq!(0, leader_id);
+    }
+
+    tracing::info!("--- returns at once if a leader is known");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        router.wait(&2, timeout()).current_leader(0, "node-2 knows the leader").await?;
+
+        let leader_id = n2.await_leader(Some(Duration::from_millis(0))).await?;
+        assert_eq!(0, leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Fencing tokens strictly increase across writes and never go backward across a leader change.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn fencing_token() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- fencing token of the current leader");
+    let mut last_token = n0.get_fencing_token().await?;
+    assert_eq!(log_index, last_token);
+
+    tracing::info!(log_index, "--- fencing tokens of writes strictly increase");
+    {
+        for i in 0..5 {
+            let resp = n0.client_write(ClientRequest::make_request("foo", i)).await?;
+            assert!(resp.fencing_token() > last_token);
+            last_token = resp.fencing_token();
+        }
+
+        assert_eq!(last_token, n0.get_fencing_token().await?);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- fencing token does not go backward across a leader change"
+    );
+    {
+        // Let the leader lease expire
+        tokio::time::sleep(Duration::from_millis(700)).await;
+
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let token = n1.get_fencing_token().await?;
+        assert!(token > last_token, "new leader token {} > {}", token, last_token);
+        last_token = token;
+
+        let resp = n1.client_write(ClientRequest::make_request("foo", 5)).await?;
+        assert!(resp.fencing_token() > last_token);
+    }
+
+    tracing::info!(log_index, "--- the stale leader can not issue a fencing token");
+    {
+        let res = n0.get_fencing_token().await;
+        assert!(res.is_err(), "stale leader must not return a fencing token: {:?}", res);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
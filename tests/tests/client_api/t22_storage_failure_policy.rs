@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::Fatal;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::StorageFailurePolicy;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `StorageFailurePolicy::Degrade`, a node whose storage keeps failing enters degraded mode:
+/// it rejects writes and reports it in metrics. It recovers once storage heals.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn storage_failure_policy_degrade() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            storage_failure_policy: StorageFailurePolicy::Degrade,
+            storage_failure_threshold: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- make saving committed log id fail, write a log");
+    let pending_write = {
+        sm0.block.set_failing(BlockOperation::SaveCommitted);
+
+        let n = n0.clone();
+        let h = tokio::spawn(async move { n.client_write(ClientRequest::make_request("foo", 1)).await });
+        log_index += 1;
+
+        n0.wait(timeout()).metrics(|m| m.storage_degraded, "node-0 is degraded").await?;
+        h
+    };
+
+    tracing::info!(log_index, "--- degraded node-0 keeps running and rejects writes");
+    {
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, m.state);
+        assert!(m.running_state.is_ok());
+
+        let res = router.send_client_request(0, ClientRequest::make_request("foo", 2)).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        match err {
+            ClientWriteError::StorageDegraded(degraded) => {
+                assert_eq!(0, degraded.node_id);
+                assert!(degraded.failures >= 3);
+            }
+            _ => unreachable!("expect StorageDegraded, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- heal storage, node-0 recovers");
+    {
+        sm0.block.clear_failing(BlockOperation::SaveCommitted);
+
+        n0.wait(timeout()).metrics(|m| !m.storage_degraded, "node-0 recovered").await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "the pending write is applied").await?;
+
+        pending_write.await??;
+
+        router.send_client_request(0, ClientRequest::make_request("foo", 3)).await?;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "writes are accepted again").await?;
+    }
+
+    Ok(())
+}
+
+/// With `StorageFailurePolicy::Shutdown`, a node shuts down once storage failures reach the
+/// threshold.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn storage_failure_policy_shutdown() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            storage_failure_policy: StorageFailurePolicy::Shutdown,
+            storage_failure_threshold: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- make saving committed log id fail, node-0 shuts down");
+    {
+        sm0.block.set_failing(BlockOperation::SaveCommitted);
+
+        let _rx = n0.client_write_ff(ClientRequest::make_request("foo", 1)).await?;
+
+        n0.wait(timeout()).metrics(|m| m.state == ServerState::Shutdown, "node-0 shuts down").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert!(
+            matches!(m.running_state, Err(Fatal::StorageError(_))),
+            "expect StorageError, got: {:?}",
+            m.running_state
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
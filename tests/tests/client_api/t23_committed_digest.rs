@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::CommittedDigestError;
+use openraft::storage::RaftLogReader;
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Compare `Raft::get_committed_digest()` of every node to detect divergence of committed entries.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn committed_digest() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write some logs");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- a healthy cluster produces identical digests");
+    {
+        let d0 = router.get_raft_handle(&0)?.get_committed_digest(log_index).await?;
+        for id in [1, 2] {
+            let d = router.get_raft_handle(&id)?.get_committed_digest(log_index).await?;
+            assert_eq!(d0, d, "node-{} has the same digest as node-0", id);
+        }
+
+        let d0_prev = router.get_raft_handle(&0)?.get_committed_digest(log_index - 1).await?;
+        assert_ne!(d0, d0_prev, "digests up to different indexes differ");
+    }
+
+    tracing::info!(log_index, "--- can not compute digest beyond committed");
+    {
+        let res = router.get_raft_handle(&0)?.get_committed_digest(log_index + 1).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(
+            CommittedDigestError::BeyondCommitted {
+                up_to: log_index + 1,
+                committed: Some(log_id(1, 0, log_index)),
+            },
+            err
+        );
+    }
+
+    tracing::info!(
+        log_index,
+        "--- purge logs, the digest is chained from the last purged log id"
+    );
+    let purge_index = log_index - 3;
+    {
+        let d0_before = router.get_raft_handle(&0)?.get_committed_digest(log_index).await?;
+
+        for id in [0, 1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            n.trigger().snapshot().await?;
+            router.wait(&id, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+            n.trigger().purge_log(purge_index).await?;
+            router.wait(&id, timeout()).purged(Some(log_id(1, 0, purge_index)), "purged").await?;
+        }
+
+        let d0 = router.get_raft_handle(&0)?.get_committed_digest(log_index).await?;
+        for id in [1, 2] {
+            let d = router.get_raft_handle(&id)?.get_committed_digest(log_index).await?;
+            assert_eq!(d0, d, "node-{} has the same digest as node-0", id);
+        }
+        assert_ne!(
+            d0_before, d0,
+            "the purged entries are replaced with the last purged log id"
+        );
+
+        let res = router.get_raft_handle(&0)?.get_committed_digest(purge_index).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(
+            CommittedDigestError::LogPurged {
+                up_to: purge_index,
+                last_purged: Some(log_id(1, 0, purge_index)),
+            },
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- corrupt a committed entry on node-2, its digest differs");
+    {
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+
+        let mut entries = sto2.try_get_log_entries(log_index..log_index + 1).await?;
+        let mut entry = entries.remove(0);
+        // Without feature `serde_json` the digest does not cover the application data.
+        entry.payload = EntryPayload::Blank;
+        sto2.blocking_append([entry]).await?;
+
+        let d0 = router.get_raft_handle(&0)?.get_committed_digest(log_index).await?;
+        let d1 = router.get_raft_handle(&1)?.get_committed_digest(log_index).await?;
+        let d2 = router.get_raft_handle(&2)?.get_committed_digest(log_index).await?;
+        assert_eq!(d0, d1);
+        assert_ne!(d0, d2, "node-2 diverges");
+
+        let d0_prev = router.get_raft_handle(&0)?.get_committed_digest(log_index - 1).await?;
+        let d2_prev = router.get_raft_handle(&2)?.get_committed_digest(log_index - 1).await?;
+        assert_eq!(d0_prev, d2_prev, "entries before the corrupted one are identical");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::NoConfiguration;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node without any voter in its membership reports the no-configuration state and refuses
+/// client writes, until it is initialized.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn no_configuration() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- node-0 has no configuration");
+    {
+        n0.wait(timeout()).metrics(|m| m.no_configuration, "node-0 has no configuration").await?;
+
+        let res = router.send_client_request(0, ClientRequest::make_request("foo", 1)).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(ClientWriteError::NoConfiguration(NoConfiguration { node_id: 0 }), err);
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Learner, m.state, "node-0 does not elect");
+    }
+
+    tracing::info!("--- initialize node-0, it recovers");
+    {
+        n0.initialize(btreeset! {0}).await?;
+
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        n0.wait(timeout()).metrics(|m| !m.no_configuration, "node-0 is configured").await?;
+
+        router.send_client_request(0, ClientRequest::make_request("foo", 2)).await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A checkpoint entry is committed and applied like a normal entry on every node.
+///
+/// The default `Entry` does not tell a checkpoint apart, thus the state machine applies it as a
+/// normal write.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn client_write_checkpoint() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            forward_client_write: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write a checkpoint between normal writes");
+    {
+        log_index += router.client_request_many(0, "foo", 2).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let resp = n0.client_write_checkpoint(ClientRequest::make_request("cp", 1)).await?;
+        log_index += 1;
+        assert_eq!(log_index, resp.log_id.index);
+
+        log_index += router.client_request_many(0, "foo", 2).await?;
+    }
+
+    tracing::info!(log_index, "--- every state machine applies the checkpoint");
+    {
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "all logs applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let sm = sm.get_state_machine().await;
+            assert!(
+                sm.client_status.contains_key("cp"),
+                "node-{} applied the checkpoint",
+                id
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- a checkpoint is not forwarded to the leader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write_checkpoint(ClientRequest::make_request("cp", 2)).await;
+
+        let err = res.unwrap_err();
+        let RaftError::APIError(ClientWriteError::ForwardToLeader(fwd)) = err else {
+            panic!("expect ForwardToLeader error, got: {}", err);
+        };
+        assert_eq!(Some(0), fwd.leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// When a large batch of client writes is committed at once, every client future of the batch is
+/// resolved by the time the batch is reported as applied, each with the response of its own entry.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn resolve_committed_batch() -> Result<()> {
+    let n = 200;
+
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- isolate followers, submit writes that can not commit");
+    let mut receivers = vec![];
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        for i in 0..n {
+            let rx = n0.client_write_ff(ClientRequest::make_request("foo", i)).await?;
+            receivers.push(rx);
+        }
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        for rx in receivers.iter_mut() {
+            assert!(rx.try_recv().is_err(), "not committed, not resolved");
+        }
+    }
+
+    let first_index = log_index + 1;
+    log_index += n;
+
+    tracing::info!(log_index, "--- restore network, the whole batch commits");
+    {
+        router.set_network_error(1, false);
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "batch applied").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- every future is resolved with its own entry, in index order"
+    );
+    {
+        for (i, mut rx) in receivers.into_iter().enumerate() {
+            let resp = rx.try_recv().expect("resolved once applied")?;
+            assert_eq!(first_index + i as u64, resp.log_id.index);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,98 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::WriteDeduplicator;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+struct HashRequest;
+
+impl WriteDeduplicator<TypeConfig> for HashRequest {
+    fn content_hash(&mut self, data: &ClientRequest) -> u64 {
+        let mut h = DefaultHasher::new();
+        (&data.client, data.serial, &data.status).hash(&mut h);
+        h.finish()
+    }
+
+    fn duplicate_response(&mut self, original: &ClientResponse) -> ClientResponse {
+        original.clone()
+    }
+}
+
+/// With a `WriteDeduplicator`, a client write identical to the last entry is not appended, and its
+/// client receives the log id of the last entry, whether it is applied or not.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn write_deduplicator() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.set_write_deduplicator(HashRequest).await?;
+
+    tracing::info!(log_index, "--- two identical writes produce one log entry");
+    {
+        let resp1 = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+        assert_eq!(log_index, resp1.log_id.index);
+
+        let resp2 = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        assert_eq!(resp1.log_id, resp2.log_id);
+        assert_eq!(resp1.data.0, resp2.data.0);
+
+        assert_eq!(Some(log_index), n0.metrics().borrow().last_log_index);
+    }
+
+    tracing::info!(log_index, "--- identical writes before the first is applied");
+    {
+        let (resp1, resp2) = futures::join!(
+            n0.client_write(ClientRequest::make_request("bar", 1)),
+            n0.client_write(ClientRequest::make_request("bar", 1)),
+        );
+        log_index += 1;
+
+        let (resp1, resp2) = (resp1?, resp2?);
+        assert_eq!(log_index, resp1.log_id.index);
+        assert_eq!(resp1.log_id, resp2.log_id);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- a write identical to an earlier but not the last entry is appended"
+    );
+    {
+        let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+        assert_eq!(log_index, resp.log_id.index);
+    }
+
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "all logs applied").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
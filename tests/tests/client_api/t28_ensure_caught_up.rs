@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::CatchUpError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower that lags behind waits in `ensure_caught_up()` until it applies the leader's
+/// `read_log_id`, and returns an error if it can not catch up in time or the leader is
+/// unreachable.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn ensure_caught_up() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- the leader returns its read log id");
+    {
+        let read_log_id = n0.ensure_caught_up(Duration::from_millis(1_000)).await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+    }
+
+    tracing::info!(log_index, "--- block replication to node-2, write to the leader");
+    {
+        router.set_network_error(2, true);
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "leader applied").await?;
+    }
+
+    tracing::info!(log_index, "--- node-2 can not catch up in time");
+    {
+        let res = n2.ensure_caught_up(Duration::from_millis(500)).await;
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(CatchUpError::Timeout(_))),
+            "expect Timeout, got: {}",
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- node-2 returns once it catches up");
+    {
+        let handle = tokio::spawn(async move { n2.ensure_caught_up(Duration::from_millis(5_000)).await });
+
+        router.set_network_error(2, false);
+
+        let read_log_id = handle.await??;
+        assert!(read_log_id.index() >= Some(log_index));
+
+        let m = router.get_metrics(&2)?;
+        assert!(m.last_applied.index() >= read_log_id.index());
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the leader is unreachable, node-2 returns ForwardToLeader"
+    );
+    {
+        router.remove_node(0);
+
+        let n2 = router.get_raft_handle(&2)?;
+        let res = n2.ensure_caught_up(Duration::from_millis(1_000)).await;
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(CatchUpError::ForwardToLeader(_))),
+            "expect ForwardToLeader, got: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::CommitStatus;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::is_committed()` tells a committed index, an index not yet committed and an index covered
+/// by the snapshot apart.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn is_committed() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs, build a snapshot and purge the logs");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs").await?;
+
+        log_index
+    };
+
+    tracing::info!(log_index, "--- write more logs");
+    {
+        log_index += router.client_request_many(0, "bar", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    tracing::info!(log_index, "--- check the commit status");
+    {
+        assert_eq!(CommitStatus::Compacted, n0.is_committed(0).await?);
+        assert_eq!(CommitStatus::Compacted, n0.is_committed(snapshot_index).await?);
+        assert!(n0.is_committed(snapshot_index).await?.is_committed());
+
+        assert_eq!(CommitStatus::Committed, n0.is_committed(snapshot_index + 1).await?);
+        assert_eq!(CommitStatus::Committed, n0.is_committed(log_index).await?);
+
+        let got = n0.is_committed(log_index + 1).await?;
+        assert_eq!(CommitStatus::NotCommitted, got);
+        assert!(!got.is_committed());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
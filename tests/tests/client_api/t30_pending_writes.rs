@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Writes submitted to a leader that lost the quorum accumulate in `Raft::get_pending_writes()`,
+/// awaiting commit, with a growing age.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn pending_writes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no pending writes");
+    {
+        let pending = n0.get_pending_writes().await?;
+        assert!(pending.is_empty(), "got: {}", pending);
+        assert_eq!(None, pending.oldest_age);
+        assert_eq!(None, pending.first_index);
+    }
+
+    tracing::info!(log_index, "--- the leader loses the quorum");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+    }
+
+    tracing::info!(log_index, "--- writes accumulate awaiting commit");
+    {
+        for i in 0..3 {
+            let r = router.clone();
+            tokio::spawn(async move { r.client_request(0, "foo", i).await });
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let first = n0.get_pending_writes().await?;
+        assert_eq!(3, first.awaiting_commit, "got: {}", first);
+        assert_eq!(0, first.awaiting_apply);
+        assert_eq!(Some(log_index + 1), first.first_index);
+        assert_eq!(Some(log_index + 3), first.last_index);
+
+        for i in 3..5 {
+            let r = router.clone();
+            tokio::spawn(async move { r.client_request(0, "foo", i).await });
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let second = n0.get_pending_writes().await?;
+        assert_eq!(5, second.awaiting_commit, "got: {}", second);
+        assert_eq!(0, second.awaiting_apply);
+        assert_eq!(Some(log_index + 1), second.first_index);
+        assert_eq!(Some(log_index + 5), second.last_index);
+
+        assert!(
+            second.oldest_age.unwrap() >= first.oldest_age.unwrap() + Duration::from_millis(200),
+            "the oldest write grows older: {} then {}",
+            first,
+            second
+        );
+    }
+
+    tracing::info!(log_index, "--- the quorum is restored, the writes are drained");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        router.wait(&0, timeout()).applied_index(Some(log_index + 5), "writes applied").await?;
+
+        let pending = n0.get_pending_writes().await?;
+        assert!(pending.is_empty(), "got: {}", pending);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With a small clock-uncertainty bound, the leader serves reads within its lease without
+/// confirming the leadership; with a large one, the lease is too short and every read falls back to
+/// confirming with a round of heartbeats.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn read_lease_clock_uncertainty() -> Result<()> {
+    let n = 10;
+
+    let confirmed = reads_confirmed_by_heartbeat(Some(0), n).await?;
+    assert_eq!(0, confirmed, "all reads are served within the lease");
+
+    let confirmed = reads_confirmed_by_heartbeat(Some(1_000), n).await?;
+    assert_eq!(n, confirmed, "every read falls back to confirming the leadership");
+
+    let confirmed = reads_confirmed_by_heartbeat(None, n).await?;
+    assert_eq!(n, confirmed, "lease reads are disabled by default");
+
+    Ok(())
+}
+
+/// Run `n` linearizable reads on the leader of a 3-node cluster and return the number of them
+/// that sent heartbeats to confirm the leadership.
+async fn reads_confirmed_by_heartbeat(uncertainty: Option<u64>, n: u64) -> Result<u64> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            read_lease_clock_uncertainty: uncertainty,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!(uncertainty = debug(uncertainty), "--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write a log to renew the lease");
+    {
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write a log").await?;
+    }
+
+    tracing::info!(log_index, "--- read from the leader");
+
+    let mut confirmed = 0;
+    for _ in 0..n {
+        let before = append_entries_count(&router);
+        router.ensure_linearizable(0).await?;
+        let after = append_entries_count(&router);
+
+        if after > before {
+            confirmed += 1;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Ok(confirmed)
+}
+
+fn append_entries_count(router: &RaftRouter) -> u64 {
+    router.get_rpc_count().get(&RPCTypes::AppendEntries).copied().unwrap_or_default()
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
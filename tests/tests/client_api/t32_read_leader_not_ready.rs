@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// With `read_min_heartbeats` set, a newly elected leader rejects linearizable reads until its
+/// blank log is committed and a round of heartbeats after it is acknowledged by a quorum.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn read_leader_not_ready() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            heartbeat_interval: 100,
+            election_timeout_min: 101,
+            election_timeout_max: 102,
+            read_min_heartbeats: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- block replication from node-1 except logs to node-0");
+    router.set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, req, from, target| {
+        if from == 1 {
+            if let RPCRequest::AppendEntries(a) = req {
+                if target == 2 || a.entries.is_empty() {
+                    let any_err = AnyError::error("block append-entries from node 1");
+                    return Err(RPCError::Network(NetworkError::new(&any_err)));
+                }
+            }
+        }
+        Ok(())
+    });
+
+    // Expire the current leader
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    tracing::info!(log_index, "--- elect node-1, it commits the blank log");
+    let n1 = router.get_raft_handle(&1)?;
+    {
+        n1.trigger().elect().await?;
+        router.wait(&1, timeout()).state(ServerState::Leader, "node-1 elected").await?;
+
+        log_index += 1;
+        n1.wait(timeout()).applied_index(Some(log_index), "node-1 committed blank log").await?;
+    }
+
+    tracing::info!(log_index, "--- reads are rejected before a heartbeat is acknowledged");
+    {
+        let res = n1.ensure_linearizable().await;
+        match res {
+            Err(RaftError::APIError(CheckIsLeaderError::LeaderNotReady(e))) => {
+                assert_eq!(1, e.leader_id);
+                assert_eq!(Some(log_index), e.noop_log_id.map(|x| x.index));
+                assert_eq!(0, e.heartbeats);
+                assert_eq!(1, e.required);
+            }
+            other => panic!("expect LeaderNotReady, got: {:?}", other),
+        }
+
+        let res = n1.get_read_log_id().await;
+        assert!(
+            matches!(res, Err(RaftError::APIError(CheckIsLeaderError::LeaderNotReady(_)))),
+            "got: {:?}",
+            res
+        );
+    }
+
+    tracing::info!(log_index, "--- unblock heartbeats, reads are served after a heartbeat");
+    {
+        router.rpc_pre_hook(RPCTypes::AppendEntries, None);
+        n1.trigger().heartbeat().await?;
+
+        // The heartbeat is acknowledged asynchronously.
+        let mut read_log_id = n1.ensure_linearizable().await;
+        for _ in 0..20 {
+            if !matches!(
+                read_log_id,
+                Err(RaftError::APIError(CheckIsLeaderError::LeaderNotReady(_)))
+            ) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            read_log_id = n1.ensure_linearizable().await;
+        }
+
+        let read_log_id = read_log_id?;
+        assert_eq!(Some(log_index), read_log_id.map(|x| x.index));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::raft::CommitWait;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A write with a commit wait is responded only after a follower in the required group receives
+/// it, and fails if none of them does in time, although it is committed by a quorum.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn commit_wait() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.set_peer_labels(btreemap! {
+        1 => btreemap! {"dc".to_string() => "east".to_string()},
+        2 => btreemap! {"dc".to_string() => "west".to_string()},
+    })
+    .await?;
+
+    tracing::info!(log_index, "--- a write waiting for dc=west is acknowledged by node-2");
+    {
+        let wait = CommitWait::new("dc", "west", Duration::from_millis(1_000));
+        let resp = n0.client_write_with_commit_wait(ClientRequest::make_request("foo", 1), wait).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index);
+
+        let m2 = router.get_raft_handle(&2)?.metrics().borrow().clone();
+        assert!(m2.last_log_index >= Some(log_index), "node-2 received the log");
+    }
+
+    tracing::info!(log_index, "--- isolate node-2, a write waiting for dc=west times out");
+    {
+        router.set_network_error(2, true);
+
+        let wait = CommitWait::new("dc", "west", Duration::from_millis(500));
+        let res = n0.client_write_with_commit_wait(ClientRequest::make_request("foo", 2), wait).await;
+        log_index += 1;
+
+        match res {
+            Err(RaftError::APIError(ClientWriteError::CommitWaitTimeout(e))) => {
+                assert_eq!(0, e.node_id);
+                assert_eq!(log_index, e.log_index);
+                assert_eq!("dc", e.label);
+                assert_eq!("west", e.value);
+            }
+            _ => unreachable!("expect CommitWaitTimeout, got: {:?}", res),
+        }
+
+        // The write is still committed by node-0 and node-1.
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by a quorum").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- a write waiting for dc=east, or without waiting, succeeds"
+    );
+    {
+        let wait = CommitWait::new("dc", "east", Duration::from_millis(1_000));
+        n0.client_write_with_commit_wait(ClientRequest::make_request("foo", 3), wait).await?;
+        log_index += 1;
+
+        n0.client_write(ClientRequest::make_request("foo", 4)).await?;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "all written").await?;
+    }
+
+    tracing::info!(log_index, "--- a write waiting for a group without member times out");
+    {
+        let wait = CommitWait::new("dc", "north", Duration::from_millis(200));
+        let res = n0.client_write_with_commit_wait(ClientRequest::make_request("foo", 5), wait).await;
+
+        assert!(matches!(
+            res,
+            Err(RaftError::APIError(ClientWriteError::CommitWaitTimeout(_)))
+        ));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
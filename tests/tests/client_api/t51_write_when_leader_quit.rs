@@ -61,16 +61,15 @@ async fn write_when_leader_quit_and_log_revert() -> Result<()> {
     {
         let n0 = router.get_raft_handle(&0)?;
         let append_res = n0
-            .append_entries(AppendEntriesRequest {
+            .append_entries(AppendEntriesRequest::new(
                 // From node 2, with a higher term 10
-                vote: Vote::new_committed(10, 1),
+                Vote::new_committed(10, 1),
                 // log_index+1 is the log index the client tries to write, in previous step.
                 // This log conflict with the log the client written, will cause raft to revert log.
-                prev_log_id: Some(log_id(10, 1, log_index + 1)),
-
-                entries: vec![],
-                leader_commit: None,
-            })
+                Some(log_id(10, 1, log_index + 1)),
+                vec![],
+                None,
+            ))
             .await?;
 
         tracing::info!(log_index, "--- append_res: {:?}", append_res);
@@ -135,18 +134,16 @@ async fn write_when_leader_switched() -> Result<()> {
     {
         let n0 = router.get_raft_handle(&0)?;
         let append_res = n0
-            .append_entries(AppendEntriesRequest {
+            .append_entries(AppendEntriesRequest::new(
                 // From node 2, with a higher term 10
-                vote: Vote::new_committed(10, 1),
+                Vote::new_committed(10, 1),
                 // log_index+1 is the log index the client tries to write, in previous step.
                 // This matches the log on node-0.
-                prev_log_id: Some(log_id(1, 0, log_index + 1)),
-
-                entries: vec![],
-
+                Some(log_id(1, 0, log_index + 1)),
+                vec![],
                 // Inform node-0 to commit the pending log.
-                leader_commit: Some(log_id(1, 0, log_index + 1)),
-            })
+                Some(log_id(1, 0, log_index + 1)),
+            ))
             .await?;
 
         dbg!(&append_res);
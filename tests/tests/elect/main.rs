@@ -9,3 +9,17 @@ mod fixtures;
 
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
+mod t12_step_down;
+mod t13_keep_term_when_isolated;
+mod t14_vote_response_after_save_vote;
+#[cfg(feature = "tracing-spans")] mod t15_vote_span;
+mod t16_elect_quorum_early;
+mod t17_leader_stickiness;
+mod t18_election_grace_period;
+mod t19_elect_self_vote;
+mod t20_vote_log;
+mod t21_election_tiebreaker;
+#[cfg(feature = "decision-trace")] mod t22_replay_decision_trace;
+mod t23_leader_idle_check;
+mod t24_election_admission;
+mod t25_deny_vote_on_save_failure;
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader steps down without choosing a successor, and another node is elected.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn step_down() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+    let term = n0.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- step down node 0");
+    {
+        n0.trigger().step_down().await?;
+
+        n0.wait(timeout()).metrics(|m| m.state != ServerState::Leader, "node 0 is no longer leader").await?;
+    }
+
+    tracing::info!(log_index, "--- another node is elected");
+    {
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "node 0 follows a new leader",
+            )
+            .await?;
+
+        let leader = m.current_leader.unwrap();
+        assert!(m.current_term > term, "a new election happened");
+
+        router.wait(&leader, timeout()).state(ServerState::Leader, "the new leader is established").await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node 0 becomes follower").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `keep_term_when_isolated` enabled, an isolated candidate does not keep increasing its term
+/// on every election timeout.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn keep_term_when_isolated() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            keep_term_when_isolated: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let leader_term = n0.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- isolate node 2, it becomes a candidate");
+    let n2 = router.get_raft_handle(&2)?;
+    {
+        router.set_network_error(2, true);
+
+        n2.wait(timeout()).state(ServerState::Candidate, "node 2 starts to elect").await?;
+    }
+
+    tracing::info!(log_index, "--- the term of node 2 stays stable while isolated");
+    {
+        let term = n2.metrics().borrow().current_term;
+        assert_eq!(leader_term + 1, term);
+
+        // Wait for many election timeouts
+        sleep(Duration::from_millis(config.election_timeout_max * 10)).await;
+
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(ServerState::Candidate, m.state);
+        assert_eq!(term, m.current_term, "term does not increase");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
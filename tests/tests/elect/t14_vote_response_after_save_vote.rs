@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::raft::VoteRequest;
+use openraft::Config;
+use openraft::RaftLogReader;
+use openraft::Vote;
+use openraft_memstore::BlockOperation;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A granted vote is not responded until the vote is persisted.
+///
+/// Otherwise a node may grant another candidate in the same term after a crash, i.e., double vote.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn vote_response_after_save_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let (mut sto1, sm1) = router.new_store();
+    router.new_raft_node_with_sto(1, sto1.clone(), sm1.clone()).await;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- delay saving vote on node-1");
+    sm1.block.set_blocking(BlockOperation::SaveVote, Duration::from_millis(1_000));
+
+    tracing::info!("--- send a vote request to node-1");
+    let vote = Vote::new(1, 0);
+    let handle = {
+        let n1 = n1.clone();
+        tokio::spawn(async move { n1.vote(VoteRequest::new(vote, None)).await })
+    };
+
+    tracing::info!("--- no response is sent before the vote is persisted");
+    {
+        sleep(Duration::from_millis(500)).await;
+
+        assert!(!handle.is_finished(), "vote response must wait for save_vote");
+        assert_eq!(None, sto1.read_vote().await?);
+    }
+
+    tracing::info!("--- the vote is persisted when the granted response is received");
+    {
+        let resp = handle.await??;
+        assert!(resp.vote_granted);
+        assert_eq!(Some(vote), sto1.read_vote().await?);
+    }
+
+    Ok(())
+}
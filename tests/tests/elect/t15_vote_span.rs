@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use openraft::raft::VoteRequest;
+use openraft::Config;
+use openraft::Vote;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Captured span name and fields, in creation order.
+type Captured = Arc<Mutex<Vec<(String, BTreeMap<String, String>)>>>;
+
+/// A test tracer that records the name and fields of every span whose name starts with `raft.`.
+struct SpanCollector {
+    spans: Captured,
+    ids: Mutex<BTreeMap<u64, usize>>,
+}
+
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl<'a> Visit for FieldVisitor<'a> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for SpanCollector
+where S: Subscriber + for<'a> LookupSpan<'a>
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if !name.starts_with("raft.") {
+            return;
+        }
+
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+
+        let mut spans = self.spans.lock().unwrap();
+        self.ids.lock().unwrap().insert(id.into_u64(), spans.len());
+        spans.push((name.to_string(), fields));
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, _ctx: Context<'_, S>) {
+        let Some(i) = self.ids.lock().unwrap().get(&id.into_u64()).copied() else {
+            return;
+        };
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut FieldVisitor(&mut spans[i].1));
+    }
+}
+
+/// With feature `tracing-spans`, handling a vote request emits a `raft.vote` span with the request
+/// and its outcome.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn vote_span() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(1).await;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    let spans: Captured = Default::default();
+    let subscriber = tracing_subscriber::registry().with(SpanCollector {
+        spans: spans.clone(),
+        ids: Default::default(),
+    });
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    tracing::info!("--- send a vote request to node-1");
+    {
+        let resp = n1.vote(VoteRequest::new(Vote::new(1, 2), None)).await?;
+        assert!(resp.vote_granted);
+    }
+
+    tracing::info!("--- a raft.vote span is emitted with the outcome recorded");
+    {
+        let spans = spans.lock().unwrap();
+        let (name, fields) = spans.iter().find(|(name, _)| name == "raft.vote").expect("raft.vote span");
+
+        assert_eq!("raft.vote", name);
+        assert_eq!(Some("1"), fields.get("node_id").map(|s| s.as_str()));
+        assert_eq!(Some("1"), fields.get("term").map(|s| s.as_str()));
+        assert_eq!(Some("2"), fields.get("candidate").map(|s| s.as_str()));
+        assert_eq!(Some("granted"), fields.get("outcome").map(|s| s.as_str()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::Instant;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A candidate becomes leader as soon as a quorum grants its vote, without waiting for the slow
+/// peers to respond.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn elect_quorum_early() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2,3,4");
+    let log_index = router.new_cluster(btreeset! {0,1,2,3,4}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- wait for the leader lease to expire");
+    {
+        tokio::time::sleep(Duration::from_millis(1_200)).await;
+    }
+
+    tracing::info!(log_index, "--- make node-3 and node-4 slow to respond");
+    {
+        router.set_node_send_delay(3, 800);
+        router.set_node_send_delay(4, 800);
+    }
+
+    tracing::info!(log_index, "--- trigger election on node 1");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+
+        let start = Instant::now();
+        n1.trigger().elect().await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < Duration::from_millis(500),
+            "granted by node 0,1,2, no need to wait for node 3,4; elapsed: {:?}",
+            elapsed
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `election_grace_period`, a heartbeat arriving slightly after the election timeout, within
+/// the grace period, prevents an election. A heartbeat arriving after the grace period does not.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn election_grace_period() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 400,
+            election_grace_period: 1_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let term = router.get_metrics(&0)?.current_term;
+
+    tracing::info!(
+        log_index,
+        "--- heartbeats are delayed past the election timeout, but within the grace period"
+    );
+    {
+        // The election timeout of a follower is at most 800 ms: the leader lease plus the
+        // election timeout.
+        router.set_network_error(0, true);
+        tokio::time::sleep(Duration::from_millis(1_200)).await;
+        router.set_network_error(0, false);
+
+        // Let the leader reach the followers again.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        for id in [0, 1, 2] {
+            let m = router.get_metrics(&id)?;
+            assert_eq!(term, m.current_term, "node-{} does not start a new term", id);
+            assert_eq!(Some(0), m.current_leader, "node-{} still follows node-0", id);
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- heartbeats are delayed past the grace period, a follower takes over"
+    );
+    {
+        router.set_network_error(0, true);
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.current_term > term && m.current_leader.is_some() && m.current_leader != Some(0),
+                "node-1 follows a new leader",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A candidate counts its own vote without sending a `VoteRequest` to itself: in a cluster of 3,
+/// the self-vote plus one granting peer is a quorum.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn elect_self_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 300,
+            election_timeout_max: 301,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate the leader node-0 and wait for the leader lease to expire"
+    );
+    {
+        router.set_network_error(0, true);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    let recorder = router.record_rpc();
+
+    tracing::info!(log_index, "--- node-1 is elected by itself and node-2");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 never sends a VoteRequest to itself");
+    {
+        let targets =
+            recorder.vote_requests().filter(|(from, _, _)| *from == 1).map(|(_, to, _)| to).collect::<Vec<_>>();
+
+        assert!(targets.contains(&2), "node-2 is asked for vote: {:?}", targets);
+        assert!(!targets.contains(&1), "node-1 does not ask itself: {:?}", targets);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::VoteDenyReason;
+use openraft::raft::VoteRequest;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node records every vote decision it makes: a vote denied because the candidate's log is
+/// stale is recorded with that reason.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn vote_log() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    let stale_last_log_id = log_id(1, 0, log_index);
+
+    tracing::info!(log_index, "--- write logs to node-0 and node-1");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait_for_log(&btreeset! {0,1}, Some(log_index), None, "replicated").await?;
+    }
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- node-1 denies a candidate with a stale log");
+    {
+        // Wait for the leader lease to expire, otherwise the vote is denied by the lease.
+        sleep(Duration::from_millis(config.election_timeout_max + 200)).await;
+
+        let resp = n1.vote(VoteRequest::new(Vote::new(2, 2), Some(stale_last_log_id))).await?;
+        assert!(!resp.vote_granted);
+    }
+
+    tracing::info!(log_index, "--- the denial and its reason are in the vote log");
+    {
+        let records = n1.get_vote_log().await?;
+        let last = records.last().unwrap();
+
+        assert_eq!(2, last.term);
+        assert_eq!(2, last.candidate);
+        assert_eq!(Some(stale_last_log_id), last.candidate_last_log_id);
+        assert!(!last.is_granted());
+        assert_eq!(
+            Some(VoteDenyReason::StaleLog {
+                last_log_id: Some(log_id(1, 0, log_index))
+            }),
+            last.denied
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::VoteDenyReason;
+use openraft::raft::PreferLowerNodeId;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// When two candidates have equally up-to-date logs, the election tiebreaker deterministically
+/// selects the winner, no matter which one campaigns first.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn election_tiebreaker() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate leader node-0, set tiebreaker on node-1 and node-2"
+    );
+    {
+        router.set_network_error(0, true);
+
+        for id in [1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            n.set_election_tiebreaker(PreferLowerNodeId).await?;
+        }
+
+        // Wait for the leader lease to expire
+        sleep(Duration::from_millis(700)).await;
+    }
+
+    tracing::info!(log_index, "--- node-2 campaigns first, but node-1 denies it");
+    let n1 = router.get_raft_handle(&1)?;
+    let n2 = router.get_raft_handle(&2)?;
+    {
+        n2.trigger().elect().await?;
+        sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(ServerState::Candidate, n2.metrics().borrow().state);
+    }
+
+    tracing::info!(log_index, "--- node-1 campaigns until it is elected");
+    {
+        // The first attempt of node-1 may be in the same term node-2 has voted for itself in.
+        for _ in 0..3 {
+            n1.trigger().elect().await?;
+            sleep(Duration::from_millis(200)).await;
+
+            if n1.metrics().borrow().state == ServerState::Leader {
+                break;
+            }
+        }
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 is elected").await?;
+
+        let records = n1.get_vote_log().await?;
+        let r = records.iter().find(|r| r.candidate == 2).unwrap();
+        assert_eq!(Some(VoteDenyReason::Tiebreak { preferred: 1 }), r.denied);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
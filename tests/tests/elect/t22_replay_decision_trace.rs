@@ -0,0 +1,100 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::testing::read_decision_trace;
+use openraft::testing::replay_decision_trace;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+#[derive(Clone, Default)]
+struct Buf(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The decision trace a follower records during an election is replayed against a fresh node,
+/// which makes the same decisions and reaches the same state.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replay_decision_trace_of_election() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    for id in [0, 1, 2] {
+        router.new_raft_node(id).await;
+    }
+
+    let n1 = router.get_raft_handle(&1)?;
+    let buf = Buf::default();
+
+    tracing::info!("--- record the trace of node-1 during an election");
+    {
+        n1.set_decision_trace(Some(buf.clone())).await?;
+
+        router.initialize(0).await?;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 elected").await?;
+
+        router.client_request_many(0, "foo", 2).await?;
+        router.wait(&1, timeout()).log_index(Some(3), "node-1 replicated").await?;
+
+        n1.set_decision_trace(None::<Buf>).await?;
+    }
+
+    let records = {
+        let data = buf.0.lock().unwrap().clone();
+        read_decision_trace::<TypeConfig>(std::str::from_utf8(&data)?)?
+    };
+
+    assert_eq!("vote-request", records[0].kind);
+    assert!(
+        records[0].decision.starts_with("grant: "),
+        "got: {}",
+        records[0].decision
+    );
+    assert!(records[1..].iter().all(|r| r.kind == "append-entries"));
+
+    tracing::info!(n = records.len(), "--- replay the trace against a fresh node-1");
+    {
+        let mut fresh = RaftRouter::new(config.clone());
+        fresh.new_raft_node(1).await;
+        let fresh_n1 = fresh.get_raft_handle(&1)?;
+
+        let replayed = replay_decision_trace(&fresh_n1, &records).await?;
+        assert_eq!(records.len(), replayed.len());
+
+        let want = n1.metrics().borrow().clone();
+        let got = fresh_n1.metrics().borrow().clone();
+
+        assert_eq!(want.state, got.state);
+        assert_eq!(want.vote, got.vote);
+        assert_eq!(want.last_log_index, got.last_log_index);
+        assert_eq!(want.membership_config, got.membership_config);
+        assert_eq!(records.last().unwrap().after, replayed.last().unwrap().after);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `leader_idle_timeout`, an idle leader keeps reaffirming its authority with a quorum even
+/// if heartbeats are disabled, and steps down once it can not reach a quorum.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leader_idle_check() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            leader_idle_timeout: Some(300),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- an idle leader keeps being acknowledged by a quorum");
+    {
+        // Stay idle for several idle timeouts.
+        sleep(Duration::from_millis(1_500)).await;
+
+        let m = router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.millis_since_quorum_ack.map_or(false, |x| x < 700),
+                "quorum ack is refreshed by idle checks",
+            )
+            .await?;
+        assert_eq!(ServerState::Leader, m.state);
+    }
+
+    tracing::info!(log_index, "--- isolate the leader, it steps down");
+    {
+        router.set_network_error(0, true);
+
+        n0.wait(timeout())
+            .metrics(|m| m.state != ServerState::Leader, "isolated idle leader steps down")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,137 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::ElectionAdmission;
+use openraft::raft::ElectionPermit;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Admits an election when the test adds a permit to the semaphore.
+#[derive(Clone)]
+struct GatedAdmission {
+    gate: Arc<Semaphore>,
+    acquired: Arc<AtomicU64>,
+    done: Arc<AtomicU64>,
+}
+
+impl GatedAdmission {
+    fn new() -> Self {
+        Self {
+            gate: Arc::new(Semaphore::new(0)),
+            acquired: Arc::new(AtomicU64::new(0)),
+            done: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl ElectionAdmission<TypeConfig> for GatedAdmission {
+    fn acquire(&mut self, _node_id: &u64) -> ElectionPermit {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+
+        let gate = self.gate.clone();
+        ElectionPermit::new(async move {
+            gate.acquire_owned().await.unwrap().forget();
+        })
+    }
+
+    fn on_election_done(&mut self, _node_id: &u64) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A node with an election admission hook does not become a candidate until the permit resolves.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn election_admission() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let n2 = router.get_raft_handle(&2)?;
+
+    let admission1 = GatedAdmission::new();
+    let admission2 = GatedAdmission::new();
+    n1.set_election_admission(admission1.clone()).await?;
+    n2.set_election_admission(admission2.clone()).await?;
+
+    let term = n1.metrics().borrow().current_term;
+
+    tracing::info!(
+        log_index,
+        "--- isolate leader node-0, followers wait for election permit"
+    );
+    {
+        router.set_network_error(0, true);
+
+        wait_until(
+            || admission1.acquired.load(Ordering::Relaxed) > 0,
+            "node-1 asks for election permit",
+        )
+        .await;
+
+        // Several election timeouts pass without an election.
+        sleep(Duration::from_millis(2_000)).await;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(term, m.current_term);
+        assert_eq!(
+            1,
+            admission1.acquired.load(Ordering::Relaxed),
+            "no new permit while waiting"
+        );
+        assert_eq!(0, admission1.done.load(Ordering::Relaxed));
+    }
+
+    tracing::info!(log_index, "--- admit node-1, it becomes leader");
+    {
+        admission1.gate.add_permits(1);
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        wait_until(
+            || admission1.done.load(Ordering::Relaxed) == 1,
+            "node-1 reports the election done",
+        )
+        .await;
+
+        let m = n1.metrics().borrow().clone();
+        assert!(m.current_term > term);
+    }
+
+    Ok(())
+}
+
+async fn wait_until(f: impl Fn() -> bool, msg: &str) {
+    for _ in 0..30 {
+        if f() {
+            return;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("timeout waiting for: {}", msg);
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::raft::VoteRequest;
+use openraft::Config;
+use openraft::RaftLogReader;
+use openraft::StorageFailurePolicy;
+use openraft::Vote;
+use openraft_memstore::BlockOperation;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// If saving a granted vote fails, the vote is responded as not granted at once, because it is
+/// not durable.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn deny_vote_on_save_failure() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            storage_failure_policy: StorageFailurePolicy::Degrade,
+            storage_failure_threshold: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let (mut sto1, sm1) = router.new_store();
+    router.new_raft_node_with_sto(1, sto1.clone(), sm1.clone()).await;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- make saving vote fail on node-1");
+    sm1.block.set_failing(BlockOperation::SaveVote);
+
+    tracing::info!("--- a vote request that would be granted is denied");
+    let vote = Vote::new(1, 0);
+    {
+        let resp = tokio::time::timeout(Duration::from_millis(1_000), n1.vote(VoteRequest::new(vote, None))).await??;
+        assert!(!resp.vote_granted, "a vote that is not saved must not be granted");
+        assert_eq!(None, sto1.read_vote().await?);
+    }
+
+    tracing::info!("--- heal storage, the vote is saved by retry and granted again");
+    {
+        sm1.block.clear_failing(BlockOperation::SaveVote);
+
+        for _ in 0..30 {
+            if sto1.read_vote().await?.is_some() {
+                break;
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(Some(vote), sto1.read_vote().await?);
+
+        let resp = n1.vote(VoteRequest::new(vote, None)).await?;
+        assert!(resp.vote_granted);
+    }
+
+    Ok(())
+}
@@ -212,6 +212,61 @@ impl<C: RaftTypeConfig> RPCRequest<C> {
     }
 }
 
+/// Records the RPC requests sent through a [`TypedRaftRouter`], in the order they are sent.
+///
+/// Every recorded request is a tuple of `(from_id, to_id, request)`.
+#[derive(Clone, Default)]
+pub struct RPCRecorder {
+    #[allow(clippy::type_complexity)]
+    requests: Arc<Mutex<Vec<(MemNodeId, MemNodeId, RPCRequest<TypeConfig>)>>>,
+}
+
+impl RPCRecorder {
+    fn record(&self, request: RPCRequest<TypeConfig>, from: MemNodeId, to: MemNodeId) {
+        self.requests.lock().unwrap().push((from, to, request));
+    }
+
+    /// Returns the recorded AppendEntries requests.
+    pub fn append_requests(&self) -> impl Iterator<Item = (MemNodeId, MemNodeId, AppendEntriesRequest<TypeConfig>)> {
+        self.filter_requests(|r| match r {
+            RPCRequest::AppendEntries(r) => Some(r.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the recorded InstallSnapshot requests.
+    pub fn snapshot_requests(
+        &self,
+    ) -> impl Iterator<Item = (MemNodeId, MemNodeId, InstallSnapshotRequest<TypeConfig>)> {
+        self.filter_requests(|r| match r {
+            RPCRequest::InstallSnapshot(r) => Some(r.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the recorded Vote requests.
+    pub fn vote_requests(&self) -> impl Iterator<Item = (MemNodeId, MemNodeId, VoteRequest<TypeConfig>)> {
+        self.filter_requests(|r| match r {
+            RPCRequest::Vote(r) => Some(r.clone()),
+            _ => None,
+        })
+    }
+
+    /// Remove all recorded requests.
+    pub fn clear(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+
+    fn filter_requests<T>(
+        &self,
+        f: impl Fn(&RPCRequest<TypeConfig>) -> Option<T>,
+    ) -> impl Iterator<Item = (MemNodeId, MemNodeId, T)> {
+        let requests = self.requests.lock().unwrap();
+        let got = requests.iter().filter_map(|(from, to, r)| f(r).map(|x| (*from, *to, x))).collect::<Vec<_>>();
+        got.into_iter()
+    }
+}
+
 /// Arguments: `(router, rpc, from_id, to_id)`
 pub type RPCPreHook =
     Box<dyn Fn(&TypedRaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static>;
@@ -234,6 +289,9 @@ pub struct TypedRaftRouter {
     /// 0 means no delay.
     send_delay: Arc<AtomicU64>,
 
+    /// To emulate a slow link, a fixed delay in milliseconds for sending to a node.
+    node_send_delay: Arc<Mutex<HashMap<MemNodeId, u64>>>,
+
     /// To simulate PartialSuccess for AppendEntries RPCs.
     ///
     /// If the quota is set to `Some(n)`, then the AppendEntries RPC consumes the quota,
@@ -245,6 +303,9 @@ pub struct TypedRaftRouter {
 
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<Mutex<HashMap<RPCTypes, RPCPreHook>>>,
+
+    /// If set, every RPC request sent is recorded.
+    rpc_recorder: Arc<Mutex<Option<RPCRecorder>>>,
 }
 
 /// Default `RaftRouter` for memstore.
@@ -277,9 +338,11 @@ impl Builder {
             nodes: Default::default(),
             fail_rpc: Default::default(),
             send_delay: Arc::new(AtomicU64::new(send_delay)),
+            node_send_delay: Default::default(),
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
             rpc_pre_hook: Default::default(),
+            rpc_recorder: Default::default(),
         }
     }
 }
@@ -310,6 +373,22 @@ impl TypedRaftRouter {
         tokio::time::sleep(timeout).await;
     }
 
+    /// Set a fixed delay in milliseconds for every RPC sent to node `id`, in addition to the random
+    /// send delay.
+    pub fn set_node_send_delay(&self, id: MemNodeId, ms: u64) {
+        let mut delays = self.node_send_delay.lock().unwrap();
+        delays.insert(id, ms);
+    }
+
+    async fn node_send_delay(&self, id: MemNodeId) {
+        let ms = self.node_send_delay.lock().unwrap().get(&id).copied().unwrap_or_default();
+        if ms == 0 {
+            return;
+        }
+
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+    }
+
     pub fn set_append_entries_quota(&mut self, quota: Option<u64>) {
         let mut append_entries_quota = self.append_entries_quota.lock().unwrap();
         *append_entries_quota = quota;
@@ -325,6 +404,22 @@ impl TypedRaftRouter {
         self.rpc_count.lock().unwrap().clone()
     }
 
+    /// Start recording every RPC request sent through this router, and return the recorder.
+    ///
+    /// Requests sent before this call are not recorded. Calling it again replaces the previous
+    /// recorder.
+    pub fn record_rpc(&self) -> RPCRecorder {
+        let recorder = RPCRecorder::default();
+        *self.rpc_recorder.lock().unwrap() = Some(recorder.clone());
+        recorder
+    }
+
+    fn record_rpc_request(&self, request: impl Into<RPCRequest<TypeConfig>>, from: MemNodeId, to: MemNodeId) {
+        if let Some(recorder) = self.rpc_recorder.lock().unwrap().as_ref() {
+            recorder.record(request.into(), from, to);
+        }
+    }
+
     /// Create a cluster: 0 is the initial leader, others are voters and learners
     ///
     /// NOTE: it create a single node cluster first, then change it to a multi-voter cluster.
@@ -440,6 +535,12 @@ impl TypedRaftRouter {
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn new_raft_node_with_sto(&mut self, id: MemNodeId, log_store: MemLogStore, sm: MemStateMachine) {
         let node = Raft::new(id, self.config.clone(), self.clone(), log_store.clone(), sm.clone()).await.unwrap();
+        self.insert_raft_node(id, node, log_store, sm);
+    }
+
+    /// Add a Raft node built by the caller, e.g., with a wrapped network factory, to the routing
+    /// table.
+    pub fn insert_raft_node(&mut self, id: MemNodeId, node: MemRaft, log_store: MemLogStore, sm: MemStateMachine) {
         let mut rt = self.nodes.lock().unwrap();
         rt.insert(id, (node, log_store, sm));
     }
@@ -986,9 +1087,11 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
 
         tracing::debug!("append_entries to id={} {}", self.target, rpc);
         self.owner.count_rpc(RPCTypes::AppendEntries);
+        self.owner.record_rpc_request(rpc.clone(), from_id, self.target);
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.node_send_delay(self.target).await;
 
         // decrease quota if quota is set
         let truncated = {
@@ -1032,7 +1135,9 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         // If entries are truncated by quota, return an partial success response.
         if let Some(truncated) = truncated {
             match resp {
-                AppendEntriesResponse::Success => Ok(AppendEntriesResponse::PartialSuccess(truncated)),
+                AppendEntriesResponse::Success | AppendEntriesResponse::SuccessWithLogState { .. } => {
+                    Ok(AppendEntriesResponse::PartialSuccess(truncated))
+                }
                 _ => Ok(resp),
             }
         } else {
@@ -1050,9 +1155,11 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         let from_id = rpc.vote.leader_id().voted_for().unwrap();
 
         self.owner.count_rpc(RPCTypes::InstallSnapshot);
+        self.owner.record_rpc_request(rpc.clone(), from_id, self.target);
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.node_send_delay(self.target).await;
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
@@ -1071,9 +1178,11 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         let from_id = rpc.vote.leader_id().voted_for().unwrap();
 
         self.owner.count_rpc(RPCTypes::Vote);
+        self.owner.record_rpc_request(rpc.clone(), from_id, self.target);
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.node_send_delay(self.target).await;
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
@@ -1082,6 +1191,41 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
 
         Ok(resp)
     }
+
+    /// Forward a client write to the target Raft node.
+    async fn client_write(
+        &mut self,
+        app_data: ClientRequest,
+        _option: RPCOption,
+    ) -> Result<ClientWriteResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig, ClientWriteError<MemConfig>>>>
+    {
+        self.owner.rand_send_delay().await;
+        self.owner.node_send_delay(self.target).await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.client_write(app_data).await;
+        let resp = resp.map_err(|e| RemoteError::new(self.target, e))?;
+
+        Ok(resp)
+    }
+
+    /// Get the read log id from the target Raft node.
+    async fn get_read_log_id(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<LogId<MemNodeId>>, RPCError<MemConfig, RaftError<MemConfig, CheckIsLeaderError<MemConfig>>>>
+    {
+        self.owner.rand_send_delay().await;
+        self.owner.node_send_delay(self.target).await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.get_read_log_id().await;
+        let (read_log_id, _applied) = resp.map_err(|e| RemoteError::new(self.target, e))?;
+
+        Ok(read_log_id)
+    }
 }
 
 pub enum ValueTest<T> {
@@ -9,8 +9,11 @@ mod fixtures;
 
 mod t10_initialization;
 mod t11_shutdown;
+mod t12_startup_recovered_state;
+mod t13_prepare_shutdown;
 mod t50_follower_restart_does_not_interrupt;
 mod t50_single_follower_restart;
 mod t50_single_leader_restart_re_apply_logs;
+mod t50_startup_term_log_gap;
 mod t90_issue_607_single_restart;
 mod t90_issue_920_non_voter_leader_restart;
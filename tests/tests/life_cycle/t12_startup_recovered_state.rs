@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftLogStorage;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft::ServerState;
+use openraft::Vote;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node started with a storage that has a vote, logs, a committed log id and a snapshot
+/// recovers all of them before serving any request.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn startup_recovered_state() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs and build a snapshot on node-1");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied").await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().snapshot().await?;
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index), "node-1 snapshot").await?;
+
+        log_index
+    };
+
+    tracing::info!(log_index, "--- write more logs, then shut down node-1");
+    let (mut log_store, sm) = {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied").await?;
+
+        let (n1, log_store, sm) = router.remove_node(1).unwrap();
+        n1.shutdown().await?;
+
+        (log_store, sm)
+    };
+
+    tracing::info!(log_index, "--- purge the logs in the snapshot");
+    {
+        log_store.purge(log_id(1, 0, snapshot_index)).await?;
+
+        let st = log_store.get_log_state().await?;
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), st.last_purged_log_id);
+        assert_eq!(Some(log_id(1, 0, log_index)), st.last_log_id);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- restart node-1 isolated, it recovers the state from storage"
+    );
+    {
+        router.set_network_error(1, true);
+        router.new_raft_node_with_sto(1, log_store.clone(), sm).await;
+
+        // A request is handled only after the state is recovered.
+        let n1 = router.get_raft_handle(&1)?;
+        let (vote, committed) = n1.with_raft_state(|st| (*st.vote_ref(), st.committed)).await?;
+        assert_eq!(Vote::new_committed(1, 0), vote);
+        assert_eq!(Some(log_index), committed.index());
+
+        let m = router.get_metrics(&1)?;
+
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(1, m.current_term);
+        assert_eq!(Vote::new_committed(1, 0), m.vote);
+        assert_eq!(Some(log_index), m.last_log_index);
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), m.snapshot);
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), m.purged);
+        assert_eq!(
+            btreeset! {0,1,2},
+            m.membership_config.membership().voter_ids().collect()
+        );
+
+        let committed = log_store.read_committed().await?;
+        assert_eq!(Some(log_index), committed.index());
+
+        router
+            .wait(&1, timeout())
+            .applied_index(Some(log_index), "node-1 applies up to the stored committed log id")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use tokio::time::Instant;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `prepare_shutdown()` rejects new writes and waits for the pending writes to be committed and
+/// replicated.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn prepare_shutdown_waits_for_pending_writes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- isolate followers, writes can not commit");
+    let writes = {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let writes = (0..3)
+            .map(|i| {
+                let r = router.clone();
+                tokio::spawn(async move { r.send_client_request(0, ClientRequest::make_request("foo", i)).await })
+            })
+            .collect::<Vec<_>>();
+
+        log_index += 3;
+        n0.wait(timeout()).log_index(Some(log_index), "writes are appended").await?;
+        writes
+    };
+
+    tracing::info!(log_index, "--- prepare shutdown, it waits for the pending writes");
+    {
+        let n = n0.clone();
+        let prepare = tokio::spawn(async move { n.prepare_shutdown(Duration::from_millis(5_000)).await });
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!prepare.is_finished(), "pending writes are not yet committed");
+
+        let res = router.send_client_request(0, ClientRequest::make_request("bar", 1)).await;
+        match res {
+            Err(RaftError::APIError(ClientWriteError::ShuttingDown(e))) => {
+                assert_eq!(0, e.node_id);
+            }
+            other => panic!("expect ShuttingDown, got: {:?}", other),
+        }
+
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        let report = prepare.await??;
+        assert!(report.is_settled(), "got: {}", report);
+
+        for w in writes {
+            w.await??;
+        }
+        n0.wait(timeout()).applied_index(Some(log_index), "writes are applied").await?;
+    }
+
+    Ok(())
+}
+
+/// `prepare_shutdown()` reports the writes and replication that did not finish before the timeout.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn prepare_shutdown_reports_unfinished() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- isolate followers, writes can not commit");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        for i in 0..2 {
+            let r = router.clone();
+            tokio::spawn(async move { r.send_client_request(0, ClientRequest::make_request("foo", i)).await });
+        }
+
+        log_index += 2;
+        n0.wait(timeout()).log_index(Some(log_index), "writes are appended").await?;
+    }
+
+    tracing::info!(log_index, "--- prepare shutdown, the writes do not commit in time");
+    {
+        let now = Instant::now();
+        let report = n0.prepare_shutdown(Duration::from_millis(500)).await?;
+        assert!(now.elapsed() >= Duration::from_millis(500));
+
+        assert!(!report.is_settled());
+        assert_eq!(2, report.pending_writes.awaiting_commit);
+        assert_eq!(0, report.pending_writes.awaiting_apply);
+        assert_eq!(Some(log_index - 1), report.pending_writes.first_index);
+        assert_eq!(Some(log_index), report.pending_writes.last_index);
+        assert_eq!(btreeset! {1,2}, report.replicating);
+        assert!(report.sending_snapshot.is_empty());
+        assert!(!report.building_snapshot);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
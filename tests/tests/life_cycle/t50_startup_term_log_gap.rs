@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::storage::RaftLogStorage;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node restarted with a term far above its last log term enters the degraded state and does not
+/// elect itself, until it is cleared.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn startup_term_log_gap() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_term_log_gap: Some(100),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let m0 = router.get_metrics(&0)?;
+    assert!(!m0.term_gap_degraded);
+
+    tracing::info!(log_index, "--- stop node-0 and corrupt its vote");
+    let (sto, sm) = {
+        let (node, mut sto, sm) = router.remove_node(0).unwrap();
+        node.shutdown().await?;
+
+        // The last log term is 1. Vote for another node so that node-0 does not appear as a
+        // Candidate.
+        sto.save_vote(&Vote::new(1_000, 1)).await?;
+        (sto, sm)
+    };
+
+    tracing::info!(log_index, "--- restart node-0, it does not elect");
+    {
+        router.new_raft_node_with_sto(0, sto, sm).await;
+
+        router.wait(&0, timeout()).metrics(|m| m.term_gap_degraded, "node-0 is degraded").await?;
+
+        // Longer than an election timeout
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        let m0 = router.get_metrics(&0)?;
+        assert_eq!(ServerState::Follower, m0.state);
+        assert_eq!(1_000, m0.current_term, "node-0 does not elect");
+    }
+
+    tracing::info!(log_index, "--- clear the degraded state, node-0 becomes leader");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().clear_term_gap_degraded().await?;
+
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 elected").await?;
+        log_index += 1;
+
+        let m0 = router.get_metrics(&0)?;
+        assert!(!m0.term_gap_degraded);
+
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 works").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -8,3 +8,8 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_check_log_gaps;
+mod t30_get_last_applied;
+mod t40_subscribe_committed;
+mod t50_term_of_index;
+mod t60_get_log_segment;
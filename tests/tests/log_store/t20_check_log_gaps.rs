@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::LogGap;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Missing log entries between the last purged log and the last log are reported by
+/// `Raft::check_log_gaps()`.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn check_log_gaps() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "0", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+    let gap = |first, last| LogGap { first, last };
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (sto0, _sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- a contiguous log has no gap");
+    {
+        let gaps = n0.check_log_gaps().await?;
+        assert_eq!(Vec::<LogGap>::new(), gaps);
+    }
+
+    tracing::info!(log_index, "--- remove entries to make gaps");
+    {
+        sto0.remove_log_entry(5).await;
+        sto0.remove_log_entry(8).await;
+        sto0.remove_log_entry(9).await;
+
+        let gaps = n0.check_log_gaps().await?;
+        assert_eq!(vec![gap(5, 5), gap(8, 9)], gaps);
+    }
+
+    tracing::info!(log_index, "--- a missing last log is a gap too");
+    {
+        sto0.remove_log_entry(log_index).await;
+
+        let gaps = n0.check_log_gaps().await?;
+        assert_eq!(vec![gap(5, 5), gap(8, 9), gap(log_index, log_index)], gaps);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
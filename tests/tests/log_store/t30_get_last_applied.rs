@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::LastApplied;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::get_last_applied()` returns the last applied entry, or only its log id if it is purged
+/// from the log.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn get_last_applied() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- nothing is applied before initialization");
+    {
+        router.new_raft_node(0).await;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let last_applied = n0.get_last_applied().await?;
+        assert!(last_applied.is_none(), "got: {:?}", last_applied);
+    }
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- the last applied entry is read from the log");
+    {
+        let last_applied = n0.get_last_applied().await?;
+
+        let Some(LastApplied::Entry(entry)) = last_applied else {
+            panic!("expect an entry, got: {:?}", last_applied);
+        };
+        assert_eq!(log_id(1, 0, log_index), entry.log_id);
+
+        let EntryPayload::Normal(req) = entry.payload else {
+            panic!("expect a normal entry, got: {:?}", entry.payload);
+        };
+        assert_eq!("foo", req.client);
+        assert_eq!(9, req.serial);
+    }
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the last applied entry is covered by the snapshot");
+    {
+        let last_applied = n0.get_last_applied().await?;
+
+        let Some(LastApplied::CoveredBySnapshot(applied)) = last_applied else {
+            panic!("expect covered by snapshot, got: {:?}", last_applied);
+        };
+        assert_eq!(log_id(1, 0, log_index), applied);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::raft::Committed;
+use openraft::raft::CommittedStream;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::LogId;
+use openraft::SnapshotPolicy;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A subscriber from index 1 receives the committed entries in the log, then the newly committed
+/// ones.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn subscribe_committed_backfill_and_follow() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut stream = n0.subscribe_committed(1).await?;
+
+    tracing::info!(log_index, "--- receive the existing committed entries");
+    {
+        for i in 1..=log_index {
+            let log_id = next_entry(&mut stream).await?;
+            assert_eq!(log_id.index, i);
+        }
+    }
+
+    tracing::info!(log_index, "--- receive the newly committed entries");
+    {
+        let start = log_index;
+        log_index += router.client_request_many(0, "bar", 5).await?;
+
+        for i in start + 1..=log_index {
+            let log_id = next_entry(&mut stream).await?;
+            assert_eq!(log_id.index, i);
+        }
+    }
+
+    Ok(())
+}
+
+/// A subscriber from a purged index is redirected to the snapshot first.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn subscribe_committed_redirect_to_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the purged entries are redirected to the snapshot");
+    let mut stream = n0.subscribe_committed(1).await?;
+    {
+        let got = next(&mut stream).await?;
+        let Committed::Snapshot(snapshot) = got else {
+            panic!("expect a snapshot, got: {}", got);
+        };
+        assert_eq!(log_id(1, 0, log_index), snapshot);
+    }
+
+    tracing::info!(log_index, "--- receive the newly committed entries after the snapshot");
+    {
+        let start = log_index;
+        log_index += router.client_request_many(0, "bar", 5).await?;
+
+        for i in start + 1..=log_index {
+            let log_id = next_entry(&mut stream).await?;
+            assert_eq!(log_id.index, i);
+        }
+    }
+
+    Ok(())
+}
+
+async fn next(stream: &mut CommittedStream<TypeConfig>) -> Result<Committed<TypeConfig>> {
+    let got = tokio::time::timeout(timeout().unwrap(), stream.next()).await?;
+    let got = got.expect("stream should not end")?;
+    Ok(got)
+}
+
+async fn next_entry(stream: &mut CommittedStream<TypeConfig>) -> Result<LogId<u64>> {
+    let got = next(stream).await?;
+    let Committed::Entry(entry) = got else {
+        panic!("expect an entry, got: {}", got);
+    };
+    Ok(entry.log_id)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Call `Raft::term_of_index()` to read the term of a log entry, which is `None` for purged logs
+/// and logs beyond the last one.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn term_of_index() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            // Disable building snapshot by policy.
+            snapshot_policy: SnapshotPolicy::Never,
+            // Disable auto purge by policy.
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write some logs in term 1");
+    let term_1_last = {
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        log_index
+    };
+
+    tracing::info!(log_index, "--- elect again and write some logs in term 2");
+    {
+        n0.trigger().elect().await?;
+        log_index += 1;
+        router.wait(&0, timeout()).metrics(|m| m.current_term == 2, "node-0 term 2").await?;
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    tracing::info!(log_index, "--- read terms");
+    {
+        assert_eq!(Some(0), n0.term_of_index(0).await?);
+        assert_eq!(Some(1), n0.term_of_index(1).await?);
+        assert_eq!(Some(1), n0.term_of_index(term_1_last).await?);
+        assert_eq!(Some(2), n0.term_of_index(term_1_last + 1).await?);
+        assert_eq!(Some(2), n0.term_of_index(log_index).await?);
+        assert_eq!(None, n0.term_of_index(log_index + 1).await?, "beyond the last log");
+    }
+
+    tracing::info!(log_index, "--- build snapshot and purge logs");
+    let snapshot_index = term_1_last + 2;
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(2, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(snapshot_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(2, 0, snapshot_index)), "node-0 purged").await?;
+    }
+
+    tracing::info!(log_index, "--- no term for the purged logs");
+    {
+        assert_eq!(None, n0.term_of_index(term_1_last).await?);
+        assert_eq!(None, n0.term_of_index(snapshot_index).await?);
+        assert_eq!(Some(2), n0.term_of_index(snapshot_index + 1).await?);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::raft::LogSegment;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::RaftLogId;
+use openraft::SnapshotPolicy;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Call `Raft::get_log_segment()` to page through the committed logs with a byte budget, and get
+/// `LogSegment::BelowSnapshot` for the purged logs.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn get_log_segment() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            // Disable building snapshot by policy.
+            snapshot_policy: SnapshotPolicy::Never,
+            // Disable auto purge by policy.
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write a large log");
+    {
+        log_index += router.client_request_many(0, "0", 200).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    tracing::info!(log_index, "--- page through the log with a byte budget");
+    {
+        let mut next = 1;
+        let mut pages = 0;
+
+        loop {
+            let segment = n0.get_log_segment(next, u64::MAX, 1024).await?;
+            let LogSegment::Entries(entries) = segment else {
+                panic!("expect entries, got: {}", segment);
+            };
+
+            if entries.is_empty() {
+                break;
+            }
+
+            for ent in entries.iter() {
+                assert_eq!(next, ent.get_log_id().index, "entries are in order without gap");
+                next += 1;
+            }
+            pages += 1;
+        }
+
+        assert_eq!(log_index + 1, next, "read all committed logs");
+        assert!(pages > 1, "the byte budget splits the log into pages, got: {}", pages);
+    }
+
+    tracing::info!(log_index, "--- the end index is exclusive");
+    {
+        let LogSegment::Entries(entries) = n0.get_log_segment(3, 6, u64::MAX).await? else {
+            panic!("expect entries");
+        };
+        let indexes = entries.iter().map(|e| e.get_log_id().index).collect::<Vec<_>>();
+        assert_eq!(vec![3, 4, 5], indexes);
+    }
+
+    tracing::info!(log_index, "--- at least one entry is returned with a zero byte budget");
+    {
+        let LogSegment::Entries(entries) = n0.get_log_segment(3, u64::MAX, 0).await? else {
+            panic!("expect entries");
+        };
+        let indexes = entries.iter().map(|e| e.get_log_id().index).collect::<Vec<_>>();
+        assert_eq!(vec![3], indexes);
+    }
+
+    tracing::info!(log_index, "--- build snapshot and purge logs");
+    let purge_index = 100;
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(purge_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, purge_index)), "node-0 purged").await?;
+    }
+
+    tracing::info!(log_index, "--- purged logs are below the snapshot");
+    {
+        for from in [1, purge_index] {
+            let segment = n0.get_log_segment(from, u64::MAX, 1024).await?;
+            let LogSegment::BelowSnapshot { purged } = segment else {
+                panic!("expect below snapshot for {}, got: {}", from, segment);
+            };
+            assert_eq!(log_id(1, 0, purge_index), purged);
+        }
+
+        let LogSegment::Entries(entries) = n0.get_log_segment(purge_index + 1, u64::MAX, u64::MAX).await? else {
+            panic!("expect entries");
+        };
+        assert_eq!(Some(purge_index + 1), entries.first().map(|e| e.get_log_id().index));
+        assert_eq!(Some(log_index), entries.last().map(|e| e.get_log_id().index));
+    }
+
+    Ok(())
+}
+
+/// Logs purged after `Raft::get_log_segment()` checks the purged log id and before it reads the
+/// log are reported as `LogSegment::BelowSnapshot`, not as a segment that skips them.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn get_log_segment_purged_while_reading() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            // Disable building snapshot by policy.
+            snapshot_policy: SnapshotPolicy::Never,
+            // Disable auto purge by policy.
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs and build snapshot");
+    {
+        log_index += router.client_request_many(0, "0", 50).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- block reading log and page from the start");
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+    let handle = {
+        sm0.block.set_blocking(BlockOperation::ReadLog, Duration::from_millis(1_000));
+
+        let n0 = n0.clone();
+        tokio::spawn(async move { n0.get_log_segment(1, u64::MAX, 1024).await })
+    };
+
+    tracing::info!(log_index, "--- purge logs while the segment is being read");
+    let purge_index = 20;
+    {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        n0.trigger().purge_log(purge_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, purge_index)), "node-0 purged").await?;
+    }
+
+    tracing::info!(log_index, "--- the purged logs are below the snapshot");
+    {
+        let segment = handle.await??;
+        let LogSegment::BelowSnapshot { purged } = segment else {
+            panic!("expect below snapshot, got: {}", segment);
+        };
+        assert_eq!(log_id(1, 0, purge_index), purged);
+
+        sm0.block.clear_blocking(BlockOperation::ReadLog);
+
+        let LogSegment::Entries(entries) = n0.get_log_segment(purge_index + 1, u64::MAX, u64::MAX).await? else {
+            panic!("expect entries");
+        };
+        assert_eq!(Some(purge_index + 1), entries.first().map(|e| e.get_log_id().index));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
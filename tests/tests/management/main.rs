@@ -8,3 +8,5 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_raft_config;
+mod t20_unsafe_commit_quorum;
+mod t30_runtime_election_timeout;
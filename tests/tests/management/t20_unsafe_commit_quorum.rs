@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::RaftError;
+use openraft::error::UnsafeCommitQuorumNotAllowed;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With the unsafe commit quorum override, a minority commits, and disabling it restores the normal
+/// quorum.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn unsafe_commit_quorum() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            allow_unsafe_commit_quorum: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- lose a majority: a write can not be committed");
+    let write = {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let n = n0.clone();
+        let write = tokio::spawn(async move { n.client_write(ClientRequest::make_request("foo", 1)).await });
+        log_index += 1;
+
+        let res = n0.wait(timeout()).applied_index(Some(log_index), "not committed by a minority").await;
+        assert!(res.is_err(), "a minority must not commit: {:?}", res);
+
+        write
+    };
+
+    tracing::info!(log_index, "--- lower the commit quorum to the surviving node 0");
+    {
+        n0.set_unsafe_commit_quorum(Some(btreeset! {0})).await?;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by the surviving node").await?;
+        let resp = write.await??;
+        assert_eq!(log_index, resp.log_id.index);
+
+        n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- restore the normal commit quorum");
+    {
+        n0.set_unsafe_commit_quorum(None).await?;
+
+        let n = n0.clone();
+        tokio::spawn(async move { n.client_write(ClientRequest::make_request("foo", 3)).await });
+        log_index += 1;
+
+        let res = n0.wait(timeout()).applied_index(Some(log_index), "not committed by a minority").await;
+        assert!(res.is_err(), "a minority must not commit: {:?}", res);
+    }
+
+    tracing::info!(log_index, "--- majority restored: the write is committed");
+    {
+        router.set_network_error(1, false);
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by a quorum").await?;
+    }
+
+    Ok(())
+}
+
+/// Lowering the commit quorum is refused if `Config::allow_unsafe_commit_quorum` is disabled.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn unsafe_commit_quorum_not_allowed() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- lowering the commit quorum is refused");
+    {
+        let res = n0.set_unsafe_commit_quorum(Some(btreeset! {0})).await;
+        assert_eq!(Err(RaftError::APIError(UnsafeCommitQuorumNotAllowed {})), res);
+
+        n0.set_unsafe_commit_quorum(None).await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
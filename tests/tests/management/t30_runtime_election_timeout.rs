@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::ConfigError;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Widening the election timeout at runtime delays the next election, and resetting it restores
+/// the election timeout in `Config`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn runtime_election_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- widen election timeout on every node");
+    {
+        for id in [0, 1, 2] {
+            router.get_raft_handle(&id)?.runtime_config().election_timeout(2_000, 2_001).await?;
+        }
+
+        // Let the heartbeats renew the election timeout with the new range.
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- isolate leader-0: no election before the widened timeout"
+    );
+    {
+        router.set_network_error(0, true);
+
+        sleep(Duration::from_millis(1_500)).await;
+
+        for id in [1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().clone();
+            assert_eq!(
+                ServerState::Follower,
+                m.state,
+                "node-{} must not elect before the widened timeout",
+                id
+            );
+            assert_eq!(1, m.current_term, "node-{} does not increase term", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- a new leader is elected after the widened timeout");
+    let leader = {
+        let m = router
+            .wait(&1, Some(Duration::from_millis(5_000)))
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "new leader elected",
+            )
+            .await?;
+        m.current_leader.unwrap()
+    };
+
+    tracing::info!(log_index, "--- restore node-0 and reset election timeout on every node");
+    {
+        router.set_network_error(0, false);
+        router
+            .wait(&0, Some(Duration::from_millis(5_000)))
+            .metrics(|m| m.current_leader == Some(leader), "node-0 follows the new leader")
+            .await?;
+
+        for id in [0, 1, 2] {
+            router.get_raft_handle(&id)?.runtime_config().reset_election_timeout().await?;
+        }
+
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- isolate leader-{}: elect with the election timeout in Config",
+        leader
+    );
+    {
+        router.set_network_error(leader, true);
+
+        let follower = if leader == 1 { 2 } else { 1 };
+        router
+            .wait(&follower, Some(Duration::from_millis(1_500)))
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(leader),
+                "new leader elected with the election timeout in Config",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// An invalid election timeout is rejected.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn runtime_election_timeout_invalid() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            heartbeat_interval: 50,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- election timeout must be greater than heartbeat interval"
+    );
+    {
+        let n0 = router.get_raft_handle(&0)?;
+
+        let res = n0.runtime_config().election_timeout(50, 100).await;
+        assert_eq!(
+            Err(RaftError::APIError(ConfigError::ElectionTimeoutLTHeartBeat {
+                election_timeout_min: 50,
+                heartbeat_interval: 50,
+            })),
+            res
+        );
+
+        let res = n0.runtime_config().election_timeout(300, 300).await;
+        assert_eq!(
+            Err(RaftError::APIError(ConfigError::ElectionTimeout { min: 300, max: 300 })),
+            res
+        );
+    }
+
+    Ok(())
+}
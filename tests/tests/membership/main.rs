@@ -11,14 +11,21 @@ mod t10_learner_restart;
 mod t10_single_node;
 mod t11_add_learner;
 mod t12_concurrent_write_and_add_learner;
+mod t13_reject_invalid_membership;
+mod t14_register_node;
 mod t20_change_membership;
 mod t21_change_membership_cases;
+mod t22_promote_learners;
+mod t23_cluster_state;
+mod t24_change_membership_in_progress;
 mod t30_commit_joint_config;
+mod t30_elect_during_membership_change;
 mod t30_elect_with_new_config;
 mod t31_add_remove_follower;
 mod t31_remove_leader;
 mod t31_removed_follower;
 mod t51_remove_unreachable_follower;
+mod t52_member_removal_grace;
 mod t99_issue_471_adding_learner_uses_uninit_leader_id;
 mod t99_issue_584_replication_state_reverted;
 mod t99_new_leader_auto_commit_uniform_config;
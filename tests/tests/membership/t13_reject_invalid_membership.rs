@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::error::EmptyMembership;
+use openraft::error::InitializeError;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Initializing with an empty membership is rejected without writing any log.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn initialize_with_empty_membership() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let res = n0.initialize(BTreeSet::<u64>::new()).await;
+    let err = res.unwrap_err().into_api_error().unwrap();
+    assert_eq!(InitializeError::EmptyMembership(EmptyMembership {}), err);
+
+    let metrics = n0.metrics().borrow().clone();
+    assert_eq!(None, metrics.last_log_index, "no log is written");
+
+    Ok(())
+}
+
+/// Changing membership to an empty config is rejected without writing any log.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_to_empty_membership() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let res = n0.change_membership(BTreeSet::<u64>::new(), false).await;
+    let err = res.unwrap_err().into_api_error().unwrap();
+    assert_eq!(
+        ClientWriteError::ChangeMembershipError(ChangeMembershipError::EmptyMembership(EmptyMembership {})),
+        err
+    );
+
+    let metrics = n0.metrics().borrow().clone();
+    assert_eq!(Some(log_index), metrics.last_log_index, "no log is written");
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::RaftNetworkFactory;
+use openraft::Config;
+use openraft::Raft;
+use openraft::ServerState;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+use crate::fixtures::RaftRouterNetwork;
+
+/// A network factory that records the registered nodes.
+struct RecordingNetwork {
+    inner: RaftRouter,
+    registered: Arc<Mutex<Vec<(MemNodeId, ())>>>,
+}
+
+impl RaftNetworkFactory<TypeConfig> for RecordingNetwork {
+    type Network = RaftRouterNetwork;
+
+    async fn new_client(&mut self, target: MemNodeId, node: &()) -> Self::Network {
+        self.inner.new_client(target, node).await
+    }
+
+    async fn register_node(&mut self, target: MemNodeId, node: &()) {
+        self.registered.lock().unwrap().push((target, *node));
+    }
+}
+
+/// Adding a node with `ChangeMembers` registers the node to the network layer.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn register_node() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let registered = Arc::new(Mutex::new(vec![]));
+
+    tracing::info!("--- start node-0 with a recording network");
+    {
+        let (log_store, sm) = router.new_store();
+        let network = RecordingNetwork {
+            inner: router.clone(),
+            registered: registered.clone(),
+        };
+        let n0 = Raft::new(0, config.clone(), network, log_store.clone(), sm.clone()).await?;
+        router.insert_raft_node(0, n0, log_store, sm);
+    }
+
+    tracing::info!("--- initialize node-0, nothing to register");
+    let mut log_index = 0;
+    {
+        router.initialize(0).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "initialized").await?;
+
+        assert!(registered.lock().unwrap().is_empty(), "node-0 itself is not registered");
+    }
+
+    tracing::info!(log_index, "--- add learner node-1, it is registered");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 added").await?;
+
+        assert_eq!(vec![(1, ())], *registered.lock().unwrap());
+    }
+
+    tracing::info!(log_index, "--- promote node-1, nothing new to register");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership(btreeset! {0,1}, false).await?;
+        log_index += 2;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-1 promoted").await?;
+
+        assert_eq!(vec![(1, ())], *registered.lock().unwrap());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
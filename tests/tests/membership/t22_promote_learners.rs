@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Promote multiple learners in a single membership change, with `reject_lagging_promotion`
+/// enabled.
+///
+/// The change is rejected as a whole if any of the learners is lagging, and succeeds once all of
+/// them caught up.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn promote_learners_only_when_all_caught_up() -> anyhow::Result<()> {
+    let lag_threshold = 5;
+
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            replication_lag_threshold: lag_threshold,
+            reject_lagging_promotion: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- isolate learner 4 and write logs");
+    {
+        router.set_network_error(4, true);
+
+        let n = lag_threshold as usize * 2;
+        router.client_request_many(0, "foo", n).await?;
+        log_index += n as u64;
+
+        for id in [0, 1, 2, 3] {
+            router
+                .wait(&id, timeout())
+                .applied_index(Some(log_index), "all logs replicated except node-4")
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- promoting 3 and 4 fails because 4 is lagging");
+    {
+        let res = leader.change_membership([0, 1, 2, 3, 4], false).await;
+        let raft_err = res.unwrap_err();
+        tracing::debug!("raft_err: {:?}", raft_err);
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerIsLagging(err)) => {
+                assert_eq!(4, err.node_id);
+                assert!(err.distance > lag_threshold);
+            }
+            _ => {
+                unreachable!("expect LearnerIsLagging")
+            }
+        }
+
+        let m = leader.metrics().borrow().membership_config.clone();
+        assert_eq!(
+            vec![btreeset! {0,1,2}],
+            m.membership().get_joint_config().clone(),
+            "membership is not changed"
+        );
+    }
+
+    tracing::info!(log_index, "--- restore learner 4 and wait for it to catch up");
+    {
+        router.set_network_error(4, false);
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        router.wait(&4, timeout()).applied_index(Some(log_index), "node-4 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- promoting 3 and 4 in one change");
+    {
+        leader.change_membership([0, 1, 2, 3, 4], false).await?;
+        log_index += 2;
+
+        for id in [0, 1, 2, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "uniform config applied").await?;
+        }
+
+        let m = leader.metrics().borrow().membership_config.clone();
+        assert_eq!(
+            vec![btreeset! {0,1,2,3,4}],
+            m.membership().get_joint_config().clone(),
+            "3 and 4 are promoted to voters"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
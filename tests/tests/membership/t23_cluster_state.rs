@@ -0,0 +1,126 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `get_cluster_state()` returns an internally consistent view while membership changes and
+/// client writes are in progress.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn cluster_state_is_consistent() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- change membership and write logs concurrently");
+
+    let done = Arc::new(AtomicBool::new(false));
+
+    let changes = {
+        let leader = leader.clone();
+        let done = done.clone();
+        tokio::spawn(async move {
+            for _ in 0..5 {
+                leader.change_membership([0, 1, 2, 3], true).await?;
+                leader.change_membership([0, 1, 2], true).await?;
+            }
+            done.store(true, Ordering::Relaxed);
+            anyhow::Ok(())
+        })
+    };
+
+    let writes = {
+        let router = router.clone();
+        let done = done.clone();
+        tokio::spawn(async move {
+            while !done.load(Ordering::Relaxed) {
+                router.client_request_many(0, "foo", 5).await?;
+            }
+            anyhow::Ok(())
+        })
+    };
+
+    let mut n = 0;
+    while !done.load(Ordering::Relaxed) {
+        for id in [0, 1, 3] {
+            let st = router.get_raft_handle(&id)?.get_cluster_state().await?;
+            n += 1;
+
+            assert!(
+                st.membership.log_id() <= &st.committed,
+                "node-{}: membership is committed: {}",
+                id,
+                st
+            );
+            assert!(
+                st.last_applied <= st.committed,
+                "node-{}: applied is not beyond committed: {}",
+                id,
+                st
+            );
+            assert!(
+                st.committed.map(|x| x.leader_id.term) <= Some(st.current_term),
+                "node-{}: committed is not from a future term: {}",
+                id,
+                st
+            );
+
+            if id == 0 {
+                assert_eq!(Some(0), st.current_leader, "{}", st);
+                assert!(
+                    st.membership.voter_ids().any(|x| x == 0),
+                    "leader is a voter of the committed membership: {}",
+                    st
+                );
+            }
+        }
+    }
+
+    changes.await??;
+    writes.await??;
+
+    tracing::info!(log_index, "--- read {} cluster states", n);
+
+    tracing::info!(log_index, "--- the final state reflects the last membership change");
+    {
+        // Every write and membership change has returned, thus every log is committed.
+        // The metrics may not yet reflect the last log, read it from the cluster state.
+        let st = leader.get_cluster_state().await?;
+        log_index = st.committed.index().unwrap();
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "all logs applied").await?;
+        router.wait(&0, timeout()).log_index(Some(log_index), "no more logs").await?;
+
+        assert_eq!(
+            btreeset! {0,1,2},
+            st.membership.voter_ids().collect(),
+            "the last committed membership: {}",
+            st
+        );
+        assert_eq!(Some(0), st.current_leader);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
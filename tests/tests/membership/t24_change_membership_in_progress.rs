@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Only one membership change can be in flight: a change proposed while the previous membership
+/// log is not committed is rejected with `InProgress`, until the previous one is committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_membership_in_progress() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate node-1 and node-2, the first change can not commit"
+    );
+    let first = {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let n0 = n0.clone();
+        let handle = tokio::spawn(async move { n0.change_membership(btreeset! {0,1,2,3}, false).await });
+
+        router.wait(&0, timeout()).log_index(Some(log_index + 1), "joint config appended").await?;
+        sleep(Duration::from_millis(200)).await;
+        assert!(!handle.is_finished());
+
+        handle
+    };
+
+    tracing::info!(log_index, "--- the second change is rejected");
+    {
+        let res = n0.change_membership(btreeset! {0,1,3}, false).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+
+        match err {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::InProgress(e)) => {
+                assert_eq!(Some(log_index), e.committed.index());
+                assert_eq!(Some(log_index + 1), e.membership_log_id.index());
+            }
+            _ => panic!("expect InProgress, got: {}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- restore the network, the first change commits");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        let resp = first.await??;
+        log_index += 2;
+        assert_eq!(log_index, resp.log_id.index);
+    }
+
+    tracing::info!(log_index, "--- the next change is accepted");
+    {
+        n0.change_membership(btreeset! {0,1,3}, false).await?;
+        log_index += 2;
+
+        let m = router.get_metrics(&0)?;
+        assert_eq!(
+            btreeset! {0,1,3},
+            m.membership_config.membership().voter_ids().collect(),
+            "node-2 is removed"
+        );
+        assert_eq!(Some(log_index), m.membership_config.log_id().index());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A membership change commits while an election is in flight.
+///
+/// - Node 1 starts an election with config `{0,1,2}` while it is isolated.
+/// - Leader 0 commits a membership change to `{0,3,4}` meanwhile.
+/// - Node 1 can not be elected: the voters of its config have seen the newer config.
+/// - A node in the new config is elected by a quorum of the new config.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn elect_during_membership_change() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+    let n3 = router.get_raft_handle(&3)?;
+
+    tracing::info!(log_index, "--- isolate node 1 and let it start an election");
+    {
+        router.set_network_error(1, true);
+
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Candidate, "node 1 is electing").await?;
+    }
+
+    tracing::info!(log_index, "--- change membership to {{0,3,4}} while node 1 is electing");
+    {
+        n0.change_membership([0, 3, 4], false).await?;
+        log_index += 2;
+
+        for id in [0, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "new config is applied").await?;
+        }
+        // Node 2 is removed and does not receive the last uniform config log.
+        router.wait(&2, timeout()).applied_index(Some(log_index - 1), "joint config is applied").await?;
+    }
+
+    tracing::info!(log_index, "--- isolate leader 0, restore node 1");
+    {
+        router.set_network_error(0, true);
+        router.set_network_error(1, false);
+
+        // Wait for leader lease to expire
+        sleep(Duration::from_millis(700)).await;
+    }
+
+    tracing::info!(log_index, "--- node 1 can not be elected with the stale config");
+    {
+        n1.trigger().elect().await?;
+
+        let res = n1.wait(Some(Duration::from_millis(500))).state(ServerState::Leader, "node 1 is not elected").await;
+        assert!(
+            res.is_err(),
+            "node 1 must not be elected by a quorum of the stale config"
+        );
+    }
+
+    tracing::info!(log_index, "--- node 3 is elected by a quorum of the new config");
+    {
+        n3.trigger().elect().await?;
+        log_index += 1;
+
+        n3.wait(timeout()).state(ServerState::Leader, "node 3 is elected").await?;
+        router
+            .wait(&4, timeout())
+            .metrics(
+                |m| m.current_leader == Some(3) && m.last_applied.map(|x| x.index) == Some(log_index),
+                "node 4 follows node 3",
+            )
+            .await?;
+
+        let m = n3.metrics().borrow().clone();
+        assert_eq!(
+            vec![btreeset! {0,3,4}],
+            m.membership_config.membership().get_joint_config().clone()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
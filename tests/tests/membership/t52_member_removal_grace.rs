@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::ChangeMembers;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Removing a node added within `Config::member_removal_grace` is rejected, unless it is forced.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn member_removal_grace() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            member_removal_grace: 60_000,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add learner 1, 2 and promote 1 to voter");
+    {
+        router.new_raft_node(1).await;
+        router.new_raft_node(2).await;
+        router.add_learner(0, 1).await?;
+        router.add_learner(0, 2).await?;
+        log_index += 2;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership(btreeset! {0,1}, true).await?;
+        log_index += 2;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "promote 1").await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- removing recently added voter 1 is rejected");
+    {
+        let res = n0.change_membership(btreeset! {0}, true).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        match err {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::RecentlyAdded(e)) => {
+                assert_eq!(1, e.node_id);
+                assert_eq!(Duration::from_millis(60_000), e.grace);
+            }
+            _ => panic!("expect RecentlyAdded, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- removing recently added learner 2 is rejected");
+    {
+        let res = n0.change_membership(ChangeMembers::RemoveNodes(btreeset! {2}), false).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        match err {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::RecentlyAdded(e)) => {
+                assert_eq!(2, e.node_id);
+            }
+            _ => panic!("expect RecentlyAdded, got: {:?}", err),
+        }
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index), metrics.last_log_index, "no log is written");
+    }
+
+    tracing::info!(log_index, "--- forced removal succeeds");
+    {
+        n0.force_change_membership(btreeset! {0}, false).await?;
+        log_index += 2;
+
+        n0.force_change_membership(ChangeMembers::RemoveNodes(btreeset! {2}), false).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "force remove 1, 2").await?;
+
+        let metrics = n0.metrics().borrow().clone();
+        let nodes = metrics.membership_config.nodes().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(vec![0], nodes);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -8,9 +8,17 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_current_leader;
+mod t10_follower_leader_contact;
 mod t10_leader_last_ack;
 mod t10_purged;
 mod t10_server_metrics_and_data_metrics;
 mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
 mod t40_metrics_wait;
+mod t50_commit_latency;
+mod t50_rpc_latency;
+mod t55_follower_log_state;
+mod t60_replication_detail;
+mod t70_metrics_history;
+mod t80_config_mismatch;
+mod t90_node_labels;
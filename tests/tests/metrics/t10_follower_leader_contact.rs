@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::AsyncRuntimeOf;
+use openraft::AsyncRuntime;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower reports the time since it last heard from the leader: it resets on heartbeat and
+/// grows when heartbeats stop.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn follower_leader_contact() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- leader does not report leader contact");
+    {
+        assert_eq!(None, n0.metrics().borrow().millis_since_leader_contact);
+        assert_eq!(None, n0.time_since_leader_contact().await?);
+    }
+
+    tracing::info!(log_index, "--- heartbeat; follower leader contact resets");
+    {
+        n0.trigger().heartbeat().await?;
+        n1.wait(timeout())
+            .metrics(
+                |x| matches!(x.millis_since_leader_contact, Some(m) if m < 100),
+                "leader contact refreshed",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- no heartbeat for 500 ms; follower leader contact grows");
+    {
+        AsyncRuntimeOf::<TypeConfig>::sleep(Duration::from_millis(500)).await;
+
+        let elapsed = n1.time_since_leader_contact().await?.unwrap();
+        assert!(elapsed >= Duration::from_millis(400), "elapsed: {:?}", elapsed);
+
+        n1.wait(timeout())
+            .metrics(
+                |x| matches!(x.millis_since_leader_contact, Some(m) if m >= 400),
+                "leader contact grows",
+            )
+            .await?;
+        assert!(n1.data_metrics().borrow().millis_since_leader_contact >= Some(400));
+    }
+
+    tracing::info!(log_index, "--- heartbeat again; follower leader contact resets");
+    {
+        n0.trigger().heartbeat().await?;
+        n1.wait(timeout())
+            .metrics(
+                |x| matches!(x.millis_since_leader_contact, Some(m) if m < 100),
+                "leader contact refreshed again",
+            )
+            .await?;
+
+        let elapsed = n1.time_since_leader_contact().await?.unwrap();
+        assert!(elapsed < Duration::from_millis(500), "elapsed: {:?}", elapsed);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::CommitLatency;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports a higher commit latency when a follower it needs for a quorum is behind a
+/// slow link, compared to a cluster with only fast links.
+///
+/// A cluster of two voters is used, so that the slow follower is required to commit.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn commit_latency() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let delay = Duration::from_millis(50);
+
+    tracing::info!("--- all links are fast");
+    let fast = write_and_get_commit_latency(config.clone(), None).await?;
+    tracing::info!("fast commit_latency: {}", fast);
+
+    tracing::info!("--- the link to node 1 is slow");
+    let slow = write_and_get_commit_latency(config.clone(), Some(delay)).await?;
+    tracing::info!("slow commit_latency: {}", slow);
+
+    assert!(fast.p99 < delay, "fast: {}", fast);
+    assert!(slow.p50 >= delay, "slow: {}", slow);
+    assert!(slow.p50 > fast.p50);
+
+    Ok(())
+}
+
+/// Create a cluster of 0,1, write some logs and return the commit latency reported by the leader.
+///
+/// The membership changes to initialize the cluster are client writes too, they are fast and are
+/// included in the returned latency.
+async fn write_and_get_commit_latency(config: Arc<Config>, delay: Option<Duration>) -> Result<CommitLatency> {
+    let mut router = RaftRouter::new(config);
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    if let Some(delay) = delay {
+        router.set_node_send_delay(1, delay.as_millis() as u64);
+    }
+
+    log_index += router.client_request_many(0, "foo", 10).await?;
+
+    let m = n0
+        .wait(timeout())
+        .metrics(
+            |m| m.last_applied.map(|x| x.index) >= Some(log_index) && m.commit_latency.map(|x| x.samples) >= Some(10),
+            "commit latency of all writes are measured",
+        )
+        .await?;
+
+    Ok(m.commit_latency.unwrap())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports a higher RPC latency for a peer behind a slow link.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn rpc_latency() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let delay = Duration::from_millis(30);
+
+    tracing::info!(log_index, "--- slow down the link to node 2 and write logs");
+    {
+        router.set_node_send_delay(2, delay.as_millis() as u64);
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 catches up").await?;
+    }
+
+    tracing::info!(log_index, "--- node 2 has a higher latency");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| match (m.rpc_latency.get(&1), m.rpc_latency.get(&2)) {
+                    (Some(l1), Some(l2)) => l2.max >= delay && l2.average > l1.average,
+                    _ => false,
+                },
+                "latency of node 2 is higher",
+            )
+            .await?;
+
+        tracing::info!("rpc_latency: {:?}", m.rpc_latency);
+    }
+
+    tracing::info!(log_index, "--- remove node 2, its latency is forgotten");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership([0, 1], false).await?;
+
+        n0.wait(timeout())
+            .metrics(|m| !m.rpc_latency.contains_key(&2), "latency of node 2 is removed")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
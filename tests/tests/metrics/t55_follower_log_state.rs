@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Heartbeat responses report the committed and last log id of each follower to the leader,
+/// without any `AppendEntries` carrying logs.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn follower_log_state() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            report_log_state: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs, followers learn the commit");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "followers committed").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- isolate node-2, node-1 commits more logs");
+    let lagging_at = log_index;
+    {
+        router.set_network_error(2, true);
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 committed").await?;
+    }
+
+    tracing::info!(log_index, "--- heartbeats report the follower log state");
+    {
+        let recorder = router.record_rpc();
+
+        n0.trigger().heartbeat().await?;
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| {
+                    m.follower_log_state.get(&1).map(|s| (s.committed, s.last_log_id))
+                        == Some((Some(log_id(1, 0, log_index)), Some(log_id(1, 0, log_index))))
+                },
+                "node-1 reports it is up to date",
+            )
+            .await?;
+
+        let s2 = m.follower_log_state[&2];
+        assert_eq!(Some(log_id(1, 0, lagging_at)), s2.committed, "node-2 lags behind");
+        assert_eq!(Some(log_id(1, 0, lagging_at)), s2.last_log_id, "node-2 lags behind");
+
+        assert_eq!(
+            0,
+            recorder.append_requests().filter(|(_, to, req)| *to == 1 && !req.entries.is_empty()).count(),
+            "no logs are sent to node-1"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
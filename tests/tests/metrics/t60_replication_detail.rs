@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::RaftError;
+use openraft::metrics::ReplicationPhase;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Inspect the replication detail on the leader: a lagging follower shows a lower matching log
+/// and a stale last-ack time.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replication_detail() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all followers are up to date");
+    {
+        // A new cluster is ready once a quorum has the logs, the other follower may still lag.
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    m.replication.as_ref().map_or(false, |r| {
+                        r.iter().filter(|(id, _)| **id != 0).all(|(_, x)| x.map(|l| l.index) == Some(log_index))
+                    })
+                },
+                "all followers are up to date",
+            )
+            .await?;
+
+        let detail = n0.get_replication_detail().await?;
+        assert_eq!(btreeset! {1,2}, detail.keys().copied().collect());
+
+        for (id, d) in detail.iter() {
+            assert_eq!(Some(log_index), d.matching.map(|x| x.index), "node-{}", id);
+            assert_eq!(log_index + 1, d.next_index, "node-{}", id);
+            assert_eq!(ReplicationPhase::Streaming, d.phase, "node-{}", id);
+            assert!(d.since_last_ack.is_some(), "node-{}", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- isolate node-2 and write logs, node-2 lags behind");
+    {
+        router.set_network_error(2, true);
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 is up to date").await?;
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        n0.trigger().heartbeat().await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the lagging node-2 shows a lower matching log and a stale ack"
+    );
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| m.millis_since_quorum_ack < Some(200),
+                "heartbeat is acknowledged by node-1",
+            )
+            .await?;
+
+        let detail = n0.get_replication_detail().await?;
+        let d1 = &detail[&1];
+        let d2 = &detail[&2];
+
+        assert_eq!(Some(log_index), d1.matching.map(|x| x.index));
+        assert!(d2.matching.map(|x| x.index) < Some(log_index));
+
+        let ack1 = d1.since_last_ack.unwrap();
+        let ack2 = d2.since_last_ack.unwrap();
+        assert!(
+            ack2 > ack1,
+            "node-2 ack {:?} is staler than node-1 ack {:?}",
+            ack2,
+            ack1
+        );
+        assert!(ack2 >= Duration::from_millis(300));
+    }
+
+    tracing::info!(log_index, "--- a follower returns ForwardToLeader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.get_replication_detail().await;
+        let err = res.unwrap_err();
+        assert!(matches!(err, RaftError::APIError(_)), "got: {:?}", err);
+        assert_eq!(Some(0), err.api_error().unwrap().leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `metrics_history_size`, a node keeps the most recent metrics samples taken on ticks, in
+/// time order, and evicts the oldest ones.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn metrics_history() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 400,
+            metrics_history_size: 5,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- wait for several ticks, the history is full");
+    let history = {
+        // A tick is every 75 ms.
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        let history = n0.get_metrics_history().await?;
+        assert_eq!(5, history.len());
+
+        for w in history.windows(2) {
+            assert!(w[0].time < w[1].time, "samples are in time order");
+        }
+        history
+    };
+
+    tracing::info!(log_index, "--- write logs, the newer samples reflect them");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        let newer = n0.get_metrics_history().await?;
+        assert_eq!(5, newer.len());
+
+        assert!(newer[0].time > history[4].time, "the samples taken before are evicted");
+        for s in newer.iter() {
+            assert_eq!(Some(log_index), s.metrics.last_log_index);
+        }
+    }
+
+    Ok(())
+}
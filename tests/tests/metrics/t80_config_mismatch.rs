@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Raft;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node receiving AppendEntries from a leader with different timing config reports the leader in
+/// `RaftMetrics::config_mismatches`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn config_mismatch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            exchange_config_hash: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mismatched_config = Arc::new(
+        Config {
+            election_timeout_min: 600,
+            election_timeout_max: 601,
+            ..(*config).clone()
+        }
+        .validate()?,
+    );
+
+    assert_ne!(config.timing_hash(), mismatched_config.timing_hash());
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- add learner 1 with mismatched config, learner 2 with the same config"
+    );
+    {
+        let (log_store, sm) = router.new_store();
+        let n1 = Raft::new(
+            1,
+            mismatched_config.clone(),
+            router.clone(),
+            log_store.clone(),
+            sm.clone(),
+        )
+        .await?;
+        router.insert_raft_node(1, n1, log_store, sm);
+        router.add_learner(0, 1).await?;
+
+        router.new_raft_node(2).await;
+        router.add_learner(0, 2).await?;
+    }
+
+    tracing::info!(log_index, "--- node 1 reports the mismatched leader");
+    {
+        let expected = btreemap! {0 => config.timing_hash()};
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.config_mismatches == expected,
+                "node 1 reports mismatch with node 0",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- node 2 reports no mismatch");
+    {
+        let m2 = router.get_raft_handle(&2)?.metrics().borrow().clone();
+        assert!(m2.config_mismatches.is_empty(), "got: {:?}", m2.config_mismatches);
+
+        let m0 = router.get_raft_handle(&0)?.metrics().borrow().clone();
+        assert!(m0.config_mismatches.is_empty(), "got: {:?}", m0.config_mismatches);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
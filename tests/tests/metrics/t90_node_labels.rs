@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Raft;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Captures the fields of the `RaftCore` spans.
+#[derive(Clone, Default)]
+struct CoreSpanFields {
+    fields: Arc<Mutex<Vec<BTreeMap<String, String>>>>,
+}
+
+impl<S: Subscriber> Layer<S> for CoreSpanFields {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() != "RaftCore" {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        self.fields.lock().unwrap().push(visitor.0);
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(BTreeMap<String, String>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// The labels set in config are included in the metrics of the node and in the `RaftCore` span
+/// that the events of the node are emitted in.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn node_labels() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let labeled_config = Arc::new(
+        Config {
+            labels: vec![
+                ("zone".to_string(), "us-east-1a".to_string()),
+                ("role".to_string(), "replica".to_string()),
+            ],
+            ..(*config).clone()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add learner 1 with labels");
+    {
+        let (log_store, sm) = router.new_store();
+        let n1 = Raft::new(1, labeled_config.clone(), router.clone(), log_store.clone(), sm.clone()).await?;
+        router.insert_raft_node(1, n1, log_store, sm);
+
+        router.add_learner(0, 1).await?;
+    }
+
+    tracing::info!(log_index, "--- labels are in the metrics of node-1 only");
+    {
+        let want = btreemap! {
+            "role".to_string() => "replica".to_string(),
+            "zone".to_string() => "us-east-1a".to_string(),
+        };
+
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.wait(timeout()).applied_index(Some(log_index + 1), "node-1 receives logs").await?;
+        assert_eq!(want, *m.labels);
+        assert!(m.to_string().contains("labels:{role=replica,zone=us-east-1a}"));
+
+        let server_metrics = n1.server_metrics().borrow().clone();
+        assert_eq!(want, *server_metrics.labels);
+
+        let n0 = router.get_raft_handle(&0)?;
+        assert!(n0.metrics().borrow().labels.is_empty());
+    }
+
+    tracing::info!(log_index, "--- labels are in the span of the events of a node");
+    {
+        // Run a node on its own thread, so that every event of it goes to the capturing
+        // subscriber, instead of the global one.
+        let capture = CoreSpanFields::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        let handle = std::thread::spawn(move || -> Result<()> {
+            let _guard = tracing::subscriber::set_default(subscriber);
+            let rt = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+            rt.block_on(async move {
+                let mut router = RaftRouter::new(labeled_config);
+                router.new_raft_node(2).await;
+                router.get_raft_handle(&2)?.shutdown().await?;
+                Ok(())
+            })
+        });
+        handle.join().unwrap()?;
+
+        let fields = capture.fields.lock().unwrap().clone();
+        assert_eq!(1, fields.len());
+        assert_eq!(Some(&"2".to_string()), fields[0].get("id"));
+        assert_eq!(
+            Some(&"role=replica,zone=us-east-1a".to_string()),
+            fields[0].get("labels")
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
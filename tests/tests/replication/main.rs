@@ -5,8 +5,17 @@
 mod fixtures;
 
 mod t10_append_entries_partial_success;
+mod t11_rpc_recorder;
+mod t12_commit_propagation;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
 mod t51_append_entries_too_large;
+mod t52_learner_replication_budget;
+mod t54_witness;
+mod t55_replication_hints;
+mod t56_snapshot_lag_threshold;
+mod t57_pause_replication;
+mod t58_replication_stall;
+mod t59_force_snapshot_to;
 #[cfg(feature = "loosen-follower-log-revert")]
 mod t60_feature_loosen_follower_log_revert;
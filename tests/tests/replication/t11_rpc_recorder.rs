@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The RPC recorder captures the AppendEntries requests in the order they are sent.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn rpc_recorder_append_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    let recorder = router.record_rpc();
+
+    tracing::info!(log_index, "--- write 3 logs one by one");
+    {
+        for _ in 0..3 {
+            log_index += router.client_request_many(0, "0", 1).await?;
+            router.wait(&1, None).applied_index(Some(log_index), "learner received log").await?;
+        }
+    }
+
+    let got = recorder
+        .append_requests()
+        .filter(|(_from, _to, req)| !req.entries.is_empty())
+        .map(|(from, to, req)| {
+            let indexes = req.entries.iter().map(|e| e.log_id.index).collect::<Vec<_>>();
+            (from, to, req.prev_log_id.index(), indexes)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            (0, 1, Some(log_index - 3), vec![log_index - 2]),
+            (0, 1, Some(log_index - 2), vec![log_index - 1]),
+            (0, 1, Some(log_index - 1), vec![log_index]),
+        ],
+        got
+    );
+
+    assert_eq!(0, recorder.snapshot_requests().count());
+    assert_eq!(0, recorder.vote_requests().count());
+
+    recorder.clear();
+    assert_eq!(0, recorder.append_requests().count());
+
+    Ok(())
+}
+
+/// The RPC recorder captures the InstallSnapshot requests sent to a learner lacking logs.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn rpc_recorder_install_snapshot() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs to trigger snapshot and purge");
+    {
+        log_index += router.client_request_many(0, "0", (snapshot_threshold * 2) as usize).await?;
+        router.wait(&0, None).metrics(|x| x.purged.is_some(), "logs purged").await?;
+    }
+
+    let recorder = router.record_rpc();
+
+    tracing::info!(log_index, "--- add learner 1, which receives a snapshot");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, None).applied_index(Some(log_index), "learner received all logs").await?;
+    }
+
+    let snapshot_requests = recorder.snapshot_requests().collect::<Vec<_>>();
+    assert!(!snapshot_requests.is_empty());
+    for (from, to, _req) in snapshot_requests {
+        assert_eq!((0, 1), (from, to));
+    }
+
+    Ok(())
+}
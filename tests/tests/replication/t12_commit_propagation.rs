@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// When the leader commits a log, it sends the new commit index to followers at once, with an
+/// AppendEntries request without entries, so that followers apply it without waiting for the next
+/// heartbeat or the next log.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn commit_propagation() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let recorder = router.record_rpc();
+
+    tracing::info!(log_index, "--- write one log, followers apply it without heartbeat");
+    {
+        log_index += router.client_request_many(0, "0", 1).await?;
+
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "follower applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- the commit index is sent to every follower");
+    {
+        // A follower that receives the log after it is committed, gets the commit index along with
+        // the log. The follower that forms the quorum with the leader already has the log, thus it
+        // gets the commit index without entries.
+        let mut without_entries = vec![];
+
+        for target in [1, 2] {
+            let committed = recorder
+                .append_requests()
+                .filter(|(_from, to, _req)| *to == target)
+                .map(|(_from, _to, req)| (req.leader_commit.index(), req.entries.is_empty()))
+                .collect::<Vec<_>>();
+
+            assert!(
+                committed.iter().any(|(c, _)| *c == Some(log_index)),
+                "commit index {} is sent to node-{}: {:?}",
+                log_index,
+                target,
+                committed
+            );
+
+            if committed.contains(&(Some(log_index), true)) {
+                without_entries.push(target);
+            }
+        }
+
+        assert!(
+            !without_entries.is_empty(),
+            "commit index {} is sent to at least one follower without entries",
+            log_index
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::AppendEntriesRequest;
+use openraft::Config;
+use openraft::RPCTypes;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Learners are throttled by `learner_replication_budget` only when a quorum of voters is backed
+/// up: a single lagging voter does not slow down a learner catching up.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn learner_replication_budget() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            max_payload_entries: 20,
+            learner_replication_budget: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster of voter 0,1,2 and learner 3");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- isolate voter 2, a quorum of voters is still caught up");
+    {
+        router.set_network_error(2, true);
+    }
+
+    let max_to_learner = Arc::new(AtomicU64::new(0));
+
+    tracing::info!(log_index, "--- record the payload size sent to learner 3");
+    {
+        let m = max_to_learner.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _id, target| {
+            let r: AppendEntriesRequest<_> = req.try_into().unwrap();
+            if target == 3 {
+                m.fetch_max(r.entries.len() as u64, Ordering::Relaxed);
+            }
+            Ok(())
+        });
+    }
+
+    tracing::info!(log_index, "--- isolate learner 3 and write logs");
+    {
+        router.set_network_error(3, true);
+
+        log_index += router.client_request_many(0, "0", 100).await?;
+        for id in [0, 1] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "voters commit").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- restore learner 3, it catches up with full payloads");
+    {
+        router.set_network_error(3, false);
+        router.get_raft_handle(&0)?.trigger().heartbeat().await?;
+
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner 3 caught up").await?;
+    }
+
+    assert_eq!(
+        20,
+        max_to_learner.load(Ordering::Relaxed),
+        "learner is not throttled by a single lagging voter"
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,219 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::CatchUpError;
+use openraft::error::CommittedDigestError;
+use openraft::error::NodeIsWitness;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A witness is replicated with log metadata only, counts toward the commit quorum, and never
+/// elects itself.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn witness() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- make node-2 a witness");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.add_witnesses(btreeset! {2}).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2] {
+            router
+                .wait(&id, timeout())
+                .metrics(
+                    |m| m.membership_config.membership().is_witness(&2),
+                    format!("node-{} sees node-2 as witness", id),
+                )
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- witness receives log metadata only");
+    {
+        let first = log_index + 1;
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "witness received logs").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 received logs").await?;
+
+        let (mut sto1, _sm1) = router.get_storage_handle(&1)?;
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+
+        let full = sto1.try_get_log_entries(first..=log_index).await?;
+        let witness = sto2.try_get_log_entries(first..=log_index).await?;
+
+        assert_eq!(
+            full.iter().map(|e| e.log_id).collect::<Vec<_>>(),
+            witness.iter().map(|e| e.log_id).collect::<Vec<_>>()
+        );
+        assert!(full.iter().all(|e| matches!(e.payload, EntryPayload::Normal(_))));
+        assert!(witness.iter().all(|e| matches!(e.payload, EntryPayload::Blank)));
+    }
+
+    tracing::info!(log_index, "--- witness does not serve reads or digests");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        let want = NodeIsWitness { node_id: 2 };
+
+        let err = n2.read_barrier().await.unwrap_err();
+        assert_eq!(Some(&want), err.api_error());
+
+        let err = n2.ensure_caught_up(Duration::from_millis(500)).await.unwrap_err();
+        assert!(matches!(err.api_error(), Some(CatchUpError::IsWitness(e)) if e == &want));
+
+        let err = n2.get_committed_digest(log_index).await.unwrap_err();
+        assert_eq!(Some(&CommittedDigestError::IsWitness(want)), err.api_error());
+    }
+
+    tracing::info!(log_index, "--- isolate node-1, witness counts toward commit quorum");
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "0", 1).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "committed by node-0 and witness").await?;
+
+        router.set_network_error(1, false);
+        log_index += router.client_request_many(0, "0", 1).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- witness does not elect itself");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect().await?;
+        sleep(Duration::from_millis(200)).await;
+
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(1, m.current_term);
+    }
+
+    tracing::info!(log_index, "--- remove leader, node-1 is elected with the witness vote");
+    {
+        router.remove_node(0);
+
+        // Wait for the leader lease to expire.
+        sleep(Duration::from_millis(700)).await;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        assert_eq!(ServerState::Follower, n2.metrics().borrow().state);
+    }
+
+    Ok(())
+}
+
+/// A witness is stored in membership, thus after a restart, a witness still does not elect
+/// itself and the leader still replicates log metadata only to it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn witness_restart() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- make node-2 a witness");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.add_witnesses(btreeset! {2}).await?;
+        log_index += 1;
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- restart every node");
+    {
+        for id in [0, 1, 2] {
+            let (n, sto, sm) = router.remove_node(id).unwrap();
+            n.shutdown().await?;
+            router.new_raft_node_with_sto(id, sto, sm).await;
+        }
+
+        for id in [0, 1, 2] {
+            router
+                .wait(&id, timeout())
+                .metrics(
+                    |m| m.membership_config.membership().is_witness(&2),
+                    format!("node-{} sees node-2 as witness after restart", id),
+                )
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- restarted witness does not elect itself");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        let term = n2.metrics().borrow().current_term;
+
+        n2.trigger().elect().await?;
+        sleep(Duration::from_millis(200)).await;
+
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(term, m.current_term);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- elect node-1, it replicates log metadata only to the witness"
+    );
+    {
+        // Wait for the leader lease to expire.
+        sleep(Duration::from_millis(700)).await;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+        log_index += 1;
+
+        let first = log_index + 1;
+        log_index += router.client_request_many(1, "0", 5).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "witness received logs").await?;
+
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+        let witness = sto2.try_get_log_entries(first..=log_index).await?;
+
+        assert_eq!(5, witness.len());
+        assert!(witness.iter().all(|e| matches!(e.payload, EntryPayload::Blank)));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
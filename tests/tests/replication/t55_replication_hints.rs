@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::storage::RaftLogStorage;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `persist_replication_hints`, a leader re-elected after restart starts replicating to a
+/// lagging follower right after its last known matching log, instead of searching from the tip.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replication_hints() -> Result<()> {
+    let (hint, first_prev) = restart_leader_with_lagging_follower(true).await?;
+    assert_eq!(
+        Some(hint),
+        first_prev,
+        "the first AppendEntries probes right after the hinted matching log"
+    );
+
+    let (hint, first_prev) = restart_leader_with_lagging_follower(false).await?;
+    assert_ne!(
+        Some(hint),
+        first_prev,
+        "without hints, the first AppendEntries probes the middle of the log"
+    );
+
+    Ok(())
+}
+
+/// Restart the leader node-0 while node-2 lags behind, and return the matching log index of
+/// node-2 before restart and the prev log index of the first AppendEntries sent to it after
+/// restart.
+async fn restart_leader_with_lagging_follower(persist_replication_hints: bool) -> Result<(u64, Option<u64>)> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            persist_replication_hints,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write some logs to all nodes");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+    }
+
+    let node_2_matching = log_index;
+
+    tracing::info!(log_index, "--- isolate node-2, it lags behind");
+    {
+        router.set_network_error(2, true);
+
+        log_index += router.client_request_many(0, "foo", 40).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied logs").await?;
+    }
+
+    let (mut sto0, sm0) = router.get_storage_handle(&0)?;
+
+    if persist_replication_hints {
+        tracing::info!(log_index, "--- wait for the leader to save replication hints");
+
+        let want = btreemap! {1 => log_index, 2 => node_2_matching};
+        let mut hints = BTreeMap::new();
+        for _ in 0..100 {
+            hints = sto0.read_replication_hints().await?;
+            if hints == want {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(want, hints);
+    }
+
+    tracing::info!(log_index, "--- restart node-0, it is restored as leader");
+    let recorder = {
+        let (n0, _, _) = router.remove_node(0).unwrap();
+        n0.shutdown().await?;
+
+        // Let the AppendEntries still in flight from the shut down node-0 fail, so that they are
+        // not recorded as sent by the restarted node-0.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        router.set_network_error(2, false);
+
+        let recorder = router.record_rpc();
+        router.new_raft_node_with_sto(0, sto0, sm0).await;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        recorder
+    };
+
+    tracing::info!(log_index, "--- node-2 catches up");
+    {
+        router.wait(&2, timeout()).log_index(Some(log_index), "node-2 caught up").await?;
+    }
+
+    let first_prev = recorder.append_requests().find(|(_from, to, _)| *to == 2).unwrap().2.prev_log_id.index();
+
+    Ok((node_2_matching, first_prev))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `snapshot_lag_threshold`, a follower that falls behind by more than the threshold is
+/// replicated with a snapshot, even though the logs it lacks are not purged. A follower within the
+/// threshold is replicated with logs.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn snapshot_lag_threshold() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 1_000,
+            snapshot_lag_threshold: Some(50),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1,2}).await?;
+
+    tracing::info!(log_index, "--- isolate node-2, it falls far behind");
+    {
+        router.set_network_error(2, true);
+
+        log_index += router.client_request_many(0, "foo", 90).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 keeps up").await?;
+    }
+
+    tracing::info!(log_index, "--- isolate node-1, it falls slightly behind");
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 applied all logs").await?;
+    }
+
+    let snapshot_last = log_id(1, 0, log_index);
+
+    tracing::info!(log_index, "--- build a snapshot on node-0, no log is purged");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(snapshot_last, "node-0 built a snapshot").await?;
+
+        let purged = n0.metrics().borrow().purged;
+        assert_eq!(None, purged, "the logs node-1 and node-2 lack are not purged");
+
+        // Let the AppendEntries issued before the snapshot was built fail, so that they are not
+        // sent once the network is restored.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    tracing::info!(log_index, "--- restore network, write a log to trigger replication");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "caught up").await?;
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- node-2 is replicated with the snapshot, node-1 with logs"
+    );
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).snapshot(snapshot_last, "node-2 installed the snapshot").await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        let snapshot = n1.metrics().borrow().snapshot;
+        assert_eq!(None, snapshot, "node-1 did not receive a snapshot");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
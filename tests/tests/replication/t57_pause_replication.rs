@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower paused by the leader receives no logs but stays a member and does not block
+/// commits. When resumed, it is caught up with a snapshot since the logs it lacks are purged.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn pause_replication() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster of 5 voters");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2,3,4}, btreeset! {}).await?;
+    let paused_at = log_index;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let term = n0.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- pause replication to node-4, logs are still committed");
+    {
+        n0.trigger().pause_replication(4).await?;
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "replicated to unpaused").await?;
+        }
+
+        // Longer than an election timeout: node-4 still receives heartbeats.
+        sleep(Duration::from_millis(1_000)).await;
+
+        let m4 = router.get_metrics(&4)?;
+        assert_eq!(Some(paused_at), m4.last_log_index, "node-4 receives no logs");
+        assert_eq!(term, m4.current_term, "node-4 does not elect");
+        assert_eq!(Some(0), m4.current_leader);
+
+        let m0 = router.get_metrics(&0)?;
+        assert!(m0.membership_config.membership().voter_ids().any(|id| id == 4));
+    }
+
+    tracing::info!(log_index, "--- purge logs on the leader");
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "logs purged").await?;
+    }
+
+    tracing::info!(log_index, "--- resume replication, node-4 is caught up with a snapshot");
+    {
+        n0.trigger().resume_replication(4).await?;
+
+        router.wait(&4, timeout()).snapshot(log_id(1, 0, log_index), "node-4 installed snapshot").await?;
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&4, timeout()).applied_index(Some(log_index), "node-4 caught up").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::blank_ent;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `append_rejection_threshold`, a follower that keeps rejecting AppendEntries is reported in
+/// `RaftMetrics::replication_stalls` and is replicated with a snapshot, even though the logs it
+/// lacks are not purged.
+///
+/// - Node-1 starts with 100 logs of term 1, conflicting with the leader's logs of term 2.
+/// - Finding the matching log takes several rejected probes, more than the threshold.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replication_stall() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 1_000,
+            append_rejection_threshold: Some(2),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- elect node-0 again, write logs at term 2");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().elect().await?;
+        log_index += 1;
+        n0.wait(timeout()).applied_index(Some(log_index), "node-0 re-elected").await?;
+
+        log_index += router.client_request_many(0, "foo", 100).await?;
+    }
+
+    let snapshot_last = log_id(2, 0, log_index);
+
+    tracing::info!(log_index, "--- build a snapshot on node-0, no log is purged");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(snapshot_last, "node-0 built a snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- start node-1 with conflicting logs");
+    {
+        let (mut sto1, sm1) = router.new_store();
+        sto1.blocking_append([blank_ent(0, 0, 0)]).await?;
+        for i in 1..=100 {
+            sto1.blocking_append([blank_ent(1, 1, i)]).await?;
+        }
+        router.new_raft_node_with_sto(1, sto1, sm1).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- add node-1 as learner, it is replicated with the snapshot"
+    );
+    {
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout())
+            .metrics(
+                |m| m.replication_stalls.get(&1).copied().unwrap_or_default() >= 1,
+                "replication to node-1 stalled",
+            )
+            .await?;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 caught up").await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).snapshot(snapshot_last, "node-1 installed the snapshot").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Trigger::force_snapshot_to()` makes the leader catch up a lagging follower with a snapshot,
+/// even though the logs it lacks are not purged.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn force_snapshot_to() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 1_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- forcing a snapshot to an up to date follower is a no-op");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "node-0 built a snapshot").await?;
+
+        n0.trigger().force_snapshot_to(1).await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let m1 = router.get_metrics(&1)?;
+        assert_eq!(None, m1.snapshot, "node-1 installed no snapshot");
+    }
+
+    tracing::info!(log_index, "--- isolate node-1, it falls behind");
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "foo", 50).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 applied all logs").await?;
+    }
+
+    let snapshot_last = log_id(1, 0, log_index);
+
+    tracing::info!(log_index, "--- build a snapshot on node-0, no log is purged");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(snapshot_last, "node-0 built a snapshot").await?;
+
+        let purged = n0.metrics().borrow().purged;
+        assert_eq!(None, purged, "the logs node-1 lacks are not purged");
+    }
+
+    tracing::info!(log_index, "--- force a snapshot to node-1, then restore network");
+    {
+        // Replication to node-1 keeps failing and being retried: the snapshot is retried as well,
+        // until node-1 installs it.
+        n0.trigger().force_snapshot_to(1).await?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        router.set_network_error(1, false);
+
+        router.wait(&1, timeout()).snapshot(snapshot_last, "node-1 installed the snapshot").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 is replicated with logs after the snapshot");
+    {
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 keeps up").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
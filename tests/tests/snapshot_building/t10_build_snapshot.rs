@@ -133,12 +133,12 @@ async fn build_snapshot() -> Result<()> {
             .new_client(1, &())
             .await
             .append_entries(
-                AppendEntriesRequest {
-                    vote: Vote::new_committed(1, 0),
-                    prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
-                    entries: vec![],
-                    leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
-                },
+                AppendEntriesRequest::new(
+                    Vote::new_committed(1, 0),
+                    Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+                    vec![],
+                    Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+                ),
                 option,
             )
             .await?;
@@ -64,13 +64,13 @@ async fn building_snapshot_does_not_block_apply() -> Result<()> {
     {
         let next = log_index + 1;
 
-        let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-            vote: Vote::new_committed(1, 0),
-            prev_log_id: Some(log_id(1, 0, log_index)),
-            entries: vec![blank_ent(1, 0, next)],
+        let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+            Vote::new_committed(1, 0),
+            Some(log_id(1, 0, log_index)),
+            vec![blank_ent(1, 0, next)],
             // Append and commit this entry
-            leader_commit: Some(log_id(1, 0, next)),
-        };
+            Some(log_id(1, 0, next)),
+        );
 
         let mut cli = router.new_client(1, &()).await;
         let option = RPCOption::new(Duration::from_millis(1_000));
@@ -6,14 +6,19 @@ mod fixtures;
 
 mod t10_api_install_snapshot;
 mod t10_api_install_snapshot_with_lower_vote;
+mod t10_api_install_stale_snapshot;
 mod t20_startup_snapshot;
 mod t30_purge_in_snapshot_logs;
 mod t31_snapshot_overrides_membership;
 mod t32_snapshot_uses_prev_snap_membership;
 mod t33_snapshot_delete_conflict_logs;
 mod t34_replication_does_not_block_purge;
+mod t35_install_snapshot_adopts_membership;
+mod t36_newer_snapshot_cancels_streaming;
+mod t37_initialize_with_snapshot;
 mod t50_snapshot_line_rate_to_snapshot;
 mod t50_snapshot_when_lacking_log;
 mod t51_after_snapshot_add_learner_and_request_a_log;
 mod t60_snapshot_chunk_size;
+mod t61_snapshot_bandwidth;
 mod t90_issue_808_snapshot_to_unreachable_node_should_not_block;
@@ -39,11 +39,7 @@ async fn snapshot_arguments() -> Result<()> {
     let make_req = || InstallSnapshotRequest {
         // force it to be a follower
         vote: Vote::new_committed(2, 1),
-        meta: SnapshotMeta {
-            snapshot_id: "ss1".into(),
-            last_log_id: Some(log_id(1, 0, 0)),
-            last_membership: Default::default(),
-        },
+        meta: SnapshotMeta::new(Some(log_id(1, 0, 0)), Default::default(), "ss1".into()),
         offset: 0,
         data: vec![1, 2, 3],
         done: false,
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// API test: a snapshot older than the applied state of the target node is not installed, and
+/// the state machine does not go backward.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn install_stale_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- build a snapshot on node-0");
+    let stale = {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.get_snapshot().await?.unwrap()
+    };
+
+    tracing::info!(log_index, "--- write more logs, node-1 applies beyond the snapshot");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied").await?;
+    }
+
+    tracing::info!(log_index, "--- install the stale snapshot to node-1, it is rejected");
+    {
+        let (_sto, sm) = router.get_storage_handle(&1)?;
+        let sm_before = sm.get_state_machine().await;
+
+        let vote = n1.with_raft_state(|st| *st.vote_ref()).await?;
+        let resp = n1.install_full_snapshot(vote, stale).await?;
+        assert_eq!(vote, resp.vote, "respond with the current vote");
+
+        let m = router.get_metrics(&1)?;
+        assert_eq!(Some(log_index), m.last_applied.index(), "applied does not go backward");
+        assert_eq!(None, m.snapshot, "the stale snapshot is not installed");
+
+        let sm_after = sm.get_state_machine().await;
+        assert_eq!(sm_before.last_applied_log, sm_after.last_applied_log);
+        assert_eq!(sm_before.client_status, sm_after.client_status);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
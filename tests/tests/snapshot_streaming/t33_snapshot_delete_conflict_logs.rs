@@ -90,10 +90,10 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
     {
         router.new_raft_node(1).await;
 
-        let req = AppendEntriesRequest {
-            vote: Vote::new_committed(1, 0),
-            prev_log_id: None,
-            entries: vec![
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(1, 0),
+            None,
+            vec![
                 blank_ent(0, 0, 0),
                 blank_ent(1, 0, 1),
                 // conflict membership will be replaced with membership in snapshot
@@ -115,8 +115,8 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {4,5}], None)),
                 },
             ],
-            leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
-        };
+            Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        );
         let option = RPCOption::new(Duration::from_millis(1_000));
 
         router.new_client(1, &()).await.append_entries(req, option).await?;
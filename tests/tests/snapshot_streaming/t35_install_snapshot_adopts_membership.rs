@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node that installs a snapshot adopts the membership recorded in the snapshot.
+///
+/// - Build a snapshot on the leader of a cluster of 0,1,2.
+/// - Install it on a new node 3 that has no log and no membership.
+/// - Node 3 learns its peers from the snapshot.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn install_snapshot_adopts_membership() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let snap;
+
+    tracing::info!(log_index, "--- write logs and build a snapshot on node-0");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        snap = n0.get_snapshot().await?.unwrap();
+        assert_eq!(
+            vec![btreeset! {0,1,2}],
+            snap.meta.last_membership.membership().get_joint_config().clone(),
+            "snapshot records the membership"
+        );
+    }
+
+    tracing::info!(log_index, "--- install the snapshot on a new node-3");
+    {
+        router.new_raft_node(3).await;
+        let n3 = router.get_raft_handle(&3)?;
+
+        let m = n3.metrics().borrow().membership_config.clone();
+        assert_eq!(None, m.log_id().as_ref(), "node-3 has no membership");
+
+        n3.install_full_snapshot(Vote::new_committed(1, 0), snap.clone()).await?;
+
+        let m = n3
+            .wait(timeout())
+            .metrics(
+                |m| m.snapshot == Some(log_id(1, 0, log_index)),
+                "node-3 installed snapshot",
+            )
+            .await?;
+
+        assert_eq!(
+            &snap.meta.last_membership,
+            m.membership_config.as_ref(),
+            "node-3 adopts the membership of the snapshot"
+        );
+        assert_eq!(
+            btreeset! {0,1,2},
+            m.membership_config.membership().voter_ids().collect(),
+            "node-3 knows its peers"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
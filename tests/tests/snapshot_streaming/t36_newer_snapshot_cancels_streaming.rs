@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Snapshot;
+use openraft::SnapshotPolicy;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A newer snapshot arriving while an older one is being received cancels the older one, and
+/// the newer one is installed. The older one can not be continued or restarted.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn newer_snapshot_cancels_streaming() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_, mut sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- build an older snapshot and a newer snapshot on node-0");
+    let older = {
+        log_index += router.client_request_many(0, "0", 5).await?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "older snapshot").await?;
+        sm0.get_current_snapshot().await?.unwrap()
+    };
+    let newer = {
+        log_index += router.client_request_many(0, "0", 5).await?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "newer snapshot").await?;
+        sm0.get_current_snapshot().await?.unwrap()
+    };
+
+    router.new_raft_node(1).await;
+    let n1 = router.get_raft_handle(&1)?;
+
+    let vote = Vote::new_committed(1, 0);
+    let older = chunks(vote, older);
+    let newer = chunks(vote, newer);
+
+    tracing::info!(log_index, "--- node-1 begins receiving the older snapshot");
+    {
+        n1.install_snapshot(older[0].clone()).await?;
+    }
+
+    tracing::info!(log_index, "--- the newer snapshot cancels the older one");
+    {
+        n1.install_snapshot(newer[0].clone()).await?;
+    }
+
+    tracing::info!(log_index, "--- the older snapshot can not be continued or restarted");
+    {
+        let res = n1.install_snapshot(older[1].clone()).await;
+        assert!(res.is_err(), "cancelled stream can not be continued");
+
+        let res = n1.install_snapshot(older[0].clone()).await;
+        assert!(res.is_err(), "older snapshot does not cancel the newer one");
+    }
+
+    tracing::info!(log_index, "--- the newer snapshot completes");
+    {
+        for req in &newer[1..] {
+            n1.install_snapshot(req.clone()).await?;
+        }
+
+        let last = newer[0].meta.last_log_id.unwrap();
+        n1.wait(timeout()).snapshot(last, "newer snapshot installed").await?;
+        n1.wait(timeout()).applied_index(Some(log_index), "state machine replaced").await?;
+    }
+
+    Ok(())
+}
+
+/// Split a snapshot into 2 chunks.
+fn chunks(vote: Vote<u64>, snapshot: Snapshot<TypeConfig>) -> Vec<InstallSnapshotRequest<TypeConfig>> {
+    let data = snapshot.snapshot.into_inner();
+    let mid = data.len() / 2;
+
+    vec![
+        InstallSnapshotRequest {
+            vote,
+            meta: snapshot.meta.clone(),
+            offset: 0,
+            data: data[..mid].to_vec(),
+            done: false,
+        },
+        InstallSnapshotRequest {
+            vote,
+            meta: snapshot.meta.clone(),
+            offset: mid as u64,
+            data: data[mid..].to_vec(),
+            done: true,
+        },
+    ]
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,133 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::storage::RaftLogReader;
+use openraft::storage::RaftLogStorage;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Membership;
+use openraft::ServerState;
+use openraft::Snapshot;
+use openraft::SnapshotMeta;
+use openraft::StoredMembership;
+use openraft_memstore::MemStoreStateMachine;
+use openraft_memstore::SNAPSHOT_FORMAT_VERSION;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Bootstrap a cluster from a dataset with `Raft::initialize_with_snapshot()`:
+///
+/// - Node-0 is seeded with a snapshot built from the dataset and becomes the leader.
+/// - A new member is brought up entirely by the snapshot, without replicating the logs before it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn initialize_with_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+    router.new_raft_node(1).await;
+
+    // The dataset was produced by another cluster, up to log index 100 in term 3.
+    let snapshot_last = log_id(3, 0, 100);
+    let n_keys = 50;
+
+    tracing::info!("--- build a seed snapshot from the dataset");
+    let snapshot = {
+        let membership = StoredMembership::new(Some(log_id(3, 0, 1)), Membership::new(vec![btreeset! {0}], None));
+
+        let sm = MemStoreStateMachine {
+            last_applied_log: Some(snapshot_last),
+            last_membership: membership.clone(),
+            client_serial_responses: Default::default(),
+            client_status: (0..n_keys).map(|i| (format!("k{}", i), format!("v{}", i))).collect(),
+        };
+
+        Snapshot {
+            meta: SnapshotMeta::new(Some(snapshot_last), membership, "seed".to_string())
+                .with_format_version(SNAPSHOT_FORMAT_VERSION),
+            snapshot: Box::new(Cursor::new(serde_json::to_vec(&sm)?)),
+        }
+    };
+
+    let mut log_index = snapshot_last.index;
+
+    tracing::info!(log_index, "--- seed node-0 with the snapshot, it becomes leader");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.initialize_with_snapshot(snapshot).await?;
+
+        // The blank log of the new leader.
+        log_index += 1;
+
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "node-0 applied blank log").await?;
+        n0.wait(timeout()).snapshot(snapshot_last, "node-0 has the seed snapshot").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(
+            4, m.current_term,
+            "leader term is greater than the term in the snapshot"
+        );
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        let sm0 = sm0.get_state_machine().await;
+        assert_eq!(n_keys, sm0.client_status.len());
+    }
+
+    tracing::info!(log_index, "--- not allowed to initialize twice");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let res = n0.initialize(btreemap! {0 => ()}).await;
+        assert!(res.is_err());
+    }
+
+    tracing::info!(log_index, "--- add node-1, it is brought up by the snapshot");
+    {
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).snapshot(snapshot_last, "node-1 installed the seed snapshot").await?;
+        n1.wait(timeout()).applied_index(Some(log_index), "node-1 caught up").await?;
+
+        let (mut sto1, sm1) = router.get_storage_handle(&1)?;
+
+        let state = sto1.get_log_state().await?;
+        assert_eq!(Some(snapshot_last), state.last_purged_log_id);
+
+        let logs = sto1.try_get_log_entries(0..=snapshot_last.index).await?;
+        assert!(logs.is_empty(), "no log before the snapshot is replicated");
+
+        let sm1 = sm1.get_state_machine().await;
+        assert_eq!(n_keys, sm1.client_status.len());
+        assert_eq!(Some(&"v0".to_string()), sm1.client_status.get("k0"));
+    }
+
+    tracing::info!(log_index, "--- promote node-1 to voter");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership([0, 1], false).await?;
+        log_index += 2;
+
+        for id in [0, 1] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "membership {0,1} is applied").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use tokio::time::Instant;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+const CHUNK_SIZE: u64 = 256;
+
+/// With `snapshot_max_bytes_per_sec`, sending a snapshot takes proportionally longer, while logs
+/// are still replicated to other followers during the transfer.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn snapshot_bandwidth() -> Result<()> {
+    let (size, unlimited) = add_learner_by_snapshot(None).await?;
+
+    // Limit the bandwidth so that sending the snapshot takes about one second.
+    let (_, limited) = add_learner_by_snapshot(Some(size)).await?;
+
+    // The first chunk is sent at once.
+    let want = Duration::from_secs_f64((size - CHUNK_SIZE) as f64 / size as f64);
+
+    tracing::info!(size, ?unlimited, ?limited, ?want, "snapshot transfer time");
+
+    assert!(limited >= want, "limited: {:?} >= want: {:?}", limited, want);
+    assert!(
+        limited > unlimited,
+        "limited: {:?} > unlimited: {:?}",
+        limited,
+        unlimited
+    );
+
+    Ok(())
+}
+
+/// Build a cluster of voters `{0,1}` whose logs are purged, add node-2 as a learner, and return
+/// the size of the snapshot and the time it takes node-2 to install it.
+async fn add_learner_by_snapshot(max_bytes_per_sec: Option<u64>) -> Result<(u64, Duration)> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            snapshot_max_chunk_size: CHUNK_SIZE,
+            snapshot_max_bytes_per_sec: max_bytes_per_sec,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs, build a snapshot and purge logs");
+    let snapshot_last = {
+        for i in 0..100 {
+            router.send_client_request(0, ClientRequest::make_request(format!("client-{}", i), 1)).await?;
+        }
+        log_index += 100;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+
+        let snapshot_last = log_id(1, 0, log_index);
+        n0.wait(timeout()).snapshot(snapshot_last, "node-0 built a snapshot").await?;
+        n0.wait(timeout()).purged(Some(snapshot_last), "node-0 purged logs").await?;
+        snapshot_last
+    };
+
+    let size = {
+        let (_sto0, mut sm0) = router.get_storage_handle(&0)?;
+        let snapshot = sm0.get_current_snapshot().await?.unwrap();
+        snapshot.snapshot.into_inner().len() as u64
+    };
+
+    tracing::info!(log_index, size, "--- add node-2, it receives the snapshot");
+    let start = Instant::now();
+    {
+        router.new_raft_node(2).await;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.add_learner(2, (), false).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- logs are replicated to node-1 during the transfer");
+    {
+        router.send_client_request(0, ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied the log").await?;
+
+        if let Some(max) = max_bytes_per_sec {
+            let n0 = router.get_raft_handle(&0)?;
+            let m = n0
+                .wait(timeout())
+                .metrics(|m| m.snapshot_send_rate > 0, "node-0 reports snapshot send rate")
+                .await?;
+            assert!(
+                m.snapshot_send_rate <= max * 2,
+                "send rate {} is limited by {}",
+                m.snapshot_send_rate,
+                max
+            );
+
+            let n2 = router.get_raft_handle(&2)?;
+            let snapshot = n2.metrics().borrow().snapshot;
+            assert_eq!(None, snapshot, "node-2 is still receiving the snapshot");
+        }
+    }
+
+    let elapsed = {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).snapshot(snapshot_last, "node-2 installed the snapshot").await?;
+        let elapsed = start.elapsed();
+
+        n2.wait(timeout()).applied_index(Some(log_index), "node-2 caught up").await?;
+        elapsed
+    };
+
+    Ok((size, elapsed))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -9,3 +9,8 @@ mod fixtures;
 
 mod t10_total_order_apply;
 mod t20_state_machine_apply_membership;
+mod t30_apply_observer;
+mod t50_set_applied_index;
+mod t60_apply_coordinator;
+mod t70_apply_rate_limit;
+mod t80_apply_stall_backlog;
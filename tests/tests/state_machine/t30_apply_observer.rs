@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::ApplyObserver;
+use openraft::Config;
+use openraft::Entry;
+use openraft::RaftLogId;
+use openraft::SnapshotMeta;
+use openraft::SnapshotPolicy;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Event {
+    Apply(u64),
+    Snapshot(Option<u64>),
+}
+
+/// Records every observed entry and installed snapshot.
+#[derive(Clone, Default)]
+struct IndexRecorder {
+    events: Arc<Mutex<Vec<Event>>>,
+    responses: Arc<Mutex<Vec<ClientResponse>>>,
+}
+
+impl IndexRecorder {
+    fn events(&self) -> Vec<Event> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl ApplyObserver<TypeConfig> for IndexRecorder {
+    fn on_apply(&mut self, entry: &Entry<TypeConfig>, response: &ClientResponse) {
+        self.events.lock().unwrap().push(Event::Apply(entry.get_log_id().index));
+        self.responses.lock().unwrap().push(response.clone());
+    }
+
+    fn on_install_snapshot(&mut self, meta: &SnapshotMeta<TypeConfig>) {
+        self.events.lock().unwrap().push(Event::Snapshot(meta.last_log_id.map(|x| x.index)));
+    }
+}
+
+/// The apply observer sees every applied entry exactly once, in index order, on every node.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_observer() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let mut recorders = vec![];
+    for id in [0, 1, 2] {
+        let recorder = IndexRecorder::default();
+        router.get_raft_handle(&id)?.set_apply_observer(recorder.clone()).await?;
+        recorders.push((id, recorder));
+    }
+
+    let n = 50;
+
+    tracing::info!(log_index, "--- write {} logs", n);
+    {
+        log_index += router.client_request_many(0, "foo", n).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied on every node").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- every node observed entries in strict index order");
+    {
+        let want = ((log_index - n as u64 + 1)..=log_index).map(Event::Apply).collect::<Vec<_>>();
+
+        for (id, recorder) in recorders {
+            assert_eq!(
+                want,
+                recorder.events(),
+                "node-{} observed entries in order without gap or duplicate",
+                id
+            );
+            assert_eq!(
+                n,
+                recorder.responses.lock().unwrap().len(),
+                "node-{} observed the response of every entry",
+                id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// A node that receives a snapshot observes the snapshot first, then every entry after it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_observer_install_snapshot() -> Result<()> {
+    let snapshot_threshold: u64 = 20;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs to build a snapshot and purge logs");
+    let snapshot_index = snapshot_threshold - 1;
+    {
+        log_index += router.client_request_many(0, "0", (snapshot_index - log_index) as usize).await?;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.purged.map(|x| x.index) == Some(snapshot_index),
+                "logs in snapshot are purged",
+            )
+            .await?;
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+    }
+
+    tracing::info!(log_index, "--- add a learner with an observer");
+    let recorder = IndexRecorder::default();
+    {
+        router.new_raft_node(1).await;
+        router.get_raft_handle(&1)?.set_apply_observer(recorder.clone()).await?;
+
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner applied all logs").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the learner observed the snapshot, then the following entries"
+    );
+    {
+        let mut want = vec![Event::Snapshot(Some(snapshot_index))];
+        want.extend(((snapshot_index + 1)..=log_index).map(Event::Apply));
+
+        assert_eq!(want, recorder.events());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ApplySkipped;
+use openraft::error::ClientWriteError;
+use openraft::error::SetAppliedIndexError;
+use openraft::raft::ApplyObserver;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogIdOptionExt;
+use openraft::RaftLogId;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Records the index of every observed entry.
+#[derive(Clone, Default)]
+struct IndexRecorder {
+    indexes: Arc<Mutex<Vec<u64>>>,
+}
+
+impl ApplyObserver<TypeConfig> for IndexRecorder {
+    fn on_apply(&mut self, entry: &Entry<TypeConfig>, _response: &ClientResponse) {
+        self.indexes.lock().unwrap().push(entry.get_log_id().index);
+    }
+}
+
+/// `Raft::set_applied_index()` skips applying committed entries the state machine already has, and
+/// refuses to go beyond the committed index.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn set_applied_index() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            allow_unsafe_set_applied: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+
+    let recorder = IndexRecorder::default();
+    n0.set_apply_observer(recorder.clone()).await?;
+
+    tracing::info!(
+        log_index,
+        "--- block applying by holding the state machine in snapshot building"
+    );
+    {
+        sm0.block.set_blocking(BlockOperation::BuildSnapshot, Duration::from_millis(1_000));
+        n0.trigger().snapshot().await?;
+
+        // Wait for the snapshot building to start and acquire the state machine lock.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let first = log_index + 1;
+
+    tracing::info!(log_index, "--- write one log, the worker blocks on applying it");
+    {
+        let _rx = n0.client_write_ff(ClientRequest::make_request("c0", 1)).await?;
+        log_index += 1;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- write more logs, they are committed but queued for applying"
+    );
+    let queued = {
+        let mut rxs = vec![];
+        for i in 1..5 {
+            let rx = n0.client_write_ff(ClientRequest::make_request(format!("c{}", i), 1)).await?;
+            rxs.push(rx);
+        }
+        log_index += 4;
+
+        loop {
+            let committed = n0.with_raft_state(|st| st.committed.index()).await?;
+            if committed == Some(log_index) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(Some(first - 1), n0.metrics().borrow().last_applied.index());
+
+        rxs
+    };
+
+    tracing::info!(log_index, "--- can not set applied index beyond committed");
+    {
+        let res = n0.set_applied_index(log_index + 1).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(
+            SetAppliedIndexError::BeyondCommitted {
+                index: log_index + 1,
+                committed: Some(log_id(1, 0, log_index)),
+            },
+            err
+        );
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the state machine is restored out-of-band, skip the queued logs"
+    );
+    {
+        n0.set_applied_index(log_index).await?;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "applied index is advanced").await?;
+
+        let got = recorder.indexes.lock().unwrap().clone();
+        assert_eq!(vec![first], got, "only the log being applied is applied");
+
+        let sm = sm0.get_state_machine().await;
+        assert!(sm.client_status.contains_key("c0"));
+        for i in 1..5 {
+            assert!(
+                !sm.client_status.contains_key(&format!("c{}", i)),
+                "skipped log c{} is not applied",
+                i
+            );
+        }
+
+        for (i, rx) in queued.into_iter().enumerate() {
+            let res = rx.await?;
+            let index = first + 1 + i as u64;
+            assert_eq!(
+                Err(ClientWriteError::ApplySkipped(ApplySkipped { node_id: 0, index })),
+                res.map(|_| ()),
+                "the client of skipped log {} is told it is skipped",
+                index
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- logs after it are applied as usual");
+    {
+        router.send_client_request(0, ClientRequest::make_request("c5", 1)).await?;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "new log is applied").await?;
+
+        let got = recorder.indexes.lock().unwrap().clone();
+        assert_eq!(vec![first, log_index], got);
+    }
+
+    Ok(())
+}
+
+/// `Raft::set_applied_index()` is refused if `Config::allow_unsafe_set_applied` is disabled.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn set_applied_index_not_allowed() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let res = n0.set_applied_index(log_index).await;
+    let err = res.unwrap_err().into_api_error().unwrap();
+    assert_eq!(SetAppliedIndexError::NotAllowed, err);
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
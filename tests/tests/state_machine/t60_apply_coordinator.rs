@@ -0,0 +1,155 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::ApplyCoordinator;
+use openraft::raft::ApplyPermit;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogId;
+use openraft::RaftLogId;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+use tokio::sync::watch;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A mock sequencer shared by two groups: an entry of group `b` is permitted only after group `a`
+/// applied the entry at the same index.
+struct Sequencer {
+    a_applied: watch::Sender<u64>,
+
+    /// `(group, index)` of every applied entry, in the global apply order.
+    applied: Mutex<Vec<(&'static str, u64)>>,
+}
+
+struct GroupCoordinator {
+    group: &'static str,
+    sequencer: Arc<Sequencer>,
+}
+
+impl ApplyCoordinator<TypeConfig> for GroupCoordinator {
+    fn acquire(&mut self, entry: &Entry<TypeConfig>) -> ApplyPermit {
+        if self.group == "a" {
+            return ApplyPermit::ready();
+        }
+
+        let index = entry.get_log_id().index;
+        let mut a_applied = self.sequencer.a_applied.subscribe();
+        ApplyPermit::new(async move {
+            let _ = a_applied.wait_for(|a| *a >= index).await;
+        })
+    }
+
+    fn on_applied(&mut self, log_id: &LogId<u64>) {
+        self.sequencer.applied.lock().unwrap().push((self.group, log_id.index));
+
+        if self.group == "a" {
+            self.sequencer.a_applied.send_replace(log_id.index);
+        }
+    }
+}
+
+/// With an apply coordinator shared by two groups, an entry is applied only when the coordinator
+/// permits, and every group still applies its own entries in index order.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_coordinator() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router_a = RaftRouter::new(config.clone());
+    let mut router_b = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing two groups");
+    let mut log_index = router_a.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    let log_index_b = router_b.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    assert_eq!(log_index, log_index_b);
+
+    let sequencer = Arc::new(Sequencer {
+        a_applied: watch::Sender::new(log_index),
+        applied: Mutex::new(vec![]),
+    });
+
+    let a0 = router_a.get_raft_handle(&0)?;
+    let b0 = router_b.get_raft_handle(&0)?;
+
+    for (group, raft) in [("a", &a0), ("b", &b0)] {
+        let coordinator = GroupCoordinator {
+            group,
+            sequencer: sequencer.clone(),
+        };
+        raft.set_apply_coordinator(coordinator).await?;
+    }
+
+    let n = 5;
+
+    tracing::info!(log_index, "--- write to group b, it is not applied before group a");
+    let b_writes = {
+        let handle = tokio::spawn(async move {
+            for i in 0..n {
+                b0.client_write(ClientRequest::make_request("b", i)).await?;
+            }
+            Ok::<_, anyhow::Error>(())
+        });
+
+        sleep(Duration::from_millis(500)).await;
+
+        let m = router_b.get_metrics(&0)?;
+        assert_eq!(
+            Some(log_index),
+            m.last_applied.map(|x| x.index),
+            "group b waits for group a"
+        );
+        assert!(!handle.is_finished());
+
+        handle
+    };
+
+    tracing::info!(log_index, "--- write to group a, both groups apply");
+    {
+        router_a.client_request_many(0, "a", n as usize).await?;
+        b_writes.await??;
+        log_index += n;
+
+        router_b.wait(&0, timeout()).applied_index(Some(log_index), "group b applied").await?;
+    }
+
+    tracing::info!(log_index, "--- the global order respects the coordinator");
+    {
+        let applied = sequencer.applied.lock().unwrap().clone();
+
+        for group in ["a", "b"] {
+            let indexes = applied.iter().filter(|(g, _)| *g == group).map(|(_, i)| *i).collect::<Vec<_>>();
+            let want = (log_index - n + 1..=log_index).collect::<Vec<_>>();
+            assert_eq!(want, indexes, "group {} applies its own entries in index order", group);
+        }
+
+        for (pos, (group, index)) in applied.iter().enumerate() {
+            if *group == "b" {
+                assert!(
+                    applied[..pos].contains(&("a", *index)),
+                    "b-{} is applied after a-{}",
+                    index,
+                    index
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use tokio::time::Instant;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `apply_rate_limit`, committed entries are applied no faster than the limit, and the
+/// entries waiting to apply are reported by the `apply_backlog` metric.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_rate_limit() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            apply_rate_limit: Some(5),
+            apply_rate_interval: 100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 20 entries at once");
+    let start = Instant::now();
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        for i in 0..20 {
+            let n0 = n0.clone();
+            tokio::spawn(async move { n0.client_write(ClientRequest::make_request("foo", i)).await });
+        }
+        log_index += 20;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- entries are committed at once, but applied at the capped rate"
+    );
+    {
+        let m = router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.last_log_index == Some(log_index) && m.apply_backlog > 0,
+                "all entries are committed and some wait to apply",
+            )
+            .await?;
+
+        let applied = m.last_applied.map_or(0, |x| x.index);
+        assert!(
+            log_index - applied >= m.apply_backlog,
+            "backlog {} counts at most the entries not yet applied",
+            m.apply_backlog
+        );
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "all entries applied").await?;
+
+        // 20 entries at 5 per 100 ms take at least 3 more windows after the first one.
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(300),
+            "applying is rate limited, elapsed: {:?}",
+            elapsed
+        );
+
+        let m = router.get_metrics(&0)?;
+        assert_eq!(0, m.apply_backlog);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `apply_stall_backlog`, a failing apply is retried instead of shutting down the node, and
+/// writes are rejected once too many committed entries wait to apply, until applying recovers.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_stall_backlog() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            heartbeat_interval: 50,
+            apply_stall_backlog: Some(3),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_log0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- make apply fail, write 4 entries");
+    let mut handles = vec![];
+    {
+        sm0.block.set_failing(BlockOperation::Apply);
+
+        // Submit one by one, so that none is rejected before the backlog exceeds the threshold.
+        for i in 0..4 {
+            handles.push(n0.client_write_ff(ClientRequest::make_request("foo", i)).await?);
+        }
+        log_index += 4;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.last_log_index == Some(log_index) && m.apply_backlog == 4,
+                "all entries are committed but none is applied",
+            )
+            .await?;
+
+        // Let at least one retry fail.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    tracing::info!(log_index, "--- a new write is rejected while apply is stalled");
+    {
+        let res = n0.client_write(ClientRequest::make_request("foo", 5)).await;
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::ApplyStalled(e)) => {
+                assert_eq!(0, e.node_id);
+                assert_eq!(4, e.backlog);
+                assert_eq!(3, e.threshold);
+            }
+            _ => panic!("expect ApplyStalled, got: {:?}", err),
+        }
+
+        let m = router.get_metrics(&0)?;
+        assert!(m.running_state.is_ok(), "the node is not shut down by a failed apply");
+    }
+
+    tracing::info!(log_index, "--- apply recovers, pending writes complete");
+    {
+        sm0.block.clear_failing(BlockOperation::Apply);
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "all entries applied").await?;
+
+        for h in handles {
+            h.await??;
+        }
+    }
+
+    tracing::info!(log_index, "--- writes are accepted again");
+    {
+        n0.client_write(ClientRequest::make_request("foo", 5)).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "new write applied").await?;
+    }
+
+    Ok(())
+}
+
+/// A retry after a failed apply that applied some of the entries resumes after the last applied
+/// log id of the state machine, so that no entry is applied twice.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_retry_resumes_after_partial_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            heartbeat_interval: 50,
+            apply_stall_backlog: Some(100),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_log0, sm0) = router.get_storage_handle(&0)?;
+    let applied_before = sm0.applied_count();
+
+    tracing::info!(log_index, "--- make apply fail, write 5 entries");
+    let mut handles = vec![];
+    {
+        sm0.block.set_failing(BlockOperation::Apply);
+
+        for i in 0..5 {
+            let n0 = n0.clone();
+            handles.push(tokio::spawn(async move {
+                n0.client_write(ClientRequest::make_request(format!("foo-{}", i), 0)).await
+            }));
+        }
+        log_index += 5;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.last_log_index == Some(log_index) && m.apply_backlog == 5,
+                "all entries are committed but none is applied",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- every apply applies only the first entry then fails");
+    {
+        sm0.block.set_failing(BlockOperation::ApplyPartially);
+        sm0.block.clear_failing(BlockOperation::Apply);
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "all entries applied").await?;
+
+        let applied = sm0.applied_count() - applied_before;
+        assert_eq!(5, applied, "every entry is applied exactly once");
+
+        sm0.block.clear_failing(BlockOperation::ApplyPartially);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- clients of entries applied by a failed apply receive ApplyResultLost"
+    );
+    {
+        let mut lost = 0;
+        for h in handles {
+            match h.await? {
+                Ok(_) => {}
+                Err(RaftError::APIError(ClientWriteError::ApplyResultLost(e))) => {
+                    assert_eq!(0, e.node_id);
+                    lost += 1;
+                }
+                Err(e) => panic!("unexpected error: {:?}", e),
+            }
+        }
+        assert!(lost > 0, "some entries are applied by a failed apply");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}